@@ -2,23 +2,28 @@
 
 use anyhow::{bail, Result};
 use clap::Args;
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 
 use crate::docker::{self, DockerContainer};
-use crate::session::repo_config;
-use crate::session::{civilizations, Config, GroupTree, Instance, SandboxInfo, Storage};
+use crate::session::builder::{ensure_project_dir, InstanceParams};
+use crate::session::{
+    civilizations, detect_tool, expand_path, manager, parse_extra_volume, repo_config,
+    resolve_config, resolve_config_with_repo, Config, HooksConfig, Instance, SandboxInfo, Storage,
+    SUPPORTED_TOOLS,
+};
 
 #[derive(Args)]
 pub struct AddArgs {
-    /// Project directory (defaults to current directory)
-    #[arg(default_value = ".")]
-    path: PathBuf,
+    /// Project directory (defaults to the configured [session] default_path,
+    /// then the current directory)
+    path: Option<PathBuf>,
 
     /// Session title (defaults to folder name)
     #[arg(short = 't', long)]
     title: Option<String>,
 
-    /// Group path (defaults to parent folder)
+    /// Group path (defaults to the configured [session] default_group)
     #[arg(short = 'g', long)]
     group: Option<String>,
 
@@ -50,78 +55,74 @@ pub struct AddArgs {
     #[arg(long = "sandbox-image")]
     sandbox_image: Option<String>,
 
+    /// Mount a host directory in the sandbox, as host:container[:ro] (repeatable, implies --sandbox)
+    #[arg(long = "volume", value_name = "HOST:CONTAINER[:ro]")]
+    volumes: Vec<String>,
+
+    /// Docker network for the sandbox container: host, none, or a named network (implies --sandbox)
+    #[arg(long)]
+    network: Option<String>,
+
     /// Automatically trust repository hooks without prompting
     #[arg(long = "trust-hooks")]
     trust_hooks: bool,
+
+    /// Create the project directory if it does not exist
+    #[arg(long = "create-dir")]
+    create_dir: bool,
+
+    /// Start from a [presets.<name>] entry in the config; other flags override it
+    #[arg(long)]
+    preset: Option<String>,
+
+    /// Print what would be created (paths, image, env, hooks) without changing anything
+    #[arg(long = "dry-run")]
+    dry_run: bool,
 }
 
 pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
-    let mut path = if args.path.as_os_str() == "." {
-        std::env::current_dir()?
+    let session_config = resolve_config(profile)?.session;
+    let preset = match &args.preset {
+        Some(name) => Some(resolve_config(profile)?.preset(name)?.clone()),
+        None => None,
+    };
+    let preset_tool = match &preset {
+        Some(p) => p.tool()?,
+        None => None,
+    };
+    let requested = match &args.path {
+        Some(p) if p.as_os_str() == "." => std::env::current_dir()?,
+        Some(p) => expand_path(&p.to_string_lossy())?,
+        None => match session_config.default_path() {
+            Some(p) => p,
+            None => std::env::current_dir()?,
+        },
+    };
+    let create_dir = args.create_dir || session_config.create_dir;
+    let would_create_dir = args.dry_run && create_dir && !requested.exists();
+    // Titles and hooks use the original directory, even for worktree sessions
+    let project_dir = if would_create_dir {
+        std::env::current_dir()?.join(&requested)
     } else {
-        args.path.canonicalize()?
+        ensure_project_dir(&requested, create_dir)?;
+        requested.canonicalize()?
     };
 
-    if !path.is_dir() {
-        bail!("Path is not a directory: {}", path.display());
+    if args.network.as_deref().is_some_and(|n| n.trim().is_empty()) {
+        bail!("--network cannot be empty");
     }
 
-    let mut worktree_info_opt = None;
-
-    if let Some(branch_raw) = &args.worktree_branch {
-        use crate::git::GitWorktree;
-        use crate::session::WorktreeInfo;
-        use chrono::Utc;
-
-        let branch = branch_raw.trim();
-
-        if !GitWorktree::is_git_repo(&path) {
-            bail!("Path is not in a git repository\nTip: Navigate to a git repository first");
-        }
-
-        let config = Config::load()?;
-
-        let main_repo_path = GitWorktree::find_main_repo(&path)?;
-        let git_wt = GitWorktree::new(main_repo_path.clone())?;
-
-        let session_id = uuid::Uuid::new_v4().to_string();
-        let session_id_short = &session_id[..8];
-
-        // Choose appropriate template based on repo type (bare vs regular)
-        // Use main_repo_path (not path) to correctly detect bare repos when running from a worktree
-        let template = if GitWorktree::is_bare_repo(&main_repo_path) {
-            &config.worktree.bare_repo_path_template
-        } else {
-            &config.worktree.path_template
-        };
-        let worktree_path = git_wt.compute_path(branch, template, session_id_short)?;
-
-        if worktree_path.exists() {
-            bail!(
-                "Worktree already exists at {}\nTip: Use 'aoe add {}' to add the existing worktree",
-                worktree_path.display(),
-                worktree_path.display()
-            );
-        }
-
-        println!("Creating worktree at: {}", worktree_path.display());
-        git_wt.create_worktree(branch, &worktree_path, args.create_branch)?;
-
-        path = worktree_path;
-
-        worktree_info_opt = Some(WorktreeInfo {
-            branch: branch.to_string(),
-            main_repo_path: main_repo_path.to_string_lossy().to_string(),
-            managed_by_aoe: true,
-            created_at: Utc::now(),
-            cleanup_on_delete: true,
-        });
+    for volume in &args.volumes {
+        parse_extra_volume(volume)?;
+    }
 
-        println!("✓ Worktree created successfully");
+    let worktree_branch = args.worktree_branch.as_deref().map(str::trim);
+    if worktree_branch.is_some() && !crate::git::GitWorktree::is_git_repo(&project_dir) {
+        bail!("Path is not in a git repository\nTip: Navigate to a git repository first");
     }
 
     let storage = Storage::new(profile)?;
-    let (mut instances, groups) = storage.load_with_groups()?;
+    let instances = storage.load()?;
 
     // Resolve parent session if specified
     let mut group_path = args.group.clone();
@@ -135,11 +136,22 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
     } else {
         None
     };
+    let config = resolve_config_with_repo(profile, &project_dir)?;
+    let session_defaults = &config.session;
+    if group_path.is_none() {
+        group_path = session_defaults.default_group().map(str::to_string);
+    }
 
     // Generate title
     let final_title = if let Some(title) = &args.title {
         let trimmed_title = title.trim();
-        if is_duplicate_session(&instances, trimmed_title, path.to_str().unwrap_or("")) {
+        if worktree_branch.is_none()
+            && is_duplicate_session(
+                &instances,
+                trimmed_title,
+                project_dir.to_str().unwrap_or(""),
+            )
+        {
             println!(
                 "Session already exists with same title and path: {}",
                 trimmed_title
@@ -149,137 +161,114 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
         trimmed_title.to_string()
     } else {
         let existing_titles: Vec<&str> = instances.iter().map(|i| i.title.as_str()).collect();
-        civilizations::generate_random_title(&existing_titles)
+        civilizations::generate_title(
+            session_defaults,
+            worktree_branch,
+            project_dir.to_str().unwrap_or(""),
+            &existing_titles,
+        )
     };
 
-    let mut instance = Instance::new(&final_title, path.to_str().unwrap_or(""));
-
-    if let Some(group) = &group_path {
-        instance.group_path = group.trim().to_string();
-    }
-
-    if let Some(parent) = parent_id {
-        instance.parent_session_id = Some(parent);
-    }
-
+    let mut tool = "claude".to_string();
+    let mut command = String::new();
     if let Some(cmd) = &args.command {
-        instance.command = cmd.clone();
-        instance.tool = detect_tool(cmd)?;
-    }
-
-    if let Some(worktree_info) = worktree_info_opt {
-        instance.worktree_info = Some(worktree_info);
-    }
-
-    // Handle sandbox setup
-    let use_sandbox = args.sandbox || args.sandbox_image.is_some();
-    let config = Config::load()?;
-
-    if use_sandbox || config.sandbox.enabled_by_default {
-        if !docker::is_docker_available() {
-            if use_sandbox {
-                bail!(
-                    "Docker is not installed or not accessible.\n\
-                     Install Docker: https://docs.docker.com/get-docker/\n\
-                     Tip: Use 'aoe add' without --sandbox to run directly on host"
-                );
-            }
-        } else {
-            let container_name = DockerContainer::generate_name(&instance.id);
-            let image = args
-                .sandbox_image
-                .as_ref()
-                .map(|s| s.trim().to_string())
-                .unwrap_or_else(docker::effective_default_image);
-            instance.sandbox_info = Some(SandboxInfo {
-                enabled: true,
-                container_id: None,
-                image,
-                container_name,
-                created_at: None,
-                yolo_mode: None,
-                extra_env_keys: None,
-                extra_env_values: None,
-            });
+        command = cmd.clone();
+        tool = detect_tool(cmd)?;
+    } else if let Some(preset) = &preset {
+        if let Some(preset_tool) = &preset_tool {
+            tool = preset_tool.clone();
         }
-    }
-
-    // Check for repository hooks
-    let hook_result: Result<()> = (|| {
-        match repo_config::check_hook_trust(&path) {
-            Ok(repo_config::HookTrustStatus::NeedsTrust { hooks, hooks_hash }) => {
-                let should_trust = if args.trust_hooks {
-                    true
-                } else {
-                    println!("\nRepository hooks detected in .aoe/config.toml:");
-                    if !hooks.on_create.is_empty() {
-                        println!("  on_create:");
-                        for cmd in &hooks.on_create {
-                            println!("    {}", cmd);
-                        }
-                    }
-                    if !hooks.on_launch.is_empty() {
-                        println!("  on_launch:");
-                        for cmd in &hooks.on_launch {
-                            println!("    {}", cmd);
-                        }
-                    }
-                    print!("\nTrust and run these hooks? [y/N] ");
-                    use std::io::Write;
-                    std::io::stdout().flush()?;
-                    let mut input = String::new();
-                    std::io::stdin().read_line(&mut input)?;
-                    input.trim().eq_ignore_ascii_case("y")
-                };
-
-                if should_trust {
-                    trust_and_run_on_create(&path, &hooks_hash, &hooks)?;
-                } else {
-                    println!("Hooks skipped (session created without running hooks)");
-                }
-            }
-            Ok(repo_config::HookTrustStatus::Trusted(hooks)) => {
-                if !hooks.on_create.is_empty() {
-                    println!("Running on_create hooks...");
-                    repo_config::execute_hooks(&hooks.on_create, &path)?;
-                    println!("✓ on_create hooks completed");
-                }
-            }
-            Ok(repo_config::HookTrustStatus::NoHooks) => {}
-            Err(e) => {
-                tracing::warn!("Failed to check repo hooks: {}", e);
-            }
+        if let Some(cmd) = preset.command() {
+            command = cmd.to_string();
         }
-        Ok(())
-    })();
-
-    if let Err(e) = hook_result {
-        // Clean up worktree if we created one
-        if let Some(ref wt_info) = instance.worktree_info {
-            if wt_info.managed_by_aoe {
-                if let Ok(git_wt) =
-                    crate::git::GitWorktree::new(std::path::PathBuf::from(&wt_info.main_repo_path))
-                {
-                    let _ = git_wt.remove_worktree(&path);
-                }
-            }
+    } else if let Some(default_tool) = &session_defaults.default_tool {
+        if SUPPORTED_TOOLS.contains(&default_tool.as_str()) {
+            tool = default_tool.clone();
         }
-        return Err(e);
     }
 
-    instances.push(instance.clone());
+    // Handle sandbox setup
+    let preset_sandbox = preset.as_ref().and_then(|p| p.sandbox);
+    let use_sandbox = args.sandbox
+        || args.sandbox_image.is_some()
+        || !args.volumes.is_empty()
+        || args.network.is_some()
+        || preset_sandbox == Some(true);
+    let sandbox = if use_sandbox || preset_sandbox.unwrap_or(config.sandbox.enabled_by_default) {
+        if !docker::is_docker_available() && use_sandbox {
+            bail!(
+                "Docker is not installed or not accessible.\n\
+                 Install Docker: https://docs.docker.com/get-docker/\n\
+                 Tip: Use 'aoe add' without --sandbox to run directly on host"
+            );
+        }
+        docker::is_docker_available()
+    } else {
+        false
+    };
+    let sandbox_image = args
+        .sandbox_image
+        .as_ref()
+        .or(preset.as_ref().and_then(|p| p.sandbox_image.as_ref()))
+        .map(|s| s.trim().to_string())
+        .unwrap_or_else(|| config.sandbox.default_image.clone());
+    let preset = preset.unwrap_or_default();
+
+    let params = InstanceParams {
+        title: final_title,
+        path: project_dir.to_string_lossy().to_string(),
+        group: group_path
+            .as_deref()
+            .map(str::trim)
+            .unwrap_or("")
+            .to_string(),
+        tool,
+        command,
+        worktree_branch: worktree_branch.map(str::to_string),
+        create_new_branch: args.create_branch,
+        sandbox,
+        sandbox_image,
+        yolo_mode: preset.yolo_mode.unwrap_or(false),
+        extra_env_keys: preset.environment,
+        extra_env_values: preset
+            .environment_values
+            .iter()
+            .map(|(key, val)| format!("{}={}", key, val))
+            .collect(),
+        extra_volumes: args.volumes.clone(),
+        network: args.network.clone(),
+        parent_session_id: parent_id,
+    };
 
-    // Rebuild group tree
-    let mut group_tree = GroupTree::new_with_groups(&instances, &groups);
-    if !instance.group_path.is_empty() {
-        group_tree.create_group(&instance.group_path);
+    if args.dry_run {
+        // The worktree does not exist yet, so read hooks from the original checkout
+        let instance = preview_instance(storage.profile(), &params, &config)?;
+        print_dry_run(
+            storage.profile(),
+            &instance,
+            &config,
+            would_create_dir,
+            args.create_branch,
+            &project_dir,
+            args.trust_hooks,
+        );
+        return Ok(());
     }
 
-    storage.save_with_groups(&instances, &group_tree)?;
+    let trust_hooks = args.trust_hooks;
+    let instance = manager::create_session_with_hook_approval(profile, params, |hooks| {
+        if trust_hooks || confirm_repo_hooks(hooks)? {
+            println!("✓ Repository hooks trusted");
+            Ok(true)
+        } else {
+            println!("Hooks skipped (session created without running repository hooks)");
+            Ok(false)
+        }
+    })?;
 
-    println!("✓ Added session: {}", final_title);
+    println!("✓ Added session: {}", instance.title);
     println!("  Profile: {}", storage.profile());
-    println!("  Path:    {}", path.display());
+    println!("  Path:    {}", instance.project_path);
     println!("  Group:   {}", instance.group_path);
     println!("  ID:      {}", instance.id);
     if let Some(cmd) = &args.command {
@@ -288,26 +277,29 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
     if let Some(parent) = &args.parent {
         println!("  Parent:  {}", parent);
     }
+    if let Some(name) = &args.preset {
+        println!("  Preset:  {}", name);
+    }
     if instance.sandbox_info.is_some() {
         println!("  Sandbox: enabled");
+        let missing = instance.missing_env_vars();
+        if !missing.is_empty() {
+            eprintln!(
+                "Warning: host environment variables not set, they will not be passed to the container: {}",
+                missing.join(", ")
+            );
+        }
     }
 
     if args.launch {
-        let idx = instances
-            .iter()
-            .position(|i| i.id == instance.id)
-            .expect("just added instance");
-        instances[idx].start_with_size(crate::terminal::get_size())?;
-        storage.save_with_groups(&instances, &group_tree)?;
-
-        let tmux_session = crate::tmux::Session::new(&instance.id, &instance.title)?;
-        tmux_session.attach()?;
+        manager::start_session(profile, &instance.id)?;
+        manager::attach_session(profile, &instance.id)?;
     } else {
         println!();
         println!("Next steps:");
         println!(
             "  agent-of-empires session start {}   # Start the session",
-            final_title
+            instance.title
         );
         println!("  agent-of-empires                         # Open TUI and press Enter to attach");
     }
@@ -315,47 +307,185 @@ pub async fn run(profile: &str, args: AddArgs) -> Result<()> {
     Ok(())
 }
 
-pub fn is_duplicate_session(instances: &[Instance], title: &str, path: &str) -> bool {
-    let normalized_path = path.trim_end_matches('/');
-    instances.iter().any(|inst| {
-        let existing_path = inst.project_path.trim_end_matches('/');
-        existing_path == normalized_path && inst.title == title
-    })
+/// Show repository hooks that are not trusted yet and ask whether to run them
+fn confirm_repo_hooks(hooks: &HooksConfig) -> Result<bool> {
+    println!("\nRepository hooks detected in .aoe/config.toml:");
+    if !hooks.on_create.is_empty() {
+        println!("  on_create:");
+        for cmd in &hooks.on_create {
+            println!("    {}", cmd);
+        }
+    }
+    if !hooks.on_launch.is_empty() {
+        println!("  on_launch:");
+        for cmd in &hooks.on_launch {
+            println!("    {}", cmd);
+        }
+    }
+    print!("\nTrust and run these hooks? [y/N] ");
+    use std::io::Write;
+    std::io::stdout().flush()?;
+    let mut input = String::new();
+    std::io::stdin().read_line(&mut input)?;
+    Ok(input.trim().eq_ignore_ascii_case("y"))
 }
 
-fn trust_and_run_on_create(
-    project_path: &Path,
-    hooks_hash: &str,
-    hooks: &crate::session::HooksConfig,
-) -> Result<()> {
-    repo_config::trust_repo(project_path, hooks_hash)?;
-    println!("✓ Repository hooks trusted");
-    if !hooks.on_create.is_empty() {
-        println!("Running on_create hooks...");
-        repo_config::execute_hooks(&hooks.on_create, project_path)?;
-        println!("✓ on_create hooks completed");
+/// The session `params` would create, built in memory for `--dry-run`. The
+/// worktree path is computed but nothing is created.
+fn preview_instance(profile: &str, params: &InstanceParams, config: &Config) -> Result<Instance> {
+    use crate::git::GitWorktree;
+    use crate::session::WorktreeInfo;
+
+    let mut path = PathBuf::from(&params.path);
+    let mut worktree_info = None;
+    if let Some(branch) = &params.worktree_branch {
+        let main_repo_path = GitWorktree::find_main_repo(&path)?;
+        let git_wt = GitWorktree::new(main_repo_path.clone())?;
+        let template = if GitWorktree::is_bare_repo(&main_repo_path) {
+            &config.worktree.bare_repo_path_template
+        } else {
+            &config.worktree.path_template
+        };
+        let session_id = uuid::Uuid::new_v4().to_string();
+        path = git_wt.compute_path(
+            branch,
+            template,
+            &session_id[..8],
+            &config.worktree.branch_separator,
+        )?;
+        worktree_info = Some(WorktreeInfo {
+            branch: branch.clone(),
+            main_repo_path: main_repo_path.to_string_lossy().to_string(),
+            managed_by_aoe: true,
+            created_at: chrono::Utc::now(),
+            cleanup_on_delete: true,
+            base_branch: None,
+        });
     }
-    Ok(())
+
+    let mut instance = Instance::new(&params.title, path.to_str().unwrap_or(""));
+    instance.profile = profile.to_string();
+    instance.group_path = params.group.clone();
+    instance.parent_session_id = params.parent_session_id.clone();
+    instance.tool = params.tool.clone();
+    instance.command = params.command.clone();
+    instance.worktree_info = worktree_info;
+    if params.sandbox {
+        let extra_env_values: HashMap<String, String> = params
+            .extra_env_values
+            .iter()
+            .filter_map(|entry| entry.split_once('='))
+            .map(|(key, val)| (key.to_string(), val.to_string()))
+            .collect();
+        instance.sandbox_info = Some(SandboxInfo {
+            enabled: true,
+            container_id: None,
+            image: params.sandbox_image.clone(),
+            container_name: DockerContainer::generate_name(&instance.id),
+            created_at: None,
+            yolo_mode: params.yolo_mode.then_some(true),
+            extra_env_keys: (!params.extra_env_keys.is_empty())
+                .then(|| params.extra_env_keys.clone()),
+            extra_env_values: (!extra_env_values.is_empty()).then_some(extra_env_values),
+            extra_volumes: (!params.extra_volumes.is_empty()).then(|| params.extra_volumes.clone()),
+            network: params.network.clone(),
+        });
+    }
+    Ok(instance)
 }
 
-pub fn detect_tool(cmd: &str) -> Result<String> {
-    let cmd_lower = cmd.to_lowercase();
-    if cmd_lower.is_empty() || cmd_lower.contains("claude") {
-        Ok("claude".to_string())
-    } else if cmd_lower.contains("opencode") || cmd_lower.contains("open-code") {
-        Ok("opencode".to_string())
-    } else if cmd_lower.contains("vibe") || cmd_lower.contains("mistral-vibe") {
-        Ok("vibe".to_string())
-    } else if cmd_lower.contains("codex") {
-        Ok("codex".to_string())
-    } else if cmd_lower.contains("gemini") {
-        Ok("gemini".to_string())
+/// Print what `aoe add` would create for `instance`, without creating it.
+/// `config` is the profile plus repo config the session would be created with.
+fn print_dry_run(
+    profile: &str,
+    instance: &Instance,
+    config: &Config,
+    would_create_dir: bool,
+    new_branch: bool,
+    hooks_path: &Path,
+    trust_hooks: bool,
+) {
+    println!("Dry run, nothing was created:");
+    println!("  Title:    {}", instance.title);
+    println!("  Profile:  {}", profile);
+    if would_create_dir {
+        println!("  Path:     {} (would be created)", instance.project_path);
     } else {
-        bail!(
-            "Unknown tool in command: {}\n\
-             Supported tools: claude, opencode, vibe, codex, gemini\n\
-             Tip: Command must contain one of the supported tool names",
-            cmd
-        )
+        println!("  Path:     {}", instance.project_path);
+    }
+    if let Some(wt) = &instance.worktree_info {
+        let kind = if new_branch { "new branch" } else { "branch" };
+        println!(
+            "  Worktree: {} {} of {} (would be created)",
+            kind, wt.branch, wt.main_repo_path
+        );
+    }
+    println!("  Group:    {}", instance.group_path);
+    println!("  Tool:     {}", instance.tool);
+    if !instance.command.is_empty() {
+        println!("  Cmd:      {}", instance.command);
+    }
+
+    match &instance.sandbox_info {
+        Some(sandbox) if instance.is_sandboxed() => {
+            let image_state = if docker::image_exists_locally(&sandbox.image) {
+                "present locally"
+            } else {
+                "would be pulled"
+            };
+            println!("  Sandbox:  {} ({})", sandbox.image, image_state);
+            if sandbox.yolo_mode == Some(true) {
+                println!("  YOLO:     enabled");
+            }
+            let env = instance.sandbox_env_preview_with(config);
+            if !env.is_empty() {
+                println!("  Env:      {}", env.join(", "));
+            }
+            let missing = instance.missing_env_vars_with(config);
+            if !missing.is_empty() {
+                println!("  Unset:    {} (not passed)", missing.join(", "));
+            }
+            if let Some(volumes) = &sandbox.extra_volumes {
+                println!("  Volumes:  {}", volumes.join(", "));
+            }
+            if let Some(network) = &sandbox.network {
+                println!("  Network:  {}", network);
+            }
+        }
+        _ => println!("  Sandbox:  disabled"),
     }
+
+    let (hooks, trust) = match repo_config::check_hook_trust(hooks_path) {
+        Ok(repo_config::HookTrustStatus::NoHooks) => {
+            println!("  Hooks:    none");
+            return;
+        }
+        Ok(repo_config::HookTrustStatus::Trusted(hooks)) => (hooks, "trusted"),
+        Ok(repo_config::HookTrustStatus::NeedsTrust { hooks, .. }) => {
+            if trust_hooks {
+                (hooks, "not trusted yet, --trust-hooks would trust them")
+            } else {
+                (hooks, "not trusted yet, you would be asked first")
+            }
+        }
+        Err(e) => {
+            println!("  Hooks:    failed to check repo hooks: {}", e);
+            return;
+        }
+    };
+    println!("  Hooks:    {}", trust);
+    for cmd in &hooks.on_create {
+        println!("    on_create: {}", cmd);
+    }
+    for cmd in &hooks.on_launch {
+        println!("    on_launch: {}", cmd);
+    }
+}
+
+pub fn is_duplicate_session(instances: &[Instance], title: &str, path: &str) -> bool {
+    let normalized_path = path.trim_end_matches('/');
+    instances.iter().any(|inst| {
+        let existing_path = inst.project_path.trim_end_matches('/');
+        existing_path == normalized_path && inst.title == title
+    })
 }
@@ -10,8 +10,10 @@ use super::group::GroupCommands;
 use super::import::ImportArgs;
 use super::init::InitArgs;
 use super::list::ListArgs;
+use super::migrate::MigrateArgs;
 use super::profile::ProfileCommands;
 use super::remove::RemoveArgs;
+use super::sandbox::SandboxCommands;
 use super::session::SessionCommands;
 use super::sounds::SoundsCommands;
 use super::status::StatusArgs;
@@ -35,6 +37,11 @@ pub struct Cli {
     #[arg(short = 'p', long, global = true, env = "AGENT_OF_EMPIRES_PROFILE")]
     pub profile: Option<String>,
 
+    /// Launch the TUI as a read-only dashboard: sessions and statuses are shown,
+    /// but creating, deleting, renaming, and attaching are disabled
+    #[arg(long)]
+    pub readonly: bool,
+
     #[command(subcommand)]
     pub command: Option<Commands>,
 }
@@ -79,6 +86,12 @@ pub enum Commands {
         command: Option<ProfileCommands>,
     },
 
+    /// Manage Docker sandbox resources
+    Sandbox {
+        #[command(subcommand)]
+        command: SandboxCommands,
+    },
+
     /// Manage git worktrees for parallel development
     Worktree {
         #[command(subcommand)]
@@ -97,6 +110,9 @@ pub enum Commands {
         command: SoundsCommands,
     },
 
+    /// Show or apply pending data migrations
+    Migrate(MigrateArgs),
+
     /// Uninstall Agent of Empires
     Uninstall(UninstallArgs),
 }
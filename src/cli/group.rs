@@ -162,7 +162,7 @@ async fn delete_group(profile: &str, args: GroupDeleteArgs) -> Result<()> {
     // Check for sessions in this group
     let session_count = instances
         .iter()
-        .filter(|i| i.group_path == name || i.group_path.starts_with(&format!("{}/", name)))
+        .filter(|i| super::in_group(&i.group_path, name))
         .count();
 
     if session_count > 0 {
@@ -176,7 +176,7 @@ async fn delete_group(profile: &str, args: GroupDeleteArgs) -> Result<()> {
 
         // Move sessions to default group
         for inst in &mut instances {
-            if inst.group_path == name || inst.group_path.starts_with(&format!("{}/", name)) {
+            if super::in_group(&inst.group_path, name) {
                 inst.group_path = String::new();
             }
         }
@@ -198,10 +198,8 @@ async fn move_session(profile: &str, args: GroupMoveArgs) -> Result<()> {
     let (mut instances, groups) = storage.load_with_groups()?;
 
     let identifier = args.identifier.trim();
-    let inst = instances
-        .iter_mut()
-        .find(|i| i.id == identifier || i.id.starts_with(identifier) || i.title == identifier)
-        .ok_or_else(|| anyhow::anyhow!("Session not found: {}", identifier))?;
+    let idx = super::resolve_session_index(identifier, &instances)?;
+    let inst = &mut instances[idx];
 
     let group = args.group.trim();
     let old_group = inst.group_path.clone();
@@ -7,9 +7,9 @@ use clap::Args;
 use serde::Deserialize;
 
 use crate::session::builder::{self, InstanceParams};
-use crate::session::{GroupTree, Storage};
+use crate::session::{detect_tool, resolve_config, GroupTree, Storage};
 
-use super::add::{detect_tool, is_duplicate_session};
+use super::add::is_duplicate_session;
 
 #[derive(Args)]
 pub struct ImportArgs {
@@ -82,6 +82,7 @@ pub async fn run(profile: &str, args: ImportArgs) -> Result<()> {
         return Ok(());
     }
 
+    let config = resolve_config(profile)?;
     let storage = Storage::new(profile)?;
     let (mut instances, groups) = storage.load_with_groups()?;
     let mut group_tree = GroupTree::new_with_groups(&instances, &groups);
@@ -163,13 +164,14 @@ pub async fn run(profile: &str, args: ImportArgs) -> Result<()> {
         let sandbox_image = entry
             .sandbox_image
             .clone()
-            .unwrap_or_else(crate::docker::effective_default_image);
+            .unwrap_or_else(|| config.sandbox.default_image.clone());
 
         let params = InstanceParams {
             title: entry.title.clone(),
             path: path_str.clone(),
             group: group.clone(),
             tool: tool.clone(),
+            command: String::new(),
             worktree_branch: entry.worktree.clone(),
             create_new_branch: entry.create_branch,
             sandbox: entry.sandbox,
@@ -177,11 +179,14 @@ pub async fn run(profile: &str, args: ImportArgs) -> Result<()> {
             yolo_mode: entry.yolo,
             extra_env_keys: Vec::new(),
             extra_env_values: Vec::new(),
+            extra_volumes: Vec::new(),
+            network: None,
+            parent_session_id: None,
         };
 
         let existing_titles: Vec<&str> = instances.iter().map(|i| i.title.as_str()).collect();
 
-        match builder::build_instance(params, &existing_titles) {
+        match builder::build_instance(storage.profile(), params, &existing_titles) {
             Ok(result) => {
                 let mut instance = result.instance;
 
@@ -1,15 +1,17 @@
 //! `agent-of-empires list` command implementation
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use clap::Args;
+use ratatui::style::Color;
 use serde::Serialize;
+use std::io::IsTerminal;
 
-use crate::session::{Instance, Storage};
+use crate::session::{resolve_config, Instance, Status, Storage, SUPPORTED_TOOLS};
+use crate::tui::styles::Theme;
 
 const TABLE_COL_TITLE: usize = 20;
 const TABLE_COL_GROUP: usize = 15;
 const TABLE_COL_PATH: usize = 40;
-const TABLE_COL_ID_DISPLAY: usize = 12;
 
 #[derive(Args)]
 pub struct ListArgs {
@@ -20,6 +22,86 @@ pub struct ListArgs {
     /// List sessions from all profiles
     #[arg(long)]
     all: bool,
+
+    /// Only show sessions using this tool ("default" uses the configured default tool)
+    #[arg(long)]
+    tool: Option<String>,
+
+    /// Only show sessions with this status
+    #[arg(long, value_parser = ["running", "waiting", "idle", "error", "exited"])]
+    status: Option<String>,
+
+    #[command(flatten)]
+    age: super::AgeFilterArgs,
+
+    /// Plain output without colors or status icons (also when NO_COLOR is set or stdout is not a terminal)
+    #[arg(long)]
+    no_color: bool,
+}
+
+/// Filters applied to loaded sessions before rendering. All set filters must match.
+struct ListFilter<'a> {
+    tool: Option<String>,
+    status: Option<Status>,
+    age: &'a super::AgeFilterArgs,
+}
+
+impl<'a> ListFilter<'a> {
+    fn from_args(profile: &str, args: &'a ListArgs) -> Result<Self> {
+        let tool = match args.tool.as_deref().map(str::trim) {
+            None => None,
+            Some("default") => Some(
+                resolve_config(profile)?
+                    .session
+                    .default_tool
+                    .unwrap_or_else(|| "claude".to_string()),
+            ),
+            Some(tool) if SUPPORTED_TOOLS.contains(&tool) => Some(tool.to_string()),
+            Some(tool) => bail!(
+                "Unknown tool '{}'. Expected one of: default, {}",
+                tool,
+                SUPPORTED_TOOLS.join(", ")
+            ),
+        };
+
+        let status = args.status.as_deref().map(|s| match s {
+            "running" => Status::Running,
+            "waiting" => Status::Waiting,
+            "error" => Status::Error,
+            "exited" => Status::Exited,
+            _ => Status::Idle,
+        });
+
+        Ok(Self {
+            tool,
+            status,
+            age: &args.age,
+        })
+    }
+
+    /// Drop sessions that don't match. Live status costs a pane capture per
+    /// session, so it is only queried from tmux when filtering by status or
+    /// when the output shows it (`show_status`).
+    fn apply(&self, instances: &mut Vec<Instance>, show_status: bool) {
+        if let Some(tool) = &self.tool {
+            instances.retain(|inst| &inst.tool == tool);
+        }
+        instances.retain(|inst| self.age.matches(inst));
+
+        if self.status.is_some() || show_status {
+            crate::tmux::refresh_session_cache();
+            for inst in instances.iter_mut() {
+                inst.update_status();
+            }
+        }
+
+        if let Some(status) = self.status {
+            instances.retain(|inst| match inst.status {
+                Status::Starting => status == Status::Idle,
+                current => current == status,
+            });
+        }
+    }
 }
 
 #[derive(Serialize)]
@@ -31,52 +113,104 @@ struct SessionJson {
     tool: String,
     #[serde(skip_serializing_if = "String::is_empty")]
     command: String,
+    status: Status,
     profile: String,
     created_at: chrono::DateTime<chrono::Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_accessed_at: Option<chrono::DateTime<chrono::Utc>>,
 }
 
-fn print_table_header() {
+/// Print sessions as a table whose columns are sized to the widest cell (capped at
+/// the `TABLE_COL_*` limits). With color, rows are prefixed with the same
+/// status icons the TUI uses; plain output leaves the status column out.
+/// IDs are shortened to the shortest prefix that is unique among `all`.
+fn print_table(instances: &[Instance], all: &[Instance], color: bool) {
+    let theme = Theme::default();
+    let rows: Vec<[String; 4]> = instances
+        .iter()
+        .map(|inst| {
+            [
+                super::truncate(&inst.title, TABLE_COL_TITLE),
+                super::truncate(&inst.group_path, TABLE_COL_GROUP),
+                super::truncate(&inst.project_path, TABLE_COL_PATH),
+                super::short_id(&inst.id, all, super::SHORT_ID_MIN_LEN).to_string(),
+            ]
+        })
+        .collect();
+
+    let headers = ["TITLE", "GROUP", "PATH", "ID"];
+    let mut widths = headers.map(str::len);
+    for row in &rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let indent = if color { "  " } else { "" };
     println!(
-        "{:<width_title$} {:<width_group$} {:<width_path$} ID",
-        "TITLE",
-        "GROUP",
-        "PATH",
-        width_title = TABLE_COL_TITLE,
-        width_group = TABLE_COL_GROUP,
-        width_path = TABLE_COL_PATH
+        "{}{:<w0$} {:<w1$} {:<w2$} {}",
+        indent,
+        headers[0],
+        headers[1],
+        headers[2],
+        headers[3],
+        w0 = widths[0],
+        w1 = widths[1],
+        w2 = widths[2]
     );
     println!(
         "{}",
-        "-".repeat(TABLE_COL_TITLE + TABLE_COL_GROUP + TABLE_COL_PATH + TABLE_COL_ID_DISPLAY + 5)
+        "-".repeat(indent.len() + widths.iter().sum::<usize>() + 3)
     );
+
+    for (inst, row) in instances.iter().zip(&rows) {
+        let icon = if color {
+            paint(inst.status.icon(), theme.status_color(inst.status), color) + " "
+        } else {
+            String::new()
+        };
+        println!(
+            "{}{:<w0$} {:<w1$} {:<w2$} {}",
+            icon,
+            row[0],
+            row[1],
+            row[2],
+            row[3],
+            w0 = widths[0],
+            w1 = widths[1],
+            w2 = widths[2]
+        );
+    }
 }
 
-fn print_table_row(inst: &Instance) {
-    let title = super::truncate(&inst.title, TABLE_COL_TITLE);
-    let group = super::truncate(&inst.group_path, TABLE_COL_GROUP);
-    let path = super::truncate(&inst.project_path, TABLE_COL_PATH);
-    let id_display = super::truncate_id(&inst.id, TABLE_COL_ID_DISPLAY);
-    println!(
-        "{:<width_title$} {:<width_group$} {:<width_path$} {}",
-        title,
-        group,
-        path,
-        id_display,
-        width_title = TABLE_COL_TITLE,
-        width_group = TABLE_COL_GROUP,
-        width_path = TABLE_COL_PATH
-    );
+/// Color is used only when stdout is a terminal, `NO_COLOR` is unset, and
+/// `--no-color` wasn't passed.
+fn use_color(no_color: bool) -> bool {
+    !no_color && std::env::var_os("NO_COLOR").is_none() && std::io::stdout().is_terminal()
+}
+
+/// Wrap `text` in a 24-bit ANSI foreground color escape when enabled.
+fn paint(text: &str, color: Color, enabled: bool) -> String {
+    match color {
+        Color::Rgb(r, g, b) if enabled => format!("\x1b[38;2;{};{};{}m{}\x1b[0m", r, g, b, text),
+        _ => text.to_string(),
+    }
 }
 
 pub async fn run(profile: &str, args: ListArgs) -> Result<()> {
+    let filter = ListFilter::from_args(profile, &args)?;
+
     if args.all {
-        return run_all_profiles(args.json).await;
+        return run_all_profiles(args.json, use_color(args.no_color), &filter).await;
     }
 
     let storage = Storage::new(profile)?;
-    let (instances, _) = storage.load_with_groups()?;
+    let (mut instances, _) = storage.load_with_groups()?;
+    let all = instances.clone();
+    let color = use_color(args.no_color);
+    filter.apply(&mut instances, args.json || color);
 
-    if instances.is_empty() {
+    if instances.is_empty() && !args.json {
         println!("No sessions found in profile '{}'.", storage.profile());
         return Ok(());
     }
@@ -91,8 +225,10 @@ pub async fn run(profile: &str, args: ListArgs) -> Result<()> {
                 group: inst.group_path.clone(),
                 tool: inst.tool.clone(),
                 command: inst.command.clone(),
+                status: inst.status,
                 profile: storage.profile().to_string(),
                 created_at: inst.created_at,
+                last_accessed_at: inst.last_accessed_at,
             })
             .collect();
         println!("{}", serde_json::to_string_pretty(&sessions)?);
@@ -100,10 +236,7 @@ pub async fn run(profile: &str, args: ListArgs) -> Result<()> {
     }
 
     println!("Profile: {}\n", storage.profile());
-    print_table_header();
-    for inst in &instances {
-        print_table_row(inst);
-    }
+    print_table(&instances, &all, color);
     println!("\nTotal: {} sessions", instances.len());
 
     crate::update::print_update_notice().await;
@@ -111,7 +244,7 @@ pub async fn run(profile: &str, args: ListArgs) -> Result<()> {
     Ok(())
 }
 
-async fn run_all_profiles(json: bool) -> Result<()> {
+async fn run_all_profiles(json: bool, color: bool, filter: &ListFilter<'_>) -> Result<()> {
     let profiles = crate::session::list_profiles()?;
 
     if profiles.is_empty() {
@@ -123,7 +256,8 @@ async fn run_all_profiles(json: bool) -> Result<()> {
         let mut all_sessions: Vec<SessionJson> = Vec::new();
         for profile_name in &profiles {
             if let Ok(storage) = Storage::new(profile_name) {
-                if let Ok((instances, _)) = storage.load_with_groups() {
+                if let Ok((mut instances, _)) = storage.load_with_groups() {
+                    filter.apply(&mut instances, true);
                     for inst in instances {
                         all_sessions.push(SessionJson {
                             id: inst.id,
@@ -132,8 +266,10 @@ async fn run_all_profiles(json: bool) -> Result<()> {
                             group: inst.group_path,
                             tool: inst.tool,
                             command: inst.command,
+                            status: inst.status,
                             profile: profile_name.clone(),
                             created_at: inst.created_at,
+                            last_accessed_at: inst.last_accessed_at,
                         });
                     }
                 }
@@ -146,16 +282,15 @@ async fn run_all_profiles(json: bool) -> Result<()> {
     let mut total_sessions = 0;
     for profile_name in &profiles {
         if let Ok(storage) = Storage::new(profile_name) {
-            if let Ok((instances, _)) = storage.load_with_groups() {
+            if let Ok((mut instances, _)) = storage.load_with_groups() {
+                let all = instances.clone();
+                filter.apply(&mut instances, color);
                 if instances.is_empty() {
                     continue;
                 }
 
                 println!("\n═══ Profile: {} ═══\n", profile_name);
-                print_table_header();
-                for inst in &instances {
-                    print_table_row(inst);
-                }
+                print_table(&instances, &all, color);
                 println!("({} sessions)", instances.len());
                 total_sessions += instances.len();
             }
@@ -0,0 +1,56 @@
+//! `aoe migrate` command implementation
+
+use anyhow::Result;
+use clap::{Args, Subcommand};
+
+use crate::migrations;
+
+#[derive(Args)]
+pub struct MigrateArgs {
+    #[command(subcommand)]
+    command: Option<MigrateCommands>,
+
+    /// Show what would be migrated without applying anything
+    #[arg(long)]
+    dry_run: bool,
+}
+
+#[derive(Subcommand)]
+pub enum MigrateCommands {
+    /// List data migrations and whether each has been applied
+    Status,
+}
+
+pub fn run(args: MigrateArgs) -> Result<()> {
+    match args.command {
+        Some(MigrateCommands::Status) => {
+            for m in migrations::status() {
+                let state = if m.applied { "applied" } else { "pending" };
+                println!("v{:03}  {:<8}  {}", m.version, state, m.name);
+            }
+            Ok(())
+        }
+        None => {
+            let pending = migrations::pending();
+            if pending.is_empty() {
+                println!("No pending migrations.");
+                return Ok(());
+            }
+
+            if args.dry_run {
+                println!("Would run {} migration(s):", pending.len());
+            } else {
+                println!("Running {} migration(s):", pending.len());
+            }
+            for m in &pending {
+                println!("  v{:03} {}: {}", m.version, m.name, m.description);
+            }
+
+            if !args.dry_run {
+                migrations::run_migrations()?;
+                println!("✓ Migrations complete");
+            }
+            Ok(())
+        }
+    }
+}
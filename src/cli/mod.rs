@@ -6,8 +6,10 @@ pub mod group;
 pub mod import;
 pub mod init;
 pub mod list;
+pub mod migrate;
 pub mod profile;
 pub mod remove;
+pub mod sandbox;
 pub mod session;
 pub mod sounds;
 pub mod status;
@@ -17,31 +19,132 @@ pub mod worktree;
 
 pub use definition::{Cli, Commands};
 
+pub use crate::session::{
+    glob_match, in_group, is_glob, resolve_session, resolve_session_index, short_id, truncate_id,
+    SHORT_ID_MIN_LEN,
+};
+
 use crate::session::Instance;
 use anyhow::{bail, Result};
+use chrono::{DateTime, Duration, Local, NaiveDate, NaiveDateTime, TimeZone, Utc};
+use clap::Args;
+use std::io::{self, IsTerminal, Write};
+
+/// Age filters shared by `list` and `status`. Each bound accepts a relative
+/// duration (`30m`, `24h`, `7d`, `2w`) measured back from now, or an absolute
+/// date (`2026-01-31`, `2026-01-31 14:00` in local time, or RFC 3339).
+#[derive(Args, Default)]
+pub struct AgeFilterArgs {
+    /// Only sessions created before this time
+    #[arg(long, visible_alias = "until", value_parser = parse_time_bound)]
+    pub created_before: Option<DateTime<Utc>>,
+
+    /// Only sessions created after this time
+    #[arg(long, visible_alias = "since", value_parser = parse_time_bound)]
+    pub created_after: Option<DateTime<Utc>>,
+
+    /// Only sessions last accessed before this time (never-accessed sessions use their creation time)
+    #[arg(long, value_parser = parse_time_bound)]
+    pub accessed_before: Option<DateTime<Utc>>,
 
-pub fn resolve_session<'a>(identifier: &str, instances: &'a [Instance]) -> Result<&'a Instance> {
-    // Try exact ID match
-    if let Some(inst) = instances.iter().find(|i| i.id == identifier) {
-        return Ok(inst);
+    /// Only sessions last accessed after this time (never-accessed sessions use their creation time)
+    #[arg(long, value_parser = parse_time_bound)]
+    pub accessed_after: Option<DateTime<Utc>>,
+}
+
+impl AgeFilterArgs {
+    pub fn matches(&self, inst: &Instance) -> bool {
+        let accessed = inst.last_accessed_at.unwrap_or(inst.created_at);
+        self.created_before.map_or(true, |t| inst.created_at < t)
+            && self.created_after.map_or(true, |t| inst.created_at > t)
+            && self.accessed_before.map_or(true, |t| accessed < t)
+            && self.accessed_after.map_or(true, |t| accessed > t)
     }
+}
 
-    // Try ID prefix match
-    if let Some(inst) = instances.iter().find(|i| i.id.starts_with(identifier)) {
-        return Ok(inst);
+/// Parse a time bound relative to the current time. See [`AgeFilterArgs`].
+pub fn parse_time_bound(value: &str) -> Result<DateTime<Utc>, String> {
+    parse_time_bound_at(value, Utc::now(), &Local)
+}
+
+/// Dates and times without an offset are read in `tz`
+fn parse_time_bound_at<Tz: TimeZone>(
+    value: &str,
+    now: DateTime<Utc>,
+    tz: &Tz,
+) -> Result<DateTime<Utc>, String> {
+    let value = value.trim();
+
+    if let Some(unit) = value.chars().last().filter(char::is_ascii_alphabetic) {
+        if let Ok(amount) = value[..value.len() - 1].parse::<i64>() {
+            let duration = match unit {
+                's' => Duration::try_seconds(amount),
+                'm' => Duration::try_minutes(amount),
+                'h' => Duration::try_hours(amount),
+                'd' => Duration::try_days(amount),
+                'w' => Duration::try_weeks(amount),
+                _ => {
+                    return Err(format!(
+                        "unknown duration unit '{}' (use s, m, h, d, or w)",
+                        unit
+                    ))
+                }
+            };
+            return duration
+                .and_then(|d| now.checked_sub_signed(d))
+                .ok_or_else(|| format!("duration '{}' is out of range", value));
+        }
     }
 
-    // Try exact title match
-    if let Some(inst) = instances.iter().find(|i| i.title == identifier) {
-        return Ok(inst);
+    if let Ok(time) = DateTime::parse_from_rfc3339(value) {
+        return Ok(time.with_timezone(&Utc));
     }
+    let naive = ["%Y-%m-%d %H:%M:%S", "%Y-%m-%d %H:%M", "%Y-%m-%dT%H:%M"]
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(value, format).ok())
+        .or_else(|| {
+            NaiveDate::parse_from_str(value, "%Y-%m-%d")
+                .ok()
+                .map(|date| date.and_hms_opt(0, 0, 0).expect("midnight is valid"))
+        });
+    if let Some(naive) = naive {
+        // A time skipped by a DST change has no local equivalent; a repeated
+        // one resolves to its first occurrence
+        return tz
+            .from_local_datetime(&naive)
+            .earliest()
+            .map(|time| time.with_timezone(&Utc))
+            .ok_or_else(|| format!("'{}' does not exist in the local time zone", value));
+    }
+
+    Err(format!(
+        "invalid time '{}' (expected a duration like 7d, 24h, 30m or a date like 2026-01-31)",
+        value
+    ))
+}
 
-    // Try path match
-    if let Some(inst) = instances.iter().find(|i| i.project_path == identifier) {
-        return Ok(inst);
+/// Ask a yes/no question on stdin. Returns `default` when the user just presses Enter.
+///
+/// Bails instead of prompting when stdin is not a terminal, so scripts fail fast
+/// and are pointed at `--yes` rather than hanging on input that never arrives.
+pub fn confirm(prompt: &str, default: bool) -> Result<bool> {
+    if !io::stdin().is_terminal() {
+        bail!("Confirmation required but stdin is not a terminal. Re-run with --yes to proceed.");
     }
 
-    bail!("Session not found: {}", identifier)
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+    print!("{} {} ", prompt, hint);
+    io::stdout().flush()?;
+
+    let mut response = String::new();
+    io::stdin().read_line(&mut response)?;
+    let response = response.trim().to_lowercase();
+
+    Ok(match response.as_str() {
+        "" => default,
+        "y" | "yes" => true,
+        _ => false,
+    })
 }
 
 pub fn truncate(s: &str, max: usize) -> String {
@@ -56,10 +159,93 @@ pub fn truncate(s: &str, max: usize) -> String {
     }
 }
 
-pub fn truncate_id(id: &str, max_len: usize) -> &str {
-    if id.len() > max_len {
-        &id[..max_len]
-    } else {
-        id
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_time_bound_relative() {
+        let now = Utc::now();
+        assert_eq!(
+            parse_time_bound_at("30m", now, &Utc).unwrap(),
+            now - Duration::minutes(30)
+        );
+        assert_eq!(
+            parse_time_bound_at("24h", now, &Utc).unwrap(),
+            now - Duration::hours(24)
+        );
+        assert_eq!(
+            parse_time_bound_at("7d", now, &Utc).unwrap(),
+            now - Duration::days(7)
+        );
+        assert_eq!(
+            parse_time_bound_at("2w", now, &Utc).unwrap(),
+            now - Duration::weeks(2)
+        );
+        assert!(parse_time_bound_at("7y", now, &Utc).is_err());
+        assert!(parse_time_bound_at("d", now, &Utc).is_err());
+    }
+
+    #[test]
+    fn test_parse_time_bound_absolute() {
+        let now = Utc::now();
+        let expected = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        assert_eq!(
+            parse_time_bound_at("2026-01-31", now, &Utc).unwrap(),
+            expected("2026-01-31T00:00:00Z")
+        );
+        assert_eq!(
+            parse_time_bound_at("2026-01-31 14:30", now, &Utc).unwrap(),
+            expected("2026-01-31T14:30:00Z")
+        );
+        assert_eq!(
+            parse_time_bound_at("2026-01-31T14:30:00+02:00", now, &Utc).unwrap(),
+            expected("2026-01-31T12:30:00Z")
+        );
+        assert!(parse_time_bound_at("last tuesday", now, &Utc).is_err());
+    }
+
+    #[test]
+    fn test_parse_time_bound_reads_naive_times_in_local_zone() {
+        let now = Utc::now();
+        let tz = chrono::FixedOffset::east_opt(2 * 3600).unwrap();
+        let expected = |s: &str| DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc);
+        assert_eq!(
+            parse_time_bound_at("2026-01-31", now, &tz).unwrap(),
+            expected("2026-01-30T22:00:00Z")
+        );
+        assert_eq!(
+            parse_time_bound_at("2026-01-31 14:30", now, &tz).unwrap(),
+            expected("2026-01-31T12:30:00Z")
+        );
+        // An explicit offset wins over the local zone
+        assert_eq!(
+            parse_time_bound_at("2026-01-31T14:30:00Z", now, &tz).unwrap(),
+            expected("2026-01-31T14:30:00Z")
+        );
+    }
+
+    #[test]
+    fn test_age_filter_matches() {
+        let now = Utc::now();
+        let mut old = Instance::new("old", "/tmp/old");
+        old.created_at = now - Duration::days(30);
+        let mut touched = Instance::new("touched", "/tmp/touched");
+        touched.created_at = now - Duration::days(30);
+        touched.last_accessed_at = Some(now - Duration::hours(1));
+
+        let stale = AgeFilterArgs {
+            accessed_before: Some(now - Duration::days(7)),
+            ..Default::default()
+        };
+        assert!(stale.matches(&old));
+        assert!(!stale.matches(&touched));
+
+        let recent = AgeFilterArgs {
+            created_after: Some(now - Duration::days(7)),
+            ..Default::default()
+        };
+        assert!(!recent.matches(&old));
+        assert!(recent.matches(&Instance::new("new", "/tmp/new")));
     }
 }
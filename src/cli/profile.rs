@@ -2,13 +2,12 @@
 
 use anyhow::{bail, Result};
 use clap::Subcommand;
-use std::io::{self, Write};
 
 use crate::session;
 
 #[derive(Subcommand)]
 pub enum ProfileCommands {
-    /// List all profiles
+    /// List all profiles with session counts
     #[command(alias = "ls")]
     List,
 
@@ -24,6 +23,10 @@ pub enum ProfileCommands {
     Delete {
         /// Profile name
         name: String,
+
+        /// Skip confirmation prompt
+        #[arg(short = 'y', long)]
+        yes: bool,
     },
 
     /// Show or set default profile
@@ -33,11 +36,11 @@ pub enum ProfileCommands {
     },
 }
 
-pub async fn run(command: Option<ProfileCommands>) -> Result<()> {
+pub async fn run(active_profile: &str, command: Option<ProfileCommands>) -> Result<()> {
     match command {
-        Some(ProfileCommands::List) | None => list_profiles().await,
+        Some(ProfileCommands::List) | None => list_profiles(active_profile).await,
         Some(ProfileCommands::Create { name }) => create_profile(&name).await,
-        Some(ProfileCommands::Delete { name }) => delete_profile(&name).await,
+        Some(ProfileCommands::Delete { name, yes }) => delete_profile(&name, yes).await,
         Some(ProfileCommands::Default { name }) => {
             if let Some(n) = name {
                 set_default_profile(&n).await
@@ -48,29 +51,60 @@ pub async fn run(command: Option<ProfileCommands>) -> Result<()> {
     }
 }
 
-async fn list_profiles() -> Result<()> {
-    let profiles = session::list_profiles()?;
+async fn list_profiles(active_profile: &str) -> Result<()> {
+    let summaries = session::summarize_profiles()?;
     let config = session::load_config()?;
     let default_profile = config
         .as_ref()
         .map(|c| c.default_profile.as_str())
         .unwrap_or(session::DEFAULT_PROFILE);
+    let active_profile = if active_profile.is_empty() {
+        session::DEFAULT_PROFILE
+    } else {
+        active_profile
+    };
 
-    if profiles.is_empty() {
+    if summaries.is_empty() {
         println!("No profiles found.");
         println!("Run 'agent-of-empires' to create the default profile automatically.");
         return Ok(());
     }
 
-    println!("Profiles:");
-    for p in &profiles {
-        if p == default_profile {
-            println!("  * {} (default)", p);
+    let name_width = summaries
+        .iter()
+        .map(|s| s.name.chars().count())
+        .max()
+        .unwrap_or(0)
+        .max("PROFILE".len());
+
+    println!(
+        "    {:<name_width$}  {:>8}  {:>7}",
+        "PROFILE", "SESSIONS", "RUNNING"
+    );
+    for summary in &summaries {
+        let marker = if summary.name == active_profile {
+            "*"
         } else {
-            println!("    {}", p);
+            " "
+        };
+        let mut tags = Vec::new();
+        if summary.name == default_profile {
+            tags.push("default");
         }
+        if summary.name == active_profile {
+            tags.push("active");
+        }
+        let tags = if tags.is_empty() {
+            String::new()
+        } else {
+            format!("  ({})", tags.join(", "))
+        };
+        println!(
+            "  {} {:<name_width$}  {:>8}  {:>7}{}",
+            marker, summary.name, summary.sessions, summary.running, tags
+        );
     }
-    println!("\nTotal: {} profiles", profiles.len());
+    println!("\nTotal: {} profiles", summaries.len());
 
     Ok(())
 }
@@ -82,17 +116,12 @@ async fn create_profile(name: &str) -> Result<()> {
     Ok(())
 }
 
-async fn delete_profile(name: &str) -> Result<()> {
-    print!(
-        "Are you sure you want to delete profile '{}'? This will remove all sessions in this profile. [y/N] ",
+async fn delete_profile(name: &str, yes: bool) -> Result<()> {
+    let prompt = format!(
+        "Are you sure you want to delete profile '{}'? This will remove all sessions in this profile.",
         name
     );
-    io::stdout().flush()?;
-
-    let mut response = String::new();
-    io::stdin().read_line(&mut response)?;
-
-    if response.trim().to_lowercase() != "y" {
+    if !yes && !super::confirm(&prompt, false)? {
         println!("Cancelled.");
         return Ok(());
     }
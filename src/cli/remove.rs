@@ -2,22 +2,38 @@
 
 use anyhow::{bail, Result};
 use clap::Args;
+use std::collections::HashMap;
+use std::path::Path;
 
 use crate::docker::DockerContainer;
-use crate::session::{Config, GroupTree, Instance, Storage};
+use crate::session::manager::{self, CleanupOptions};
+use crate::session::{resolve_config, Config, GroupTree, Instance, Storage};
 
 #[derive(Args)]
 pub struct RemoveArgs {
-    /// Session ID or title to remove
-    identifier: String,
+    /// Session ID or title to remove. Glob patterns (`*`, `?`) match titles and branches
+    #[arg(required_unless_present = "group")]
+    identifier: Option<String>,
+
+    /// Remove every session in this group (and its subgroups)
+    #[arg(short = 'g', long)]
+    group: Option<String>,
 
     /// Delete worktree directory (default: keep worktree)
-    #[arg(long = "delete-worktree")]
+    #[arg(long = "delete-worktree", visible_alias = "worktree")]
     delete_worktree: bool,
 
+    /// Delete the container even if sandbox.auto_cleanup is disabled
+    #[arg(long = "container", conflicts_with = "keep_container")]
+    delete_container: bool,
+
     /// Keep container instead of deleting it (default: delete per config)
     #[arg(long = "keep-container")]
     keep_container: bool,
+
+    /// Skip confirmation prompts
+    #[arg(short = 'y', long)]
+    yes: bool,
 }
 
 fn needs_worktree_cleanup(inst: &Instance, args: &RemoveArgs) -> bool {
@@ -26,142 +42,276 @@ fn needs_worktree_cleanup(inst: &Instance, args: &RemoveArgs) -> bool {
         .is_some_and(|wt| wt.managed_by_aoe && args.delete_worktree)
 }
 
+fn needs_container_cleanup(args: &RemoveArgs, config: &Config) -> bool {
+    !args.keep_container && (args.delete_container || config.sandbox.auto_cleanup)
+}
+
+/// Expand the identifier and/or `--group` filter into the ids of the sessions to remove.
+/// A plain identifier must resolve to exactly one session; only glob patterns and
+/// `--group` may select several.
+fn select_sessions(instances: &[Instance], args: &RemoveArgs) -> Result<Vec<String>> {
+    let mut candidates: Vec<&Instance> = match &args.group {
+        Some(group) => {
+            let group = group.trim();
+            instances
+                .iter()
+                .filter(|i| super::in_group(&i.group_path, group))
+                .collect()
+        }
+        None => instances.iter().collect(),
+    };
+
+    if let Some(identifier) = &args.identifier {
+        if super::is_glob(identifier) {
+            candidates.retain(|i| {
+                super::glob_match(identifier, &i.title)
+                    || i.worktree_info
+                        .as_ref()
+                        .is_some_and(|wt| super::glob_match(identifier, &wt.branch))
+            });
+        } else {
+            let owned: Vec<Instance> = candidates.into_iter().cloned().collect();
+            let inst = super::resolve_session(identifier, &owned)?;
+            return Ok(vec![inst.id.clone()]);
+        }
+    }
+
+    if candidates.is_empty() {
+        let what = match (&args.identifier, &args.group) {
+            (Some(pattern), Some(group)) => format!("'{}' in group '{}'", pattern, group),
+            (Some(pattern), None) => format!("'{}'", pattern),
+            (None, Some(group)) => format!("in group '{}'", group),
+            (None, None) => String::new(),
+        };
+        bail!("No sessions match {}", what);
+    }
+
+    Ok(candidates.into_iter().map(|i| i.id.clone()).collect())
+}
+
 pub async fn run(profile: &str, args: RemoveArgs) -> Result<()> {
     let storage = Storage::new(profile)?;
     let (instances, groups) = storage.load_with_groups()?;
 
-    let mut found = false;
-    let mut removed_title = String::new();
-    let mut new_instances = Vec::with_capacity(instances.len());
-
-    for inst in instances {
-        if inst.id == args.identifier
-            || inst.id.starts_with(&args.identifier)
-            || inst.title == args.identifier
-        {
-            found = true;
-            removed_title = inst.title.clone();
-
-            let will_cleanup_worktree = needs_worktree_cleanup(&inst, &args);
-
-            // Show warning and get confirmation for worktree deletion
-            let user_confirmed = if will_cleanup_worktree {
-                use std::io::{self, Write};
-
-                let wt_info = inst.worktree_info.as_ref().unwrap();
-                println!("\nThis will delete:");
-                println!(
-                    "  - Worktree: {} (branch: {})",
-                    inst.project_path, wt_info.branch
-                );
-                print!("\nProceed? (Y/n): ");
-                io::stdout().flush()?;
-
-                let mut response = String::new();
-                io::stdin().read_line(&mut response)?;
-                let response = response.trim().to_lowercase();
-
-                response.is_empty() || response == "y" || response == "yes"
-            } else {
-                true
-            };
-
-            // Handle worktree cleanup
-            if will_cleanup_worktree {
-                if user_confirmed {
-                    use crate::git::GitWorktree;
-                    use std::path::PathBuf;
-
-                    let wt_info = inst.worktree_info.as_ref().unwrap();
-                    let worktree_path = PathBuf::from(&inst.project_path);
-                    let main_repo = PathBuf::from(&wt_info.main_repo_path);
-
-                    match GitWorktree::new(main_repo) {
-                        Ok(git_wt) => {
-                            if let Err(e) = git_wt.remove_worktree(&worktree_path) {
-                                eprintln!("Warning: failed to remove worktree: {}", e);
-                                eprintln!(
-                                    "You may need to remove it manually with: git worktree remove {}",
-                                    inst.project_path
-                                );
-                            } else {
-                                println!("✓ Worktree removed");
-                            }
-                        }
-                        Err(e) => {
-                            eprintln!("Warning: failed to access git repository: {}", e);
-                        }
-                    }
-                } else {
-                    println!("Worktree preserved at: {}", inst.project_path);
-                }
-            } else if let Some(wt_info) = &inst.worktree_info {
-                // Worktree exists but not scheduled for deletion (user didn't use --delete-worktree)
-                if wt_info.managed_by_aoe {
-                    println!(
-                        "Worktree preserved at: {} (use --delete-worktree to remove)",
-                        inst.project_path
-                    );
-                }
-            }
+    let selected = select_sessions(&instances, &args)?;
+    // Ids must be unambiguous among every session, not just the ones removed
+    let short_ids: HashMap<String, String> = selected
+        .iter()
+        .map(|id| {
+            let short = super::short_id(id, &instances, super::SHORT_ID_MIN_LEN);
+            (id.clone(), short.to_string())
+        })
+        .collect();
+    let (to_remove, mut remaining): (Vec<Instance>, Vec<Instance>) = instances
+        .into_iter()
+        .partition(|inst| selected.contains(&inst.id));
 
-            // Kill tmux session if it exists
-            if let Ok(tmux_session) = crate::tmux::Session::new(&inst.id, &inst.title) {
-                if tmux_session.exists() {
-                    if let Err(e) = tmux_session.kill() {
-                        eprintln!("Warning: failed to kill tmux session: {}", e);
-                        eprintln!(
-                            "Session removed from Agent of Empires but may still be running in tmux"
-                        );
-                    }
-                }
-            }
+    // Worktrees still used by sessions we're keeping must survive
+    let shared_by: HashMap<String, usize> = to_remove
+        .iter()
+        .map(|inst| (inst.id.clone(), inst.worktree_shared_by(&remaining)))
+        .filter(|(_, count)| *count > 0)
+        .collect();
+
+    let worktree_cleanups: Vec<&Instance> = to_remove
+        .iter()
+        .filter(|inst| needs_worktree_cleanup(inst, &args) && !shared_by.contains_key(&inst.id))
+        .collect();
 
-            // Container cleanup (if config allows and user didn't request --keep-container)
-            if let Some(sandbox) = &inst.sandbox_info {
-                if sandbox.enabled && !args.keep_container {
-                    let config = Config::load().ok().unwrap_or_default();
-                    if config.sandbox.auto_cleanup {
-                        let container = DockerContainer::from_session_id(&inst.id);
-                        if container.exists().unwrap_or(false) {
-                            if let Err(e) = container.remove(true) {
-                                eprintln!("Warning: failed to remove container: {}", e);
-                            } else {
-                                println!("✓ Container removed");
-                            }
-                        }
-                    } else {
-                        println!(
-                            "Container preserved: {} (auto_cleanup disabled in config)",
-                            sandbox.container_name
-                        );
-                    }
-                } else if args.keep_container {
-                    println!("Container preserved: {}", sandbox.container_name);
-                }
+    // Show warning and get confirmation for bulk removal or worktree deletion
+    if !args.yes && (to_remove.len() > 1 || !worktree_cleanups.is_empty()) {
+        println!("\nThis will delete:");
+        if to_remove.len() > 1 {
+            for inst in &to_remove {
+                println!("  - Session: {} ({})", inst.title, short_ids[&inst.id]);
             }
-        } else {
-            new_instances.push(inst);
+        }
+        for inst in &worktree_cleanups {
+            let wt_info = inst.worktree_info.as_ref().unwrap();
+            println!(
+                "  - Worktree: {} (branch: {})",
+                inst.project_path, wt_info.branch
+            );
+        }
+        println!();
+        if !super::confirm("Proceed?", true)? {
+            println!("Cancelled.");
+            return Ok(());
+        }
+    }
+
+    let config = resolve_config(profile).ok().unwrap_or_default();
+    let mut removed_titles = Vec::with_capacity(to_remove.len());
+    let mut failed = 0;
+    for inst in to_remove {
+        if let Err(e) = remove_instance(&inst, &args, &config, &remaining) {
+            eprintln!("Error: failed to remove '{}': {:#}", inst.title, e);
+            eprintln!("The session was kept so the removal can be retried");
+            failed += 1;
+            remaining.push(inst);
+            continue;
+        }
+        removed_titles.push(inst.title);
+    }
+
+    // Rebuild group tree and save
+    let group_tree = GroupTree::new_with_groups(&remaining, &groups);
+    storage.save_with_groups(&remaining, &group_tree)?;
+
+    if removed_titles.len() == 1 {
+        println!(
+            "✓ Removed session: {} (from profile '{}')",
+            removed_titles[0],
+            storage.profile()
+        );
+    } else if !removed_titles.is_empty() {
+        println!(
+            "✓ Removed {} sessions from profile '{}':",
+            removed_titles.len(),
+            storage.profile()
+        );
+        for title in &removed_titles {
+            println!("  - {}", title);
         }
     }
 
-    if !found {
+    if failed > 0 {
         bail!(
-            "Session not found in profile '{}': {}",
-            storage.profile(),
-            args.identifier
+            "Failed to remove {} session{}",
+            failed,
+            if failed == 1 { "" } else { "s" }
         );
     }
 
-    // Rebuild group tree and save
-    let group_tree = GroupTree::new_with_groups(&new_instances, &groups);
-    storage.save_with_groups(&new_instances, &group_tree)?;
+    Ok(())
+}
+
+/// Tear down a single session's worktree, tmux session, and container
+/// through [`manager::remove_session_resources`], reporting each step. The
+/// worktree is kept while one of `remaining` still uses it. Every step is
+/// attempted; an error lists the ones that failed, in which case the session
+/// must be kept.
+fn remove_instance(
+    inst: &Instance,
+    args: &RemoveArgs,
+    config: &Config,
+    remaining: &[Instance],
+) -> Result<()> {
+    let delete_worktree = needs_worktree_cleanup(inst, args);
+    let shared_by = inst.worktree_shared_by(remaining);
+    if delete_worktree && shared_by > 0 {
+        println!(
+            "Worktree preserved at: {} (shared by {} other session{})",
+            inst.project_path,
+            shared_by,
+            if shared_by == 1 { "" } else { "s" }
+        );
+    } else if let Some(wt_info) = &inst.worktree_info {
+        // Worktree exists but not scheduled for deletion (user didn't use --delete-worktree)
+        if wt_info.managed_by_aoe && !delete_worktree {
+            println!(
+                "Worktree preserved at: {} (use --delete-worktree to remove)",
+                inst.project_path
+            );
+        }
+    }
+
+    // Container cleanup (--container forces it, --keep-container skips it, otherwise config decides)
+    let sandbox = inst.sandbox_info.as_ref().filter(|s| s.enabled);
+    let delete_sandbox = sandbox.is_some() && needs_container_cleanup(args, config);
+    if let Some(sandbox) = sandbox.filter(|_| !delete_sandbox) {
+        if args.keep_container {
+            println!("Container preserved: {}", sandbox.container_name);
+        } else {
+            println!(
+                "Container preserved: {} (auto_cleanup disabled in config)",
+                sandbox.container_name
+            );
+        }
+    }
 
-    println!(
-        "✓ Removed session: {} (from profile '{}')",
-        removed_title,
-        storage.profile()
+    let removes_worktree =
+        delete_worktree && shared_by == 0 && Path::new(&inst.project_path).exists();
+    let removes_container = delete_sandbox
+        && DockerContainer::from_session_id(&inst.id)
+            .exists()
+            .unwrap_or(false);
+
+    let errors = manager::remove_session_resources(
+        inst,
+        remaining,
+        CleanupOptions {
+            delete_worktree,
+            delete_branch: false,
+            delete_sandbox,
+        },
     );
+    let failed = |resource: &str| errors.iter().any(|e| e.starts_with(resource));
 
+    if removes_worktree {
+        if failed("Worktree:") {
+            eprintln!(
+                "You may need to remove it manually with: git worktree remove {}",
+                inst.project_path
+            );
+        } else {
+            println!("✓ Worktree removed");
+        }
+    }
+    if removes_container && !failed("Container:") {
+        println!("✓ Container removed");
+    }
+
+    if !errors.is_empty() {
+        bail!("{}", errors.join("; "));
+    }
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::WorktreeInfo;
+
+    #[tokio::test]
+    #[serial_test::serial]
+    async fn test_failed_cleanup_keeps_session() {
+        let home = tempfile::TempDir::new().unwrap();
+        std::env::set_var("HOME", home.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", home.path().join(".config"));
+
+        // A managed worktree whose main repo is not a git repository
+        let worktree = home.path().join("worktree");
+        std::fs::create_dir_all(&worktree).unwrap();
+        let mut inst = Instance::new("broken", worktree.to_str().unwrap());
+        inst.worktree_info = Some(WorktreeInfo {
+            branch: "feature".to_string(),
+            main_repo_path: home.path().join("not-a-repo").to_string_lossy().to_string(),
+            managed_by_aoe: true,
+            created_at: chrono::Utc::now(),
+            cleanup_on_delete: true,
+            base_branch: None,
+        });
+        let storage = Storage::new("default").unwrap();
+        let instances = vec![inst.clone()];
+        storage
+            .save_with_groups(&instances, &GroupTree::new_with_groups(&instances, &[]))
+            .unwrap();
+
+        let args = RemoveArgs {
+            identifier: Some(inst.id.clone()),
+            group: None,
+            delete_worktree: true,
+            delete_container: false,
+            keep_container: false,
+            yes: true,
+        };
+        assert!(run("default", args).await.is_err());
+
+        let (remaining, _) = storage.load_with_groups().unwrap();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].id, inst.id);
+        assert!(worktree.exists());
+    }
+}
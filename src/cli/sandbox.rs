@@ -0,0 +1,61 @@
+//! `agent-of-empires sandbox` subcommands implementation
+
+use anyhow::{bail, Result};
+use clap::{Args, Subcommand};
+
+use crate::docker;
+use crate::session::{profile_auth_volumes, resolve_config};
+
+#[derive(Subcommand)]
+pub enum SandboxCommands {
+    /// Pull the sandbox image and create auth volumes ahead of time
+    Prewarm(PrewarmArgs),
+}
+
+#[derive(Args)]
+pub struct PrewarmArgs {
+    /// Image to pull (defaults to the profile's sandbox.default_image)
+    #[arg(long)]
+    image: Option<String>,
+}
+
+pub async fn run(profile: &str, command: SandboxCommands) -> Result<()> {
+    match command {
+        SandboxCommands::Prewarm(args) => prewarm(profile, args),
+    }
+}
+
+fn prewarm(profile: &str, args: PrewarmArgs) -> Result<()> {
+    if !docker::is_docker_available() {
+        bail!("Docker is not installed");
+    }
+    if !docker::is_daemon_running() {
+        bail!("Docker daemon is not running");
+    }
+
+    let config = resolve_config(profile)?;
+    let image = args
+        .image
+        .unwrap_or_else(|| config.sandbox.default_image.clone());
+
+    if docker::image_exists_locally(&image) {
+        println!("Image {} is already present", image);
+    } else {
+        // docker pull runs in our foreground process group, so Ctrl-C stops it
+        // along with aoe; nothing has been created yet at this point
+        println!("Pulling {}... (Ctrl-C to cancel)", image);
+        docker::ensure_image_streamed(&image)?;
+        println!("✓ Pulled {}", image);
+    }
+
+    let retries = config.sandbox.docker_retries;
+    for volume in profile_auth_volumes(profile) {
+        docker::with_retries(retries, "Creating volume", || {
+            docker::ensure_named_volume(&volume)
+        })?;
+        println!("✓ Auth volume {}", volume);
+    }
+
+    println!("\nSandbox is ready for profile '{}'", profile);
+    Ok(())
+}
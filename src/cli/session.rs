@@ -3,8 +3,10 @@
 use anyhow::{bail, Result};
 use clap::{Args, Subcommand};
 use serde::Serialize;
+use std::io::IsTerminal;
 
-use crate::session::{GroupTree, Storage};
+use crate::session::redact::{is_secret_key, REDACTED};
+use crate::session::{manager, GroupTree, Instance, Storage};
 
 #[derive(Subcommand)]
 pub enum SessionCommands {
@@ -20,9 +22,19 @@ pub enum SessionCommands {
     /// Attach to session interactively
     Attach(SessionIdArgs),
 
+    /// Change a session's title
+    Rename(RenameArgs),
+
+    /// Open a shell where the agent runs: inside the container for sandboxed
+    /// sessions, in the project directory otherwise
+    Shell(SessionIdArgs),
+
     /// Show session details
     Show(ShowArgs),
 
+    /// Show everything aoe knows about a session, for debugging
+    Info(InfoArgs),
+
     /// Auto-detect current session
     Current(CurrentArgs),
 }
@@ -33,6 +45,15 @@ pub struct SessionIdArgs {
     identifier: String,
 }
 
+#[derive(Args)]
+pub struct RenameArgs {
+    /// Session ID or title
+    identifier: String,
+
+    /// New title
+    title: String,
+}
+
 #[derive(Args)]
 pub struct ShowArgs {
     /// Session ID or title (optional, auto-detects in tmux)
@@ -43,6 +64,20 @@ pub struct ShowArgs {
     json: bool,
 }
 
+#[derive(Args)]
+pub struct InfoArgs {
+    /// Session ID or title
+    identifier: String,
+
+    /// Output as JSON
+    #[arg(long)]
+    json: bool,
+
+    /// Include the exact command the agent is launched with (secrets redacted)
+    #[arg(long)]
+    show_command: bool,
+}
+
 #[derive(Args)]
 pub struct CurrentArgs {
     /// Just session name (for scripting)
@@ -74,31 +109,17 @@ pub async fn run(profile: &str, command: SessionCommands) -> Result<()> {
         SessionCommands::Stop(args) => stop_session(profile, args).await,
         SessionCommands::Restart(args) => restart_session(profile, args).await,
         SessionCommands::Attach(args) => attach_session(profile, args).await,
+        SessionCommands::Rename(args) => rename_session(profile, args).await,
+        SessionCommands::Shell(args) => open_shell(profile, args).await,
         SessionCommands::Show(args) => show_session(profile, args).await,
+        SessionCommands::Info(args) => session_info(profile, args).await,
         SessionCommands::Current(args) => current_session(args).await,
     }
 }
 
 async fn start_session(profile: &str, args: SessionIdArgs) -> Result<()> {
-    let storage = Storage::new(profile)?;
-    let (mut instances, groups) = storage.load_with_groups()?;
-
-    let idx = instances
-        .iter()
-        .position(|i| {
-            i.id == args.identifier
-                || i.id.starts_with(&args.identifier)
-                || i.title == args.identifier
-        })
-        .ok_or_else(|| anyhow::anyhow!("Session not found: {}", args.identifier))?;
-
-    instances[idx].start_with_size(crate::terminal::get_size())?;
-    let title = instances[idx].title.clone();
-
-    let group_tree = GroupTree::new_with_groups(&instances, &groups);
-    storage.save_with_groups(&instances, &group_tree)?;
-
-    println!("✓ Started session: {}", title);
+    let instance = manager::start_session(profile, &args.identifier)?;
+    println!("✓ Started session: {}", instance.title);
     Ok(())
 }
 
@@ -123,14 +144,7 @@ async fn restart_session(profile: &str, args: SessionIdArgs) -> Result<()> {
     let storage = Storage::new(profile)?;
     let (mut instances, groups) = storage.load_with_groups()?;
 
-    let idx = instances
-        .iter()
-        .position(|i| {
-            i.id == args.identifier
-                || i.id.starts_with(&args.identifier)
-                || i.title == args.identifier
-        })
-        .ok_or_else(|| anyhow::anyhow!("Session not found: {}", args.identifier))?;
+    let idx = super::resolve_session_index(&args.identifier, &instances)?;
 
     instances[idx].restart_with_size(crate::terminal::get_size())?;
     let title = instances[idx].title.clone();
@@ -143,20 +157,54 @@ async fn restart_session(profile: &str, args: SessionIdArgs) -> Result<()> {
 }
 
 async fn attach_session(profile: &str, args: SessionIdArgs) -> Result<()> {
+    manager::attach_session(profile, &args.identifier)
+}
+
+async fn rename_session(profile: &str, args: RenameArgs) -> Result<()> {
     let storage = Storage::new(profile)?;
-    let (instances, _) = storage.load_with_groups()?;
+    let instances = storage.load()?;
+    let old_title = super::resolve_session(&args.identifier, &instances)?
+        .title
+        .clone();
 
-    let inst = super::resolve_session(&args.identifier, &instances)?;
-    let tmux_session = crate::tmux::Session::new(&inst.id, &inst.title)?;
+    let instance = manager::rename_session(profile, &args.identifier, &args.title)?;
+    println!("✓ Renamed session: {} → {}", old_title, instance.title);
+    Ok(())
+}
 
-    if !tmux_session.exists() {
-        bail!(
-            "Session is not running. Start it first with: agent-of-empires session start {}",
-            args.identifier
-        );
+async fn open_shell(profile: &str, args: SessionIdArgs) -> Result<()> {
+    let storage = Storage::new(profile)?;
+    let (mut instances, groups) = storage.load_with_groups()?;
+
+    let idx = super::resolve_session_index(&args.identifier, &instances)?;
+
+    if instances[idx].is_sandboxed() {
+        if !std::io::stdin().is_terminal() {
+            bail!("A container shell needs an interactive terminal");
+        }
+
+        let (cmd, redactor) = instances[idx].container_shell_command()?;
+
+        // Starting the container may have recreated it with a new id
+        let group_tree = GroupTree::new_with_groups(&instances, &groups);
+        storage.save_with_groups(&instances, &group_tree)?;
+
+        tracing::debug!("Opening container shell: {}", redactor.redact(&cmd));
+        std::process::Command::new("sh")
+            .args(["-c", &cmd])
+            .status()?;
+    } else {
+        let path = &instances[idx].project_path;
+        if !std::path::Path::new(path).is_dir() {
+            bail!("Project path no longer exists: {}", path);
+        }
+
+        let shell = std::env::var("SHELL").unwrap_or_else(|_| "/bin/sh".to_string());
+        std::process::Command::new(shell)
+            .current_dir(path)
+            .status()?;
     }
 
-    tmux_session.attach()?;
     Ok(())
 }
 
@@ -217,6 +265,234 @@ async fn show_session(profile: &str, args: ShowArgs) -> Result<()> {
     Ok(())
 }
 
+/// Replace the per-session sandbox env values of credential-looking keys with
+/// a placeholder, as `--show-command` does for the launch command
+fn mask_secret_env_values(inst: &mut Instance) {
+    let values = inst
+        .sandbox_info
+        .as_mut()
+        .and_then(|sb| sb.extra_env_values.as_mut());
+    for (key, value) in values.into_iter().flatten() {
+        if is_secret_key(key) {
+            *value = REDACTED.to_string();
+        }
+    }
+}
+
+/// Names of the tmux sessions aoe uses for an instance
+#[derive(Serialize)]
+struct TmuxNames {
+    session: String,
+    running: bool,
+    terminal: String,
+    terminal_running: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    container_terminal: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    container_terminal_running: Option<bool>,
+}
+
+async fn session_info(profile: &str, args: InfoArgs) -> Result<()> {
+    let storage = Storage::new(profile)?;
+    let (instances, _) = storage.load_with_groups()?;
+    let mut inst = super::resolve_session(&args.identifier, &instances)?.clone();
+
+    crate::tmux::refresh_session_cache();
+    inst.update_status();
+
+    let session = crate::tmux::Session::generate_name(&inst.id, &inst.title);
+    let terminal = crate::tmux::TerminalSession::generate_name(&inst.id, &inst.title);
+    let container_terminal = inst
+        .is_sandboxed()
+        .then(|| crate::tmux::ContainerTerminalSession::generate_name(&inst.id, &inst.title));
+    let tmux = TmuxNames {
+        running: crate::tmux::session_exists(&session),
+        terminal_running: crate::tmux::session_exists(&terminal),
+        container_terminal_running: container_terminal
+            .as_deref()
+            .map(crate::tmux::session_exists),
+        session,
+        terminal,
+        container_terminal,
+    };
+
+    let container = if inst.is_sandboxed() {
+        crate::docker::DockerContainer::from_session_id(&inst.id)
+            .inspect_state()
+            .ok()
+            .flatten()
+    } else {
+        None
+    };
+
+    let launch_command = args.show_command.then(|| {
+        let (cmd, redactor) = inst.build_launch_command();
+        cmd.map(|cmd| redactor.redact(&cmd))
+    });
+    // The launch command above needs the real values; nothing printed below does
+    mask_secret_env_values(&mut inst);
+
+    if args.json {
+        let mut value = serde_json::to_value(&inst)?;
+        if let Some(obj) = value.as_object_mut() {
+            obj.insert("profile".to_string(), storage.profile().into());
+            if let Some(cmd) = &launch_command {
+                obj.insert("launch_command".to_string(), cmd.clone().into());
+            }
+            obj.insert("tmux".to_string(), serde_json::to_value(&tmux)?);
+            if let Some(state) = &container {
+                obj.insert(
+                    "container".to_string(),
+                    serde_json::json!({
+                        "status": state.status,
+                        "running": state.running,
+                        "started_at": state.started_at,
+                    }),
+                );
+            }
+        }
+        println!("{}", serde_json::to_string_pretty(&value)?);
+        return Ok(());
+    }
+
+    let time = |t: &chrono::DateTime<chrono::Utc>| t.format("%Y-%m-%d %H:%M:%S UTC").to_string();
+    let yes_no = |b: bool| if b { "Yes" } else { "No" };
+
+    println!("Session: {}\n", inst.title);
+    println!("  ID:             {}", inst.id);
+    println!("  Profile:        {}", storage.profile());
+    println!("  Path:           {}", inst.project_path);
+    println!(
+        "  Group:          {}",
+        if inst.group_path.is_empty() {
+            "(none)"
+        } else {
+            &inst.group_path
+        }
+    );
+    println!("  Tool:           {}", inst.tool);
+    println!("  Command:        {}", inst.get_tool_command());
+    println!("  Status:         {:?}", inst.status);
+    if let Some(err) = &inst.last_error {
+        println!("  Last error:     {}", err);
+    }
+    println!("  Created:        {}", time(&inst.created_at));
+    if let Some(t) = &inst.last_accessed_at {
+        println!("  Last accessed:  {}", time(t));
+    }
+    if let Some(parent) = &inst.parent_session_id {
+        println!("  Parent:         {}", parent);
+    }
+    if let Some(id) = &inst.claude_session_id {
+        println!("  Claude session: {}", id);
+    }
+    if let Some(base) = &inst.diff_base {
+        println!("  Diff base:      {}", base);
+    }
+
+    println!("\nTmux:");
+    println!(
+        "  Agent:          {} ({})",
+        tmux.session,
+        if tmux.running { "running" } else { "stopped" }
+    );
+    println!(
+        "  Terminal:       {} ({})",
+        tmux.terminal,
+        if tmux.terminal_running {
+            "running"
+        } else {
+            "stopped"
+        }
+    );
+    if let (Some(name), Some(running)) = (&tmux.container_terminal, tmux.container_terminal_running)
+    {
+        println!(
+            "  Container term: {} ({})",
+            name,
+            if running { "running" } else { "stopped" }
+        );
+    }
+    if let Some(term) = &inst.terminal_info {
+        println!("  Terminal created: {}", yes_no(term.created));
+        if let Some(t) = &term.created_at {
+            println!("  Terminal since:   {}", time(t));
+        }
+    }
+
+    match &inst.worktree_info {
+        Some(wt) => {
+            println!("\nWorktree:");
+            println!("  Branch:         {}", wt.branch);
+            if let Some(base) = &wt.base_branch {
+                println!("  Base branch:    {}", base);
+            }
+            println!("  Main repo:      {}", wt.main_repo_path);
+            println!("  Managed by aoe: {}", yes_no(wt.managed_by_aoe));
+            println!("  Cleanup:        {}", yes_no(wt.cleanup_on_delete));
+            println!("  Created:        {}", time(&wt.created_at));
+        }
+        None => println!("\nWorktree: none"),
+    }
+
+    match &inst.sandbox_info {
+        Some(sb) => {
+            println!("\nSandbox:");
+            println!("  Enabled:        {}", yes_no(sb.enabled));
+            println!("  Image:          {}", sb.image);
+            println!("  Container:      {}", sb.container_name);
+            if let Some(id) = &sb.container_id {
+                println!("  Container ID:   {}", id);
+            }
+            println!(
+                "  YOLO mode:      {}",
+                yes_no(sb.yolo_mode.unwrap_or(false))
+            );
+            if let Some(network) = &sb.network {
+                println!("  Network:        {}", network);
+            }
+            if let Some(t) = &sb.created_at {
+                println!("  Created:        {}", time(t));
+            }
+            match &container {
+                Some(state) => {
+                    println!("  State:          {}", state.status);
+                    if let Some(t) = &state.started_at {
+                        println!("  Started:        {}", time(t));
+                    }
+                }
+                None if sb.enabled => println!("  State:          no container"),
+                None => {}
+            }
+            if let Some(keys) = sb.extra_env_keys.as_ref().filter(|k| !k.is_empty()) {
+                println!("  Env keys:       {}", keys.join(", "));
+            }
+            if let Some(values) = sb.extra_env_values.as_ref().filter(|v| !v.is_empty()) {
+                let mut pairs: Vec<_> = values.iter().collect();
+                pairs.sort();
+                println!("  Env values:");
+                for (key, value) in pairs {
+                    println!("    {}={}", key, value);
+                }
+            }
+            if let Some(volumes) = sb.extra_volumes.as_ref().filter(|v| !v.is_empty()) {
+                println!("  Volumes:");
+                for volume in volumes {
+                    println!("    {}", volume);
+                }
+            }
+        }
+        None => println!("\nSandbox: none"),
+    }
+
+    if let Some(cmd) = &launch_command {
+        println!("\nLaunch command:");
+        println!("  {}", cmd.as_deref().unwrap_or("(none, starts a shell)"));
+    }
+
+    Ok(())
+}
+
 async fn current_session(args: CurrentArgs) -> Result<()> {
     // Auto-detect profile and session from tmux
     let current_session = std::env::var("TMUX_PANE")
@@ -19,6 +19,13 @@ pub struct StatusArgs {
     /// Output as JSON
     #[arg(long)]
     json: bool,
+
+    /// Only count sessions in this group (and its subgroups)
+    #[arg(short = 'g', long)]
+    group: Option<String>,
+
+    #[command(flatten)]
+    age: super::AgeFilterArgs,
 }
 
 #[derive(Default)]
@@ -27,15 +34,17 @@ struct StatusCounts {
     waiting: usize,
     idle: usize,
     error: usize,
+    exited: usize,
     total: usize,
 }
 
-#[derive(Serialize)]
+#[derive(Default, Serialize)]
 struct StatusJson {
     waiting: usize,
     running: usize,
     idle: usize,
     error: usize,
+    exited: usize,
     total: usize,
 }
 
@@ -43,13 +52,23 @@ pub async fn run(profile: &str, args: StatusArgs) -> Result<()> {
     let storage = Storage::new(profile)?;
     let (mut instances, _) = storage.load_with_groups()?;
 
+    let group = args.group.as_deref().map(str::trim);
+    if let Some(group) = group {
+        instances.retain(|inst| super::in_group(&inst.group_path, group));
+    }
+    instances.retain(|inst| args.age.matches(inst));
+    let scope = match group {
+        Some(group) => format!("group '{}' of profile '{}'", group, storage.profile()),
+        None => format!("profile '{}'", storage.profile()),
+    };
+
     if instances.is_empty() {
         if args.json {
-            println!(r#"{{"waiting": 0, "running": 0, "idle": 0, "error": 0, "total": 0}}"#);
+            println!("{}", serde_json::to_string(&StatusJson::default())?);
         } else if args.quiet {
             println!("0");
         } else {
-            println!("No sessions in profile '{}'.", storage.profile());
+            println!("No sessions in {}.", scope);
         }
         return Ok(());
     }
@@ -70,26 +89,28 @@ pub async fn run(profile: &str, args: StatusArgs) -> Result<()> {
             running: counts.running,
             idle: counts.idle,
             error: counts.error,
+            exited: counts.exited,
             total: counts.total,
         };
         println!("{}", serde_json::to_string(&status_json)?);
     } else if args.quiet {
         println!("{}", counts.waiting);
     } else if args.verbose {
-        print_status_group("WAITING", "◐", Status::Waiting, &instances);
-        print_status_group("RUNNING", "●", Status::Running, &instances);
-        print_status_group("IDLE", "○", Status::Idle, &instances);
-        print_status_group("ERROR", "✕", Status::Error, &instances);
-        println!(
-            "Total: {} sessions in profile '{}'",
-            counts.total,
-            storage.profile()
-        );
+        print_status_group("WAITING", Status::Waiting, &instances);
+        print_status_group("RUNNING", Status::Running, &instances);
+        print_status_group("IDLE", Status::Idle, &instances);
+        print_status_group("EXITED", Status::Exited, &instances);
+        print_status_group("ERROR", Status::Error, &instances);
+        println!("Total: {} sessions in {}", counts.total, scope);
     } else {
-        println!(
+        let mut summary = format!(
             "{} waiting • {} running • {} idle",
             counts.waiting, counts.running, counts.idle
         );
+        if counts.exited > 0 {
+            summary.push_str(&format!(" • {} exited", counts.exited));
+        }
+        println!("{}", summary);
     }
 
     // Show update notice if available (skip for JSON/quiet output)
@@ -108,6 +129,7 @@ fn count_by_status(instances: &[crate::session::Instance]) -> StatusCounts {
             Status::Waiting => counts.waiting += 1,
             Status::Idle => counts.idle += 1,
             Status::Error => counts.error += 1,
+            Status::Exited => counts.exited += 1,
             Status::Starting => counts.idle += 1,
             Status::Deleting => {}
         }
@@ -116,12 +138,7 @@ fn count_by_status(instances: &[crate::session::Instance]) -> StatusCounts {
     counts
 }
 
-fn print_status_group(
-    label: &str,
-    symbol: &str,
-    status: Status,
-    instances: &[crate::session::Instance],
-) {
+fn print_status_group(label: &str, status: Status, instances: &[crate::session::Instance]) {
     let matching: Vec<_> = instances.iter().filter(|i| i.status == status).collect();
     if matching.is_empty() {
         return;
@@ -130,7 +147,13 @@ fn print_status_group(
     println!("{} ({}):", label, matching.len());
     for inst in matching {
         let path = shorten_path(&inst.project_path);
-        println!("  {} {:<16} {:<10} {}", symbol, inst.title, inst.tool, path);
+        println!(
+            "  {} {:<16} {:<10} {}",
+            status.icon(),
+            inst.title,
+            inst.tool,
+            path
+        );
     }
     println!();
 }
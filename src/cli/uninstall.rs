@@ -7,6 +7,9 @@ use std::io::{self, Write};
 use std::path::PathBuf;
 use std::process::Command;
 
+use crate::docker::{self, DockerContainer};
+use crate::tmux;
+
 #[derive(Args)]
 pub struct UninstallArgs {
     /// Keep data directory (sessions, config, logs)
@@ -17,6 +20,18 @@ pub struct UninstallArgs {
     #[arg(long)]
     keep_tmux_config: bool,
 
+    /// Keep sandbox containers
+    #[arg(long)]
+    keep_containers: bool,
+
+    /// Keep running aoe tmux sessions
+    #[arg(long)]
+    keep_sessions: bool,
+
+    /// Also remove the shared auth volumes (this signs tools out in sandboxes)
+    #[arg(long)]
+    remove_volumes: bool,
+
     /// Show what would be removed without removing
     #[arg(long)]
     dry_run: bool,
@@ -29,6 +44,24 @@ pub struct UninstallArgs {
 struct FoundItem {
     item_type: String,
     path: PathBuf,
+    /// Container, tmux session or volume name (empty for files)
+    name: String,
+}
+
+/// Counts of runtime artifacts removed, for the final report
+#[derive(Default)]
+struct Cleaned {
+    sessions: usize,
+    containers: usize,
+    volumes: usize,
+}
+
+fn named_item(item_type: &str, name: String) -> FoundItem {
+    FoundItem {
+        item_type: item_type.to_string(),
+        path: PathBuf::new(),
+        name,
+    }
 }
 
 pub async fn run(args: UninstallArgs) -> Result<()> {
@@ -69,6 +102,7 @@ pub async fn run(args: UninstallArgs) -> Result<()> {
         found_items.push(FoundItem {
             item_type: "homebrew".to_string(),
             path: PathBuf::new(),
+            name: String::new(),
         });
         println!("Found: Homebrew installation");
     }
@@ -97,6 +131,7 @@ pub async fn run(args: UninstallArgs) -> Result<()> {
             found_items.push(FoundItem {
                 item_type: "binary".to_string(),
                 path: loc.clone(),
+                name: String::new(),
             });
             println!("Found: Binary at {}", loc.display());
         }
@@ -125,6 +160,7 @@ pub async fn run(args: UninstallArgs) -> Result<()> {
             found_items.push(FoundItem {
                 item_type: "data".to_string(),
                 path: data_dir.clone(),
+                name: String::new(),
             });
             println!("Found: Data directory at {}", data_dir.display());
             println!(
@@ -141,11 +177,36 @@ pub async fn run(args: UninstallArgs) -> Result<()> {
             found_items.push(FoundItem {
                 item_type: "tmux".to_string(),
                 path: tmux_conf.clone(),
+                name: String::new(),
             });
             println!("Found: tmux configuration in ~/.tmux.conf");
         }
     }
 
+    // Check for sandbox containers and auth volumes
+    if docker::is_docker_available() && docker::is_daemon_running() {
+        for name in docker::list_sandbox_containers().unwrap_or_default() {
+            println!("Found: Sandbox container {}", name);
+            found_items.push(named_item("container", name));
+        }
+        for name in docker::list_auth_volumes().unwrap_or_default() {
+            println!("Found: Auth volume {}", name);
+            found_items.push(named_item("volume", name));
+        }
+    }
+
+    // Check for running aoe tmux sessions. If we are running inside one,
+    // kill it last so the rest of the uninstall still happens.
+    let current_session = std::env::var("TMUX")
+        .ok()
+        .and_then(|_| tmux::get_current_session_name());
+    let mut sessions = tmux::list_aoe_sessions();
+    sessions.sort_by_key(|name| Some(name) == current_session.as_ref());
+    for name in sessions {
+        println!("Found: tmux session {}", name);
+        found_items.push(named_item("session", name));
+    }
+
     println!();
 
     if found_items.is_empty() {
@@ -176,6 +237,30 @@ pub async fn run(args: UninstallArgs) -> Result<()> {
                     println!("  • tmux config block in ~/.tmux.conf");
                 }
             }
+            "container" => {
+                if args.keep_containers {
+                    println!("  ○ Sandbox container: {} (keeping)", item.name);
+                } else {
+                    println!("  • Sandbox container: {}", item.name);
+                }
+            }
+            "session" => {
+                if args.keep_sessions {
+                    println!("  ○ tmux session: {} (keeping)", item.name);
+                } else {
+                    println!("  • tmux session: {}", item.name);
+                }
+            }
+            "volume" => {
+                if args.remove_volumes {
+                    println!("  • Auth volume: {} (tool logins)", item.name);
+                } else {
+                    println!(
+                        "  ○ Auth volume: {} (keeping, use --remove-volumes to remove)",
+                        item.name
+                    );
+                }
+            }
             _ => {}
         }
     }
@@ -205,6 +290,8 @@ pub async fn run(args: UninstallArgs) -> Result<()> {
     println!("Uninstalling...");
     println!();
 
+    let mut cleaned = Cleaned::default();
+
     // Perform uninstall
     for item in &found_items {
         match item.item_type.as_str() {
@@ -230,6 +317,33 @@ pub async fn run(args: UninstallArgs) -> Result<()> {
                     println!("✓ Data directory removed: {}", item.path.display());
                 }
             }
+            "container" if !args.keep_containers => {
+                let container = DockerContainer {
+                    name: item.name.clone(),
+                    image: String::new(),
+                };
+                match container.remove(true) {
+                    Ok(()) => {
+                        cleaned.containers += 1;
+                        println!("✓ Sandbox container removed: {}", item.name)
+                    }
+                    Err(e) => println!("✗ Failed to remove container {}: {}", item.name, e),
+                }
+            }
+            "session" if !args.keep_sessions => match tmux::Session::from_name(&item.name).kill() {
+                Ok(()) => {
+                    cleaned.sessions += 1;
+                    println!("✓ tmux session killed: {}", item.name)
+                }
+                Err(e) => println!("✗ Failed to kill tmux session {}: {}", item.name, e),
+            },
+            "volume" if args.remove_volumes => match docker::remove_volume(&item.name) {
+                Ok(()) => {
+                    cleaned.volumes += 1;
+                    println!("✓ Auth volume removed: {}", item.name)
+                }
+                Err(e) => println!("✗ Failed to remove volume {}: {}", item.name, e),
+            },
             "tmux" if !args.keep_tmux_config => {
                 println!("Removing tmux configuration...");
                 if let Ok(content) = fs::read_to_string(&item.path) {
@@ -260,6 +374,14 @@ pub async fn run(args: UninstallArgs) -> Result<()> {
         }
     }
 
+    if cleaned.sessions + cleaned.containers + cleaned.volumes > 0 {
+        println!();
+        println!(
+            "Cleaned up {} tmux session(s), {} container(s), {} volume(s)",
+            cleaned.sessions, cleaned.containers, cleaned.volumes
+        );
+    }
+
     println!();
     println!("╔════════════════════════════════════════╗");
     println!("║     Uninstall complete!                ║");
@@ -280,6 +402,10 @@ pub async fn run(args: UninstallArgs) -> Result<()> {
         println!("Note: tmux config preserved in ~/.tmux.conf");
     }
 
+    if args.keep_containers && found_items.iter().any(|i| i.item_type == "container") {
+        println!("Note: Sandbox containers preserved");
+    }
+
     println!();
     println!("Thank you for using Agent of Empires!");
     println!("Feedback: https://github.com/njbrake/agent-of-empires/issues");
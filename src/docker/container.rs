@@ -1,5 +1,7 @@
 use super::error::{DockerError, Result};
 use crate::cli::truncate_id;
+use crate::process::{run_with_timeout, COMMAND_TIMEOUT, PROBE_TIMEOUT};
+use chrono::{DateTime, Utc};
 use std::process::Command;
 
 pub struct VolumeMount {
@@ -16,13 +18,109 @@ pub struct ContainerConfig {
     pub environment: Vec<(String, String)>,
     pub cpu_limit: Option<String>,
     pub memory_limit: Option<String>,
+    /// Docker network to attach to (`host`, `none`, or a named network)
+    pub network: Option<String>,
+    /// Mount the root filesystem read-only, with a writable tmpfs at /tmp
+    pub read_only_rootfs: bool,
+    pub cap_drop: Vec<String>,
+    pub cap_add: Vec<String>,
+    pub security_opt: Vec<String>,
 }
 
+/// Capabilities kept by the hardened preset. Enough for package managers and
+/// file ownership changes in the workspace, nothing network- or kernel-related.
+pub const HARDENED_CAP_ADD: &[&str] = &[
+    "CHOWN",
+    "DAC_OVERRIDE",
+    "FOWNER",
+    "FSETID",
+    "SETUID",
+    "SETGID",
+    "KILL",
+];
+
+/// Security options applied by the hardened preset.
+pub const HARDENED_SECURITY_OPT: &[&str] = &["no-new-privileges"];
+
+/// Prefix shared by every sandbox container name
+pub const CONTAINER_NAME_PREFIX: &str = "aoe-sandbox-";
+
 pub struct DockerContainer {
     pub name: String,
     pub image: String,
 }
 
+/// The parts of `docker inspect`'s `.State` aoe cares about
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerState {
+    /// `created`, `running`, `paused`, `restarting`, `exited`, `dead`, ...
+    pub status: String,
+    pub running: bool,
+    pub exit_code: i64,
+    pub oom_killed: bool,
+    /// When the container was last started; `None` if it never was
+    pub started_at: Option<DateTime<Utc>>,
+}
+
+impl ContainerState {
+    /// `inspect -f` template whose output `parse` reads
+    const FORMAT: &'static str = "{{.State.Status}}|{{.State.Running}}|{{.State.ExitCode}}|{{.State.OOMKilled}}|{{.State.StartedAt}}";
+
+    fn parse(output: &str) -> Option<Self> {
+        let mut parts = output.trim().split('|');
+        let status = parts.next()?.to_string();
+        let running = parts.next()? == "true";
+        let exit_code = parts.next()?.parse().ok()?;
+        let oom_killed = parts.next()? == "true";
+        // Docker reports a never-started container as the zero time (year 1)
+        let started_at = parts
+            .next()
+            .and_then(|s| DateTime::parse_from_rfc3339(s).ok())
+            .map(|t| t.with_timezone(&Utc))
+            .filter(|t| t.timestamp() > 0);
+        Some(Self {
+            status,
+            running,
+            exit_code,
+            oom_killed,
+            started_at,
+        })
+    }
+
+    /// How long the container has been running, if it is
+    pub fn uptime(&self, now: DateTime<Utc>) -> Option<chrono::Duration> {
+        self.started_at.filter(|_| self.running).map(|t| now - t)
+    }
+
+    /// Whether the container ran and then stopped
+    pub fn has_exited(&self) -> bool {
+        !self.running && self.status != "created"
+    }
+
+    /// Human-readable reason the container stopped, with a hint where the
+    /// exit code points at a likely fix. `None` unless it has exited.
+    pub fn exit_reason(&self) -> Option<String> {
+        if !self.has_exited() {
+            return None;
+        }
+        Some(match (self.oom_killed, self.exit_code) {
+            (true, code) => format!(
+                "container exited code {} (OOMKilled), out of memory; raise sandbox.memory_limit",
+                code
+            ),
+            (false, 137) => {
+                "container exited code 137 (killed), likely OOM; raise sandbox.memory_limit"
+                    .to_string()
+            }
+            (false, 143) => "container exited code 143, stopped by docker stop".to_string(),
+            (false, code) => format!(
+                "container exited code {}; see the container logs for details",
+                code
+            ),
+        })
+    }
+}
+
 impl DockerContainer {
     pub fn new(session_id: &str, image: &str) -> Self {
         Self {
@@ -39,27 +137,29 @@ impl DockerContainer {
     }
 
     pub fn generate_name(session_id: &str) -> String {
-        format!("aoe-sandbox-{}", truncate_id(session_id, 8))
+        format!("{}{}", CONTAINER_NAME_PREFIX, truncate_id(session_id, 8))
     }
 
     pub fn exists(&self) -> Result<bool> {
-        let output = Command::new("docker")
-            .args(["container", "inspect", &self.name])
-            .output()?;
+        let output = run_with_timeout(
+            Command::new("docker").args(["container", "inspect", &self.name]),
+            PROBE_TIMEOUT,
+        )?;
 
         Ok(output.status.success())
     }
 
     pub fn is_running(&self) -> Result<bool> {
-        let output = Command::new("docker")
-            .args([
+        let output = run_with_timeout(
+            Command::new("docker").args([
                 "container",
                 "inspect",
                 "-f",
                 "{{.State.Running}}",
                 &self.name,
-            ])
-            .output()?;
+            ]),
+            PROBE_TIMEOUT,
+        )?;
 
         if !output.status.success() {
             return Ok(false);
@@ -69,6 +169,43 @@ impl DockerContainer {
         Ok(stdout.trim() == "true")
     }
 
+    /// Current state of the container, or `None` if it does not exist.
+    pub fn inspect_state(&self) -> Result<Option<ContainerState>> {
+        let output = run_with_timeout(
+            Command::new("docker").args([
+                "container",
+                "inspect",
+                "-f",
+                ContainerState::FORMAT,
+                &self.name,
+            ]),
+            PROBE_TIMEOUT,
+        )?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        Ok(ContainerState::parse(&String::from_utf8_lossy(
+            &output.stdout,
+        )))
+    }
+
+    /// Full ID of the container with this name, or `None` if it does not exist.
+    pub fn id(&self) -> Result<Option<String>> {
+        let output = run_with_timeout(
+            Command::new("docker").args(["container", "inspect", "-f", "{{.Id}}", &self.name]),
+            PROBE_TIMEOUT,
+        )?;
+
+        if !output.status.success() {
+            return Ok(None);
+        }
+
+        let id = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        Ok((!id.is_empty()).then_some(id))
+    }
+
     /// Build the docker run arguments from the container config.
     /// Separated from `create` to enable unit testing.
     pub(crate) fn build_create_args(&self, config: &ContainerConfig) -> Vec<String> {
@@ -116,6 +253,32 @@ impl DockerContainer {
             args.push(mem.clone());
         }
 
+        if let Some(network) = &config.network {
+            args.push("--network".to_string());
+            args.push(network.clone());
+        }
+
+        for cap in &config.cap_drop {
+            args.push("--cap-drop".to_string());
+            args.push(cap.clone());
+        }
+
+        for cap in &config.cap_add {
+            args.push("--cap-add".to_string());
+            args.push(cap.clone());
+        }
+
+        for opt in &config.security_opt {
+            args.push("--security-opt".to_string());
+            args.push(opt.clone());
+        }
+
+        if config.read_only_rootfs {
+            args.push("--read-only".to_string());
+            args.push("--tmpfs".to_string());
+            args.push("/tmp".to_string());
+        }
+
         args.push(self.image.clone());
         args.push("sleep".to_string());
         args.push("infinity".to_string());
@@ -130,7 +293,7 @@ impl DockerContainer {
 
         let args = self.build_create_args(config);
 
-        let output = Command::new("docker").args(&args).output()?;
+        let output = run_with_timeout(Command::new("docker").args(&args), COMMAND_TIMEOUT)?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -143,6 +306,10 @@ impl DockerContainer {
             if stderr.contains("No such image") || stderr.contains("Unable to find image") {
                 return Err(DockerError::ImageNotFound(self.image.clone()));
             }
+            // Lost a race with another create, or a removal is still in progress
+            if stderr.contains("is already in use") {
+                return Err(DockerError::ContainerAlreadyExists(self.name.clone()));
+            }
             return Err(DockerError::CreateFailed(stderr.to_string()));
         }
 
@@ -151,9 +318,10 @@ impl DockerContainer {
     }
 
     pub fn start(&self) -> Result<()> {
-        let output = Command::new("docker")
-            .args(["start", &self.name])
-            .output()?;
+        let output = run_with_timeout(
+            Command::new("docker").args(["start", &self.name]),
+            COMMAND_TIMEOUT,
+        )?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -164,7 +332,10 @@ impl DockerContainer {
     }
 
     pub fn stop(&self) -> Result<()> {
-        let output = Command::new("docker").args(["stop", &self.name]).output()?;
+        let output = run_with_timeout(
+            Command::new("docker").args(["stop", &self.name]),
+            COMMAND_TIMEOUT,
+        )?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -184,7 +355,7 @@ impl DockerContainer {
         }
         args.push(self.name.clone());
 
-        let output = Command::new("docker").args(&args).output()?;
+        let output = run_with_timeout(Command::new("docker").args(&args), COMMAND_TIMEOUT)?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -197,6 +368,33 @@ impl DockerContainer {
         Ok(())
     }
 
+    /// The last `tail` lines of the container's logs (stdout and stderr).
+    /// Works for stopped containers too; fails if the container doesn't exist.
+    pub fn logs(&self, tail: usize) -> Result<String> {
+        // docker replays the container's stderr on our stderr; sending both to
+        // one pipe keeps the lines in the order the container wrote them
+        let output = run_with_timeout(
+            Command::new("sh").args([
+                "-c",
+                r#"exec docker logs --tail "$1" "$2" 2>&1"#,
+                "sh",
+                &tail.to_string(),
+                &self.name,
+            ]),
+            COMMAND_TIMEOUT,
+        )?;
+        let logs = String::from_utf8_lossy(&output.stdout).into_owned();
+
+        if !output.status.success() {
+            if logs.contains("No such container") {
+                return Err(DockerError::ContainerNotFound(self.name.clone()));
+            }
+            return Err(DockerError::CommandFailed(logs));
+        }
+
+        Ok(logs)
+    }
+
     pub fn exec_command(&self) -> Vec<String> {
         vec![
             "docker".to_string(),
@@ -210,7 +408,7 @@ impl DockerContainer {
         let mut args = vec!["exec", &self.name];
         args.extend(cmd);
 
-        let output = Command::new("docker").args(&args).output()?;
+        let output = run_with_timeout(Command::new("docker").args(&args), COMMAND_TIMEOUT)?;
 
         Ok(output)
     }
@@ -232,6 +430,61 @@ mod tests {
         assert_eq!(name, "aoe-sandbox-abcdefgh");
     }
 
+    #[test]
+    fn test_parse_container_state() {
+        let state = ContainerState::parse("exited|false|137|true\n").unwrap();
+        assert_eq!(
+            state,
+            ContainerState {
+                status: "exited".to_string(),
+                running: false,
+                exit_code: 137,
+                oom_killed: true,
+                started_at: None,
+            }
+        );
+        assert!(ContainerState::parse("garbage").is_none());
+    }
+
+    #[test]
+    fn test_parse_container_started_at() {
+        let state =
+            ContainerState::parse("running|true|0|false|2026-01-02T03:04:05.123456789Z").unwrap();
+        let started = DateTime::parse_from_rfc3339("2026-01-02T03:04:05.123456789Z")
+            .unwrap()
+            .with_timezone(&Utc);
+        assert_eq!(state.started_at, Some(started));
+        let now = started + chrono::Duration::hours(3);
+        assert_eq!(state.uptime(now), Some(chrono::Duration::hours(3)));
+
+        let never = ContainerState::parse("created|false|0|false|0001-01-01T00:00:00Z").unwrap();
+        assert_eq!(never.started_at, None);
+
+        let stopped = ContainerState::parse("exited|false|143|false|2026-01-02T03:04:05Z").unwrap();
+        assert!(stopped.started_at.is_some());
+        assert_eq!(stopped.uptime(now), None);
+    }
+
+    #[test]
+    fn test_container_exit_reason() {
+        let state = |s: &str| ContainerState::parse(s).unwrap();
+        assert_eq!(state("running|true|0|false").exit_reason(), None);
+        assert_eq!(state("created|false|0|false").exit_reason(), None);
+
+        let oom = state("exited|false|137|true").exit_reason().unwrap();
+        assert!(oom.contains("code 137") && oom.contains("OOMKilled"));
+        assert!(oom.contains("memory_limit"));
+
+        let killed = state("exited|false|137|false").exit_reason().unwrap();
+        assert!(killed.contains("likely OOM") && killed.contains("memory_limit"));
+
+        let stopped = state("exited|false|143|false").exit_reason().unwrap();
+        assert!(stopped.contains("docker stop"));
+
+        let failed = state("exited|false|1|false").exit_reason().unwrap();
+        assert!(failed.starts_with("container exited code 1;"));
+    }
+
     #[test]
     fn test_exec_command() {
         let container = DockerContainer::new("test1234567890ab", "ubuntu:latest");
@@ -253,6 +506,11 @@ mod tests {
             environment: vec![],
             cpu_limit: None,
             memory_limit: None,
+            network: None,
+            read_only_rootfs: false,
+            cap_drop: vec![],
+            cap_add: vec![],
+            security_opt: vec![],
         };
 
         let args = container.build_create_args(&config);
@@ -282,6 +540,11 @@ mod tests {
             environment: vec![],
             cpu_limit: None,
             memory_limit: None,
+            network: None,
+            read_only_rootfs: false,
+            cap_drop: vec![],
+            cap_add: vec![],
+            security_opt: vec![],
         };
 
         let args = container.build_create_args(&config);
@@ -289,4 +552,120 @@ mod tests {
         // No -v flags at all
         assert!(!args.contains(&"-v".to_string()));
     }
+
+    #[test]
+    fn test_network_in_create_args() {
+        let container = DockerContainer::new("test1234567890ab", "alpine:latest");
+        let mut config = ContainerConfig {
+            working_dir: "/workspace".to_string(),
+            volumes: vec![],
+            named_volumes: vec![],
+            anonymous_volumes: vec![],
+            environment: vec![],
+            cpu_limit: None,
+            memory_limit: None,
+            network: None,
+            read_only_rootfs: false,
+            cap_drop: vec![],
+            cap_add: vec![],
+            security_opt: vec![],
+        };
+
+        let args = container.build_create_args(&config);
+        assert!(!args.contains(&"--network".to_string()));
+
+        config.network = Some("host".to_string());
+        let args = container.build_create_args(&config);
+        let pos = args.iter().position(|a| a == "--network").unwrap();
+        assert_eq!(args[pos + 1], "host");
+        // Options must come before the image
+        assert!(pos < args.iter().position(|a| a == "alpine:latest").unwrap());
+    }
+
+    #[test]
+    fn test_read_only_rootfs_in_create_args() {
+        let container = DockerContainer::new("test1234567890ab", "alpine:latest");
+        let mut config = ContainerConfig {
+            working_dir: "/workspace".to_string(),
+            volumes: vec![],
+            named_volumes: vec![("aoe-claude-auth".to_string(), "/root/.claude".to_string())],
+            anonymous_volumes: vec![],
+            environment: vec![],
+            cpu_limit: None,
+            memory_limit: None,
+            network: None,
+            read_only_rootfs: false,
+            cap_drop: vec![],
+            cap_add: vec![],
+            security_opt: vec![],
+        };
+
+        let args = container.build_create_args(&config);
+        assert!(!args.contains(&"--read-only".to_string()));
+        assert!(!args.contains(&"--tmpfs".to_string()));
+
+        config.read_only_rootfs = true;
+        let args = container.build_create_args(&config);
+        assert!(args.contains(&"--read-only".to_string()));
+        let pos = args.iter().position(|a| a == "--tmpfs").unwrap();
+        assert_eq!(args[pos + 1], "/tmp");
+        // Auth volumes stay writable
+        assert!(args.contains(&"aoe-claude-auth:/root/.claude".to_string()));
+    }
+
+    #[test]
+    fn test_security_options_in_create_args() {
+        let container = DockerContainer::new("test1234567890ab", "alpine:latest");
+        let config = ContainerConfig {
+            working_dir: "/workspace".to_string(),
+            volumes: vec![],
+            named_volumes: vec![],
+            anonymous_volumes: vec![],
+            environment: vec![],
+            cpu_limit: None,
+            memory_limit: None,
+            network: None,
+            read_only_rootfs: false,
+            cap_drop: vec!["ALL".to_string()],
+            cap_add: vec!["CHOWN".to_string(), "SETUID".to_string()],
+            security_opt: vec!["no-new-privileges".to_string()],
+        };
+
+        let args = container.build_create_args(&config);
+        let values = |flag: &str| -> Vec<&str> {
+            args.iter()
+                .enumerate()
+                .filter(|(_, a)| *a == flag)
+                .map(|(i, _)| args[i + 1].as_str())
+                .collect()
+        };
+
+        assert_eq!(values("--cap-drop"), vec!["ALL"]);
+        assert_eq!(values("--cap-add"), vec!["CHOWN", "SETUID"]);
+        assert_eq!(values("--security-opt"), vec!["no-new-privileges"]);
+    }
+
+    #[test]
+    fn test_no_security_options_by_default() {
+        let container = DockerContainer::new("test1234567890ab", "alpine:latest");
+        let config = ContainerConfig {
+            working_dir: "/workspace".to_string(),
+            volumes: vec![],
+            named_volumes: vec![],
+            anonymous_volumes: vec![],
+            environment: vec![],
+            cpu_limit: None,
+            memory_limit: None,
+            network: None,
+            read_only_rootfs: false,
+            cap_drop: vec![],
+            cap_add: vec![],
+            security_opt: vec![],
+        };
+
+        let args = container.build_create_args(&config);
+        assert!(!args.contains(&"--cap-drop".to_string()));
+        assert!(!args.contains(&"--cap-add".to_string()));
+        assert!(!args.contains(&"--security-opt".to_string()));
+    }
 }
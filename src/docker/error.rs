@@ -31,6 +31,9 @@ pub enum DockerError {
     #[error("Docker image not found: {0}")]
     ImageNotFound(String),
 
+    #[error("offline: image not present: {0} (pull it while online, or turn off [app] offline)")]
+    OfflineImageMissing(String),
+
     #[error("Failed to create container: {0}")]
     CreateFailed(String),
 
@@ -50,4 +53,81 @@ pub enum DockerError {
     IoError(#[from] std::io::Error),
 }
 
+/// Fragments of Docker error output that indicate a temporary condition
+/// (busy daemon, flaky network or registry) rather than a real failure.
+const TRANSIENT_MARKERS: &[&str] = &[
+    "timeout",
+    "timed out",
+    "deadline exceeded",
+    "connection reset",
+    "connection refused",
+    "broken pipe",
+    "unexpected eof",
+    "temporary failure",
+    "try again",
+    "too many requests",
+    "toomanyrequests",
+    "service unavailable",
+    "bad gateway",
+    "server misbehaving",
+];
+
+impl DockerError {
+    /// Whether retrying the command that produced this error may succeed.
+    /// Missing images, name conflicts and permission problems never do.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            DockerError::ImageNotFound(msg)
+            | DockerError::CreateFailed(msg)
+            | DockerError::StartFailed(msg)
+            | DockerError::CommandFailed(msg) => is_transient_message(msg),
+            DockerError::IoError(e) => e.kind() == std::io::ErrorKind::TimedOut,
+            _ => false,
+        }
+    }
+}
+
+fn is_transient_message(msg: &str) -> bool {
+    let msg = msg.to_lowercase();
+    TRANSIENT_MARKERS.iter().any(|marker| msg.contains(marker))
+}
+
 pub type Result<T> = std::result::Result<T, DockerError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_transient_errors() {
+        assert!(DockerError::ImageNotFound(
+            "ubuntu: Error response from daemon: Get \"https://registry-1.docker.io/v2/\": net/http: TLS handshake timeout".to_string()
+        )
+        .is_transient());
+        assert!(
+            DockerError::CommandFailed("read: connection reset by peer".to_string()).is_transient()
+        );
+        assert!(DockerError::StartFailed("context deadline exceeded".to_string()).is_transient());
+        assert!(
+            DockerError::ImageNotFound("toomanyrequests: rate limit".to_string()).is_transient()
+        );
+        let timed_out = std::io::Error::new(std::io::ErrorKind::TimedOut, "docker timed out");
+        assert!(DockerError::IoError(timed_out).is_transient());
+    }
+
+    #[test]
+    fn test_terminal_errors() {
+        assert!(!DockerError::ImageNotFound(
+            "nope:latest: Error response from daemon: manifest for nope:latest not found"
+                .to_string()
+        )
+        .is_transient());
+        assert!(!DockerError::ContainerAlreadyExists("aoe-sandbox-1".to_string()).is_transient());
+        assert!(!DockerError::PermissionDenied.is_transient());
+        assert!(!DockerError::DaemonNotRunning.is_transient());
+        assert!(!DockerError::StartFailed(
+            "error mounting \"/gone\": no such file or directory".to_string()
+        )
+        .is_transient());
+    }
+}
@@ -1,10 +1,19 @@
 pub mod container;
 pub mod error;
 
-pub use container::{ContainerConfig, DockerContainer, VolumeMount};
+pub use container::{
+    ContainerConfig, ContainerState, DockerContainer, VolumeMount, CONTAINER_NAME_PREFIX,
+    HARDENED_CAP_ADD, HARDENED_SECURITY_OPT,
+};
 pub use error::{DockerError, Result};
 
+use std::collections::HashMap;
 use std::process::Command;
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use std::time::{Duration, Instant};
+
+use crate::process::{run_with_timeout, COMMAND_TIMEOUT, PROBE_TIMEOUT, PULL_TIMEOUT};
+use crate::session::profile_config::MAX_DOCKER_RETRIES;
 
 pub const CLAUDE_AUTH_VOLUME: &str = "aoe-claude-auth";
 pub const OPENCODE_AUTH_VOLUME: &str = "aoe-opencode-auth";
@@ -12,24 +21,116 @@ pub const VIBE_AUTH_VOLUME: &str = "aoe-vibe-auth";
 pub const CODEX_AUTH_VOLUME: &str = "aoe-codex-auth";
 pub const GEMINI_AUTH_VOLUME: &str = "aoe-gemini-auth";
 
+/// Base names of the shared tool auth volumes. Profile-scoped volumes add a
+/// `-<profile>` suffix to these.
+pub const AUTH_VOLUMES: [&str; 5] = [
+    CLAUDE_AUTH_VOLUME,
+    OPENCODE_AUTH_VOLUME,
+    VIBE_AUTH_VOLUME,
+    CODEX_AUTH_VOLUME,
+    GEMINI_AUTH_VOLUME,
+];
+
+/// Delay before the first retry of a transient failure; doubles per attempt
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// Longest wait between two attempts, however many retries are configured
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(30);
+
+/// Run `op`, retrying up to `retries` more times with exponential backoff while
+/// it fails with a transient error. `what` names the operation in logs.
+/// `retries` is capped at `MAX_DOCKER_RETRIES`, since a hand-edited config
+/// skips the settings validation.
+pub fn with_retries<T>(retries: u64, what: &str, op: impl FnMut() -> Result<T>) -> Result<T> {
+    retry_with_delay(retries, RETRY_BASE_DELAY, what, op)
+}
+
+fn retry_with_delay<T>(
+    retries: u64,
+    base_delay: Duration,
+    what: &str,
+    mut op: impl FnMut() -> Result<T>,
+) -> Result<T> {
+    let retries = retries.min(MAX_DOCKER_RETRIES);
+    let mut attempt = 0;
+    loop {
+        match op() {
+            Err(e) if attempt < retries && e.is_transient() => {
+                let delay = base_delay
+                    .saturating_mul(2u32.saturating_pow(attempt as u32))
+                    .min(RETRY_MAX_DELAY);
+                attempt += 1;
+                tracing::warn!(
+                    "{} failed ({}), retrying in {:?} (attempt {}/{})",
+                    what,
+                    e,
+                    delay,
+                    attempt,
+                    retries
+                );
+                std::thread::sleep(delay);
+            }
+            result => return result,
+        }
+    }
+}
+
+/// How long availability check results are reused before docker is probed again
+const AVAILABILITY_TTL: Duration = Duration::from_secs(5);
+
+type CachedCheck = RwLock<Option<(bool, Instant)>>;
+
+static DOCKER_AVAILABLE: CachedCheck = RwLock::new(None);
+static DAEMON_RUNNING: CachedCheck = RwLock::new(None);
+
+/// Return the cached result in `slot` if it is younger than `ttl`, otherwise
+/// run `probe` and cache its result.
+fn cached_check(slot: &CachedCheck, ttl: Duration, probe: impl FnOnce() -> bool) -> bool {
+    if let Ok(cache) = slot.read() {
+        if let Some((result, time)) = *cache {
+            if time.elapsed() < ttl {
+                return result;
+            }
+        }
+    }
+
+    let result = probe();
+    if let Ok(mut cache) = slot.write() {
+        *cache = Some((result, Instant::now()));
+    }
+    result
+}
+
+/// Forget cached availability results, e.g. after a failed check so that
+/// starting Docker takes effect on the next attempt.
+pub fn invalidate_availability_cache() {
+    for slot in [&DOCKER_AVAILABLE, &DAEMON_RUNNING] {
+        if let Ok(mut cache) = slot.write() {
+            *cache = None;
+        }
+    }
+}
+
+/// Whether the docker CLI is installed. Cached for a few seconds.
 pub fn is_docker_available() -> bool {
-    Command::new("docker")
-        .arg("--version")
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
+    cached_check(&DOCKER_AVAILABLE, AVAILABILITY_TTL, || {
+        run_with_timeout(Command::new("docker").arg("--version"), PROBE_TIMEOUT)
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    })
 }
 
+/// Whether the Docker daemon responds. Cached for a few seconds.
 pub fn is_daemon_running() -> bool {
-    Command::new("docker")
-        .args(["info"])
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
+    cached_check(&DAEMON_RUNNING, AVAILABILITY_TTL, || {
+        run_with_timeout(Command::new("docker").args(["info"]), PROBE_TIMEOUT)
+            .map(|o| o.status.success())
+            .unwrap_or(false)
+    })
 }
 
 pub fn get_docker_version() -> Result<String> {
-    let output = Command::new("docker").arg("--version").output()?;
+    let output = run_with_timeout(Command::new("docker").arg("--version"), PROBE_TIMEOUT)?;
 
     if !output.status.success() {
         return Err(DockerError::NotInstalled);
@@ -39,15 +140,16 @@ pub fn get_docker_version() -> Result<String> {
 }
 
 pub fn image_exists_locally(image: &str) -> bool {
-    Command::new("docker")
-        .args(["image", "inspect", image])
-        .output()
-        .map(|o| o.status.success())
-        .unwrap_or(false)
+    run_with_timeout(
+        Command::new("docker").args(["image", "inspect", image]),
+        PROBE_TIMEOUT,
+    )
+    .map(|o| o.status.success())
+    .unwrap_or(false)
 }
 
 pub fn pull_image(image: &str) -> Result<()> {
-    let output = Command::new("docker").args(["pull", image]).output()?;
+    let output = run_with_timeout(Command::new("docker").args(["pull", image]), PULL_TIMEOUT)?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -61,28 +163,97 @@ pub fn pull_image(image: &str) -> Result<()> {
     Ok(())
 }
 
+/// Like `pull_image`, but docker's layer progress goes straight to the
+/// terminal. There is no timeout, so only use this in interactive commands:
+/// the child shares the terminal's foreground process group, so Ctrl-C
+/// interrupts the pull and the caller together.
+pub fn pull_image_streamed(image: &str) -> Result<()> {
+    let status = Command::new("docker")
+        .args(["pull", image])
+        .stdin(std::process::Stdio::null())
+        .status()?;
+
+    if !status.success() {
+        return Err(DockerError::ImageNotFound(format!(
+            "{}: docker pull exited with {}",
+            image, status
+        )));
+    }
+
+    Ok(())
+}
+
+/// One lock per image, so sessions created at the same time wait on a single
+/// pull of a shared image instead of each starting their own
+static IMAGE_PULL_LOCKS: OnceLock<Mutex<HashMap<String, Arc<Mutex<()>>>>> = OnceLock::new();
+
+fn image_pull_lock(image: &str) -> Arc<Mutex<()>> {
+    let locks = IMAGE_PULL_LOCKS.get_or_init(|| Mutex::new(HashMap::new()));
+    let mut locks = locks.lock().unwrap_or_else(|e| e.into_inner());
+    locks.entry(image.to_string()).or_default().clone()
+}
+
 /// Ensure an image is available locally.
 /// If the image exists locally, uses it as-is (supports local-only images).
-/// If not, attempts to pull from the registry.
+/// If not, attempts to pull from the registry. Concurrent calls for the same
+/// image share one pull. In offline mode a missing image is an error instead.
 pub fn ensure_image(image: &str) -> Result<()> {
-    if image_exists_locally(image) {
+    let pull = if crate::session::is_offline() {
+        offline_pull
+    } else {
+        pull_image
+    };
+    ensure_image_with(image, image_exists_locally, pull)
+}
+
+/// `ensure_image` with pull progress streamed to the terminal
+pub fn ensure_image_streamed(image: &str) -> Result<()> {
+    let pull = if crate::session::is_offline() {
+        offline_pull
+    } else {
+        pull_image_streamed
+    };
+    ensure_image_with(image, image_exists_locally, pull)
+}
+
+/// Stands in for a pull in offline mode
+fn offline_pull(image: &str) -> Result<()> {
+    Err(DockerError::OfflineImageMissing(image.to_string()))
+}
+
+fn ensure_image_with(
+    image: &str,
+    exists: impl Fn(&str) -> bool,
+    pull: impl FnOnce(&str) -> Result<()>,
+) -> Result<()> {
+    if exists(image) {
         tracing::info!("Using local Docker image '{}'", image);
         return Ok(());
     }
 
+    let lock = image_pull_lock(image);
+    let _guard = lock.lock().unwrap_or_else(|e| e.into_inner());
+    // Another session may have pulled the image while we waited for the lock
+    if exists(image) {
+        tracing::info!("Docker image '{}' was pulled by another session", image);
+        return Ok(());
+    }
+
     tracing::info!("Pulling Docker image '{}'", image);
-    pull_image(image)
+    pull(image)
 }
 
 pub fn ensure_named_volume(name: &str) -> Result<()> {
-    let check = Command::new("docker")
-        .args(["volume", "inspect", name])
-        .output()?;
+    let check = run_with_timeout(
+        Command::new("docker").args(["volume", "inspect", name]),
+        PROBE_TIMEOUT,
+    )?;
 
     if !check.status.success() {
-        let create = Command::new("docker")
-            .args(["volume", "create", name])
-            .output()?;
+        let create = run_with_timeout(
+            Command::new("docker").args(["volume", "create", name]),
+            COMMAND_TIMEOUT,
+        )?;
 
         if !create.status.success() {
             let stderr = String::from_utf8_lossy(&create.stderr);
@@ -96,6 +267,84 @@ pub fn ensure_named_volume(name: &str) -> Result<()> {
     Ok(())
 }
 
+/// Run a docker listing command and return its non-empty output lines.
+fn list_names(args: &[&str]) -> Result<Vec<String>> {
+    let output = run_with_timeout(Command::new("docker").args(args), COMMAND_TIMEOUT)?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(DockerError::CommandFailed(stderr.trim().to_string()));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty())
+        .map(str::to_string)
+        .collect())
+}
+
+/// Names of all aoe sandbox containers, running or stopped.
+pub fn list_sandbox_containers() -> Result<Vec<String>> {
+    let filter = format!("name=^{}", CONTAINER_NAME_PREFIX);
+    let names = list_names(&["ps", "-a", "--filter", &filter, "--format", "{{.Names}}"])?;
+    // The name filter is a substring/regex match; double check the prefix
+    Ok(names
+        .into_iter()
+        .filter(|name| name.starts_with(CONTAINER_NAME_PREFIX))
+        .collect())
+}
+
+/// How many aoe sandbox containers are running. Errors (e.g. Docker is not
+/// running) count as none.
+pub fn count_running_sandbox_containers() -> usize {
+    let filter = format!("name=^{}", CONTAINER_NAME_PREFIX);
+    list_names(&["ps", "--filter", &filter, "--format", "{{.Names}}"])
+        .map(|names| {
+            names
+                .iter()
+                .filter(|name| name.starts_with(CONTAINER_NAME_PREFIX))
+                .count()
+        })
+        .unwrap_or(0)
+}
+
+/// Names of the tool auth volumes that exist, in any scope.
+pub fn list_auth_volumes() -> Result<Vec<String>> {
+    let names = list_names(&["volume", "ls", "--format", "{{.Name}}"])?;
+    Ok(names
+        .into_iter()
+        .filter(|name| is_auth_volume(name))
+        .collect())
+}
+
+pub fn remove_volume(name: &str) -> Result<()> {
+    let output = run_with_timeout(
+        Command::new("docker").args(["volume", "rm", name]),
+        COMMAND_TIMEOUT,
+    )?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(DockerError::CommandFailed(format!(
+            "Failed to remove volume {}: {}",
+            name,
+            stderr.trim()
+        )));
+    }
+
+    Ok(())
+}
+
+fn is_auth_volume(name: &str) -> bool {
+    AUTH_VOLUMES.iter().any(|base| {
+        name == *base
+            || name
+                .strip_prefix(base)
+                .is_some_and(|rest| rest.starts_with('-'))
+    })
+}
+
 /// The hardcoded fallback sandbox image.
 pub fn default_sandbox_image() -> &'static str {
     "ghcr.io/tslateman/aoe-sandbox:lite"
@@ -117,6 +366,72 @@ mod tests {
         !is_docker_available() || !is_daemon_running()
     }
 
+    #[test]
+    fn test_concurrent_ensure_image_pulls_once() {
+        use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+        let pulled = Arc::new(AtomicBool::new(false));
+        let pulls = Arc::new(AtomicUsize::new(0));
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let pulled = pulled.clone();
+                let pulls = pulls.clone();
+                std::thread::spawn(move || {
+                    ensure_image_with(
+                        "aoe-test/concurrent-pull:1",
+                        |_| pulled.load(Ordering::SeqCst),
+                        |_| {
+                            pulls.fetch_add(1, Ordering::SeqCst);
+                            std::thread::sleep(Duration::from_millis(100));
+                            pulled.store(true, Ordering::SeqCst);
+                            Ok(())
+                        },
+                    )
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            handle.join().unwrap().unwrap();
+        }
+        assert_eq!(pulls.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn test_ensure_image_failed_pull_lets_next_caller_retry() {
+        let image = "aoe-test/failed-pull:1";
+        let result = ensure_image_with(
+            image,
+            |_| false,
+            |i| Err(DockerError::ImageNotFound(i.to_string())),
+        );
+        assert!(result.is_err());
+
+        let mut retried = false;
+        ensure_image_with(
+            image,
+            |_| false,
+            |_| {
+                retried = true;
+                Ok(())
+            },
+        )
+        .unwrap();
+        assert!(retried);
+    }
+
+    #[test]
+    fn test_offline_missing_image_fails_without_retrying() {
+        let result = ensure_image_with("aoe-test/offline:1", |_| false, offline_pull);
+        let err = result.unwrap_err();
+        assert!(matches!(err, DockerError::OfflineImageMissing(_)));
+        assert!(err.to_string().starts_with("offline: image not present"));
+        assert!(!err.is_transient());
+
+        ensure_image_with("aoe-test/offline:1", |_| true, offline_pull).unwrap();
+    }
+
     #[test]
     fn test_image_exists_locally_with_common_image() {
         if skip_if_no_docker() {
@@ -168,4 +483,97 @@ mod tests {
         let result = ensure_image("nonexistent-image-that-does-not-exist:v999");
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_is_auth_volume() {
+        assert!(is_auth_volume("aoe-claude-auth"));
+        assert!(is_auth_volume("aoe-claude-auth-work"));
+        assert!(is_auth_volume("aoe-gemini-auth-my.profile"));
+        assert!(!is_auth_volume("aoe-claude-authx"));
+        assert!(!is_auth_volume("my-volume"));
+    }
+
+    #[test]
+    fn test_cached_check_reuses_result_within_ttl() {
+        let slot: CachedCheck = RwLock::new(None);
+        let mut probes = 0;
+        for _ in 0..3 {
+            assert!(cached_check(&slot, Duration::from_secs(60), || {
+                probes += 1;
+                true
+            }));
+        }
+        assert_eq!(probes, 1);
+    }
+
+    #[test]
+    fn test_cached_check_probes_again_after_ttl() {
+        let slot: CachedCheck = RwLock::new(None);
+        let mut probes = 0;
+        for _ in 0..2 {
+            cached_check(&slot, Duration::ZERO, || {
+                probes += 1;
+                false
+            });
+        }
+        assert_eq!(probes, 2);
+    }
+
+    #[test]
+    fn test_retry_recovers_from_transient_failure() {
+        let mut calls = 0;
+        let result = retry_with_delay(2, Duration::ZERO, "test", || {
+            calls += 1;
+            if calls < 3 {
+                Err(DockerError::CommandFailed("i/o timeout".to_string()))
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(result.unwrap(), 3);
+    }
+
+    #[test]
+    fn test_retry_gives_up_after_limit() {
+        let mut calls = 0;
+        let result: Result<()> = retry_with_delay(2, Duration::ZERO, "test", || {
+            calls += 1;
+            Err(DockerError::CommandFailed("i/o timeout".to_string()))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 3);
+    }
+
+    #[test]
+    fn test_retry_stops_on_terminal_failure() {
+        let mut calls = 0;
+        let result: Result<()> = retry_with_delay(2, Duration::ZERO, "test", || {
+            calls += 1;
+            Err(DockerError::ImageNotFound("manifest unknown".to_string()))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_zero_retries_runs_once() {
+        let mut calls = 0;
+        let result: Result<()> = retry_with_delay(0, Duration::ZERO, "test", || {
+            calls += 1;
+            Err(DockerError::CommandFailed("i/o timeout".to_string()))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+    }
+
+    #[test]
+    fn test_retries_capped_at_max() {
+        let mut calls = 0;
+        let result: Result<()> = retry_with_delay(40, Duration::ZERO, "test", || {
+            calls += 1;
+            Err(DockerError::CommandFailed("i/o timeout".to_string()))
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, MAX_DOCKER_RETRIES + 1);
+    }
 }
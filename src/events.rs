@@ -0,0 +1,352 @@
+//! Session lifecycle events for external integrations
+//!
+//! When `[events]` is configured, every lifecycle event is POSTed as JSON to
+//! `webhook` and/or passed to `command` (run with `sh -c`). Delivery happens
+//! one event at a time on a single background worker so it never blocks the
+//! TUI; when the worker falls behind, new events are dropped, and failures are
+//! only logged. The command receives:
+//!   - `AOE_EVENT`: the event type (`created`, `started`, `status_changed`, `deleted`)
+//!   - `AOE_SESSION_ID`, `AOE_SESSION_TITLE`, `AOE_PROFILE`: the session
+//!   - `AOE_STATUS`, `AOE_PREVIOUS_STATUS`: set for `status_changed`
+//!   - `AOE_EVENT_JSON`: the same payload the webhook receives
+
+use std::process::Command;
+use std::sync::mpsc::{self, Receiver, SyncSender, TrySendError};
+use std::sync::OnceLock;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::process::run_with_timeout;
+use crate::session::{EventsConfig, Instance, Status};
+
+const WEBHOOK_TIMEOUT: Duration = Duration::from_secs(10);
+const COMMAND_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Deliveries waiting for the worker before new events are dropped
+const QUEUE_SIZE: usize = 256;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EventKind {
+    Created,
+    Started,
+    StatusChanged,
+    Deleted,
+}
+
+impl EventKind {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            EventKind::Created => "created",
+            EventKind::Started => "started",
+            EventKind::StatusChanged => "status_changed",
+            EventKind::Deleted => "deleted",
+        }
+    }
+}
+
+/// The payload delivered to the webhook and command
+#[derive(Debug, Clone, Serialize)]
+pub struct Event {
+    pub event: EventKind,
+    pub session_id: String,
+    pub title: String,
+    pub profile: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<Status>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_status: Option<Status>,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl Event {
+    pub fn new(event: EventKind, inst: &Instance, profile: &str) -> Self {
+        Self {
+            event,
+            session_id: inst.id.clone(),
+            title: inst.title.clone(),
+            profile: profile.to_string(),
+            status: None,
+            previous_status: None,
+            timestamp: Utc::now(),
+        }
+    }
+
+    pub fn status_changed(inst: &Instance, profile: &str, old: Status, new: Status) -> Self {
+        Self {
+            status: Some(new),
+            previous_status: Some(old),
+            ..Self::new(EventKind::StatusChanged, inst, profile)
+        }
+    }
+
+    fn env_vars(&self, json: &str) -> Vec<(&'static str, String)> {
+        let mut vars = vec![
+            ("AOE_EVENT", self.event.as_str().to_string()),
+            ("AOE_SESSION_ID", self.session_id.clone()),
+            ("AOE_SESSION_TITLE", self.title.clone()),
+            ("AOE_PROFILE", self.profile.clone()),
+            ("AOE_EVENT_JSON", json.to_string()),
+        ];
+        if let Some(status) = self.status {
+            vars.push(("AOE_STATUS", status_name(status)));
+        }
+        if let Some(status) = self.previous_status {
+            vars.push(("AOE_PREVIOUS_STATUS", status_name(status)));
+        }
+        vars
+    }
+}
+
+fn status_name(status: Status) -> String {
+    serde_json::to_value(status)
+        .ok()
+        .and_then(|v| v.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+/// Whether a status transition means the agent has just come up. Sessions
+/// sit in `Starting` from launch until the first poll sees the agent.
+pub fn is_start_transition(old: Status, new: Status) -> bool {
+    old == Status::Starting && matches!(new, Status::Running | Status::Waiting | Status::Idle)
+}
+
+/// Deliver an event to the configured sinks (fire-and-forget, non-blocking)
+pub fn emit(config: &EventsConfig, event: Event) {
+    let webhook = config.webhook.clone().filter(|s| !s.trim().is_empty());
+    let command = config.command.clone().filter(|s| !s.trim().is_empty());
+    if webhook.is_none() && command.is_none() {
+        return;
+    }
+
+    let json = match serde_json::to_string(&event) {
+        Ok(json) => json,
+        Err(e) => {
+            tracing::warn!("Failed to serialize {} event: {}", event.event.as_str(), e);
+            return;
+        }
+    };
+
+    if let Some(command) = command {
+        let vars = event.env_vars(&json);
+        queue(Delivery::Command { command, vars });
+    }
+
+    if let Some(url) = webhook {
+        if crate::session::is_offline() {
+            tracing::debug!(
+                "Offline mode is on, not posting {} event to webhook",
+                event.event.as_str()
+            );
+        } else {
+            queue(Delivery::Webhook { url, json });
+        }
+    }
+}
+
+enum Delivery {
+    Command {
+        command: String,
+        vars: Vec<(&'static str, String)>,
+    },
+    Webhook {
+        url: String,
+        json: String,
+    },
+}
+
+/// Hand a delivery to the worker, starting it on first use
+fn queue(delivery: Delivery) {
+    static WORKER: OnceLock<Option<SyncSender<Delivery>>> = OnceLock::new();
+
+    let worker = WORKER.get_or_init(|| {
+        let (tx, rx) = mpsc::sync_channel(QUEUE_SIZE);
+        match std::thread::Builder::new()
+            .name("aoe-events".to_string())
+            .spawn(move || deliver_all(rx))
+        {
+            Ok(_) => Some(tx),
+            Err(e) => {
+                tracing::warn!("Failed to start event delivery worker: {}", e);
+                None
+            }
+        }
+    });
+
+    match worker.as_ref().map(|tx| tx.try_send(delivery)) {
+        Some(Err(TrySendError::Full(_))) => {
+            tracing::warn!("Event delivery is falling behind, dropping an event")
+        }
+        Some(Err(TrySendError::Disconnected(_))) => {
+            tracing::warn!("Event delivery worker has stopped, dropping an event")
+        }
+        Some(Ok(())) | None => {}
+    }
+}
+
+fn deliver_all(rx: Receiver<Delivery>) {
+    // Built on the first webhook and reused for the rest
+    let mut webhooks: Option<WebhookClient> = None;
+
+    for delivery in rx {
+        match delivery {
+            Delivery::Command { command, vars } => run_command(&command, vars, COMMAND_TIMEOUT),
+            Delivery::Webhook { url, json } => {
+                if webhooks.is_none() {
+                    match WebhookClient::new() {
+                        Ok(client) => webhooks = Some(client),
+                        Err(e) => {
+                            tracing::warn!("Failed to set up event webhooks: {}", e);
+                            continue;
+                        }
+                    }
+                }
+                if let Some(client) = &webhooks {
+                    client.post(&url, json);
+                }
+            }
+        }
+    }
+}
+
+/// Run the event command, killing it (and anything it spawned) after `timeout`
+fn run_command(command: &str, vars: Vec<(&'static str, String)>, timeout: Duration) {
+    let result = run_with_timeout(Command::new("sh").args(["-c", command]).envs(vars), timeout);
+
+    match result {
+        Ok(output) if output.status.success() => {}
+        Ok(output) => tracing::warn!(
+            "Event command exited with {:?}: {}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        ),
+        Err(e) => tracing::warn!("Failed to run event command: {}", e),
+    }
+}
+
+struct WebhookClient {
+    runtime: tokio::runtime::Runtime,
+    client: reqwest::Client,
+}
+
+impl WebhookClient {
+    fn new() -> Result<Self, String> {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| format!("failed to start runtime: {}", e))?;
+        let client = reqwest::Client::builder()
+            .user_agent("agent-of-empires")
+            .timeout(WEBHOOK_TIMEOUT)
+            .build()
+            .map_err(|e| format!("failed to build client: {}", e))?;
+        Ok(Self { runtime, client })
+    }
+
+    fn post(&self, url: &str, json: String) {
+        let result = self.runtime.block_on(
+            self.client
+                .post(url)
+                .header(reqwest::header::CONTENT_TYPE, "application/json")
+                .body(json)
+                .send(),
+        );
+
+        match result {
+            Ok(response) if response.status().is_success() => {}
+            Ok(response) => {
+                tracing::warn!("Event webhook {} returned HTTP {}", url, response.status())
+            }
+            Err(e) => tracing::warn!("Event webhook {} failed: {}", url, e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_status_changed_payload() {
+        let inst = Instance::new("api", "/tmp/api");
+        let event = Event::status_changed(&inst, "default", Status::Running, Status::Waiting);
+        let json: serde_json::Value = serde_json::to_value(&event).unwrap();
+        assert_eq!(json["event"], "status_changed");
+        assert_eq!(json["session_id"], inst.id.as_str());
+        assert_eq!(json["status"], "waiting");
+        assert_eq!(json["previous_status"], "running");
+    }
+
+    #[test]
+    fn test_lifecycle_payload_omits_statuses() {
+        let inst = Instance::new("api", "/tmp/api");
+        let json = serde_json::to_value(Event::new(EventKind::Deleted, &inst, "default")).unwrap();
+        assert_eq!(json["event"], "deleted");
+        assert!(json.get("status").is_none());
+        assert!(json.get("previous_status").is_none());
+    }
+
+    #[test]
+    fn test_command_env_vars() {
+        let inst = Instance::new("api", "/tmp/api");
+        let event = Event::status_changed(&inst, "work", Status::Waiting, Status::Running);
+        let vars = event.env_vars("{}");
+        let get = |key: &str| {
+            vars.iter()
+                .find(|(k, _)| *k == key)
+                .map(|(_, v)| v.as_str())
+        };
+        assert_eq!(get("AOE_EVENT"), Some("status_changed"));
+        assert_eq!(get("AOE_SESSION_ID"), Some(inst.id.as_str()));
+        assert_eq!(get("AOE_PROFILE"), Some("work"));
+        assert_eq!(get("AOE_STATUS"), Some("running"));
+        assert_eq!(get("AOE_PREVIOUS_STATUS"), Some("waiting"));
+    }
+
+    #[test]
+    fn test_is_start_transition() {
+        assert!(is_start_transition(Status::Starting, Status::Running));
+        assert!(is_start_transition(Status::Starting, Status::Idle));
+        assert!(!is_start_transition(Status::Starting, Status::Error));
+        assert!(!is_start_transition(Status::Idle, Status::Running));
+    }
+
+    #[test]
+    fn test_run_command_times_out() {
+        let started = std::time::Instant::now();
+        run_command("sleep 10", Vec::new(), Duration::from_millis(200));
+        assert!(started.elapsed() < Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_emit_runs_command_on_worker() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let out = dir.path().join("event");
+        let config = EventsConfig {
+            command: Some(format!("printf %s \"$AOE_EVENT\" > '{}'", out.display())),
+            ..Default::default()
+        };
+        let inst = Instance::new("api", "/tmp/api");
+        emit(&config, Event::new(EventKind::Created, &inst, "default"));
+
+        let deadline = std::time::Instant::now() + Duration::from_secs(5);
+        while std::fs::read_to_string(&out).unwrap_or_default() != "created" {
+            assert!(
+                std::time::Instant::now() < deadline,
+                "event command never ran"
+            );
+            std::thread::sleep(Duration::from_millis(20));
+        }
+    }
+
+    #[test]
+    fn test_emit_without_sinks_is_a_noop() {
+        let inst = Instance::new("api", "/tmp/api");
+        emit(
+            &EventsConfig::default(),
+            Event::new(EventKind::Created, &inst, "default"),
+        );
+    }
+}
@@ -5,10 +5,12 @@
 
 use std::collections::HashMap;
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
 use similar::{ChangeTag, TextDiff};
 
 use super::error::{GitError, Result};
+use crate::process::{run_with_timeout, COMMAND_TIMEOUT};
 
 /// Status of a file in the diff
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -204,6 +206,12 @@ fn get_commit_from_ref<'a>(
         .map_err(|_| GitError::BranchNotFound(reference.to_string()))
 }
 
+/// Whether `reference` resolves to a commit (branch, remote branch, tag, or revision).
+pub fn ref_exists(repo_path: &Path, reference: &str) -> bool {
+    git2::Repository::discover(repo_path)
+        .is_ok_and(|repo| get_commit_from_ref(&repo, reference).is_ok())
+}
+
 /// Get the merge-base tree between HEAD and the given reference.
 /// This produces GitHub-style PR diffs: only changes introduced on the
 /// current branch are shown, excluding new commits on the base branch
@@ -462,6 +470,47 @@ pub fn save_working_file_content(repo_path: &Path, file_path: &Path, content: &s
     std::fs::write(&full_path, content).map_err(GitError::IoError)
 }
 
+/// Stage a file with `git add`, including deletions
+pub fn stage_file(repo_path: &Path, file_path: &Path) -> Result<()> {
+    let path_str = file_path
+        .to_str()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid path"))?;
+    run_git(repo_path, &["add", "-A", "--", path_str])?;
+    Ok(())
+}
+
+/// Commit staged changes with `git commit -m`, honoring the user's hooks and
+/// signing config. Returns the short hash of the new commit.
+pub fn commit_staged(repo_path: &Path, message: &str) -> Result<String> {
+    run_git(repo_path, &["commit", "-m", message])?;
+    let hash = run_git(repo_path, &["rev-parse", "--short", "HEAD"])?;
+    Ok(hash.trim().to_string())
+}
+
+/// Run a git command from the repository's working directory and return its stdout
+fn run_git(repo_path: &Path, args: &[&str]) -> Result<String> {
+    let repo = git2::Repository::discover(repo_path)?;
+    let workdir = repo.workdir().ok_or(GitError::NotAGitRepo)?;
+
+    let output = run_with_timeout(
+        Command::new("git").args(args).current_dir(workdir),
+        COMMAND_TIMEOUT,
+    )?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+        let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+        // `git commit` reports "nothing to commit" on stdout
+        return Err(GitError::CommandFailed(if stderr.is_empty() {
+            stdout
+        } else {
+            stderr
+        }));
+    }
+
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
 /// List available branches in the repository
 pub fn list_branches(repo_path: &Path) -> Result<Vec<String>> {
     let repo = git2::Repository::discover(repo_path)?;
@@ -884,4 +933,37 @@ mod tests {
         let loaded = get_working_file_content(dir.path(), Path::new("test.txt")).unwrap();
         assert_eq!(loaded, content);
     }
+
+    #[test]
+    fn test_stage_and_commit_file() {
+        let (dir, repo) = setup_test_repo();
+        {
+            let mut config = repo.config().unwrap();
+            config.set_str("user.name", "Test").unwrap();
+            config.set_str("user.email", "test@example.com").unwrap();
+        }
+        fs::write(dir.path().join("test.txt"), "changed\n").unwrap();
+        fs::write(dir.path().join("other.txt"), "untouched\n").unwrap();
+
+        stage_file(dir.path(), Path::new("test.txt")).unwrap();
+        let hash = commit_staged(dir.path(), "Update test file").unwrap();
+
+        let head = repo.head().unwrap().peel_to_commit().unwrap();
+        assert!(head.id().to_string().starts_with(&hash));
+        assert_eq!(head.message(), Some("Update test file\n"));
+        // Only the staged file was committed
+        assert!(head.tree().unwrap().get_name("other.txt").is_none());
+    }
+
+    #[test]
+    fn test_commit_with_nothing_staged_fails() {
+        let (dir, repo) = setup_test_repo();
+        {
+            let mut config = repo.config().unwrap();
+            config.set_str("user.name", "Test").unwrap();
+            config.set_str("user.email", "test@example.com").unwrap();
+        }
+        let err = commit_staged(dir.path(), "Empty").unwrap_err();
+        assert!(matches!(err, GitError::CommandFailed(_)));
+    }
 }
@@ -1,6 +1,7 @@
 // Git worktree operations module
 
 use std::path::{Path, PathBuf};
+use std::process::Command;
 
 pub mod diff;
 pub mod error;
@@ -9,6 +10,8 @@ pub mod template;
 use error::{GitError, Result};
 use template::{resolve_template, TemplateVars};
 
+use crate::process::{run_with_timeout, COMMAND_TIMEOUT};
+
 pub struct WorktreeEntry {
     pub path: PathBuf,
     pub branch: Option<String>,
@@ -130,10 +133,12 @@ impl GitWorktree {
             .to_str()
             .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid path"))?;
 
-        let output = std::process::Command::new("git")
-            .args(["worktree", "add", path_str, branch])
-            .current_dir(&self.repo_path)
-            .output()?;
+        let output = run_with_timeout(
+            Command::new("git")
+                .args(["worktree", "add", path_str, branch])
+                .current_dir(&self.repo_path),
+            COMMAND_TIMEOUT,
+        )?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
@@ -150,10 +155,12 @@ impl GitWorktree {
 
     /// Prune stale worktree entries whose directories no longer exist on disk.
     fn prune_worktrees(&self) -> Result<()> {
-        let output = std::process::Command::new("git")
-            .args(["worktree", "prune"])
-            .current_dir(&self.repo_path)
-            .output()?;
+        let output = run_with_timeout(
+            Command::new("git")
+                .args(["worktree", "prune"])
+                .current_dir(&self.repo_path),
+            COMMAND_TIMEOUT,
+        )?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
@@ -272,10 +279,12 @@ impl GitWorktree {
             .to_str()
             .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::InvalidInput, "Invalid path"))?;
 
-        let output = std::process::Command::new("git")
-            .args(["worktree", "remove", path_str])
-            .current_dir(&self.repo_path)
-            .output()?;
+        let output = run_with_timeout(
+            Command::new("git")
+                .args(["worktree", "remove", path_str])
+                .current_dir(&self.repo_path),
+            COMMAND_TIMEOUT,
+        )?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
@@ -288,19 +297,23 @@ impl GitWorktree {
     /// Delete a local git branch.
     /// Returns an error if the branch doesn't exist or is currently checked out.
     pub fn delete_branch(&self, branch: &str) -> Result<()> {
-        let output = std::process::Command::new("git")
-            .args(["branch", "-d", branch])
-            .current_dir(&self.repo_path)
-            .output()?;
+        let output = run_with_timeout(
+            Command::new("git")
+                .args(["branch", "-d", branch])
+                .current_dir(&self.repo_path),
+            COMMAND_TIMEOUT,
+        )?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
             // If the branch has unmerged changes, try force delete
             if stderr.contains("not fully merged") {
-                let force_output = std::process::Command::new("git")
-                    .args(["branch", "-D", branch])
-                    .current_dir(&self.repo_path)
-                    .output()?;
+                let force_output = run_with_timeout(
+                    Command::new("git")
+                        .args(["branch", "-D", branch])
+                        .current_dir(&self.repo_path),
+                    COMMAND_TIMEOUT,
+                )?;
 
                 if !force_output.status.success() {
                     return Err(GitError::BranchNotFound(branch.to_string()));
@@ -313,7 +326,13 @@ impl GitWorktree {
         Ok(())
     }
 
-    pub fn compute_path(&self, branch: &str, template: &str, session_id: &str) -> Result<PathBuf> {
+    pub fn compute_path(
+        &self,
+        branch: &str,
+        template: &str,
+        session_id: &str,
+        branch_separator: &str,
+    ) -> Result<PathBuf> {
         let repo_name = self
             .repo_path
             .file_name()
@@ -326,6 +345,7 @@ impl GitWorktree {
             branch: branch.to_string(),
             session_id: session_id.to_string(),
             base_path: self.repo_path.clone(),
+            branch_separator: branch_separator.to_string(),
         };
 
         resolve_template(template, &vars)
@@ -474,7 +494,7 @@ mod tests {
 
         let template = "../{repo-name}-worktrees/{branch}";
         let path = git_wt
-            .compute_path("feat/test", template, "abc123")
+            .compute_path("feat/test", template, "abc123", "-")
             .unwrap();
 
         assert!(path.to_string_lossy().contains("feat-test"));
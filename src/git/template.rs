@@ -9,21 +9,80 @@ pub struct TemplateVars {
     pub branch: String,
     pub session_id: String,
     pub base_path: PathBuf,
+    /// Replaces `/` in the branch when it becomes a directory name
+    pub branch_separator: String,
 }
 
-pub fn sanitize_branch_name(branch: &str) -> String {
-    branch.replace(
-        ['/', '@', '#', '\\', ':', '*', '?', '"', '<', '>', '|'],
-        "-",
-    )
+/// Placeholders understood by `resolve_template`
+pub const PLACEHOLDERS: [&str; 6] = [
+    "{repo-name}",
+    "{repo}",
+    "{repo_parent}",
+    "{branch}",
+    "{branch_slug}",
+    "{session-id}",
+];
+
+/// Characters invalid in file names on some filesystems, or awkward in shell
+/// paths, replaced with `-` in worktree directory names
+const UNSAFE_PATH_CHARS: [char; 10] = ['@', '#', '\\', ':', '*', '?', '"', '<', '>', '|'];
+
+/// Turn a branch name into a single directory name. `/` becomes `separator`,
+/// unsafe characters become `-` and control characters are dropped. Leading
+/// and trailing dots and spaces are trimmed so the name is never `.`, `..` or
+/// hidden. Non-ASCII characters are kept. The git branch keeps its real name.
+pub fn sanitize_branch_name(branch: &str, separator: &str) -> String {
+    let mut out = String::with_capacity(branch.len());
+    for c in branch.chars() {
+        if c == '/' {
+            out.push_str(separator);
+        } else if UNSAFE_PATH_CHARS.contains(&c) {
+            out.push('-');
+        } else if !c.is_control() {
+            out.push(c);
+        }
+    }
+
+    let trimmed = out.trim_matches(|c| c == '.' || c == ' ');
+    if trimmed.is_empty() {
+        "branch".to_string()
+    } else {
+        trimmed.to_string()
+    }
+}
+
+/// Validate a branch separator: it ends up inside a single directory name, so
+/// it cannot contain a path separator or characters the sanitizer replaces
+pub fn validate_branch_separator(separator: &str) -> std::result::Result<(), String> {
+    if separator
+        .chars()
+        .any(|c| c == '/' || UNSAFE_PATH_CHARS.contains(&c) || c.is_control())
+    {
+        return Err(format!(
+            "Branch separator cannot contain '/' or any of {}",
+            UNSAFE_PATH_CHARS.iter().collect::<String>()
+        ));
+    }
+    Ok(())
 }
 
 pub fn resolve_template(template: &str, vars: &TemplateVars) -> Result<PathBuf> {
-    let sanitized_branch = sanitize_branch_name(&vars.branch);
+    let sanitized_branch = sanitize_branch_name(&vars.branch, &vars.branch_separator);
+    // The directory holding the repo, so templates can place worktrees as
+    // its siblings without relying on `..`
+    let repo_parent = vars
+        .base_path
+        .parent()
+        .unwrap_or(&vars.base_path)
+        .to_string_lossy()
+        .to_string();
 
     let resolved = template
         .replace("{repo-name}", &vars.repo_name)
+        .replace("{repo}", &vars.repo_name)
+        .replace("{repo_parent}", &repo_parent)
         .replace("{branch}", &sanitized_branch)
+        .replace("{branch_slug}", &sanitized_branch)
         .replace("{session-id}", &vars.session_id);
 
     let path = if resolved.starts_with('/') {
@@ -46,6 +105,7 @@ mod tests {
             branch: "feat/test".to_string(),
             session_id: "abc123".to_string(),
             base_path: PathBuf::from("/home/user/repos/my-repo"),
+            branch_separator: "-".to_string(),
         };
 
         let result = resolve_template("../{repo-name}-wt/{branch}", &vars).unwrap();
@@ -55,13 +115,13 @@ mod tests {
 
     #[test]
     fn test_sanitize_branch_name_replaces_slashes() {
-        let sanitized = sanitize_branch_name("feat/my-feature");
+        let sanitized = sanitize_branch_name("feat/my-feature", "-");
         assert_eq!(sanitized, "feat-my-feature");
     }
 
     #[test]
     fn test_sanitize_branch_name_handles_special_chars() {
-        let sanitized = sanitize_branch_name("feat@bug#123");
+        let sanitized = sanitize_branch_name("feat@bug#123", "-");
         assert!(!sanitized.contains("@"));
         assert!(!sanitized.contains("#"));
     }
@@ -73,6 +133,7 @@ mod tests {
             branch: "main".to_string(),
             session_id: "xyz789".to_string(),
             base_path: PathBuf::from("/repos/test"),
+            branch_separator: "-".to_string(),
         };
 
         let result = resolve_template("../wt/{repo-name}/{branch}/{session-id}", &vars).unwrap();
@@ -81,4 +142,77 @@ mod tests {
         assert!(result.to_string_lossy().contains("main"));
         assert!(result.to_string_lossy().contains("xyz789"));
     }
+
+    #[test]
+    fn test_resolve_template_sibling_of_repo() {
+        let vars = TemplateVars {
+            repo_name: "api".to_string(),
+            branch: "feat/login".to_string(),
+            session_id: "abc123".to_string(),
+            base_path: PathBuf::from("/home/user/code/api"),
+            branch_separator: "-".to_string(),
+        };
+
+        let result =
+            resolve_template("{repo_parent}/{repo}-worktrees/{branch_slug}", &vars).unwrap();
+        assert_eq!(
+            result,
+            PathBuf::from("/home/user/code/api-worktrees/feat-login")
+        );
+
+        let result = resolve_template("{repo_parent}/{repo}-{branch_slug}", &vars).unwrap();
+        assert_eq!(result, PathBuf::from("/home/user/code/api-feat-login"));
+    }
+
+    #[test]
+    fn test_sanitize_branch_name_custom_separator() {
+        assert_eq!(
+            sanitize_branch_name("feature/new-ui", "_"),
+            "feature_new-ui"
+        );
+        assert_eq!(sanitize_branch_name("a/b/c", "--"), "a--b--c");
+        assert_eq!(sanitize_branch_name("a/b", ""), "ab");
+    }
+
+    #[test]
+    fn test_sanitize_branch_name_keeps_unicode() {
+        assert_eq!(sanitize_branch_name("feat/café-ñ", "-"), "feat-café-ñ");
+        assert_eq!(sanitize_branch_name("修正/バグ", "-"), "修正-バグ");
+    }
+
+    #[test]
+    fn test_sanitize_branch_name_strips_illegal_chars() {
+        assert_eq!(
+            sanitize_branch_name("fix:a*b?c\"d<e>f|g\\h", "-"),
+            "fix-a-b-c-d-e-f-g-h"
+        );
+        assert_eq!(sanitize_branch_name("tab\there\u{7f}", "-"), "tabhere");
+        // Never a relative path component or a hidden directory
+        assert_eq!(sanitize_branch_name("..", "-"), "branch");
+        assert_eq!(sanitize_branch_name(".hidden/x.", "-"), "hidden-x");
+        assert_eq!(sanitize_branch_name("a/../b", ""), "a..b");
+    }
+
+    #[test]
+    fn test_resolve_template_branch_stays_one_directory() {
+        let vars = TemplateVars {
+            repo_name: "repo".to_string(),
+            branch: "feature/new-ui".to_string(),
+            session_id: "abc123".to_string(),
+            base_path: PathBuf::from("/repos/repo"),
+            branch_separator: "__".to_string(),
+        };
+
+        let result = resolve_template("../wt/{branch}", &vars).unwrap();
+        assert_eq!(result, PathBuf::from("/repos/repo/../wt/feature__new-ui"));
+    }
+
+    #[test]
+    fn test_validate_branch_separator() {
+        assert!(validate_branch_separator("-").is_ok());
+        assert!(validate_branch_separator("_").is_ok());
+        assert!(validate_branch_separator("").is_ok());
+        assert!(validate_branch_separator("/").is_err());
+        assert!(validate_branch_separator("a:b").is_err());
+    }
 }
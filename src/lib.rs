@@ -3,7 +3,9 @@
 pub mod cli;
 pub mod context;
 pub mod docker;
+pub mod events;
 pub mod git;
+pub mod logging;
 pub mod migrations;
 pub mod process;
 pub mod session;
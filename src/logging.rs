@@ -0,0 +1,154 @@
+//! Tracing setup
+//!
+//! `AGENT_OF_EMPIRES_DEBUG` logs to stderr for CLI commands. The TUI owns the
+//! terminal, so it never logs to stderr; with the env var set it logs to the
+//! log file instead. `[logging] enabled` writes the log file in every mode.
+
+use std::path::PathBuf;
+
+use anyhow::Result;
+use tracing_appender::rolling::{RollingFileAppender, Rotation};
+use tracing_subscriber::filter::EnvFilter;
+use tracing_subscriber::prelude::*;
+
+use crate::session::{get_app_dir, LogLevel, LoggingConfig};
+
+pub const DEBUG_ENV: &str = "AGENT_OF_EMPIRES_DEBUG";
+
+/// Directory holding `aoe.<date>.log` files
+pub fn log_dir() -> Result<PathBuf> {
+    Ok(get_app_dir()?.join("logs"))
+}
+
+/// Where tracing output should go
+#[derive(Debug, PartialEq, Eq)]
+struct Targets {
+    stderr: bool,
+    file: Option<LogLevel>,
+}
+
+fn targets(config: &LoggingConfig, debug_env: bool, tui: bool) -> Targets {
+    let file = if config.enabled {
+        Some(config.level)
+    } else if debug_env && tui {
+        Some(LogLevel::Debug)
+    } else {
+        None
+    };
+
+    Targets {
+        stderr: debug_env && !tui,
+        file,
+    }
+}
+
+fn filter(level: LogLevel) -> EnvFilter {
+    EnvFilter::new(format!("agent_of_empires={}", level.as_str()))
+}
+
+fn file_appender(config: &LoggingConfig) -> Result<RollingFileAppender> {
+    let dir = log_dir()?;
+    std::fs::create_dir_all(&dir)?;
+    Ok(RollingFileAppender::builder()
+        .rotation(Rotation::DAILY)
+        .filename_prefix("aoe")
+        .filename_suffix("log")
+        .max_log_files(config.max_files.max(1) as usize)
+        .build(dir)?)
+}
+
+/// Run `f` before the config can be read (e.g. migrations), logging to
+/// stderr when `AGENT_OF_EMPIRES_DEBUG` is set. Nothing is written to disk,
+/// and the TUI has not taken over the terminal yet.
+pub fn with_startup_logger<T>(f: impl FnOnce() -> T) -> T {
+    if std::env::var(DEBUG_ENV).is_err() {
+        return f();
+    }
+    let subscriber = tracing_subscriber::registry().with(
+        tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_filter(filter(LogLevel::Debug)),
+    );
+    tracing::subscriber::with_default(subscriber, f)
+}
+
+/// Install the global tracing subscriber. `tui` is true when the TUI is about
+/// to take over the terminal.
+pub fn init(config: &LoggingConfig, tui: bool) {
+    let targets = targets(config, std::env::var(DEBUG_ENV).is_ok(), tui);
+
+    let file_layer = targets.file.and_then(|level| match file_appender(config) {
+        Ok(appender) => Some(
+            tracing_subscriber::fmt::layer()
+                .with_writer(appender)
+                .with_ansi(false)
+                .with_filter(filter(level)),
+        ),
+        Err(e) => {
+            // The TUI has not started yet, so stderr is still safe here
+            eprintln!("Warning: file logging disabled: {}", e);
+            None
+        }
+    });
+    let stderr_layer = targets.stderr.then(|| {
+        tracing_subscriber::fmt::layer()
+            .with_writer(std::io::stderr)
+            .with_filter(filter(LogLevel::Debug))
+    });
+
+    if file_layer.is_none() && stderr_layer.is_none() {
+        return;
+    }
+
+    tracing_subscriber::registry()
+        .with(file_layer)
+        .with(stderr_layer)
+        .init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_tui_never_logs_to_stderr() {
+        let config = LoggingConfig::default();
+        assert_eq!(
+            targets(&config, true, true),
+            Targets {
+                stderr: false,
+                file: Some(LogLevel::Debug),
+            }
+        );
+    }
+
+    #[test]
+    fn test_debug_env_logs_cli_to_stderr() {
+        let config = LoggingConfig::default();
+        assert_eq!(
+            targets(&config, true, false),
+            Targets {
+                stderr: true,
+                file: None,
+            }
+        );
+        assert_eq!(
+            targets(&config, false, false),
+            Targets {
+                stderr: false,
+                file: None,
+            }
+        );
+    }
+
+    #[test]
+    fn test_enabled_config_logs_to_file_at_configured_level() {
+        let config = LoggingConfig {
+            enabled: true,
+            level: LogLevel::Warn,
+            ..Default::default()
+        };
+        assert_eq!(targets(&config, false, true).file, Some(LogLevel::Warn));
+        assert_eq!(targets(&config, false, false).file, Some(LogLevel::Warn));
+    }
+}
@@ -1,23 +1,30 @@
 //! Agent of Empires - Terminal session manager for AI coding agents
 
 use agent_of_empires::cli::{self, Cli, Commands};
+use agent_of_empires::logging;
 use agent_of_empires::migrations;
+use agent_of_empires::session::{resolve_config, resolve_profile};
 use agent_of_empires::tui;
 use anyhow::Result;
 use clap::Parser;
 
 #[tokio::main]
 async fn main() -> Result<()> {
-    if std::env::var("AGENT_OF_EMPIRES_DEBUG").is_ok() {
-        tracing_subscriber::fmt()
-            .with_env_filter("agent_of_empires=debug")
-            .init();
+    let cli = Cli::parse();
+
+    // `aoe migrate` inspects and applies migrations itself. Migrations run
+    // before anything reads the config, which may still be in its old place.
+    if !matches!(cli.command, Some(Commands::Migrate(_))) {
+        logging::with_startup_logger(migrations::run_migrations)?;
     }
 
-    migrations::run_migrations()?;
+    // Only the TUI resumes the profile it was last switched to
+    let profile = resolve_profile(cli.profile.as_deref(), cli.command.is_none());
 
-    let cli = Cli::parse();
-    let profile = cli.profile.unwrap_or_default();
+    let logging_config = resolve_config(&profile)
+        .map(|config| config.logging)
+        .unwrap_or_default();
+    logging::init(&logging_config, cli.command.is_none());
 
     match cli.command {
         Some(Commands::Add(args)) => cli::add::run(&profile, args).await,
@@ -28,7 +35,8 @@ async fn main() -> Result<()> {
         Some(Commands::Status(args)) => cli::status::run(&profile, args).await,
         Some(Commands::Session { command }) => cli::session::run(&profile, command).await,
         Some(Commands::Group { command }) => cli::group::run(&profile, command).await,
-        Some(Commands::Profile { command }) => cli::profile::run(command).await,
+        Some(Commands::Profile { command }) => cli::profile::run(&profile, command).await,
+        Some(Commands::Sandbox { command }) => cli::sandbox::run(&profile, command).await,
         Some(Commands::Worktree { command }) => cli::worktree::run(&profile, command).await,
         Some(Commands::Tmux { command }) => {
             use cli::tmux::TmuxCommands;
@@ -37,7 +45,8 @@ async fn main() -> Result<()> {
             }
         }
         Some(Commands::Sounds { command }) => cli::sounds::run(command).await,
+        Some(Commands::Migrate(args)) => cli::migrate::run(args),
         Some(Commands::Uninstall(args)) => cli::uninstall::run(args).await,
-        None => tui::run(&profile).await,
+        None => tui::run(&profile, cli.readonly).await,
     }
 }
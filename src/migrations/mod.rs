@@ -21,36 +21,73 @@ const VERSION_FILE: &str = ".schema_version";
 struct Migration {
     version: u32,
     name: &'static str,
+    /// What running the migration does, shown by `aoe migrate --dry-run`
+    description: &'static str,
     run: fn() -> Result<()>,
 }
 
 const MIGRATIONS: &[Migration] = &[Migration {
     version: 1,
     name: "xdg_linux",
+    description:
+        "Move data from ~/.agent-of-empires to $XDG_CONFIG_HOME/agent-of-empires (Linux only)",
     run: v001_xdg_linux::run,
 }];
 
-/// Run all pending migrations. Call this early in app startup.
-pub fn run_migrations() -> Result<()> {
+/// Whether a migration has been applied, as recorded in the schema version file.
+pub struct MigrationStatus {
+    pub version: u32,
+    pub name: &'static str,
+    pub description: &'static str,
+    pub applied: bool,
+}
+
+/// List every known migration with its applied state.
+pub fn status() -> Vec<MigrationStatus> {
     let current = get_current_version();
-    debug!("Current schema version: {}", current);
+    MIGRATIONS
+        .iter()
+        .map(|m| MigrationStatus {
+            version: m.version,
+            name: m.name,
+            description: m.description,
+            applied: m.version <= current,
+        })
+        .collect()
+}
 
-    if current >= CURRENT_VERSION {
+/// Migrations that `run_migrations` would apply.
+pub fn pending() -> Vec<MigrationStatus> {
+    status().into_iter().filter(|m| !m.applied).collect()
+}
+
+/// Run all pending migrations. Call this early in app startup.
+pub fn run_migrations() -> Result<()> {
+    if get_current_version() >= CURRENT_VERSION {
         return Ok(());
     }
+    apply_pending(MIGRATIONS).map(|_| ())
+}
 
-    for migration in MIGRATIONS {
-        if migration.version > current {
-            info!(
-                "Running migration v{:03}: {}",
-                migration.version, migration.name
-            );
-            (migration.run)()?;
-            set_version(migration.version)?;
-        }
+/// Run each migration newer than the recorded version, persisting the version
+/// after every step so a migration never runs twice, even if a later one fails.
+/// Returns how many migrations ran.
+fn apply_pending(migrations: &[Migration]) -> Result<usize> {
+    let current = get_current_version();
+    debug!("Current schema version: {}", current);
+
+    let mut applied = 0;
+    for migration in migrations.iter().filter(|m| m.version > current) {
+        info!(
+            "Running migration v{:03}: {}",
+            migration.version, migration.name
+        );
+        (migration.run)()?;
+        set_version(migration.version)?;
+        applied += 1;
     }
 
-    Ok(())
+    Ok(applied)
 }
 
 /// Get the current schema version by checking all possible locations.
@@ -109,6 +146,68 @@ mod tests {
         }
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn test_status_tracks_version_file() {
+        let home = tempfile::TempDir::new().unwrap();
+        std::env::set_var("HOME", home.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", home.path().join(".config"));
+
+        assert!(status().iter().all(|m| !m.applied));
+        assert_eq!(pending().len(), MIGRATIONS.len());
+
+        set_version(CURRENT_VERSION).unwrap();
+        assert!(status().iter().all(|m| m.applied));
+        assert!(pending().is_empty());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_apply_pending_runs_each_migration_once() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        static FIRST: AtomicUsize = AtomicUsize::new(0);
+        static SECOND: AtomicUsize = AtomicUsize::new(0);
+        const TEST_MIGRATIONS: &[Migration] = &[
+            Migration {
+                version: 1,
+                name: "first",
+                description: "",
+                run: || {
+                    FIRST.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                },
+            },
+            Migration {
+                version: 2,
+                name: "second",
+                description: "",
+                run: || {
+                    SECOND.fetch_add(1, Ordering::SeqCst);
+                    Ok(())
+                },
+            },
+        ];
+
+        let home = tempfile::TempDir::new().unwrap();
+        std::env::set_var("HOME", home.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", home.path().join(".config"));
+        std::fs::create_dir_all(crate::session::get_app_dir().unwrap()).unwrap();
+
+        assert_eq!(apply_pending(&TEST_MIGRATIONS[..1]).unwrap(), 1);
+        assert_eq!(apply_pending(&TEST_MIGRATIONS[..1]).unwrap(), 0);
+
+        // Adding a migration runs only the new one
+        assert_eq!(apply_pending(TEST_MIGRATIONS).unwrap(), 1);
+        assert_eq!(apply_pending(TEST_MIGRATIONS).unwrap(), 0);
+
+        assert_eq!(FIRST.load(Ordering::SeqCst), 1);
+        assert_eq!(SECOND.load(Ordering::SeqCst), 1);
+        assert_eq!(get_current_version(), 2);
+    }
+
     #[test]
     fn test_current_version_matches_last_migration() {
         if let Some(last) = MIGRATIONS.last() {
@@ -98,6 +98,12 @@ pub fn get_foreground_pid(shell_pid: u32) -> Option<u32> {
     find_process_in_group(tpgid as u32).or(Some(shell_pid))
 }
 
+/// Executable name of a process, from /proc/[pid]/comm
+pub fn get_process_name(pid: u32) -> Option<String> {
+    let comm = fs::read_to_string(format!("/proc/{}/comm", pid)).ok()?;
+    Some(comm.trim_end().to_string())
+}
+
 /// Find a process that belongs to the given process group
 fn find_process_in_group(pgrp: u32) -> Option<u32> {
     let proc_dir = Path::new("/proc");
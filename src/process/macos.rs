@@ -117,6 +117,22 @@ pub fn get_foreground_pid(shell_pid: u32) -> Option<u32> {
     find_process_in_group(tpgid as u32).or(Some(shell_pid))
 }
 
+/// Executable name of a process, from `ps -o comm=` (which prints a path)
+pub fn get_process_name(pid: u32) -> Option<String> {
+    let output = Command::new("ps")
+        .args(["-o", "comm=", "-p", &pid.to_string()])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let comm = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    let name = comm.rsplit('/').next().unwrap_or(&comm).to_string();
+    (!name.is_empty()).then_some(name)
+}
+
 /// Find a process belonging to the given process group
 fn find_process_in_group(pgrp: u32) -> Option<u32> {
     // Use ps to find processes in this group
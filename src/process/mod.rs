@@ -1,6 +1,9 @@
 //! Process utilities for tmux session management
 
-use std::process::Command;
+use std::io;
+use std::process::{Command, Output, Stdio};
+use std::sync::mpsc;
+use std::time::Duration;
 
 #[cfg(target_os = "linux")]
 mod linux;
@@ -8,12 +11,80 @@ mod linux;
 #[cfg(target_os = "macos")]
 mod macos;
 
+/// Timeout for quick status probes (session exists, pane capture, inspect)
+pub const PROBE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Timeout for ordinary one-shot commands (create, kill, worktree add)
+pub const COMMAND_TIMEOUT: Duration = Duration::from_secs(120);
+
+/// Timeout for image pulls, which download large layers
+pub const PULL_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// Run `cmd` to completion like `Command::output`, but give up after `timeout`.
+///
+/// The child runs in its own process group, so on timeout it and anything it
+/// spawned are killed. A timeout is reported as an `io::ErrorKind::TimedOut`
+/// error. Stdin is closed, so this is only for non-interactive commands.
+pub fn run_with_timeout(cmd: &mut Command, timeout: Duration) -> io::Result<Output> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+
+    let child = cmd
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    let pid = child.id();
+
+    // Waiting happens on a helper thread so the timeout costs nothing when the
+    // command finishes promptly
+    let (tx, rx) = mpsc::channel();
+    std::thread::spawn(move || {
+        let _ = tx.send(child.wait_with_output());
+    });
+
+    match rx.recv_timeout(timeout) {
+        Ok(result) => result,
+        Err(mpsc::RecvTimeoutError::Timeout) => {
+            kill_process_group(pid);
+            Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                format!(
+                    "{} timed out after {}s",
+                    cmd.get_program().to_string_lossy(),
+                    timeout.as_secs_f32()
+                ),
+            ))
+        }
+        Err(mpsc::RecvTimeoutError::Disconnected) => Err(io::Error::other(
+            "command wait thread exited without a result",
+        )),
+    }
+}
+
+#[cfg(unix)]
+fn kill_process_group(pgid: u32) {
+    use nix::sys::signal::{killpg, Signal};
+    use nix::unistd::Pid;
+
+    let _ = killpg(Pid::from_raw(pgid as i32), Signal::SIGKILL);
+}
+
+#[cfg(not(unix))]
+fn kill_process_group(pid: u32) {
+    kill_process_tree(pid);
+}
+
 /// Get the PID of the shell process running in a tmux pane
 pub fn get_pane_pid(session_name: &str) -> Option<u32> {
-    let output = Command::new("tmux")
-        .args(["display-message", "-t", session_name, "-p", "#{pane_pid}"])
-        .output()
-        .ok()?;
+    let output = run_with_timeout(
+        Command::new("tmux").args(["display-message", "-t", session_name, "-p", "#{pane_pid}"]),
+        PROBE_TIMEOUT,
+    )
+    .ok()?;
 
     if !output.status.success() {
         return None;
@@ -42,6 +113,37 @@ pub fn get_foreground_pid(shell_pid: u32) -> Option<u32> {
     }
 }
 
+/// Get the executable name of a process (e.g. `zsh`, `claude`)
+pub fn get_process_name(pid: u32) -> Option<String> {
+    #[cfg(target_os = "linux")]
+    {
+        linux::get_process_name(pid)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        macos::get_process_name(pid)
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    {
+        let _ = pid;
+        None
+    }
+}
+
+/// Interactive shells a pane falls back to when its agent exits
+const SHELL_NAMES: &[&str] = &[
+    "sh", "bash", "zsh", "fish", "dash", "ksh", "mksh", "tcsh", "csh", "nu", "xonsh", "elvish",
+];
+
+/// Whether a process or program name is a shell. Accepts login-shell names
+/// (`-zsh`) and paths (`/bin/bash`).
+pub fn is_shell(name: &str) -> bool {
+    let name = name.rsplit('/').next().unwrap_or(name);
+    SHELL_NAMES.contains(&name.trim_start_matches('-'))
+}
+
 /// Kill a process and all its descendants
 /// Sends SIGTERM first, then SIGKILL to any survivors
 pub fn kill_process_tree(pid: u32) {
@@ -61,3 +163,77 @@ pub fn kill_process_tree(pid: u32) {
         // No-op on unsupported platforms, fall back to tmux kill-session only
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_is_shell() {
+        for name in ["bash", "zsh", "-zsh", "/bin/sh", "/usr/local/bin/fish"] {
+            assert!(is_shell(name), "{name} should be a shell");
+        }
+        for name in ["claude", "node", "opencode", "docker", "bashtop", ""] {
+            assert!(!is_shell(name), "{name} should not be a shell");
+        }
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn test_get_process_name_of_child() {
+        let mut child = Command::new("sleep").arg("5").spawn().unwrap();
+        // Right after spawn the child may not have exec'd `sleep` yet
+        let deadline = Instant::now() + Duration::from_secs(2);
+        let mut name = get_process_name(child.id());
+        while name.as_deref() != Some("sleep") && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(10));
+            name = get_process_name(child.id());
+        }
+        child.kill().unwrap();
+        child.wait().unwrap();
+        assert_eq!(name.as_deref(), Some("sleep"));
+    }
+
+    #[test]
+    fn test_run_with_timeout_captures_output() {
+        let output = run_with_timeout(
+            Command::new("sh").args(["-c", "echo out; echo err >&2; exit 3"]),
+            Duration::from_secs(10),
+        )
+        .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout), "out\n");
+        assert_eq!(String::from_utf8_lossy(&output.stderr), "err\n");
+        assert_eq!(output.status.code(), Some(3));
+    }
+
+    #[test]
+    fn test_run_with_timeout_kills_hung_command() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let pid_file = temp.path().join("pid");
+        let script = format!("sleep 30 & echo $! > {}; wait", pid_file.display());
+
+        let start = Instant::now();
+        let err = run_with_timeout(
+            Command::new("sh").args(["-c", &script]),
+            Duration::from_millis(200),
+        )
+        .unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::TimedOut);
+        assert!(err.to_string().contains("sh timed out"));
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        // The background child was in the same process group, so it is gone too
+        let pid: i32 = std::fs::read_to_string(&pid_file)
+            .unwrap()
+            .trim()
+            .parse()
+            .unwrap();
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let alive = || nix::sys::signal::kill(nix::unistd::Pid::from_raw(pid), None).is_ok();
+        while alive() && Instant::now() < deadline {
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        assert!(!alive());
+    }
+}
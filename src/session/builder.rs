@@ -4,15 +4,15 @@
 //! used by both synchronous (TUI operations) and asynchronous (background poller) code paths.
 
 use std::collections::HashMap;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
-use anyhow::{bail, Result};
+use anyhow::{bail, Context, Result};
 use chrono::Utc;
 
 use crate::docker::DockerContainer;
 use crate::git::GitWorktree;
 
-use super::{civilizations, Config, Instance, SandboxInfo, WorktreeInfo};
+use super::{civilizations, Instance, SandboxInfo, WorktreeInfo};
 
 /// Parameters for creating a new session instance.
 #[derive(Debug, Clone)]
@@ -21,6 +21,8 @@ pub struct InstanceParams {
     pub path: String,
     pub group: String,
     pub tool: String,
+    /// Command to launch instead of the tool's default. Empty uses the default.
+    pub command: String,
     pub worktree_branch: Option<String>,
     pub create_new_branch: bool,
     pub sandbox: bool,
@@ -31,6 +33,12 @@ pub struct InstanceParams {
     pub extra_env_keys: Vec<String>,
     /// Additional KEY=VALUE environment variables to inject into the container.
     pub extra_env_values: Vec<String>,
+    /// Extra `host:container[:ro]` mounts for the sandbox container.
+    pub extra_volumes: Vec<String>,
+    /// Docker network for the sandbox container, instead of the configured one.
+    pub network: Option<String>,
+    /// Session to create this one as a sub-session of.
+    pub parent_session_id: Option<String>,
 }
 
 /// Result of building an instance, tracking what was created for cleanup purposes.
@@ -46,20 +54,70 @@ pub struct CreatedWorktree {
     pub main_repo_path: PathBuf,
 }
 
-/// Build an instance with all setup (worktree resolution, sandbox config).
+/// Quick checks that a sandboxed session can be created at all: an image is
+/// named and Docker is installed and running. These take milliseconds, so
+/// callers can run them before starting slow work such as image pulls.
+pub fn preflight_sandbox(image: &str) -> Result<()> {
+    if image.trim().is_empty() {
+        bail!("No sandbox image specified. Enter an image or set sandbox.default_image.");
+    }
+    if !crate::docker::is_docker_available() {
+        crate::docker::invalidate_availability_cache();
+        bail!("Docker is not installed. Please install Docker to use sandbox mode.");
+    }
+    if !crate::docker::is_daemon_running() {
+        // Re-check on the next attempt rather than reusing this result, since
+        // the user is likely to start Docker and try again straight away
+        crate::docker::invalidate_availability_cache();
+        bail!("Docker daemon is not running. Please start Docker to use sandbox mode.");
+    }
+    Ok(())
+}
+
+/// Check that a project path is a directory, or with `allow_missing`, that
+/// it is at least not something else. Nothing is created.
+pub fn check_project_dir(path: &Path, allow_missing: bool) -> Result<()> {
+    if path.is_dir() {
+        return Ok(());
+    }
+    if path.exists() {
+        bail!("Path is not a directory: {}", path.display());
+    }
+    if !allow_missing {
+        bail!("Path does not exist: {}", path.display());
+    }
+    Ok(())
+}
+
+/// Check that a project path exists and is a directory. With `create`, a
+/// missing directory is created along with its parents.
+pub fn ensure_project_dir(path: &Path, create: bool) -> Result<()> {
+    check_project_dir(path, create)?;
+    if path.is_dir() {
+        return Ok(());
+    }
+    std::fs::create_dir_all(path)
+        .with_context(|| format!("Failed to create directory {}", path.display()))
+}
+
+/// Build an instance in `profile` with all setup (worktree resolution, sandbox
+/// config). The profile's config picks the worktree path template.
 ///
 /// This does NOT start the instance or create Docker containers - that happens
 /// separately via `instance.start()`. This separation allows for proper cleanup
 /// if starting fails.
-pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Result<BuildResult> {
+pub fn build_instance(
+    profile: &str,
+    params: InstanceParams,
+    existing_titles: &[&str],
+) -> Result<BuildResult> {
     if params.sandbox {
-        if !crate::docker::is_docker_available() {
-            bail!("Docker is not installed. Please install Docker to use sandbox mode.");
-        }
-        if !crate::docker::is_daemon_running() {
-            bail!("Docker daemon is not running. Please start Docker to use sandbox mode.");
-        }
+        preflight_sandbox(&params.sandbox_image)?;
     }
+    // Created only now, after the sandbox checks, so a submit that fails
+    // early leaves nothing behind
+    let create_dir = super::resolve_config(profile).is_ok_and(|c| c.session.create_dir);
+    ensure_project_dir(Path::new(&params.path), create_dir)?;
 
     let mut final_path = PathBuf::from(&params.path)
         .canonicalize()
@@ -76,7 +134,7 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
             bail!("Path is not in a git repository");
         }
 
-        let config = Config::load()?;
+        let config = super::resolve_config_with_repo(profile, &path)?;
         let main_repo_path = GitWorktree::find_main_repo(&path)?;
         let git_wt = GitWorktree::new(main_repo_path.clone())?;
 
@@ -102,10 +160,16 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
                     managed_by_aoe: false,
                     created_at: Utc::now(),
                     cleanup_on_delete: false,
+                    base_branch: None,
                 });
             } else {
                 let session_id = uuid::Uuid::new_v4().to_string();
-                let worktree_path = git_wt.compute_path(branch, template, &session_id[..8])?;
+                let worktree_path = git_wt.compute_path(
+                    branch,
+                    template,
+                    &session_id[..8],
+                    &config.worktree.branch_separator,
+                )?;
 
                 git_wt.create_worktree(branch, &worktree_path, false)?;
 
@@ -120,16 +184,24 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
                     managed_by_aoe: true,
                     created_at: Utc::now(),
                     cleanup_on_delete: true,
+                    base_branch: None,
                 });
             }
         } else {
             let session_id = uuid::Uuid::new_v4().to_string();
-            let worktree_path = git_wt.compute_path(branch, template, &session_id[..8])?;
+            let worktree_path = git_wt.compute_path(
+                branch,
+                template,
+                &session_id[..8],
+                &config.worktree.branch_separator,
+            )?;
 
             if worktree_path.exists() {
                 bail!("Worktree already exists at {}", worktree_path.display());
             }
 
+            // New branches start from the main repo's HEAD, which becomes the diff base
+            let base_branch = GitWorktree::get_current_branch(&main_repo_path).ok();
             git_wt.create_worktree(branch, &worktree_path, true)?;
 
             final_path = worktree_path.to_string_lossy().to_string();
@@ -143,6 +215,7 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
                 managed_by_aoe: true,
                 created_at: Utc::now(),
                 cleanup_on_delete: true,
+                base_branch,
             });
         }
     }
@@ -154,12 +227,18 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
     };
 
     let mut instance = Instance::new(&final_title, &final_path);
+    instance.profile = profile.to_string();
     instance.group_path = params.group;
+    instance.parent_session_id = params.parent_session_id;
     instance.tool = params.tool.clone();
-    instance.command = match params.tool.as_str() {
-        "opencode" => "opencode".to_string(),
-        "codex" => "codex".to_string(),
-        _ => String::new(),
+    instance.command = if !params.command.trim().is_empty() {
+        params.command.trim().to_string()
+    } else {
+        match params.tool.as_str() {
+            "opencode" => "opencode".to_string(),
+            "codex" => "codex".to_string(),
+            _ => String::new(),
+        }
     };
     instance.worktree_info = worktree_info;
 
@@ -192,6 +271,8 @@ pub fn build_instance(params: InstanceParams, existing_titles: &[&str]) -> Resul
                     Some(map)
                 }
             },
+            extra_volumes: (!params.extra_volumes.is_empty()).then_some(params.extra_volumes),
+            network: params.network,
         });
     }
 
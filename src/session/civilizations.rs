@@ -1,6 +1,15 @@
-//! Age of Empires 2 civilization names for random session titles
+//! Age of Empires 2 civilization names for random session titles, plus
+//! configurable title templates
 
+use std::collections::HashSet;
+use std::path::Path;
+
+use rand::rngs::StdRng;
 use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+
+use super::SessionConfig;
+use crate::git::GitWorktree;
 
 pub const CIVILIZATIONS: &[&str] = &[
     "Armenians",
@@ -86,27 +95,147 @@ fn to_roman(n: u32) -> String {
 }
 
 pub fn generate_random_title(existing_titles: &[&str]) -> String {
-    let mut rng = rand::thread_rng();
+    generate_random_title_from(CIVILIZATIONS, existing_titles)
+}
+
+/// Env var holding a u64 seed that makes title generation reproducible
+pub const TITLE_SEED_ENV: &str = "AGENT_OF_EMPIRES_TITLE_SEED";
+
+fn title_seed() -> Option<u64> {
+    let value = std::env::var(TITLE_SEED_ENV).ok()?;
+    match value.trim().parse() {
+        Ok(seed) => Some(seed),
+        Err(_) => {
+            tracing::warn!("Ignoring {}={:?}: not a u64", TITLE_SEED_ENV, value);
+            None
+        }
+    }
+}
 
-    let available: Vec<&str> = CIVILIZATIONS
+/// Pick an unused title from `words`, adding a roman numeral suffix once
+/// every word is taken. An empty `words` falls back to the built-in list.
+///
+/// Random unless `AGENT_OF_EMPIRES_TITLE_SEED` is set, in which case the same
+/// words and existing titles always give the same result.
+pub fn generate_random_title_from(words: &[&str], existing_titles: &[&str]) -> String {
+    match title_seed() {
+        Some(seed) => {
+            generate_random_title_with_rng(words, existing_titles, &mut StdRng::seed_from_u64(seed))
+        }
+        None => generate_random_title_with_rng(words, existing_titles, &mut rand::thread_rng()),
+    }
+}
+
+/// `generate_random_title_from` with a caller-supplied RNG, for tests that
+/// need predictable titles
+pub fn generate_random_title_with_rng<R: Rng + ?Sized>(
+    words: &[&str],
+    existing_titles: &[&str],
+    rng: &mut R,
+) -> String {
+    let words = if words.is_empty() {
+        CIVILIZATIONS
+    } else {
+        words
+    };
+
+    let available: Vec<&str> = words
         .iter()
-        .filter(|civ| !existing_titles.contains(*civ))
+        .filter(|word| !existing_titles.contains(*word))
         .copied()
         .collect();
 
-    if let Some(&civ) = available.choose(&mut rng) {
-        return civ.to_string();
+    if let Some(&word) = available.choose(rng) {
+        return word.to_string();
     }
 
-    let base = CIVILIZATIONS.choose(&mut rng).unwrap_or(&"Session");
-    for n in 2..=1000 {
+    let base = words.choose(rng).unwrap_or(&"Session");
+    unique_title(base, existing_titles)
+}
+
+/// Highest roman numeral suffix tried before switching to plain numbers
+const MAX_ROMAN_SUFFIX: u32 = 1000;
+
+/// Return `base` if unused, otherwise `base` with the first free suffix:
+/// roman numerals (`II`, `III`, ...) up to `MAX_ROMAN_SUFFIX`, then `-1001`,
+/// `-1002`, ... Always terminates with a title not in `existing_titles`.
+pub fn unique_title(base: &str, existing_titles: &[&str]) -> String {
+    let existing: HashSet<&str> = existing_titles.iter().copied().collect();
+    if !existing.contains(base) {
+        return base.to_string();
+    }
+
+    for n in 2..=MAX_ROMAN_SUFFIX {
         let candidate = format!("{} {}", base, to_roman(n));
-        if !existing_titles.contains(&candidate.as_str()) {
+        if !existing.contains(candidate.as_str()) {
             return candidate;
         }
     }
 
-    format!("{} {}", base, chrono::Utc::now().timestamp())
+    // At most existing.len() candidates can be taken, so this ends
+    (MAX_ROMAN_SUFFIX + 1..)
+        .map(|n| format!("{}-{}", base, n))
+        .find(|candidate| !existing.contains(candidate.as_str()))
+        .expect("unbounded suffix range always has a free title")
+}
+
+/// Placeholders understood by `render_title_template`
+pub const TITLE_PLACEHOLDERS: [&str; 2] = ["{branch}", "{dir}"];
+
+/// Fill in a title template. `{dir}` is the basename of `project_path`.
+/// Returns None if the result is blank or a placeholder it uses has no value.
+pub fn render_title_template(
+    template: &str,
+    branch: Option<&str>,
+    project_path: &str,
+) -> Option<String> {
+    let dir = Path::new(project_path)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string());
+
+    let mut title = template.to_string();
+    for (placeholder, value) in [("{branch}", branch), ("{dir}", dir.as_deref())] {
+        if title.contains(placeholder) {
+            title = title.replace(placeholder, value.filter(|v| !v.is_empty())?);
+        }
+    }
+
+    let title = title.trim();
+    (!title.is_empty()).then(|| title.to_string())
+}
+
+/// Title for a session created without one: the configured template when it
+/// yields something, otherwise a random pick from `title_words` (or the
+/// civilization names when none are configured).
+///
+/// `branch` is the worktree branch being created, if any; otherwise the
+/// project's current branch is used.
+pub fn generate_title(
+    session: &SessionConfig,
+    branch: Option<&str>,
+    project_path: &str,
+    existing_titles: &[&str],
+) -> String {
+    let template = session.title_template.as_deref();
+    if let Some(template) = template.filter(|t| !t.trim().is_empty()) {
+        let branch = branch.map(str::to_string).or_else(|| {
+            template
+                .contains("{branch}")
+                .then(|| GitWorktree::get_current_branch(Path::new(project_path)).ok())
+                .flatten()
+        });
+        if let Some(title) = render_title_template(template, branch.as_deref(), project_path) {
+            return unique_title(&title, existing_titles);
+        }
+    }
+
+    let words: Vec<&str> = session
+        .title_words
+        .iter()
+        .map(|w| w.trim())
+        .filter(|w| !w.is_empty())
+        .collect();
+    generate_random_title_from(&words, existing_titles)
 }
 
 #[cfg(test)]
@@ -142,6 +271,124 @@ mod tests {
         assert!(!existing.contains(&title.as_str()));
     }
 
+    #[test]
+    fn test_render_title_template() {
+        assert_eq!(
+            render_title_template("{branch} @ {dir}", Some("feat/x"), "/src/my-app"),
+            Some("feat/x @ my-app".to_string())
+        );
+        assert_eq!(
+            render_title_template("{dir}", None, "/src/my-app/"),
+            Some("my-app".to_string())
+        );
+        assert_eq!(
+            render_title_template("fixed", None, "/src/my-app"),
+            Some("fixed".to_string())
+        );
+    }
+
+    #[test]
+    fn test_render_title_template_missing_value_yields_nothing() {
+        assert_eq!(
+            render_title_template("{branch} @ {dir}", None, "/src/my-app"),
+            None
+        );
+        assert_eq!(render_title_template("{dir}", Some("main"), "/"), None);
+        assert_eq!(render_title_template("  ", Some("main"), "/src/app"), None);
+    }
+
+    fn session_config(template: Option<&str>, words: &[&str]) -> SessionConfig {
+        SessionConfig {
+            title_template: template.map(str::to_string),
+            title_words: words.iter().map(|w| w.to_string()).collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_generate_title_falls_back_to_civ() {
+        let config = session_config(Some("{branch}"), &[]);
+        let title = generate_title(&config, None, "/nonexistent/path", &[]);
+        assert!(CIVILIZATIONS.contains(&title.as_str()));
+
+        let title = generate_title(&SessionConfig::default(), Some("main"), "/src/app", &[]);
+        assert!(CIVILIZATIONS.contains(&title.as_str()));
+    }
+
+    #[test]
+    fn test_generate_title_uses_template() {
+        let config = session_config(Some("{branch} @ {dir}"), &["Apollo"]);
+        let title = generate_title(&config, Some("main"), "/src/app", &[]);
+        assert_eq!(title, "main @ app");
+    }
+
+    #[test]
+    fn test_generate_title_uses_custom_words() {
+        let config = session_config(None, &["Apollo", "Gemini", "  "]);
+        for _ in 0..20 {
+            let title = generate_title(&config, None, "/src/app", &["Apollo"]);
+            assert_eq!(title, "Gemini");
+        }
+
+        let title = generate_title(&config, None, "/src/app", &["Apollo", "Gemini"]);
+        assert!(title == "Apollo II" || title == "Gemini II");
+    }
+
+    #[test]
+    fn test_tiny_word_list_always_yields_unique_titles() {
+        let mut titles: Vec<String> = Vec::new();
+        for _ in 0..300 {
+            let existing: Vec<&str> = titles.iter().map(|s| s.as_str()).collect();
+            let title = generate_random_title_from(&["Ada", "Bo"], &existing);
+            assert!(!title.is_empty());
+            assert!(!existing.contains(&title.as_str()), "duplicate {}", title);
+            titles.push(title);
+        }
+    }
+
+    #[test]
+    fn test_unique_title_switches_to_numbers_past_roman_limit() {
+        let mut taken = vec!["Ada".to_string()];
+        taken.extend((2..=MAX_ROMAN_SUFFIX).map(|n| format!("Ada {}", to_roman(n))));
+        taken.push("Ada-1001".to_string());
+        let existing: Vec<&str> = taken.iter().map(|s| s.as_str()).collect();
+
+        assert_eq!(unique_title("Ada", &existing), "Ada-1002");
+        assert_eq!(unique_title("Bo", &existing), "Bo");
+    }
+
+    #[test]
+    fn test_template_title_collision_gets_suffix() {
+        let config = session_config(Some("{dir}"), &[]);
+        let title = generate_title(&config, None, "/src/app", &["app"]);
+        assert_eq!(title, "app II");
+    }
+
+    #[test]
+    fn test_seeded_rng_gives_same_title() {
+        let existing = ["Britons", "Franks"];
+        let first = generate_random_title_with_rng(&[], &existing, &mut StdRng::seed_from_u64(7));
+        let second = generate_random_title_with_rng(&[], &existing, &mut StdRng::seed_from_u64(7));
+        assert_eq!(first, second);
+        assert!(!existing.contains(&first.as_str()));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_title_seed_env_makes_titles_reproducible() {
+        std::env::set_var(TITLE_SEED_ENV, "42");
+        let first = generate_random_title(&[]);
+        let second = generate_random_title(&[]);
+        std::env::remove_var(TITLE_SEED_ENV);
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_generate_random_title_from_empty_uses_civs() {
+        let title = generate_random_title_from(&[], &[]);
+        assert!(CIVILIZATIONS.contains(&title.as_str()));
+    }
+
     #[test]
     fn test_generate_random_title_with_all_taken_uses_roman_numerals() {
         let existing: Vec<&str> = CIVILIZATIONS.to_vec();
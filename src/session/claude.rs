@@ -0,0 +1,119 @@
+//! Correlate aoe sessions with Claude Code's own conversation ids
+//!
+//! Claude Code stores each conversation as `<id>.jsonl` under
+//! `<config dir>/projects/<encoded cwd>/`, where the encoded cwd is the working
+//! directory with every non-alphanumeric character replaced by `-`. Sessions
+//! in the same directory share that folder, so a session's current
+//! conversation is the most recently written file since its agent started
+//! that no other session has claimed.
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Directory holding Claude Code's per-project conversation logs on the host.
+/// Uses `claude.config_dir` from the aoe config, then `CLAUDE_CONFIG_DIR`,
+/// then `~/.claude`.
+pub fn projects_dir() -> Option<PathBuf> {
+    let config_dir = super::get_claude_config_dir()
+        .or_else(|| std::env::var_os("CLAUDE_CONFIG_DIR").map(PathBuf::from))
+        .or_else(|| dirs::home_dir().map(|home| home.join(".claude")))?;
+    Some(config_dir.join("projects"))
+}
+
+/// Claude Code's directory name for a working directory
+fn encode_project_path(project_path: &str) -> String {
+    project_path
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect()
+}
+
+/// Id of the most recently written Claude conversation for `project_path`
+/// under `projects_dir`, among those written at or after `since` that
+/// another session hasn't `taken`
+pub fn latest_session_id(
+    projects_dir: &Path,
+    project_path: &str,
+    since: SystemTime,
+    taken: impl Fn(&str) -> bool,
+) -> Option<String> {
+    let dir = projects_dir.join(encode_project_path(project_path));
+
+    std::fs::read_dir(dir)
+        .ok()?
+        .filter_map(|entry| entry.ok())
+        .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "jsonl"))
+        .filter_map(|entry| {
+            let modified = entry.metadata().and_then(|m| m.modified()).ok()?;
+            let id = entry.path().file_stem()?.to_str()?.to_string();
+            Some((modified, id))
+        })
+        .filter(|(modified, id)| *modified >= since && !taken(id))
+        .max()
+        .map(|(_, id)| id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, SystemTime};
+
+    #[test]
+    fn test_encode_project_path() {
+        assert_eq!(encode_project_path("/root/crate"), "-root-crate");
+        assert_eq!(
+            encode_project_path("/home/me/my.app_v2"),
+            "-home-me-my-app-v2"
+        );
+    }
+
+    #[test]
+    fn test_latest_session_id_picks_newest_conversation() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path().join("-work-app");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let now = SystemTime::now();
+        for (name, age) in [("old-id.jsonl", 60), ("new-id.jsonl", 1), ("notes.txt", 0)] {
+            let file = std::fs::File::create(dir.join(name)).unwrap();
+            file.set_modified(now - Duration::from_secs(age)).unwrap();
+        }
+
+        let since = now - Duration::from_secs(3600);
+        assert_eq!(
+            latest_session_id(temp.path(), "/work/app", since, |_| false),
+            Some("new-id".to_string())
+        );
+        assert_eq!(
+            latest_session_id(temp.path(), "/work/other", since, |_| false),
+            None
+        );
+    }
+
+    #[test]
+    fn test_latest_session_id_skips_other_sessions_conversations() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let dir = temp.path().join("-work-app");
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let now = SystemTime::now();
+        for (name, age) in [
+            ("before-start.jsonl", 120),
+            ("mine.jsonl", 30),
+            ("theirs.jsonl", 1),
+        ] {
+            let file = std::fs::File::create(dir.join(name)).unwrap();
+            file.set_modified(now - Duration::from_secs(age)).unwrap();
+        }
+
+        let started = now - Duration::from_secs(60);
+        assert_eq!(
+            latest_session_id(temp.path(), "/work/app", started, |id| id == "theirs"),
+            Some("mine".to_string())
+        );
+        assert_eq!(
+            latest_session_id(temp.path(), "/work/app", now, |_| false),
+            None
+        );
+    }
+}
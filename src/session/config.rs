@@ -4,7 +4,8 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
 
 use super::get_app_dir;
 use super::repo_config::HooksConfig;
@@ -14,6 +15,9 @@ pub struct Config {
     #[serde(default = "default_profile")]
     pub default_profile: String,
 
+    #[serde(default)]
+    pub app: AppConfig,
+
     #[serde(default)]
     pub theme: ThemeConfig,
 
@@ -44,8 +48,128 @@ pub struct Config {
     #[serde(default)]
     pub sound: crate::sound::SoundConfig,
 
+    #[serde(default)]
+    pub ui: UiConfig,
+
+    #[serde(default)]
+    pub logging: LoggingConfig,
+
+    #[serde(default)]
+    pub events: EventsConfig,
+
     #[serde(default)]
     pub app_state: AppStateConfig,
+
+    /// Per-tool regexes consulted before the built-in status heuristics,
+    /// keyed by tool name (`[status_patterns.claude]`)
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub status_patterns: HashMap<String, StatusPatternsConfig>,
+
+    /// Named new session presets (`[presets.fast]`), picked in the new
+    /// session dialog or with `aoe add --preset`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub presets: HashMap<String, PresetConfig>,
+}
+
+impl Config {
+    /// Preset names in alphabetical order
+    pub fn preset_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self.presets.keys().map(String::as_str).collect();
+        names.sort_unstable();
+        names
+    }
+
+    /// Look up a preset by name
+    pub fn preset(&self, name: &str) -> Result<&PresetConfig> {
+        match self.presets.get(name) {
+            Some(preset) => Ok(preset),
+            None if self.presets.is_empty() => {
+                anyhow::bail!("Unknown preset '{}': no [presets] are configured", name)
+            }
+            None => anyhow::bail!(
+                "Unknown preset '{}'. Available: {}",
+                name,
+                self.preset_names().join(", ")
+            ),
+        }
+    }
+}
+
+/// Options a new session starts from. Unset fields keep the usual defaults,
+/// and anything given explicitly when creating the session still wins.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PresetConfig {
+    /// Tool to run. Detected from `command` when unset.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tool: Option<String>,
+
+    /// Command to launch instead of the tool's default, e.g.
+    /// "claude --model haiku"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sandbox_image: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub yolo_mode: Option<bool>,
+
+    /// Host environment variable names passed to the sandbox
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub environment: Vec<String>,
+
+    /// Variables set in the sandbox, as in `sandbox.environment_values`
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub environment_values: HashMap<String, String>,
+}
+
+impl PresetConfig {
+    /// The tool this preset runs: `tool` when set, otherwise the tool named
+    /// in `command`. None when neither says.
+    pub fn tool(&self) -> Result<Option<String>> {
+        if let Some(tool) = self
+            .tool
+            .as_deref()
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+        {
+            if !super::SUPPORTED_TOOLS.contains(&tool) {
+                anyhow::bail!(
+                    "Unknown tool '{}' in preset. Supported tools: {}",
+                    tool,
+                    super::SUPPORTED_TOOLS.join(", ")
+                );
+            }
+            return Ok(Some(tool.to_string()));
+        }
+        match self.command() {
+            Some(cmd) => super::detect_tool(cmd).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    /// The launch command, if set to something non-empty
+    pub fn command(&self) -> Option<&str> {
+        self.command
+            .as_deref()
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+    }
+}
+
+/// Regexes that mark a tool as waiting for input or running. Each is matched
+/// against the last 30 non-empty lines of the pane, with `^` and `$` matching
+/// at line boundaries.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatusPatternsConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub waiting: Vec<String>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub running: Vec<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -61,6 +185,40 @@ pub struct AppStateConfig {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub diff_file_list_width: Option<u16>,
+
+    /// Wrap long lines in the home preview instead of clipping them
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub preview_wrap: bool,
+
+    /// Profile last switched to in the TUI, resumed when none is requested
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_profile: Option<String>,
+
+    /// Choices from the last submitted new session dialog, restored when
+    /// `ui.remember_new_session` is enabled
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_new_session: Option<NewSessionOptions>,
+}
+
+/// The new session dialog options that are remembered between sessions.
+/// Title, path and worktree branch are specific to each session and are
+/// never stored.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct NewSessionOptions {
+    #[serde(default)]
+    pub tool: String,
+
+    #[serde(default)]
+    pub group: String,
+
+    #[serde(default)]
+    pub sandbox: bool,
+
+    #[serde(default)]
+    pub sandbox_image: String,
+
+    #[serde(default)]
+    pub yolo_mode: bool,
 }
 
 /// Session-related configuration defaults
@@ -70,6 +228,108 @@ pub struct SessionConfig {
     /// If not set or tool is unavailable, falls back to first available tool
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_tool: Option<String>,
+
+    /// Group new sessions are added to when none is given (and they have no
+    /// parent). Empty or unset leaves sessions ungrouped.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_group: Option<String>,
+
+    /// Directory new sessions start in when no path is given, e.g. "~/code".
+    /// `~` and `$VAR`/`${VAR}` are expanded. Unset falls back to the current
+    /// directory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_path: Option<String>,
+
+    /// Create a new session's project directory when it does not exist
+    /// instead of rejecting the path
+    #[serde(default)]
+    pub create_dir: bool,
+
+    /// Title for new sessions created without one, e.g. "{branch} @ {dir}".
+    /// Falls back to a random civilization name when unset or when a
+    /// placeholder has no value.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_template: Option<String>,
+
+    /// Names to pick random session titles from instead of the built-in
+    /// civilizations
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub title_words: Vec<String>,
+}
+
+impl SessionConfig {
+    /// The configured default group, if set to a non-empty path
+    pub fn default_group(&self) -> Option<&str> {
+        self.default_group
+            .as_deref()
+            .map(str::trim)
+            .filter(|g| !g.is_empty())
+    }
+
+    /// The configured default path, expanded with [`expand_path`]. None when
+    /// unset, empty, or the expansion fails.
+    pub fn default_path(&self) -> Option<PathBuf> {
+        let raw = self.default_path.as_deref().map(str::trim)?;
+        if raw.is_empty() {
+            return None;
+        }
+        match expand_path(raw) {
+            Ok(path) => Some(path),
+            Err(e) => {
+                tracing::warn!("Ignoring [session] default_path: {}", e);
+                None
+            }
+        }
+    }
+}
+
+/// Expand `~`, `~user` and `$VAR`/`${VAR}` in a path the way a shell would.
+/// Fails when a referenced variable is unset or the user does not exist.
+pub fn expand_path(raw: &str) -> Result<PathBuf> {
+    let mut out = String::new();
+    let mut rest = raw;
+
+    if let Some(after) = rest.strip_prefix('~') {
+        let end = after.find('/').unwrap_or(after.len());
+        let (user, tail) = after.split_at(end);
+        let home = if user.is_empty() {
+            dirs::home_dir().ok_or_else(|| anyhow::anyhow!("Cannot determine home directory"))?
+        } else {
+            nix::unistd::User::from_name(user)
+                .ok()
+                .flatten()
+                .map(|u| u.dir)
+                .ok_or_else(|| anyhow::anyhow!("Unknown user in path: ~{}", user))?
+        };
+        out.push_str(&home.to_string_lossy());
+        rest = tail;
+    }
+
+    while let Some(pos) = rest.find('$') {
+        out.push_str(&rest[..pos]);
+        let after = &rest[pos + 1..];
+        let (name, tail) = if let Some(braced) = after.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .ok_or_else(|| anyhow::anyhow!("Unclosed ${{ in path: {}", raw))?;
+            (&braced[..end], &braced[end + 1..])
+        } else {
+            let end = after
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(after.len());
+            (&after[..end], &after[end..])
+        };
+        if name.is_empty() {
+            out.push('$');
+        } else {
+            let value = std::env::var(name)
+                .map_err(|_| anyhow::anyhow!("Environment variable ${} is not set", name))?;
+            out.push_str(&value);
+        }
+        rest = tail;
+    }
+    out.push_str(rest);
+    Ok(PathBuf::from(out))
 }
 
 /// Diff view configuration
@@ -114,6 +374,142 @@ pub struct ClaudeConfig {
     pub config_dir: Option<String>,
 }
 
+/// TUI event loop timing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UiConfig {
+    /// How long the event loop waits for input before doing periodic work (ms).
+    /// Lower is more responsive, higher uses less CPU. The loading spinner
+    /// still ticks at least every 100ms.
+    #[serde(default = "default_tick_ms")]
+    pub tick_ms: u64,
+
+    /// How often session statuses are polled (ms)
+    #[serde(default = "default_status_refresh_ms")]
+    pub status_refresh_ms: u64,
+
+    /// Consecutive polls a new status must be seen in before it is shown.
+    /// 1 shows every change immediately. Errors are always shown at once.
+    #[serde(default = "default_status_debounce_polls")]
+    pub status_debounce_polls: u64,
+
+    /// Pre-fill the new session dialog with the tool, group and sandbox
+    /// options used last time instead of the configured defaults
+    #[serde(default)]
+    pub remember_new_session: bool,
+
+    /// Open the TUI in the profile last switched to with `P` instead of the
+    /// default profile
+    #[serde(default)]
+    pub resume_last_profile: bool,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            tick_ms: default_tick_ms(),
+            status_refresh_ms: default_status_refresh_ms(),
+            status_debounce_polls: default_status_debounce_polls(),
+            remember_new_session: false,
+            resume_last_profile: false,
+        }
+    }
+}
+
+fn default_tick_ms() -> u64 {
+    50
+}
+
+fn default_status_refresh_ms() -> u64 {
+    500
+}
+
+fn default_status_debounce_polls() -> u64 {
+    2
+}
+
+/// Verbosity for the log file
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Error,
+    Warn,
+    Info,
+    #[default]
+    Debug,
+    Trace,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 5] = [
+        LogLevel::Error,
+        LogLevel::Warn,
+        LogLevel::Info,
+        LogLevel::Debug,
+        LogLevel::Trace,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            LogLevel::Error => "error",
+            LogLevel::Warn => "warn",
+            LogLevel::Info => "info",
+            LogLevel::Debug => "debug",
+            LogLevel::Trace => "trace",
+        }
+    }
+}
+
+/// File logging. Logs go to `<app dir>/logs/aoe.log`, rotated daily.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LoggingConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub level: LogLevel,
+
+    /// Number of daily log files kept before the oldest is deleted
+    #[serde(default = "default_log_max_files")]
+    pub max_files: u64,
+}
+
+impl Default for LoggingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            level: LogLevel::default(),
+            max_files: default_log_max_files(),
+        }
+    }
+}
+
+fn default_log_max_files() -> u64 {
+    7
+}
+
+/// Notifications sent to external tools on session lifecycle events
+/// (created, started, status_changed, deleted). Both sinks are optional and
+/// fire in the background; failures are only logged.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventsConfig {
+    /// URL that receives each event as a JSON POST
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+
+    /// Shell command run for each event, with the event in `AOE_EVENT*` env vars
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+/// Machine-wide behavior. Only the global config sets these.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AppConfig {
+    /// Never touch the network: no update checks, and sandbox images must
+    /// already be present locally
+    #[serde(default)]
+    pub offline: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdatesConfig {
     #[serde(default = "default_true")]
@@ -161,6 +557,11 @@ pub struct WorktreeConfig {
     #[serde(default = "default_bare_repo_template")]
     pub bare_repo_path_template: String,
 
+    /// Replaces `/` when a branch name becomes a worktree directory name,
+    /// so `feature/new-ui` gives `feature-new-ui` by default
+    #[serde(default = "default_branch_separator")]
+    pub branch_separator: String,
+
     #[serde(default = "default_true")]
     pub auto_cleanup: bool,
 
@@ -179,6 +580,7 @@ impl Default for WorktreeConfig {
             enabled: false,
             path_template: default_worktree_template(),
             bare_repo_path_template: default_bare_repo_template(),
+            branch_separator: default_branch_separator(),
             auto_cleanup: true,
             show_branch_in_tui: true,
             delete_branch_on_cleanup: false,
@@ -186,6 +588,10 @@ impl Default for WorktreeConfig {
     }
 }
 
+fn default_branch_separator() -> String {
+    "-".to_string()
+}
+
 fn default_worktree_template() -> String {
     "../{repo-name}-worktrees/{branch}".to_string()
 }
@@ -206,7 +612,8 @@ pub struct SandboxConfig {
     #[serde(default = "default_sandbox_image")]
     pub default_image: String,
 
-    #[serde(default)]
+    /// Extra `host:container[:ro]` mounts, also accepted as `extra_mounts`
+    #[serde(default, alias = "extra_mounts")]
     pub extra_volumes: Vec<String>,
 
     #[serde(default = "default_sandbox_environment")]
@@ -227,10 +634,19 @@ pub struct SandboxConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub memory_limit: Option<String>,
 
+    /// Docker network for containers (`host`, `none`, or a named network).
+    /// Unset uses the daemon's default bridge.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+
     /// Default terminal mode for sandboxed sessions (host or container)
     #[serde(default)]
     pub default_terminal_mode: DefaultTerminalMode,
 
+    /// Whether agent auth volumes are shared by all profiles or kept per profile
+    #[serde(default)]
+    pub auth_volume_scope: AuthVolumeScope,
+
     /// Relative directory paths to exclude from the host bind mount via anonymous volumes
     #[serde(default)]
     pub volume_ignores: Vec<String>,
@@ -238,6 +654,46 @@ pub struct SandboxConfig {
     /// Mount ~/.ssh into sandbox containers (default: false)
     #[serde(default)]
     pub mount_ssh: bool,
+
+    /// Mount the container root filesystem read-only, with a tmpfs at /tmp.
+    /// Agents can then only write to the workspace and mounted volumes.
+    #[serde(default)]
+    pub read_only_rootfs: bool,
+
+    /// Apply the hardened security preset: drop all capabilities, add back a
+    /// minimal set, and forbid privilege escalation
+    #[serde(default)]
+    pub hardened: bool,
+
+    /// Linux capabilities to drop (e.g. "ALL", "NET_RAW")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cap_drop: Vec<String>,
+
+    /// Linux capabilities to add back (e.g. "CHOWN")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cap_add: Vec<String>,
+
+    /// Docker security options (e.g. "no-new-privileges", "seccomp=/path/profile.json")
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub security_opt: Vec<String>,
+
+    /// Extra attempts for Docker commands that fail transiently (pulls, volume
+    /// creation, container start). 0 disables retrying.
+    #[serde(default = "default_docker_retries")]
+    pub docker_retries: u64,
+
+    /// Restart a session whose container exited unexpectedly (e.g. OOM)
+    #[serde(default)]
+    pub auto_restart: bool,
+
+    /// Stop (not remove) a session's container once nothing is attached to it
+    #[serde(default)]
+    pub stop_on_detach: bool,
+
+    /// Running sandbox containers allowed before creating another asks for
+    /// confirmation. 0 means no limit.
+    #[serde(default)]
+    pub max_concurrent_containers: u64,
 }
 
 impl Default for SandboxConfig {
@@ -252,13 +708,28 @@ impl Default for SandboxConfig {
             auto_cleanup: true,
             cpu_limit: None,
             memory_limit: None,
+            network: None,
             default_terminal_mode: DefaultTerminalMode::default(),
+            auth_volume_scope: AuthVolumeScope::default(),
             volume_ignores: Vec::new(),
             mount_ssh: false,
+            read_only_rootfs: false,
+            hardened: false,
+            cap_drop: Vec::new(),
+            cap_add: Vec::new(),
+            security_opt: Vec::new(),
+            docker_retries: default_docker_retries(),
+            auto_restart: false,
+            stop_on_detach: false,
+            max_concurrent_containers: 0,
         }
     }
 }
 
+fn default_docker_retries() -> u64 {
+    2
+}
+
 fn default_sandbox_image() -> String {
     crate::docker::default_sandbox_image().to_string()
 }
@@ -283,6 +754,48 @@ pub enum DefaultTerminalMode {
     Container,
 }
 
+/// Scope of the named Docker volumes that persist agent credentials
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum AuthVolumeScope {
+    /// One set of auth volumes shared by every profile
+    #[default]
+    Global,
+    /// Separate auth volumes per profile (e.g. `aoe-claude-auth-work`)
+    Profile,
+}
+
+impl AuthVolumeScope {
+    /// Names of every tool's auth volume for sessions in `profile`.
+    pub fn volume_names(self, profile: &str) -> Vec<String> {
+        crate::docker::AUTH_VOLUMES
+            .iter()
+            .map(|base| self.volume_name(base, profile))
+            .collect()
+    }
+
+    /// Name of the auth volume `base` for sessions in `profile`.
+    pub fn volume_name(self, base: &str, profile: &str) -> String {
+        match self {
+            AuthVolumeScope::Global => base.to_string(),
+            AuthVolumeScope::Profile => {
+                // Docker volume names allow [a-zA-Z0-9_.-]
+                let suffix: String = profile
+                    .chars()
+                    .map(|c| {
+                        if c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-') {
+                            c
+                        } else {
+                            '-'
+                        }
+                    })
+                    .collect();
+                format!("{}-{}", base, suffix)
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
 pub enum TmuxStatusBarMode {
@@ -312,6 +825,10 @@ pub struct TmuxConfig {
     /// Mouse support mode (auto, enabled, disabled)
     #[serde(default)]
     pub mouse: TmuxMouseMode,
+
+    /// Scrollback lines for new aoe panes; 0 keeps tmux's own history-limit
+    #[serde(default)]
+    pub history_limit: u64,
 }
 
 impl Default for TmuxConfig {
@@ -319,6 +836,7 @@ impl Default for TmuxConfig {
         Self {
             status_bar: TmuxStatusBarMode::Auto,
             mouse: TmuxMouseMode::Auto,
+            history_limit: 0,
         }
     }
 }
@@ -362,6 +880,13 @@ pub fn should_apply_tmux_mouse() -> Option<bool> {
     }
 }
 
+/// Scrollback to give new aoe panes in `profile`, or None to leave tmux's
+/// history-limit alone.
+pub fn tmux_history_limit(profile: &str) -> Option<u64> {
+    let config = super::resolve_config(profile).unwrap_or_default();
+    Some(config.tmux.history_limit).filter(|&limit| limit > 0)
+}
+
 fn config_path() -> Result<PathBuf> {
     Ok(get_app_dir()?.join("config.toml"))
 }
@@ -390,10 +915,37 @@ pub fn load_config() -> Result<Option<Config>> {
 pub fn save_config(config: &Config) -> Result<()> {
     let path = config_path()?;
     let content = toml::to_string_pretty(config)?;
-    fs::write(&path, content)?;
+    fs::write(&path, &content)?;
+    remember_own_write(&path, content);
     Ok(())
 }
 
+/// Contents aoe itself last wrote to each config file, so file watchers can
+/// tell aoe's writes apart from edits made in another program.
+static OWN_WRITES: Mutex<Option<HashMap<PathBuf, String>>> = Mutex::new(None);
+
+pub(crate) fn remember_own_write(path: &Path, content: String) {
+    if let Ok(mut writes) = OWN_WRITES.lock() {
+        writes
+            .get_or_insert_with(HashMap::new)
+            .insert(path.to_path_buf(), content);
+    }
+}
+
+/// Whether `content` is exactly what aoe last wrote to `path`
+pub fn is_own_write(path: &Path, content: &str) -> bool {
+    OWN_WRITES
+        .lock()
+        .ok()
+        .and_then(|writes| writes.as_ref()?.get(path).map(|own| own == content))
+        .unwrap_or(false)
+}
+
+/// Path of the global config file
+pub fn get_config_path() -> Result<PathBuf> {
+    config_path()
+}
+
 pub fn get_update_settings() -> UpdatesConfig {
     load_config()
         .ok()
@@ -402,6 +954,25 @@ pub fn get_update_settings() -> UpdatesConfig {
         .unwrap_or_default()
 }
 
+/// Env var that turns offline mode on (or off) regardless of `[app] offline`
+pub const OFFLINE_ENV: &str = "AGENT_OF_EMPIRES_OFFLINE";
+
+/// Whether offline mode is on. `AGENT_OF_EMPIRES_OFFLINE` wins when set;
+/// otherwise the global `[app] offline` setting applies.
+pub fn is_offline() -> bool {
+    match std::env::var(OFFLINE_ENV) {
+        Ok(value) => env_flag(&value),
+        Err(_) => load_config().ok().flatten().is_some_and(|c| c.app.offline),
+    }
+}
+
+fn env_flag(value: &str) -> bool {
+    !matches!(
+        value.trim().to_ascii_lowercase().as_str(),
+        "" | "0" | "false" | "no" | "off"
+    )
+}
+
 pub fn get_claude_config_dir() -> Option<PathBuf> {
     let config = load_config().ok().flatten()?;
     config.claude.config_dir.map(|s| {
@@ -418,6 +989,23 @@ pub fn get_claude_config_dir() -> Option<PathBuf> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_offline_env_flag() {
+        for on in ["1", "true", "YES", "on"] {
+            assert!(env_flag(on), "{}", on);
+        }
+        for off in ["", "0", "false", "No", " off "] {
+            assert!(!env_flag(off), "{}", off);
+        }
+    }
+
+    #[test]
+    fn test_app_offline_parses() {
+        let config: Config = toml::from_str("[app]\noffline = true\n").unwrap();
+        assert!(config.app.offline);
+        assert!(!Config::default().app.offline);
+    }
+
     // Tests for Config defaults
     #[test]
     fn test_config_default() {
@@ -448,6 +1036,162 @@ mod tests {
         assert!(!config.worktree.enabled);
     }
 
+    #[test]
+    #[serial_test::serial]
+    fn test_expand_path_tilde() {
+        let home = dirs::home_dir().unwrap();
+        assert_eq!(expand_path("~").unwrap(), home);
+        assert_eq!(expand_path("~/code/foo").unwrap(), home.join("code/foo"));
+        // A tilde that is not leading is left alone
+        assert_eq!(expand_path("/a/~/b").unwrap(), PathBuf::from("/a/~/b"));
+    }
+
+    #[test]
+    fn test_expand_path_tilde_user() {
+        let root = nix::unistd::User::from_name("root").unwrap().unwrap();
+        assert_eq!(expand_path("~root/x").unwrap(), root.dir.join("x"));
+        assert!(expand_path("~aoe-no-such-user/x").is_err());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_expand_path_env_vars() {
+        let home = std::env::var("HOME").unwrap();
+        assert_eq!(
+            expand_path("$HOME/code").unwrap(),
+            PathBuf::from(format!("{}/code", home))
+        );
+        assert_eq!(
+            expand_path("${HOME}code").unwrap(),
+            PathBuf::from(format!("{}code", home))
+        );
+        assert_eq!(
+            expand_path("/cost/$/x").unwrap(),
+            PathBuf::from("/cost/$/x")
+        );
+    }
+
+    #[test]
+    fn test_expand_path_unset_var_errors() {
+        let err = expand_path("$AOE_TEST_UNSET_EXPAND_VAR/foo").unwrap_err();
+        assert!(err.to_string().contains("AOE_TEST_UNSET_EXPAND_VAR"));
+        assert!(expand_path("${HOME").is_err());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_session_default_path_expansion() {
+        let home = dirs::home_dir().unwrap();
+        let path_for = |raw: &str| {
+            SessionConfig {
+                default_path: Some(raw.to_string()),
+                ..Default::default()
+            }
+            .default_path()
+        };
+
+        assert_eq!(path_for("~/code"), Some(home.join("code")));
+        assert_eq!(path_for("~"), Some(home.clone()));
+        assert_eq!(
+            path_for("/srv/projects"),
+            Some(PathBuf::from("/srv/projects"))
+        );
+
+        let home_var = std::env::var("HOME").unwrap();
+        assert_eq!(
+            path_for("$HOME/code"),
+            Some(PathBuf::from(format!("{}/code", home_var)))
+        );
+        assert_eq!(
+            path_for("${HOME}/code"),
+            Some(PathBuf::from(format!("{}/code", home_var)))
+        );
+
+        assert_eq!(path_for("$AOE_TEST_UNSET_DEFAULT_PATH_VAR/code"), None);
+        assert_eq!(path_for("  "), None);
+        assert_eq!(SessionConfig::default().default_path(), None);
+    }
+
+    #[test]
+    fn test_config_deserialize_presets() {
+        let toml = r#"
+            [presets.fast]
+            command = "claude --model haiku"
+
+            [presets.boxed]
+            tool = "codex"
+            sandbox = true
+            environment = ["GH_TOKEN"]
+            environment_values = { LOG = "debug" }
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        assert_eq!(config.preset_names(), vec!["boxed", "fast"]);
+
+        let fast = config.preset("fast").unwrap();
+        assert_eq!(fast.command(), Some("claude --model haiku"));
+        assert_eq!(fast.tool().unwrap().as_deref(), Some("claude"));
+        assert_eq!(fast.sandbox, None);
+
+        let boxed = config.preset("boxed").unwrap();
+        assert_eq!(boxed.tool().unwrap().as_deref(), Some("codex"));
+        assert_eq!(boxed.sandbox, Some(true));
+        assert_eq!(boxed.environment, vec!["GH_TOKEN"]);
+        assert_eq!(boxed.environment_values["LOG"], "debug");
+
+        let serialized = toml::to_string(&Config::default()).unwrap();
+        assert!(!serialized.contains("presets"));
+    }
+
+    #[test]
+    fn test_unknown_preset_lists_available() {
+        let mut config = Config::default();
+        let err = config.preset("fast").unwrap_err().to_string();
+        assert!(err.contains("no [presets] are configured"));
+
+        config
+            .presets
+            .insert("slow".to_string(), PresetConfig::default());
+        let err = config.preset("fast").unwrap_err().to_string();
+        assert!(err.contains("Available: slow"));
+    }
+
+    #[test]
+    fn test_preset_tool() {
+        let preset = |tool: Option<&str>, command: Option<&str>| PresetConfig {
+            tool: tool.map(str::to_string),
+            command: command.map(str::to_string),
+            ..Default::default()
+        };
+        assert_eq!(preset(None, None).tool().unwrap(), None);
+        assert_eq!(
+            preset(None, Some("opencode --model x")).tool().unwrap(),
+            Some("opencode".to_string())
+        );
+        // An explicit tool wins over the command
+        assert_eq!(
+            preset(Some("gemini"), Some("claude")).tool().unwrap(),
+            Some("gemini".to_string())
+        );
+        assert!(preset(Some("cursor"), None).tool().is_err());
+        assert!(preset(None, Some("aider")).tool().is_err());
+    }
+
+    #[test]
+    fn test_config_deserialize_status_patterns() {
+        let toml = r#"
+            [status_patterns.codex]
+            waiting = ["^Allow command\\?"]
+            running = ["Compacting"]
+        "#;
+        let config: Config = toml::from_str(toml).unwrap();
+        let codex = &config.status_patterns["codex"];
+        assert_eq!(codex.waiting, vec!["^Allow command\\?"]);
+        assert_eq!(codex.running, vec!["Compacting"]);
+
+        let serialized = toml::to_string(&Config::default()).unwrap();
+        assert!(!serialized.contains("status_patterns"));
+    }
+
     // Tests for ThemeConfig
     #[test]
     fn test_theme_config_default() {
@@ -557,6 +1301,16 @@ mod tests {
         assert_eq!(sb.memory_limit, Some("4g".to_string()));
     }
 
+    #[test]
+    fn test_sandbox_config_accepts_extra_mounts_alias() {
+        let sb: SandboxConfig = toml::from_str(r#"extra_mounts = ["/data:/data:ro"]"#).unwrap();
+        assert_eq!(sb.extra_volumes, vec!["/data:/data:ro"]);
+
+        let over: crate::session::SandboxConfigOverride =
+            toml::from_str(r#"extra_mounts = ["/data:/data"]"#).unwrap();
+        assert_eq!(over.extra_volumes, Some(vec!["/data:/data".to_string()]));
+    }
+
     #[test]
     fn test_sandbox_config_volume_ignores_deserialize() {
         let toml = r#"
@@ -705,6 +1459,39 @@ mod tests {
         let tmux = TmuxConfig::default();
         assert_eq!(tmux.status_bar, TmuxStatusBarMode::Auto);
         assert_eq!(tmux.mouse, TmuxMouseMode::Auto);
+        assert_eq!(tmux.history_limit, 0);
+    }
+
+    #[test]
+    fn test_tmux_config_history_limit_deserialize() {
+        let toml = r#"history_limit = 50000"#;
+        let tmux: TmuxConfig = toml::from_str(toml).unwrap();
+        assert_eq!(tmux.history_limit, 50000);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_tmux_history_limit_follows_profile() {
+        let home = tempfile::TempDir::new().unwrap();
+        std::env::set_var("HOME", home.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", home.path().join(".config"));
+
+        let work = crate::session::ProfileConfig {
+            tmux: Some(crate::session::TmuxConfigOverride {
+                history_limit: Some(50000),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        crate::session::save_profile_config("histlimit-work", &work).unwrap();
+        crate::session::save_profile_config("histlimit-home", &Default::default()).unwrap();
+
+        assert_eq!(tmux_history_limit("histlimit-work"), Some(50000));
+        assert_eq!(
+            tmux_history_limit("histlimit-home"),
+            Some(TmuxConfig::default().history_limit).filter(|&l| l > 0)
+        );
     }
 
     #[test]
@@ -844,4 +1631,57 @@ mod tests {
         assert_eq!(config.diff.default_branch, Some("main".to_string()));
         assert_eq!(config.diff.context_lines, 10);
     }
+
+    #[test]
+    fn test_auth_volume_name_global_scope() {
+        let scope = AuthVolumeScope::Global;
+        assert_eq!(
+            scope.volume_name("aoe-claude-auth", "work"),
+            "aoe-claude-auth"
+        );
+        assert_eq!(
+            scope.volume_name("aoe-claude-auth", "default"),
+            "aoe-claude-auth"
+        );
+    }
+
+    #[test]
+    fn test_auth_volume_name_profile_scope() {
+        let scope = AuthVolumeScope::Profile;
+        assert_eq!(
+            scope.volume_name("aoe-claude-auth", "work"),
+            "aoe-claude-auth-work"
+        );
+        assert_eq!(
+            scope.volume_name("aoe-codex-auth", "my profile/2"),
+            "aoe-codex-auth-my-profile-2"
+        );
+    }
+
+    #[test]
+    fn test_auth_volume_names_cover_every_tool() {
+        let names = AuthVolumeScope::Profile.volume_names("work");
+        assert_eq!(names.len(), crate::docker::AUTH_VOLUMES.len());
+        assert!(names.contains(&"aoe-claude-auth-work".to_string()));
+        assert!(names.contains(&"aoe-vibe-auth-work".to_string()));
+    }
+
+    #[test]
+    fn test_auth_volume_scope_deserialize() {
+        let config: Config = toml::from_str("[sandbox]\nauth_volume_scope = \"profile\"").unwrap();
+        assert_eq!(config.sandbox.auth_volume_scope, AuthVolumeScope::Profile);
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.sandbox.auth_volume_scope, AuthVolumeScope::Global);
+    }
+
+    #[test]
+    fn test_ui_config_defaults() {
+        let config: Config = toml::from_str("").unwrap();
+        assert_eq!(config.ui.tick_ms, 50);
+        assert_eq!(config.ui.status_refresh_ms, 500);
+
+        let config: Config = toml::from_str("[ui]\ntick_ms = 200").unwrap();
+        assert_eq!(config.ui.tick_ms, 200);
+        assert_eq!(config.ui.status_refresh_ms, 500);
+    }
 }
@@ -1,6 +1,6 @@
 //! Session instance definition and operations
 
-use anyhow::Result;
+use anyhow::{Context, Result};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -12,31 +12,61 @@ use crate::docker::{
 use crate::git::GitWorktree;
 use crate::tmux;
 
+use super::redact::Redactor;
+
 fn default_true() -> bool {
     true
 }
 
+/// Error left on a session whose container `sandbox.stop_on_detach` stopped
+pub const STOPPED_ON_DETACH: &str = "container stopped on detach; attach to start it again";
+
+/// A host session's tool is not installed, so launching it would only leave
+/// a dead tmux session behind
+#[derive(Debug, thiserror::Error)]
+#[error("tool '{tool}' not found in PATH")]
+pub struct ToolNotFound {
+    pub tool: String,
+}
+
 /// Terminal environment variables that are always passed through for proper UI/theming
 const DEFAULT_TERMINAL_ENV_VARS: &[&str] = &["TERM", "COLORTERM", "FORCE_COLOR", "NO_COLOR"];
 
 /// Shell-escape a value for safe interpolation into a shell command string.
-/// Uses double-quote escaping so values can be nested inside `bash -c '...'`
-/// (single quotes in the outer wrapper are literal, double quotes work inside).
+///
+/// Uses double-quote escaping, which is interpreted by the shell that runs the
+/// command. When the command is then nested inside `bash -c '...'`, the outer
+/// layer is handled by `wrap_command_ignore_suspend`, which escapes any single
+/// quotes this leaves in place.
 fn shell_escape(val: &str) -> String {
-    let escaped = val
-        .replace('\\', "\\\\")
+    format!("\"{}\"", escape_double_quoted(val))
+}
+
+/// `val` escaped for use between double quotes, without the quotes
+fn escape_double_quoted(val: &str) -> String {
+    val.replace('\\', "\\\\")
         .replace('"', "\\\"")
         .replace('$', "\\$")
-        .replace('`', "\\`");
-    format!("\"{}\"", escaped)
+        .replace('`', "\\`")
+}
+
+/// `val` escaped for use between single quotes, without the quotes
+fn escape_single_quoted(val: &str) -> String {
+    val.replace('\'', "'\\''")
 }
 
 /// Resolve an environment_values entry. If the value starts with `$`, read the
 /// named variable from the host environment (use `$$` to escape a literal `$`).
-/// Otherwise return the literal value.
+/// The braced forms `${VAR}`, `${VAR:-default}` and `${VAR:+alt}` follow shell
+/// semantics. Otherwise return the literal value.
+///
+/// Returns None when the referenced variable is unset and no default applies,
+/// so the entry is skipped.
 fn resolve_env_value(val: &str) -> Option<String> {
     if let Some(rest) = val.strip_prefix("$$") {
         Some(format!("${}", rest))
+    } else if let Some(expr) = val.strip_prefix("${").and_then(|v| v.strip_suffix('}')) {
+        resolve_braced_env(expr)
     } else if let Some(var_name) = val.strip_prefix('$') {
         std::env::var(var_name).ok()
     } else {
@@ -44,6 +74,23 @@ fn resolve_env_value(val: &str) -> Option<String> {
     }
 }
 
+/// Resolve the inside of a `${...}` reference
+fn resolve_braced_env(expr: &str) -> Option<String> {
+    // A set but empty variable counts as unset for `:-` and `:+`, as in the shell
+    let non_empty = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+
+    if let Some((name, default)) = expr.split_once(":-") {
+        Some(non_empty(name).unwrap_or_else(|| default.to_string()))
+    } else if let Some((name, alt)) = expr.split_once(":+") {
+        Some(match non_empty(name) {
+            Some(_) => alt.to_string(),
+            None => String::new(),
+        })
+    } else {
+        std::env::var(expr).ok()
+    }
+}
+
 /// Collect all environment variable keys from defaults, global config, and per-session extras.
 fn collect_env_keys(
     sandbox_config: &super::config::SandboxConfig,
@@ -95,26 +142,188 @@ fn collect_env_values(
     values
 }
 
+/// The `KEY=value` pairs a sandbox would receive, with credential-looking
+/// values masked
+fn preview_env(
+    sandbox_config: &super::config::SandboxConfig,
+    sandbox_info: &SandboxInfo,
+) -> Vec<String> {
+    let mut pairs: Vec<(String, String)> = collect_env_keys(sandbox_config, sandbox_info)
+        .into_iter()
+        .filter_map(|key| std::env::var(&key).ok().map(|val| (key, val)))
+        .collect();
+    pairs.extend(collect_env_values(sandbox_config, sandbox_info));
+
+    let redactor = Redactor::from_env(pairs.iter().map(|(k, v)| (k.as_str(), v.as_str())));
+    pairs
+        .iter()
+        .map(|(key, val)| redactor.redact(&format!("{}={}", key, val)))
+        .collect()
+}
+
+/// Host variables the sandbox config refers to that are not set, so the
+/// matching container variables are dropped. Covers `environment` keys (other
+/// than the terminal defaults) and `environment_values` entries that reference
+/// a host variable without a default.
+fn collect_missing_env_vars(
+    sandbox_config: &super::config::SandboxConfig,
+    sandbox_info: &SandboxInfo,
+) -> Vec<String> {
+    let mut missing: Vec<String> = collect_env_keys(sandbox_config, sandbox_info)
+        .into_iter()
+        .filter(|key| !DEFAULT_TERMINAL_ENV_VARS.contains(&key.as_str()))
+        .filter(|key| std::env::var(key).is_err())
+        .collect();
+
+    let extra_vals = sandbox_info.extra_env_values.iter().flatten();
+    for (_, val) in sandbox_config.environment_values.iter().chain(extra_vals) {
+        if resolve_env_value(val).is_some() {
+            continue;
+        }
+        let name = val
+            .strip_prefix("${")
+            .and_then(|v| v.strip_suffix('}'))
+            .or_else(|| val.strip_prefix('$'))
+            .unwrap_or(val);
+        if !missing.iter().any(|m| m == name) {
+            missing.push(name.to_string());
+        }
+    }
+
+    missing
+}
+
+/// Load the effective config (global merged with `profile`'s overrides) used
+/// when launching a session and creating its container.
+fn resolve_launch_config(profile: &str) -> super::config::Config {
+    super::profile_config::resolve_config(profile).unwrap_or_else(|e| {
+        tracing::warn!("Failed to resolve config, using defaults: {}", e);
+        Default::default()
+    })
+}
+
+/// Auth volumes the containers of sessions in `profile` mount, which
+/// `aoe sandbox prewarm` creates ahead of time.
+pub fn profile_auth_volumes(profile: &str) -> Vec<String> {
+    resolve_launch_config(profile)
+        .sandbox
+        .auth_volume_scope
+        .volume_names(profile)
+}
+
+/// Profile used for sessions that were not loaded from or created in one.
+fn default_launch_profile() -> String {
+    super::config::Config::load()
+        .map(|c| c.default_profile)
+        .unwrap_or_else(|_| super::DEFAULT_PROFILE.to_string())
+}
+
+/// Parse an extra volume entry of the form `host:container[:ro|:rw]`, checking that
+/// the host path exists (`~/` is expanded) and the container path is absolute.
+pub fn parse_extra_volume(entry: &str) -> Result<VolumeMount> {
+    let entry = entry.trim();
+    let invalid = |reason: String| anyhow::anyhow!("Invalid volume '{}': {}", entry, reason);
+
+    super::validate_volume_format(entry).map_err(invalid)?;
+    let parts: Vec<&str> = entry.split(':').collect();
+    let (host, container) = (parts[0], parts[1]);
+    let read_only = match parts.get(2) {
+        None | Some(&"rw") => false,
+        Some(&"ro") => true,
+        Some(mode) => {
+            return Err(invalid(format!(
+                "unknown mode '{}' (expected 'ro' or 'rw')",
+                mode
+            )))
+        }
+    };
+
+    if !container.starts_with('/') {
+        return Err(invalid(format!(
+            "container path '{}' must be absolute",
+            container
+        )));
+    }
+    super::validate_path_exists(host).map_err(invalid)?;
+
+    let host_path = match (host.strip_prefix("~/"), dirs::home_dir()) {
+        (Some(rest), Some(home)) => home.join(rest).to_string_lossy().to_string(),
+        _ => host.to_string(),
+    };
+
+    Ok(VolumeMount {
+        host_path,
+        container_path: container.trim_end_matches('/').to_string(),
+        read_only,
+    })
+}
+
+/// Resolve capability and security-opt args, layering the configured lists on
+/// top of the hardened preset when it is enabled.
+fn security_args(config: &super::config::SandboxConfig) -> (Vec<String>, Vec<String>, Vec<String>) {
+    fn merge(preset: &[&str], extra: &[String]) -> Vec<String> {
+        let mut out: Vec<String> = preset.iter().map(|s| s.to_string()).collect();
+        for item in extra {
+            if !out.contains(item) {
+                out.push(item.clone());
+            }
+        }
+        out
+    }
+
+    if config.hardened {
+        (
+            merge(&["ALL"], &config.cap_drop),
+            merge(crate::docker::HARDENED_CAP_ADD, &config.cap_add),
+            merge(crate::docker::HARDENED_SECURITY_OPT, &config.security_opt),
+        )
+    } else {
+        (
+            config.cap_drop.clone(),
+            config.cap_add.clone(),
+            config.security_opt.clone(),
+        )
+    }
+}
+
+/// Whether mounting at `container_path` would hide the workspace mount at `workspace`
+/// (same path, or one of its ancestors).
+fn shadows_workspace(container_path: &str, workspace: &str) -> bool {
+    container_path.is_empty()
+        || container_path == workspace
+        || workspace.starts_with(&format!("{}/", container_path))
+}
+
 /// Build docker exec environment flags from config and optional per-session extra keys.
 /// Used for `docker exec` commands (shell string interpolation, hence shell-escaping).
 /// Container creation uses `ContainerConfig.environment` (separate args, no escaping needed).
-fn build_docker_env_args(sandbox: &SandboxInfo, project_path: &str) -> String {
-    let config = super::config::Config::load().unwrap_or_default();
+///
+/// The flags carry resolved values, so the returned `Redactor` must be applied
+/// before any command containing them is logged or put in an error.
+fn build_docker_env_args(
+    sandbox: &SandboxInfo,
+    project_path: &str,
+    profile: &str,
+) -> (String, Redactor) {
+    let config = resolve_launch_config(profile);
 
     let env_keys = collect_env_keys(&config.sandbox, sandbox);
 
-    let mut args: Vec<String> = env_keys
+    let mut pairs: Vec<(String, String)> = env_keys
         .iter()
-        .filter_map(|key| {
-            std::env::var(key)
-                .ok()
-                .map(|val| format!("-e {}={}", key, shell_escape(&val)))
-        })
+        .filter_map(|key| std::env::var(key).ok().map(|val| (key.clone(), val)))
+        .collect();
+    pairs.extend(collect_env_values(&config.sandbox, sandbox));
+
+    // The command carries values shell-escaped, and launch commands are then
+    // wrapped in `bash -c '...'`, so mask those forms as well as the raw one
+    let redactor = Redactor::from_env(pairs.iter().map(|(k, v)| (k.as_str(), v.as_str())))
+        .with_encoding(escape_double_quoted)
+        .with_encoding(escape_single_quoted);
+    let mut args: Vec<String> = pairs
+        .iter()
+        .map(|(key, val)| format!("-e {}={}", key, shell_escape(val)))
         .collect();
-
-    for (key, resolved) in collect_env_values(&config.sandbox, sandbox) {
-        args.push(format!("-e {}={}", key, shell_escape(&resolved)));
-    }
 
     // Add context directory env var if enabled
     if let Some(context_dir) = get_context_dir_for_env(project_path) {
@@ -125,7 +334,15 @@ fn build_docker_env_args(sandbox: &SandboxInfo, project_path: &str) -> String {
         ));
     }
 
-    args.join(" ")
+    (args.join(" "), redactor)
+}
+
+/// Rebuild `err` with secret values masked, keeping its context chain text
+fn redact_error(redactor: &Redactor, err: anyhow::Error) -> anyhow::Error {
+    if redactor.is_empty() {
+        return err;
+    }
+    anyhow::anyhow!(redactor.redact(&format!("{:#}", err)))
 }
 
 /// Get the context directory path if context is enabled and inject_env is true.
@@ -174,6 +391,23 @@ pub enum Status {
     Error,
     Starting,
     Deleting,
+    /// The tmux session is alive but the agent exited, leaving a shell
+    Exited,
+}
+
+impl Status {
+    /// Glyph used for this status in the TUI session list and CLI output.
+    pub const fn icon(self) -> &'static str {
+        match self {
+            Status::Running => "●",
+            Status::Waiting => "◐",
+            Status::Idle => "○",
+            Status::Error => "✕",
+            Status::Starting => "◌",
+            Status::Deleting => "✗",
+            Status::Exited => "⊘",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -184,6 +418,9 @@ pub struct WorktreeInfo {
     pub created_at: DateTime<Utc>,
     #[serde(default = "default_true")]
     pub cleanup_on_delete: bool,
+    /// Branch the worktree branch was created from, used as the default diff base
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub base_branch: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -203,6 +440,12 @@ pub struct SandboxInfo {
     /// Additional KEY=VALUE environment variables (session-specific overrides)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub extra_env_values: Option<std::collections::HashMap<String, String>>,
+    /// Additional host:container[:ro] mounts, appended to the configured extra_volumes
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub extra_volumes: Option<Vec<String>>,
+    /// Docker network override for this session (takes precedence over config)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -236,6 +479,14 @@ pub struct Instance {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub terminal_info: Option<TerminalInfo>,
 
+    // Last base ref picked in the diff view
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff_base: Option<String>,
+
+    // Claude Code conversation last seen for this session, for resuming it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub claude_session_id: Option<String>,
+
     // Runtime state (not serialized)
     #[serde(skip)]
     pub last_error_check: Option<std::time::Instant>,
@@ -243,6 +494,10 @@ pub struct Instance {
     pub last_start_time: Option<std::time::Instant>,
     #[serde(skip)]
     pub last_error: Option<String>,
+    /// Profile the session belongs to, set when it is loaded or created.
+    /// Its config applies when launching the session.
+    #[serde(skip)]
+    pub profile: String,
 
     // Search optimization: pre-computed lowercase strings (not serialized)
     #[serde(skip)]
@@ -267,9 +522,12 @@ impl Instance {
             worktree_info: None,
             sandbox_info: None,
             terminal_info: None,
+            diff_base: None,
+            claude_session_id: None,
             last_error_check: None,
             last_start_time: None,
             last_error: None,
+            profile: String::new(),
             title_lower: title.to_lowercase(),
             project_path_lower: project_path.to_lowercase(),
         }
@@ -282,6 +540,16 @@ impl Instance {
         self.project_path_lower = self.project_path.to_lowercase();
     }
 
+    /// Profile whose config applies when launching this session. Instances
+    /// not yet tied to a profile use the default one.
+    fn launch_profile(&self) -> String {
+        if self.profile.is_empty() {
+            default_launch_profile()
+        } else {
+            self.profile.clone()
+        }
+    }
+
     pub fn is_sub_session(&self) -> bool {
         self.parent_session_id.is_some()
     }
@@ -290,6 +558,26 @@ impl Instance {
         self.sandbox_info.as_ref().is_some_and(|s| s.enabled)
     }
 
+    /// Number of other sessions in `instances` working in this session's worktree.
+    /// Removing a shared worktree would break those sessions.
+    pub fn worktree_shared_by<'a>(
+        &self,
+        instances: impl IntoIterator<Item = &'a Instance>,
+    ) -> usize {
+        if self.worktree_info.is_none() {
+            return 0;
+        }
+        let path = std::path::Path::new(&self.project_path);
+        instances
+            .into_iter()
+            .filter(|i| {
+                i.id != self.id
+                    && i.status != Status::Deleting
+                    && std::path::Path::new(&i.project_path) == path
+            })
+            .count()
+    }
+
     pub fn is_yolo_mode(&self) -> bool {
         self.sandbox_info
             .as_ref()
@@ -311,6 +599,43 @@ impl Instance {
         }
     }
 
+    /// Change the title, renaming the running tmux sessions (the agent, the
+    /// paired terminal and the container terminal) to match since their names
+    /// are derived from the title. A failed tmux rename is only logged.
+    pub fn set_title(&mut self, title: &str) -> Result<()> {
+        if self.title == title {
+            return Ok(());
+        }
+
+        let renames = [
+            self.tmux_session()?
+                .rename(&tmux::Session::generate_name(&self.id, title)),
+            self.terminal_tmux_session()?
+                .rename(&tmux::TerminalSession::generate_name(&self.id, title)),
+            self.container_terminal_tmux_session()?.rename(
+                &tmux::ContainerTerminalSession::generate_name(&self.id, title),
+            ),
+        ];
+        for result in renames {
+            if let Err(e) = result {
+                tracing::warn!("{}", e);
+            }
+        }
+
+        self.title = title.to_string();
+        self.update_search_cache();
+        Ok(())
+    }
+
+    /// Switch to another tool. A custom command belonged to the old tool, so
+    /// it is dropped and the new tool's default command is used.
+    pub fn set_tool(&mut self, tool: &str) {
+        if self.tool != tool {
+            self.tool = tool.to_string();
+            self.command.clear();
+        }
+    }
+
     pub fn tmux_session(&self) -> Result<tmux::Session> {
         tmux::Session::new(&self.id, &self.title)
     }
@@ -335,7 +660,7 @@ impl Instance {
 
         let is_new = !session.exists();
         if is_new {
-            session.create_with_size(&self.project_path, None, size)?;
+            session.create_with_size(&self.project_path, None, size, self.history_limit())?;
         }
 
         // Apply all configured tmux options to terminal sessions too
@@ -374,10 +699,43 @@ impl Instance {
             anyhow::bail!("Cannot create container terminal for non-sandboxed session");
         }
 
+        let session = self.container_terminal_tmux_session()?;
+        if session.exists() {
+            return Ok(());
+        }
+
+        // The tmux session is only created once the container is confirmed
+        // running, so it never wraps a `docker exec` into a dead container
+        let (cmd, redactor) = self
+            .container_shell_command()
+            .context("Could not start the sandbox container")?;
+
+        tracing::debug!("Starting container terminal: {}", redactor.redact(&cmd));
+        session
+            .create_with_size(&self.project_path, Some(&cmd), size, self.history_limit())
+            .map_err(|e| redact_error(&redactor, e))?;
+        self.apply_container_terminal_tmux_options();
+
+        Ok(())
+    }
+
+    /// Start the session's container if needed and build the interactive
+    /// `docker exec` shell command for it, in the agent's working directory
+    /// and with the agent's environment. The command carries resolved env
+    /// values, so apply the returned `Redactor` before logging it.
+    pub fn container_shell_command(&mut self) -> Result<(String, Redactor)> {
+        if !self.is_sandboxed() {
+            anyhow::bail!("Session is not sandboxed");
+        }
+
         self.ensure_container_running()?;
-        let sandbox = self.sandbox_info.as_ref().unwrap();
+        let sandbox = self
+            .sandbox_info
+            .as_ref()
+            .ok_or_else(|| anyhow::anyhow!("Session is not sandboxed"))?;
 
-        let env_args = build_docker_env_args(sandbox, &self.project_path);
+        let (env_args, redactor) =
+            build_docker_env_args(sandbox, &self.project_path, &self.launch_profile());
         let env_part = if env_args.is_empty() {
             String::new()
         } else {
@@ -390,17 +748,11 @@ impl Instance {
 
         let cmd = format!(
             "docker exec -it -w {} {}{} /bin/bash",
-            container_workdir, env_part, sandbox.container_name
+            shell_escape(&container_workdir),
+            env_part,
+            sandbox.container_name
         );
-
-        let session = self.container_terminal_tmux_session()?;
-        let is_new = !session.exists();
-        if is_new {
-            session.create_with_size(&self.project_path, Some(&cmd), size)?;
-            self.apply_container_terminal_tmux_options();
-        }
-
-        Ok(())
+        Ok((cmd, redactor))
     }
 
     pub fn kill_container_terminal(&self) -> Result<()> {
@@ -459,6 +811,12 @@ impl Instance {
             return Ok(());
         }
 
+        if let Some(missing) = self.missing_tool(tmux::is_tool_available) {
+            self.status = Status::Error;
+            self.last_error = Some(missing.to_string());
+            return Err(missing.into());
+        }
+
         // Initialize context directory if enabled (ensures CLI and TUI paths both create context)
         self.ensure_context_initialized();
 
@@ -468,12 +826,9 @@ impl Instance {
             None
         } else {
             // Start with global+profile hooks as the base
-            let profile = super::config::Config::load()
-                .map(|c| c.default_profile)
-                .unwrap_or_else(|_| "default".to_string());
-            let mut resolved_on_launch = super::profile_config::resolve_config(&profile)
-                .map(|c| c.hooks.on_launch)
-                .unwrap_or_default();
+            let mut resolved_on_launch = resolve_launch_config(&self.launch_profile())
+                .hooks
+                .on_launch;
 
             // Check if repo has trusted hooks that override
             match super::repo_config::check_hook_trust(std::path::Path::new(&self.project_path)) {
@@ -492,7 +847,7 @@ impl Instance {
             }
         };
 
-        let cmd = if self.is_sandboxed() {
+        if self.is_sandboxed() {
             self.ensure_container_running()?;
 
             // Run on_launch hooks inside the container
@@ -508,53 +863,28 @@ impl Instance {
                     }
                 }
             }
-            let sandbox = self.sandbox_info.as_ref().unwrap();
-            let tool_cmd = if self.is_yolo_mode() {
-                match self.tool.as_str() {
-                    "claude" => "claude --dangerously-skip-permissions".to_string(),
-                    "vibe" => "vibe --agent auto-approve".to_string(),
-                    "codex" => "codex --dangerously-bypass-approvals-and-sandbox".to_string(),
-                    "gemini" => "gemini --approval-mode yolo".to_string(),
-                    _ => self.get_tool_command().to_string(),
-                }
-            } else {
-                self.get_tool_command().to_string()
-            };
-            let env_args = build_docker_env_args(sandbox, &self.project_path);
-            let env_part = if env_args.is_empty() {
-                String::new()
-            } else {
-                format!("{} ", env_args)
-            };
-            Some(wrap_command_ignore_suspend(&format!(
-                "docker exec -it {}{} {}",
-                env_part, sandbox.container_name, tool_cmd
-            )))
-        } else {
+        } else if let Some(ref hook_cmds) = on_launch_hooks {
             // Run on_launch hooks on host for non-sandboxed sessions
-            if let Some(ref hook_cmds) = on_launch_hooks {
-                if let Err(e) = super::repo_config::execute_hooks(
-                    hook_cmds,
-                    std::path::Path::new(&self.project_path),
-                ) {
-                    tracing::warn!("on_launch hook failed: {}", e);
-                }
-            }
-
-            if self.command.is_empty() {
-                match self.tool.as_str() {
-                    "claude" => Some(wrap_command_ignore_suspend("claude")),
-                    "vibe" => Some(wrap_command_ignore_suspend("vibe")),
-                    "codex" => Some(wrap_command_ignore_suspend("codex")),
-                    "gemini" => Some(wrap_command_ignore_suspend("gemini")),
-                    _ => None,
-                }
-            } else {
-                Some(wrap_command_ignore_suspend(&self.command))
+            if let Err(e) = super::repo_config::execute_hooks(
+                hook_cmds,
+                std::path::Path::new(&self.project_path),
+            ) {
+                tracing::warn!("on_launch hook failed: {}", e);
             }
-        };
+        }
 
-        session.create_with_size(&self.project_path, cmd.as_deref(), size)?;
+        let (cmd, redactor) = self.build_launch_command();
+        if let Some(ref cmd) = cmd {
+            tracing::debug!("Launching agent: {}", redactor.redact(cmd));
+        }
+        session
+            .create_with_size(
+                &self.project_path,
+                cmd.as_deref(),
+                size,
+                self.history_limit(),
+            )
+            .map_err(|e| redact_error(&redactor, e))?;
 
         // Set context environment variable for non-sandboxed sessions
         // (sandboxed sessions get this via docker exec -e flags)
@@ -567,10 +897,84 @@ impl Instance {
 
         self.status = Status::Starting;
         self.last_start_time = Some(std::time::Instant::now());
+        self.last_error = None;
 
         Ok(())
     }
 
+    /// The command the agent's tmux session is created with, or `None` to
+    /// start it with a plain shell. Only builds the string: nothing is
+    /// started, so this is what `start` would run right now. The command
+    /// carries resolved env values, so apply the returned `Redactor` before
+    /// showing or logging it.
+    pub fn build_launch_command(&self) -> (Option<String>, Redactor) {
+        if !self.is_sandboxed() {
+            let cmd = if self.command.is_empty() {
+                match self.tool.as_str() {
+                    "claude" | "opencode" | "vibe" | "codex" | "gemini" => {
+                        Some(wrap_command_ignore_suspend(&self.tool))
+                    }
+                    _ => None,
+                }
+            } else {
+                Some(wrap_command_ignore_suspend(&self.command))
+            };
+            return (cmd, Redactor::default());
+        }
+
+        let sandbox = self
+            .sandbox_info
+            .as_ref()
+            .expect("sandboxed sessions have sandbox info");
+        let tool_cmd = if self.is_yolo_mode() {
+            match self.tool.as_str() {
+                "claude" => "claude --dangerously-skip-permissions".to_string(),
+                "vibe" => "vibe --agent auto-approve".to_string(),
+                "codex" => "codex --dangerously-bypass-approvals-and-sandbox".to_string(),
+                "gemini" => "gemini --approval-mode yolo".to_string(),
+                _ => self.get_tool_command().to_string(),
+            }
+        } else {
+            self.get_tool_command().to_string()
+        };
+        let (env_args, redactor) =
+            build_docker_env_args(sandbox, &self.project_path, &self.launch_profile());
+        let env_part = if env_args.is_empty() {
+            String::new()
+        } else {
+            format!("{} ", env_args)
+        };
+        let cmd = wrap_command_ignore_suspend(&format!(
+            "docker exec -it {}{} {}",
+            env_part, sandbox.container_name, tool_cmd
+        ));
+        (Some(cmd), redactor)
+    }
+
+    /// The tool this session would launch on the host, if `is_available` says
+    /// it isn't installed. A command whose program is the tool itself (such
+    /// as `opencode --port 4096`, or the plain tool name the builder stores)
+    /// is checked too. Sandboxed sessions run the tool inside the container
+    /// and other custom commands are the user's to check, so neither is
+    /// looked up, and a tool `is_available` doesn't know never blocks.
+    fn missing_tool(&self, is_available: impl Fn(&str) -> Option<bool>) -> Option<ToolNotFound> {
+        if self.is_sandboxed() {
+            return None;
+        }
+        let program = self.get_tool_command().split_whitespace().next();
+        if program != Some(self.tool.as_str()) || is_available(&self.tool) != Some(false) {
+            return None;
+        }
+        Some(ToolNotFound {
+            tool: self.tool.clone(),
+        })
+    }
+
+    /// Scrollback for this session's new tmux panes
+    fn history_limit(&self) -> Option<u64> {
+        super::config::tmux_history_limit(&self.launch_profile())
+    }
+
     /// Apply all configured tmux options (status bar, mouse, etc.) to the agent session.
     fn apply_tmux_options(&self) {
         let session_name = tmux::Session::generate_name(&self.id, &self.title);
@@ -589,15 +993,16 @@ impl Instance {
     fn set_context_env_in_tmux(&self) {
         if let Some(context_dir) = self.get_context_dir_for_env() {
             let session_name = tmux::Session::generate_name(&self.id, &self.title);
-            let _ = std::process::Command::new("tmux")
-                .args([
+            let _ = crate::process::run_with_timeout(
+                std::process::Command::new("tmux").args([
                     "set-environment",
                     "-t",
                     &session_name,
                     crate::context::CONTEXT_DIR_ENV_VAR,
                     &context_dir,
-                ])
-                .output();
+                ]),
+                crate::process::COMMAND_TIMEOUT,
+            );
         }
     }
 
@@ -681,35 +1086,115 @@ impl Instance {
         );
     }
 
+    /// Environment passed into this session's sandbox container, as `KEY=value`
+    /// entries with credential-looking values masked. Empty for sessions
+    /// without a sandbox.
+    pub fn sandbox_env_preview(&self) -> Vec<String> {
+        self.sandbox_env_preview_with(&resolve_launch_config(&self.launch_profile()))
+    }
+
+    /// `sandbox_env_preview` resolved from `config` instead of the session's
+    /// profile, e.g. the profile plus repo config a new session is created with
+    pub fn sandbox_env_preview_with(&self, config: &super::config::Config) -> Vec<String> {
+        match &self.sandbox_info {
+            Some(sandbox) if self.is_sandboxed() => preview_env(&config.sandbox, sandbox),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Host environment variables this sandboxed session's config refers to
+    /// that are not currently set. Empty for sessions without a sandbox.
+    pub fn missing_env_vars(&self) -> Vec<String> {
+        self.missing_env_vars_with(&resolve_launch_config(&self.launch_profile()))
+    }
+
+    /// `missing_env_vars` resolved from `config` instead of the session's profile
+    pub fn missing_env_vars_with(&self, config: &super::config::Config) -> Vec<String> {
+        match &self.sandbox_info {
+            Some(sandbox) if self.is_sandboxed() => {
+                collect_missing_env_vars(&config.sandbox, sandbox)
+            }
+            _ => Vec::new(),
+        }
+    }
+
     pub fn ensure_container_running(&mut self) -> Result<()> {
         let sandbox = self
             .sandbox_info
             .as_ref()
             .ok_or_else(|| anyhow::anyhow!("Cannot ensure container for non-sandboxed session"))?;
 
-        let image = &sandbox.image;
-        let container = DockerContainer::new(&self.id, image);
+        let image = sandbox.image.clone();
+        let container = DockerContainer::new(&self.id, &image);
 
         if container.is_running()? {
+            self.sync_container_id(&container);
             return Ok(());
         }
 
+        let retries = resolve_launch_config(&self.launch_profile())
+            .sandbox
+            .docker_retries;
+
         if container.exists()? {
-            container.start()?;
-            return Ok(());
+            match docker::with_retries(retries, "Starting container", || container.start()) {
+                Ok(()) => {
+                    self.sync_container_id(&container);
+                    return Ok(());
+                }
+                // A daemon that is still unreachable after the retries would fail
+                // the recreate too, and the container itself may be fine
+                Err(e) if e.is_transient() => return Err(e.into()),
+                Err(e) => {
+                    // A container that can't start (e.g. its bind mount source is gone)
+                    // is replaced rather than left blocking the name.
+                    tracing::warn!(
+                        "Failed to start container {}, recreating it: {}",
+                        container.name,
+                        e
+                    );
+                    container.remove(true)?;
+                }
+            }
         }
 
-        // Ensure image is available (always pulls to get latest)
-        docker::ensure_image(image)?;
+        // The container is gone, so any remembered id is stale
+        if let Some(ref mut sandbox) = self.sandbox_info {
+            if let Some(stale) = sandbox.container_id.take() {
+                tracing::info!(
+                    "Container {} ({}) no longer exists, recreating",
+                    container.name,
+                    super::truncate_id(&stale, 12)
+                );
+            }
+            sandbox.created_at = None;
+        }
 
-        docker::ensure_named_volume(CLAUDE_AUTH_VOLUME)?;
-        docker::ensure_named_volume(OPENCODE_AUTH_VOLUME)?;
-        docker::ensure_named_volume(VIBE_AUTH_VOLUME)?;
-        docker::ensure_named_volume(CODEX_AUTH_VOLUME)?;
-        docker::ensure_named_volume(GEMINI_AUTH_VOLUME)?;
+        // Ensure image is available (always pulls to get latest)
+        docker::with_retries(retries, "Pulling image", || docker::ensure_image(&image))?;
 
         let config = self.build_container_config()?;
-        let container_id = container.create(&config)?;
+        for (volume, _) in &config.named_volumes {
+            docker::with_retries(retries, "Creating volume", || {
+                docker::ensure_named_volume(volume)
+            })?;
+        }
+        // docker echoes bad arguments back in its errors, env values included
+        let redactor = Redactor::from_env(
+            config
+                .environment
+                .iter()
+                .map(|(k, v)| (k.as_str(), v.as_str())),
+        );
+        let container_id = match container.create(&config) {
+            Err(docker::DockerError::ContainerAlreadyExists(_)) => {
+                // Something else claimed the name between our checks; replace it once
+                container.remove(true)?;
+                container.create(&config)
+            }
+            result => result,
+        }
+        .map_err(|e| redact_error(&redactor, e.into()))?;
 
         if let Some(ref mut sandbox) = self.sandbox_info {
             sandbox.container_id = Some(container_id);
@@ -719,6 +1204,19 @@ impl Instance {
         Ok(())
     }
 
+    /// Record the id of the container currently holding this session's name,
+    /// in case it was recreated outside of aoe.
+    fn sync_container_id(&mut self, container: &DockerContainer) {
+        let Ok(Some(actual)) = container.id() else {
+            return;
+        };
+        if let Some(ref mut sandbox) = self.sandbox_info {
+            if sandbox.container_id.as_deref() != Some(actual.as_str()) {
+                sandbox.container_id = Some(actual);
+            }
+        }
+    }
+
     /// Compute volume mount paths for Docker container.
     ///
     /// For bare repo worktrees, mounts the entire bare repo and sets working_dir to the worktree.
@@ -807,21 +1305,14 @@ impl Instance {
             read_only: false,
         }];
 
-        let sandbox_config = match super::config::Config::load() {
-            Ok(c) => {
-                tracing::debug!(
-                    "Loaded sandbox config: extra_volumes={:?}, mount_ssh={}, volume_ignores={:?}",
-                    c.sandbox.extra_volumes,
-                    c.sandbox.mount_ssh,
-                    c.sandbox.volume_ignores
-                );
-                c.sandbox
-            }
-            Err(e) => {
-                tracing::warn!("Failed to load config, using defaults: {}", e);
-                Default::default()
-            }
-        };
+        let profile = self.launch_profile();
+        let sandbox_config = resolve_launch_config(&profile).sandbox;
+        tracing::debug!(
+            "Loaded sandbox config: extra_volumes={:?}, mount_ssh={}, volume_ignores={:?}",
+            sandbox_config.extra_volumes,
+            sandbox_config.mount_ssh,
+            sandbox_config.volume_ignores
+        );
 
         const CONTAINER_HOME: &str = "/root";
 
@@ -864,21 +1355,24 @@ impl Instance {
             });
         }
 
+        // Named after the session's own profile, as in `profile_auth_volumes`
+        let auth_volume = |base: &str| sandbox_config.auth_volume_scope.volume_name(base, &profile);
+
         let mut named_volumes = vec![
             (
-                CLAUDE_AUTH_VOLUME.to_string(),
+                auth_volume(CLAUDE_AUTH_VOLUME),
                 format!("{}/.claude", CONTAINER_HOME),
             ),
             (
-                OPENCODE_AUTH_VOLUME.to_string(),
+                auth_volume(OPENCODE_AUTH_VOLUME),
                 format!("{}/.local/share/opencode", CONTAINER_HOME),
             ),
             (
-                CODEX_AUTH_VOLUME.to_string(),
+                auth_volume(CODEX_AUTH_VOLUME),
                 format!("{}/.codex", CONTAINER_HOME),
             ),
             (
-                GEMINI_AUTH_VOLUME.to_string(),
+                auth_volume(GEMINI_AUTH_VOLUME),
                 format!("{}/.gemini", CONTAINER_HOME),
             ),
         ];
@@ -887,7 +1381,7 @@ impl Instance {
         // (can't have duplicate mount points)
         if !has_vibe_host_mount {
             named_volumes.push((
-                VIBE_AUTH_VOLUME.to_string(),
+                auth_volume(VIBE_AUTH_VOLUME),
                 format!("{}/.vibe", CONTAINER_HOME),
             ));
         }
@@ -895,6 +1389,14 @@ impl Instance {
         let sandbox_info = self.sandbox_info.as_ref().unwrap();
         let env_keys = collect_env_keys(&sandbox_config, sandbox_info);
 
+        let missing = collect_missing_env_vars(&sandbox_config, sandbox_info);
+        if !missing.is_empty() {
+            tracing::warn!(
+                "Host environment variables not set, not passed to container: {}",
+                missing.join(", ")
+            );
+        }
+
         let mut environment: Vec<(String, String)> = env_keys
             .iter()
             .filter_map(|key| std::env::var(key).ok().map(|val| (key.clone(), val)))
@@ -914,32 +1416,34 @@ impl Instance {
             ));
         }
 
-        // Add extra_volumes from config (host:container format)
+        // Add extra_volumes from config, then this session's own (host:container[:ro])
         // Also collect container paths to filter conflicting volume_ignores later
+        let session_volumes = sandbox_info.extra_volumes.iter().flatten();
         tracing::debug!(
-            "extra_volumes from config: {:?}",
-            sandbox_config.extra_volumes
+            "extra_volumes from config: {:?}, session: {:?}",
+            sandbox_config.extra_volumes,
+            sandbox_info.extra_volumes
         );
         let mut extra_volume_container_paths: std::collections::HashSet<String> =
             std::collections::HashSet::new();
-        for entry in &sandbox_config.extra_volumes {
-            let parts: Vec<&str> = entry.splitn(3, ':').collect();
-            if parts.len() >= 2 {
-                tracing::info!(
-                    "Mounting extra volume: {} -> {} (ro: {})",
-                    parts[0],
-                    parts[1],
-                    parts.get(2) == Some(&"ro")
+        for entry in sandbox_config.extra_volumes.iter().chain(session_volumes) {
+            let mount = parse_extra_volume(entry)?;
+            if shadows_workspace(&mount.container_path, &volumes[0].container_path) {
+                anyhow::bail!(
+                    "Invalid volume '{}': mounting at '{}' would shadow the workspace mount at '{}'",
+                    entry,
+                    mount.container_path,
+                    volumes[0].container_path
                 );
-                extra_volume_container_paths.insert(parts[1].to_string());
-                volumes.push(VolumeMount {
-                    host_path: parts[0].to_string(),
-                    container_path: parts[1].to_string(),
-                    read_only: parts.get(2) == Some(&"ro"),
-                });
-            } else {
-                tracing::warn!("Ignoring malformed extra_volume entry: {}", entry);
             }
+            tracing::info!(
+                "Mounting extra volume: {} -> {} (ro: {})",
+                mount.host_path,
+                mount.container_path,
+                mount.read_only
+            );
+            extra_volume_container_paths.insert(mount.container_path.clone());
+            volumes.push(mount);
         }
 
         // Mount context directory if enabled
@@ -983,6 +1487,19 @@ impl Instance {
             })
             .collect();
 
+        let network = sandbox_info
+            .network
+            .clone()
+            .or(sandbox_config.network.clone())
+            .filter(|n| !n.trim().is_empty());
+        if network.as_deref() == Some("host") {
+            tracing::warn!(
+                "Sandbox uses host networking: the container shares the host network stack and port mappings are ignored"
+            );
+        }
+
+        let (cap_drop, cap_add, security_opt) = security_args(&sandbox_config);
+
         Ok(ContainerConfig {
             working_dir: workspace_path,
             volumes,
@@ -991,6 +1508,11 @@ impl Instance {
             environment,
             cpu_limit: sandbox_config.cpu_limit,
             memory_limit: sandbox_config.memory_limit,
+            network,
+            read_only_rootfs: sandbox_config.read_only_rootfs,
+            cap_drop,
+            cap_add,
+            security_opt,
         })
     }
 
@@ -1049,24 +1571,69 @@ impl Instance {
         if !session.exists() {
             self.status = Status::Error;
             self.last_error_check = Some(std::time::Instant::now());
+            // A dead container takes the agent down with it, so say why.
+            // Only looked up once; starting the session clears it.
+            if self.is_sandboxed() && self.last_error.is_none() {
+                self.last_error = self.container_exit_reason();
+            }
+            return;
+        }
+
+        if self.tool_exited(&session) {
+            self.status = Status::Exited;
             return;
         }
 
         // Detect status from pane content
-        self.status = match session.detect_status(&self.tool) {
+        self.status = match session.detect_status(&self.tool, &self.launch_profile()) {
             Ok(status) => status,
             Err(_) => Status::Idle,
         };
     }
 
+    /// Whether a tmux client is viewing this session's agent or its
+    /// container terminal, from this or any other terminal
+    pub fn has_container_viewers(&self) -> bool {
+        self.tmux_session().is_ok_and(|s| s.has_clients())
+            || self
+                .container_terminal_tmux_session()
+                .is_ok_and(|s| s.has_clients())
+    }
+
+    /// Why this session's container is not running, or `None` if it is (or
+    /// Docker could not be asked)
+    pub fn container_exit_reason(&self) -> Option<String> {
+        let container = DockerContainer::from_session_id(&self.id);
+        match container.inspect_state() {
+            Ok(Some(state)) => state.exit_reason(),
+            Ok(None) => Some(format!(
+                "container {} no longer exists; starting the session recreates it",
+                container.name
+            )),
+            Err(_) => None,
+        }
+    }
+
+    /// Whether the pane has fallen back to a shell instead of running the
+    /// agent. Sessions whose own command is a shell never count as exited.
+    fn tool_exited(&self, session: &tmux::Session) -> bool {
+        if launches_shell(self.get_tool_command()) {
+            return false;
+        }
+        session
+            .foreground_command()
+            .is_some_and(|name| crate::process::is_shell(&name))
+    }
+
     pub fn capture_output_with_size(
         &self,
         lines: usize,
         width: u16,
         height: u16,
+        join_wrapped: bool,
     ) -> Result<String> {
         let session = self.tmux_session()?;
-        session.capture_pane_with_size(lines, Some(width), Some(height))
+        session.capture_pane_with_size(lines, Some(width), Some(height), join_wrapped)
     }
 }
 
@@ -1082,14 +1649,28 @@ fn generate_id() -> String {
 /// the actual command.
 ///
 /// Uses POSIX-standard `stty susp undef` which works on both Linux and macOS.
+/// Single quotes in `cmd` are escaped as `'\''` so they cannot close the
+/// wrapper's quoting.
 fn wrap_command_ignore_suspend(cmd: &str) -> String {
-    format!("bash -c 'stty susp undef; exec {}'", cmd)
+    format!(
+        "bash -c 'stty susp undef; exec {}'",
+        escape_single_quoted(cmd)
+    )
+}
+
+/// Whether a session command starts an interactive shell rather than an
+/// agent, e.g. `bash` or `/bin/zsh -l`
+fn launches_shell(command: &str) -> bool {
+    command
+        .split_whitespace()
+        .next()
+        .is_some_and(crate::process::is_shell)
 }
 
 /// All supported coding tools.
 /// When adding a new tool, update:
 /// - This constant
-/// - `detect_tool()` in cli/add.rs
+/// - `detect_tool()` below
 /// - `detect_status_from_content()` in tmux/status_detection.rs
 /// - `default_tool_fields()` in tui/settings/fields.rs (options list and match statements)
 /// - `apply_field_to_global()` and `apply_field_to_profile()` in tui/settings/fields.rs
@@ -1101,9 +1682,33 @@ pub const SUPPORTED_TOOLS: &[&str] = &["claude", "opencode", "vibe", "codex", "g
 /// - `build_container_config()` for environment variables (OpenCode uses env var)
 pub const YOLO_SUPPORTED_TOOLS: &[&str] = &["claude", "opencode", "vibe", "codex", "gemini"];
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// The tool a launch command runs, from the tool name it contains. An empty
+/// command runs claude.
+pub fn detect_tool(cmd: &str) -> Result<String> {
+    let cmd_lower = cmd.to_lowercase();
+    if cmd_lower.is_empty() || cmd_lower.contains("claude") {
+        Ok("claude".to_string())
+    } else if cmd_lower.contains("opencode") || cmd_lower.contains("open-code") {
+        Ok("opencode".to_string())
+    } else if cmd_lower.contains("vibe") || cmd_lower.contains("mistral-vibe") {
+        Ok("vibe".to_string())
+    } else if cmd_lower.contains("codex") {
+        Ok("codex".to_string())
+    } else if cmd_lower.contains("gemini") {
+        Ok("gemini".to_string())
+    } else {
+        anyhow::bail!(
+            "Unknown tool in command: {}\n\
+             Supported tools: claude, opencode, vibe, codex, gemini\n\
+             Tip: Command must contain one of the supported tool names",
+            cmd
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
 
     #[test]
     fn test_new_instance() {
@@ -1114,6 +1719,505 @@ mod tests {
         assert_eq!(inst.id.len(), 16);
     }
 
+    /// Run `cmd` through `sh -c` the way tmux does and return its stdout
+    fn run_through_shell(cmd: &str) -> String {
+        let output = std::process::Command::new("sh")
+            .args(["-c", cmd])
+            .stdin(std::process::Stdio::null())
+            .output()
+            .unwrap();
+        String::from_utf8(output.stdout).unwrap()
+    }
+
+    const TRICKY_VALUES: &[&str] = &[
+        "it's",
+        "'; echo INJECTED; '",
+        "say \"hi\"",
+        "$(echo INJECTED)",
+        "`echo INJECTED`",
+        "$HOME",
+        "back\\slash",
+        "mixed '\"$(x)`y`\\",
+    ];
+
+    #[test]
+    fn test_shell_escape_round_trips_through_shell() {
+        for val in TRICKY_VALUES {
+            let cmd = format!("printf %s {}", shell_escape(val));
+            assert_eq!(run_through_shell(&cmd), *val, "value: {}", val);
+        }
+    }
+
+    #[test]
+    fn test_wrapped_shell_escape_round_trips_through_both_shells() {
+        for val in TRICKY_VALUES {
+            let cmd = wrap_command_ignore_suspend(&format!("printf %s {}", shell_escape(val)));
+            assert_eq!(run_through_shell(&cmd), *val, "value: {}", val);
+        }
+    }
+
+    #[test]
+    fn test_wrap_command_keeps_single_quoted_args() {
+        let cmd = wrap_command_ignore_suspend("printf %s 'a b'");
+        assert_eq!(run_through_shell(&cmd), "a b");
+    }
+
+    #[test]
+    fn test_resolve_env_value_plain_forms() {
+        std::env::set_var("AOE_TEST_RESOLVE_PLAIN", "host-value");
+        assert_eq!(resolve_env_value("literal"), Some("literal".to_string()));
+        assert_eq!(
+            resolve_env_value("$AOE_TEST_RESOLVE_PLAIN"),
+            Some("host-value".to_string())
+        );
+        assert_eq!(
+            resolve_env_value("$$AOE_TEST_RESOLVE_PLAIN"),
+            Some("$AOE_TEST_RESOLVE_PLAIN".to_string())
+        );
+        assert_eq!(
+            resolve_env_value("$${AOE_TEST_RESOLVE_PLAIN}"),
+            Some("${AOE_TEST_RESOLVE_PLAIN}".to_string())
+        );
+        assert_eq!(resolve_env_value("$AOE_TEST_RESOLVE_MISSING"), None);
+    }
+
+    #[test]
+    fn test_resolve_env_value_braced() {
+        std::env::set_var("AOE_TEST_RESOLVE_BRACED", "host-value");
+        assert_eq!(
+            resolve_env_value("${AOE_TEST_RESOLVE_BRACED}"),
+            Some("host-value".to_string())
+        );
+        assert_eq!(resolve_env_value("${AOE_TEST_RESOLVE_MISSING}"), None);
+    }
+
+    #[test]
+    fn test_resolve_env_value_default() {
+        std::env::set_var("AOE_TEST_RESOLVE_DEFAULT", "host-value");
+        std::env::set_var("AOE_TEST_RESOLVE_DEFAULT_EMPTY", "");
+        assert_eq!(
+            resolve_env_value("${AOE_TEST_RESOLVE_DEFAULT:-fallback}"),
+            Some("host-value".to_string())
+        );
+        assert_eq!(
+            resolve_env_value("${AOE_TEST_RESOLVE_MISSING:-fallback}"),
+            Some("fallback".to_string())
+        );
+        assert_eq!(
+            resolve_env_value("${AOE_TEST_RESOLVE_DEFAULT_EMPTY:-fallback}"),
+            Some("fallback".to_string())
+        );
+        assert_eq!(
+            resolve_env_value("${AOE_TEST_RESOLVE_MISSING:-}"),
+            Some(String::new())
+        );
+    }
+
+    #[test]
+    fn test_resolve_env_value_alternate() {
+        std::env::set_var("AOE_TEST_RESOLVE_ALT", "host-value");
+        assert_eq!(
+            resolve_env_value("${AOE_TEST_RESOLVE_ALT:+enabled}"),
+            Some("enabled".to_string())
+        );
+        assert_eq!(
+            resolve_env_value("${AOE_TEST_RESOLVE_MISSING:+enabled}"),
+            Some(String::new())
+        );
+    }
+
+    #[test]
+    fn test_preview_env_masks_credentials() {
+        std::env::set_var("AOE_TEST_PREVIEW_TOKEN", "secret-value-123");
+        let config = super::super::config::SandboxConfig {
+            environment: vec!["AOE_TEST_PREVIEW_TOKEN".to_string()],
+            environment_values: [("MODE".to_string(), "debug".to_string())]
+                .into_iter()
+                .collect(),
+            ..Default::default()
+        };
+        let info = SandboxInfo {
+            enabled: true,
+            container_id: None,
+            image: "test-image".to_string(),
+            container_name: "test".to_string(),
+            created_at: None,
+            yolo_mode: None,
+            extra_env_keys: Some(vec!["AOE_TEST_PREVIEW_UNSET".to_string()]),
+            extra_env_values: None,
+            extra_volumes: None,
+            network: None,
+        };
+
+        let env = preview_env(&config, &info);
+        assert!(env.contains(&"AOE_TEST_PREVIEW_TOKEN=[REDACTED]".to_string()));
+        assert!(env.contains(&"MODE=debug".to_string()));
+        assert!(!env.iter().any(|e| e.contains("secret-value-123")));
+        assert!(!env.iter().any(|e| e.starts_with("AOE_TEST_PREVIEW_UNSET")));
+    }
+
+    #[test]
+    fn test_collect_missing_env_vars() {
+        std::env::set_var("AOE_TEST_MISSING_SET", "1");
+        let config = super::super::config::SandboxConfig {
+            environment: vec![
+                "TERM".to_string(),
+                "AOE_TEST_MISSING_SET".to_string(),
+                "AOE_TEST_MISSING_KEY".to_string(),
+            ],
+            environment_values: [
+                ("A".to_string(), "$AOE_TEST_MISSING_REF".to_string()),
+                ("B".to_string(), "${AOE_TEST_MISSING_BRACED}".to_string()),
+                (
+                    "C".to_string(),
+                    "${AOE_TEST_MISSING_DEFAULT:-x}".to_string(),
+                ),
+                ("D".to_string(), "literal".to_string()),
+            ]
+            .into_iter()
+            .collect(),
+            ..Default::default()
+        };
+        let info = SandboxInfo {
+            enabled: true,
+            container_id: None,
+            image: "test-image".to_string(),
+            container_name: "test".to_string(),
+            created_at: None,
+            yolo_mode: None,
+            extra_env_keys: Some(vec!["AOE_TEST_MISSING_EXTRA".to_string()]),
+            extra_env_values: None,
+            extra_volumes: None,
+            network: None,
+        };
+
+        let mut missing = collect_missing_env_vars(&config, &info);
+        missing.sort();
+        assert_eq!(
+            missing,
+            vec![
+                "AOE_TEST_MISSING_BRACED",
+                "AOE_TEST_MISSING_EXTRA",
+                "AOE_TEST_MISSING_KEY",
+                "AOE_TEST_MISSING_REF",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_redact_error_masks_secret_in_command() {
+        let redactor = Redactor::from_env([("GH_TOKEN", "ghp_secret123")]);
+        let err = anyhow::anyhow!("docker exec -e GH_TOKEN=\"ghp_secret123\" aoe-sandbox-1 claude")
+            .context("Failed to create tmux session");
+        let message = format!("{:#}", redact_error(&redactor, err));
+        assert!(!message.contains("ghp_secret123"));
+        assert!(message.contains("Failed to create tmux session"));
+        assert!(message.contains("GH_TOKEN="));
+    }
+
+    #[test]
+    fn test_set_tool_drops_custom_command() {
+        let mut inst = Instance::new("test", "/tmp/test");
+        inst.command = "claude --model opus".to_string();
+
+        inst.set_tool("claude");
+        assert_eq!(inst.command, "claude --model opus");
+
+        inst.set_tool("codex");
+        assert_eq!(inst.tool, "codex");
+        assert!(inst.command.is_empty());
+        assert_eq!(inst.get_tool_command(), "codex");
+    }
+
+    #[test]
+    fn test_set_tool_to_opencode_launches_opencode_on_host() {
+        let mut inst = Instance::new("test", "/tmp/test");
+        inst.set_tool("opencode");
+        let (cmd, _) = inst.build_launch_command();
+        assert_eq!(
+            cmd.as_deref(),
+            Some("bash -c 'stty susp undef; exec opencode'")
+        );
+    }
+
+    #[test]
+    fn test_missing_tool_reported_for_host_sessions() {
+        let mut inst = Instance::new("test", "/tmp/test");
+        inst.tool = "vibe".to_string();
+
+        let missing = inst.missing_tool(|_| Some(false)).unwrap();
+        assert_eq!(missing.to_string(), "tool 'vibe' not found in PATH");
+        assert!(inst.missing_tool(|tool| Some(tool == "vibe")).is_none());
+
+        inst.command = "my-wrapper --fast".to_string();
+        assert!(inst.missing_tool(|_| Some(false)).is_none());
+    }
+
+    #[test]
+    fn test_missing_tool_checked_when_command_runs_the_tool() {
+        let mut inst = Instance::new("test", "/tmp/test");
+        inst.tool = "opencode".to_string();
+
+        // The builder stores the tool's default command for non-claude tools
+        inst.command = "opencode".to_string();
+        assert!(inst.missing_tool(|_| Some(false)).is_some());
+
+        inst.command = "opencode --port 4096".to_string();
+        assert!(inst.missing_tool(|_| Some(false)).is_some());
+        assert!(inst.missing_tool(|_| Some(true)).is_none());
+    }
+
+    #[test]
+    fn test_missing_tool_unknown_availability_does_not_block() {
+        let mut inst = Instance::new("test", "/tmp/test");
+        inst.tool = "my-agent".to_string();
+        inst.command = "my-agent".to_string();
+        assert!(inst.missing_tool(|_| None).is_none());
+    }
+
+    #[test]
+    fn test_missing_tool_not_checked_for_sandboxed_sessions() {
+        let mut inst = Instance::new("test", "/tmp/test");
+        inst.tool = "vibe".to_string();
+        inst.sandbox_info = Some(SandboxInfo {
+            enabled: true,
+            container_id: None,
+            image: "ubuntu:latest".to_string(),
+            container_name: "aoe-sandbox-test".to_string(),
+            created_at: None,
+            yolo_mode: None,
+            extra_env_keys: None,
+            extra_env_values: None,
+            extra_volumes: None,
+            network: None,
+        });
+        assert!(inst.missing_tool(|_| Some(false)).is_none());
+    }
+
+    fn launch_command(inst: &Instance) -> String {
+        inst.build_launch_command()
+            .0
+            .expect("session should launch a command")
+    }
+
+    /// Point HOME at an empty directory so the launch reads default config
+    fn isolated_home() -> tempfile::TempDir {
+        let home = tempfile::TempDir::new().unwrap();
+        std::env::set_var("HOME", home.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", home.path().join(".config"));
+        home
+    }
+
+    fn sandboxed_tool(tool: &str, yolo_mode: bool) -> Instance {
+        let mut inst = Instance::new("test", "/tmp/test");
+        inst.tool = tool.to_string();
+        inst.sandbox_info = Some(SandboxInfo {
+            enabled: true,
+            container_id: None,
+            image: "test-image".to_string(),
+            container_name: "aoe-sandbox-test".to_string(),
+            created_at: None,
+            yolo_mode: Some(yolo_mode),
+            extra_env_keys: None,
+            extra_env_values: None,
+            extra_volumes: None,
+            network: None,
+        });
+        inst
+    }
+
+    #[test]
+    fn test_build_launch_command_for_host_tools() {
+        for tool in SUPPORTED_TOOLS {
+            let mut inst = Instance::new("test", "/tmp/test");
+            inst.tool = tool.to_string();
+            assert_eq!(
+                launch_command(&inst),
+                format!("bash -c 'stty susp undef; exec {}'", tool)
+            );
+        }
+    }
+
+    #[test]
+    fn test_build_launch_command_for_host_custom_command() {
+        let mut inst = Instance::new("test", "/tmp/test");
+        inst.command = "my-wrapper --fast".to_string();
+        assert_eq!(
+            launch_command(&inst),
+            "bash -c 'stty susp undef; exec my-wrapper --fast'"
+        );
+
+        // Single quotes survive the bash -c wrapper
+        inst.command = "claude --append-system-prompt 'be brief'".to_string();
+        assert_eq!(
+            launch_command(&inst),
+            r"bash -c 'stty susp undef; exec claude --append-system-prompt '\''be brief'\'''"
+        );
+    }
+
+    #[test]
+    fn test_build_launch_command_without_known_tool_starts_a_shell() {
+        let mut inst = Instance::new("test", "/tmp/test");
+        inst.tool = "unknown".to_string();
+        let (cmd, redactor) = inst.build_launch_command();
+        assert!(cmd.is_none());
+        assert!(redactor.is_empty());
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_build_launch_command_for_sandboxed_tools() {
+        let _home = isolated_home();
+        for tool in SUPPORTED_TOOLS {
+            let cmd = launch_command(&sandboxed_tool(tool, false));
+            assert!(
+                cmd.starts_with("bash -c 'stty susp undef; exec docker exec -it "),
+                "{}",
+                cmd
+            );
+            assert!(
+                cmd.ends_with(&format!(" aoe-sandbox-test {}'", tool)),
+                "{}",
+                cmd
+            );
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_build_launch_command_yolo_flags() {
+        let _home = isolated_home();
+        let cases = [
+            ("claude", "claude --dangerously-skip-permissions"),
+            ("vibe", "vibe --agent auto-approve"),
+            ("codex", "codex --dangerously-bypass-approvals-and-sandbox"),
+            ("gemini", "gemini --approval-mode yolo"),
+        ];
+        for (tool, expected) in cases {
+            let cmd = launch_command(&sandboxed_tool(tool, true));
+            assert!(
+                cmd.ends_with(&format!(" aoe-sandbox-test {}'", expected)),
+                "{}",
+                cmd
+            );
+
+            let cmd = launch_command(&sandboxed_tool(tool, false));
+            assert!(!cmd.contains(expected), "{}", cmd);
+        }
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_opencode_yolo_uses_permission_env_var() {
+        let _home = isolated_home();
+        let project = tempfile::TempDir::new().unwrap();
+        let sandboxed_opencode = |yolo_mode| {
+            let mut inst = sandboxed_tool("opencode", yolo_mode);
+            inst.project_path = project.path().to_string_lossy().to_string();
+            inst
+        };
+        let permission = |inst: &Instance| {
+            inst.build_container_config()
+                .unwrap()
+                .environment
+                .into_iter()
+                .find(|(key, _)| key == "OPENCODE_PERMISSION")
+                .map(|(_, value)| value)
+        };
+
+        // OpenCode has no yolo flag; the container gets OPENCODE_PERMISSION instead
+        let inst = sandboxed_opencode(true);
+        assert!(launch_command(&inst).ends_with(" aoe-sandbox-test opencode'"));
+        assert_eq!(permission(&inst).as_deref(), Some(r#"{"*":"allow"}"#));
+
+        assert_eq!(permission(&sandboxed_opencode(false)), None);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_build_launch_command_injects_env_and_redacts_secrets() {
+        let _home = isolated_home();
+        let mut inst = sandboxed_tool("claude", false);
+        if let Some(sandbox) = inst.sandbox_info.as_mut() {
+            sandbox.extra_env_values = Some(
+                [
+                    ("AOE_TEST_MODE".to_string(), "fast".to_string()),
+                    ("AOE_TEST_TOKEN".to_string(), "s3cr3t-value".to_string()),
+                ]
+                .into_iter()
+                .collect(),
+            );
+        }
+
+        let (cmd, redactor) = inst.build_launch_command();
+        let cmd = cmd.unwrap();
+        assert!(cmd.contains(r#"-e AOE_TEST_MODE="fast""#), "{}", cmd);
+        assert!(cmd.contains("s3cr3t-value"));
+
+        let shown = redactor.redact(&cmd);
+        assert!(!shown.contains("s3cr3t-value"), "{}", shown);
+        assert!(
+            shown.contains(r#"-e AOE_TEST_TOKEN="[REDACTED]""#),
+            "{}",
+            shown
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_build_launch_command_redacts_escaped_secrets() {
+        let _home = isolated_home();
+        let secret = r#"pa$s"w'd\x"#;
+        let mut inst = sandboxed_tool("claude", false);
+        if let Some(sandbox) = inst.sandbox_info.as_mut() {
+            sandbox.extra_env_values = Some(
+                [("AOE_TEST_PASSWORD".to_string(), secret.to_string())]
+                    .into_iter()
+                    .collect(),
+            );
+        }
+
+        let (cmd, redactor) = inst.build_launch_command();
+        let cmd = cmd.unwrap();
+        let err = anyhow::anyhow!("tmux new-session failed: {}", cmd).context("Failed to start");
+        for shown in [
+            redactor.redact(&cmd),
+            format!("{:#}", redact_error(&redactor, err)),
+        ] {
+            // Every form of the secret, raw or escaped, starts with "pa" and
+            // nothing else in the command contains it
+            assert!(!shown.contains("pa"), "{}", shown);
+            assert!(
+                shown.contains(crate::session::redact::REDACTED),
+                "{}",
+                shown
+            );
+        }
+    }
+
+    #[test]
+    fn test_start_with_missing_tool_sets_friendly_error() {
+        let Some(tool) = ["vibe", "gemini", "codex", "opencode"]
+            .into_iter()
+            .find(|t| tmux::is_tool_available(t) == Some(false))
+        else {
+            eprintln!("Skipping test: every supported tool is installed");
+            return;
+        };
+        let mut inst = Instance::new("aoe_test_missing_tool", "/tmp/test");
+        inst.tool = tool.to_string();
+
+        let err = inst.start().unwrap_err();
+        assert!(err.downcast_ref::<ToolNotFound>().is_some());
+        assert_eq!(inst.status, Status::Error);
+        assert_eq!(
+            inst.last_error,
+            Some(format!("tool '{}' not found in PATH", tool))
+        );
+        assert!(!inst.tmux_session().unwrap().exists());
+    }
+
     #[test]
     fn test_is_sub_session() {
         let mut inst = Instance::new("test", "/tmp/test");
@@ -1161,6 +2265,8 @@ mod tests {
             yolo_mode: Some(true),
             extra_env_keys: None,
             extra_env_values: None,
+            extra_volumes: None,
+            network: None,
         });
         assert!(inst.is_yolo_mode());
 
@@ -1190,6 +2296,8 @@ mod tests {
             yolo_mode: None,
             extra_env_keys: None,
             extra_env_values: None,
+            extra_volumes: None,
+            network: None,
         });
         assert!(!inst.is_sandboxed());
     }
@@ -1206,6 +2314,8 @@ mod tests {
             yolo_mode: None,
             extra_env_keys: None,
             extra_env_values: None,
+            extra_volumes: None,
+            network: None,
         });
         assert!(inst.is_sandboxed());
     }
@@ -1280,6 +2390,14 @@ mod tests {
         assert_eq!(status, Status::Idle);
     }
 
+    #[test]
+    fn test_launches_shell() {
+        assert!(launches_shell("bash"));
+        assert!(launches_shell("/bin/zsh -l"));
+        assert!(!launches_shell("claude --resume"));
+        assert!(!launches_shell(""));
+    }
+
     #[test]
     fn test_status_serialization() {
         let statuses = vec![
@@ -1289,6 +2407,7 @@ mod tests {
             Status::Error,
             Status::Starting,
             Status::Deleting,
+            Status::Exited,
         ];
 
         for status in statuses {
@@ -1307,6 +2426,7 @@ mod tests {
             managed_by_aoe: true,
             created_at: Utc::now(),
             cleanup_on_delete: true,
+            base_branch: None,
         };
 
         let json = serde_json::to_string(&info).unwrap();
@@ -1338,6 +2458,8 @@ mod tests {
             yolo_mode: Some(true),
             extra_env_keys: Some(vec!["MY_VAR".to_string(), "OTHER_VAR".to_string()]),
             extra_env_values: None,
+            extra_volumes: None,
+            network: None,
         };
 
         let json = serde_json::to_string(&info).unwrap();
@@ -1408,6 +2530,7 @@ mod tests {
             managed_by_aoe: true,
             created_at: Utc::now(),
             cleanup_on_delete: true,
+            base_branch: None,
         });
 
         let json = serde_json::to_string(&inst).unwrap();
@@ -1609,5 +2732,370 @@ mod tests {
             // Working dir should be set
             assert!(!working_dir.is_empty());
         }
+
+        fn git_available() -> bool {
+            std::process::Command::new("git")
+                .arg("--version")
+                .output()
+                .is_ok_and(|o| o.status.success())
+        }
+
+        fn build_bare_repo_session(path: &Path, branch: &str, create_new_branch: bool) -> Instance {
+            let params = crate::session::builder::InstanceParams {
+                title: "bare".to_string(),
+                path: path.to_string_lossy().to_string(),
+                group: String::new(),
+                tool: "claude".to_string(),
+                command: String::new(),
+                worktree_branch: Some(branch.to_string()),
+                create_new_branch,
+                sandbox: false,
+                sandbox_image: String::new(),
+                yolo_mode: false,
+                extra_env_keys: Vec::new(),
+                extra_env_values: Vec::new(),
+                extra_volumes: Vec::new(),
+                network: None,
+                parent_session_id: None,
+            };
+            crate::session::builder::build_instance("default", params, &[])
+                .unwrap()
+                .instance
+        }
+
+        #[test]
+        #[serial_test::serial]
+        fn test_create_worktree_in_bare_repo() {
+            if !git_available() {
+                eprintln!("Skipping test: git not available");
+                return;
+            }
+            let (dir, main_repo_path, worktree_path) = setup_bare_repo_with_worktree();
+            assert!(worktree_path.exists(), "git worktree add failed");
+            std::env::set_var("HOME", dir.path());
+            #[cfg(target_os = "linux")]
+            std::env::set_var("XDG_CONFIG_HOME", dir.path().join(".config"));
+
+            // Start from an existing worktree, as a user in `main/` would
+            let inst = build_bare_repo_session(&worktree_path, "feature", true);
+
+            let project_path = Path::new(&inst.project_path).canonicalize().unwrap();
+            let main_canon = main_repo_path.canonicalize().unwrap();
+            assert_eq!(
+                project_path,
+                main_canon.join("feature"),
+                "Bare repo worktrees are created beside the others via ./{{branch}}"
+            );
+            assert_eq!(
+                GitWorktree::get_current_branch(&project_path).unwrap(),
+                "feature"
+            );
+
+            let wt = inst.worktree_info.as_ref().unwrap();
+            assert!(wt.managed_by_aoe);
+            assert_eq!(
+                Path::new(&wt.main_repo_path).canonicalize().unwrap(),
+                main_canon
+            );
+
+            // The .git file is relative so the repo also resolves inside containers
+            let git_file = std::fs::read_to_string(project_path.join(".git")).unwrap();
+            assert!(git_file
+                .trim_start_matches("gitdir:")
+                .trim()
+                .starts_with(".."));
+
+            let (mount_path, _, working_dir) = inst.compute_volume_paths(&project_path).unwrap();
+            assert_eq!(Path::new(&mount_path).canonicalize().unwrap(), main_canon);
+            assert!(working_dir.ends_with("/feature"));
+        }
+
+        #[test]
+        #[serial_test::serial]
+        fn test_create_worktree_from_bare_repo_root() {
+            if !git_available() {
+                eprintln!("Skipping test: git not available");
+                return;
+            }
+            let (dir, main_repo_path, worktree_path) = setup_bare_repo_with_worktree();
+            assert!(worktree_path.exists(), "git worktree add failed");
+            std::env::set_var("HOME", dir.path());
+            #[cfg(target_os = "linux")]
+            std::env::set_var("XDG_CONFIG_HOME", dir.path().join(".config"));
+
+            let inst = build_bare_repo_session(&main_repo_path, "from-root", true);
+
+            let project_path = Path::new(&inst.project_path).canonicalize().unwrap();
+            assert_eq!(
+                project_path,
+                main_repo_path.canonicalize().unwrap().join("from-root")
+            );
+            assert_eq!(
+                GitWorktree::get_current_branch(&project_path).unwrap(),
+                "from-root"
+            );
+        }
+    }
+
+    #[test]
+    fn test_worktree_shared_by() {
+        let mut owner = Instance::new("owner", "/repo-worktrees/feature");
+        owner.worktree_info = Some(WorktreeInfo {
+            branch: "feature".to_string(),
+            main_repo_path: "/repo".to_string(),
+            managed_by_aoe: true,
+            created_at: Utc::now(),
+            cleanup_on_delete: true,
+            base_branch: None,
+        });
+        let reuser = Instance::new("reuser", "/repo-worktrees/feature/");
+        let mut deleting = Instance::new("deleting", "/repo-worktrees/feature");
+        deleting.status = Status::Deleting;
+        let unrelated = Instance::new("unrelated", "/repo");
+
+        let all = vec![owner.clone(), reuser, deleting, unrelated];
+        assert_eq!(owner.worktree_shared_by(&all), 1);
+        assert_eq!(owner.worktree_shared_by(&all[2..]), 0);
+        // Sessions without a worktree are never considered shared
+        assert_eq!(all[3].worktree_shared_by(&all), 0);
+    }
+
+    #[test]
+    fn test_parse_extra_volume() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let host = dir.path().to_str().unwrap();
+
+        let mount = parse_extra_volume(&format!("{}:/data:ro", host)).unwrap();
+        assert_eq!(mount.host_path, host);
+        assert_eq!(mount.container_path, "/data");
+        assert!(mount.read_only);
+
+        let mount = parse_extra_volume(&format!("{}:/data/", host)).unwrap();
+        assert_eq!(mount.container_path, "/data");
+        assert!(!mount.read_only);
+
+        assert!(parse_extra_volume(host).is_err());
+        assert!(parse_extra_volume(&format!("{}:data", host)).is_err());
+        assert!(parse_extra_volume(&format!("{}:/data:rx", host)).is_err());
+        assert!(parse_extra_volume("/definitely/not/here:/data").is_err());
+    }
+
+    #[test]
+    fn test_shadows_workspace() {
+        assert!(shadows_workspace("/workspace/app", "/workspace/app"));
+        assert!(shadows_workspace("/workspace", "/workspace/app"));
+        assert!(shadows_workspace("", "/workspace/app"));
+        assert!(!shadows_workspace("/workspace/app/data", "/workspace/app"));
+        assert!(!shadows_workspace(
+            "/workspace/application",
+            "/workspace/app"
+        ));
+        assert!(!shadows_workspace("/data", "/workspace/app"));
+    }
+
+    #[test]
+    fn test_security_args_hardened_preset() {
+        let mut config = crate::session::config::SandboxConfig::default();
+        let (drop, add, opt) = security_args(&config);
+        assert!(drop.is_empty() && add.is_empty() && opt.is_empty());
+
+        config.hardened = true;
+        config.cap_add = vec!["NET_BIND_SERVICE".to_string(), "CHOWN".to_string()];
+        let (drop, add, opt) = security_args(&config);
+        assert_eq!(drop, vec!["ALL"]);
+        assert!(add.contains(&"NET_BIND_SERVICE".to_string()));
+        assert_eq!(add.iter().filter(|c| *c == "CHOWN").count(), 1);
+        assert_eq!(opt, vec!["no-new-privileges"]);
+    }
+
+    fn sandboxed_instance(project: &std::path::Path, extra_volumes: Vec<String>) -> Instance {
+        let mut inst = Instance::new("test", project.to_str().unwrap());
+        inst.sandbox_info = Some(SandboxInfo {
+            enabled: true,
+            container_id: None,
+            image: "test-image".to_string(),
+            container_name: "test".to_string(),
+            created_at: None,
+            yolo_mode: None,
+            extra_env_keys: None,
+            extra_env_values: None,
+            extra_volumes: Some(extra_volumes),
+            network: None,
+        });
+        inst
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_build_container_config_includes_session_extra_volumes() {
+        let _home = isolated_home();
+        let project = tempfile::TempDir::new().unwrap();
+        let shared = tempfile::TempDir::new().unwrap();
+        let shared_path = shared.path().to_str().unwrap();
+
+        let inst = sandboxed_instance(project.path(), vec![format!("{}:/shared:ro", shared_path)]);
+        let config = inst.build_container_config().unwrap();
+
+        let mount = config
+            .volumes
+            .iter()
+            .find(|v| v.container_path == "/shared")
+            .expect("extra volume should be mounted");
+        assert_eq!(mount.host_path, shared_path);
+        assert!(mount.read_only);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_build_container_config_rejects_workspace_shadowing() {
+        let _home = isolated_home();
+        let project = tempfile::TempDir::new().unwrap();
+        let shared = tempfile::TempDir::new().unwrap();
+
+        let inst = sandboxed_instance(
+            project.path(),
+            vec![format!("{}:/workspace", shared.path().display())],
+        );
+        let err = match inst.build_container_config() {
+            Ok(_) => panic!("mount over /workspace should be rejected"),
+            Err(e) => e.to_string(),
+        };
+        assert!(err.contains("shadow"), "{}", err);
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_launch_config_follows_session_profile() {
+        let _home = isolated_home();
+        std::env::remove_var("AOE_TEST_WORK_PROFILE_ONLY");
+
+        let work = crate::session::ProfileConfig {
+            sandbox: Some(crate::session::SandboxConfigOverride {
+                environment: Some(vec!["AOE_TEST_WORK_PROFILE_ONLY".to_string()]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        crate::session::save_profile_config("work", &work).unwrap();
+
+        let project = tempfile::TempDir::new().unwrap();
+        let mut inst = sandboxed_instance(project.path(), vec![]);
+        assert!(inst.missing_env_vars().is_empty());
+
+        inst.profile = "work".to_string();
+        assert_eq!(inst.missing_env_vars(), vec!["AOE_TEST_WORK_PROFILE_ONLY"]);
+    }
+
+    #[test]
+    fn test_env_preview_with_uses_given_config() {
+        let project = tempfile::TempDir::new().unwrap();
+        let inst = sandboxed_instance(project.path(), vec![]);
+        std::env::remove_var("AOE_TEST_PREVIEW_WITH_UNSET");
+
+        let mut config = crate::session::Config::default();
+        config
+            .sandbox
+            .environment_values
+            .insert("MODE".to_string(), "debug".to_string());
+        config.sandbox.environment = vec!["AOE_TEST_PREVIEW_WITH_UNSET".to_string()];
+
+        assert!(inst
+            .sandbox_env_preview_with(&config)
+            .contains(&"MODE=debug".to_string()));
+        assert_eq!(
+            inst.missing_env_vars_with(&config),
+            vec!["AOE_TEST_PREVIEW_WITH_UNSET"]
+        );
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_build_container_config_scopes_auth_volumes_by_profile() {
+        let _home = isolated_home();
+        let project = tempfile::TempDir::new().unwrap();
+        let inst = sandboxed_instance(project.path(), vec![]);
+
+        let auth_volumes = |inst: &Instance| -> Vec<String> {
+            inst.build_container_config()
+                .unwrap()
+                .named_volumes
+                .into_iter()
+                .map(|(name, _)| name)
+                .collect()
+        };
+        assert!(auth_volumes(&inst).contains(&CLAUDE_AUTH_VOLUME.to_string()));
+
+        let mut config = crate::session::Config {
+            default_profile: "work".to_string(),
+            ..Default::default()
+        };
+        config.sandbox.auth_volume_scope = crate::session::AuthVolumeScope::Profile;
+        crate::session::save_config(&config).unwrap();
+
+        let names = auth_volumes(&inst);
+        assert!(names.contains(&"aoe-claude-auth-work".to_string()));
+        assert!(names.iter().all(|n| n.ends_with("-work")));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_prewarm_and_container_agree_on_auth_volumes() {
+        let _home = isolated_home();
+        let project = tempfile::TempDir::new().unwrap();
+
+        // The scope comes from the profile override, not the global config
+        let work = crate::session::ProfileConfig {
+            sandbox: Some(crate::session::SandboxConfigOverride {
+                auth_volume_scope: Some(crate::session::AuthVolumeScope::Profile),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        crate::session::save_profile_config("work", &work).unwrap();
+
+        let storage = crate::session::Storage::new("work").unwrap();
+        storage
+            .save(&[sandboxed_instance(project.path(), vec![])])
+            .unwrap();
+        let inst = storage.load().unwrap().remove(0);
+
+        let mut mounted: Vec<String> = inst
+            .build_container_config()
+            .unwrap()
+            .named_volumes
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        let mut prewarmed = profile_auth_volumes("work");
+        mounted.sort();
+        prewarmed.sort();
+        assert_eq!(mounted, prewarmed);
+        assert!(prewarmed.iter().all(|n| n.ends_with("-work")));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_auth_volumes_named_after_session_profile() {
+        let _home = isolated_home();
+        let project = tempfile::TempDir::new().unwrap();
+
+        let mut config = crate::session::Config {
+            default_profile: "personal".to_string(),
+            ..Default::default()
+        };
+        config.sandbox.auth_volume_scope = crate::session::AuthVolumeScope::Profile;
+        crate::session::save_config(&config).unwrap();
+
+        let mut inst = sandboxed_instance(project.path(), vec![]);
+        inst.profile = "work".to_string();
+        let names: Vec<String> = inst
+            .build_container_config()
+            .unwrap()
+            .named_volumes
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        assert!(names.contains(&"aoe-claude-auth-work".to_string()));
+        assert!(!names.iter().any(|n| n.ends_with("-personal")));
     }
 }
@@ -0,0 +1,288 @@
+//! High-level session operations for embedding aoe in other tools.
+//!
+//! Each function loads the profile's sessions, applies one operation and saves
+//! the result, so callers need neither the CLI nor the TUI. Both front ends use
+//! these for the same operations.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Result};
+
+use super::builder::{self, InstanceParams};
+use super::repo_config::{self, HookTrustStatus};
+use super::{GroupTree, HooksConfig, Instance, Storage};
+use crate::docker::DockerContainer;
+use crate::git::GitWorktree;
+
+/// Build a new session in `profile` (creating its worktree and sandbox info as
+/// requested), run its on_create hooks and save it. Repository hooks that have
+/// not been trusted are skipped; see [`create_session_with_hook_approval`].
+/// The session is not started; see [`start_session`], which runs the
+/// on_launch hooks.
+pub fn create_session(profile: &str, params: InstanceParams) -> Result<Instance> {
+    create_session_with_hook_approval(profile, params, |_| Ok(false))
+}
+
+/// Like [`create_session`], but asks `approve` about repository hooks that
+/// have not been trusted yet. Approved hooks are trusted for the repository
+/// and run like any other.
+pub fn create_session_with_hook_approval(
+    profile: &str,
+    params: InstanceParams,
+    approve: impl FnOnce(&HooksConfig) -> Result<bool>,
+) -> Result<Instance> {
+    let storage = Storage::new(profile)?;
+    let (mut instances, groups) = storage.load_with_groups()?;
+
+    let existing_titles: Vec<&str> = instances.iter().map(|i| i.title.as_str()).collect();
+    let build_result = builder::build_instance(storage.profile(), params, &existing_titles)?;
+    let mut instance = build_result.instance;
+
+    if let Err(e) = run_on_create_hooks(&mut instance, approve) {
+        builder::cleanup_instance(&instance, build_result.created_worktree.as_ref());
+        return Err(e);
+    }
+
+    instances.push(instance.clone());
+    let mut group_tree = GroupTree::new_with_groups(&instances, &groups);
+    if !instance.group_path.is_empty() {
+        group_tree.create_group(&instance.group_path);
+    }
+
+    if let Err(e) = storage.save_with_groups(&instances, &group_tree) {
+        builder::cleanup_instance(&instance, build_result.created_worktree.as_ref());
+        return Err(e);
+    }
+
+    Ok(instance)
+}
+
+/// Run the on_create hooks for a freshly built session: the global and profile
+/// hooks, with trusted repository hooks replacing them field by field.
+/// Sandboxed sessions run them in their container, which is started for it.
+fn run_on_create_hooks(
+    instance: &mut Instance,
+    approve: impl FnOnce(&HooksConfig) -> Result<bool>,
+) -> Result<()> {
+    let project_path = PathBuf::from(&instance.project_path);
+    let mut on_create = super::resolve_config(&instance.profile)?.hooks.on_create;
+
+    let repo_on_create = match repo_config::check_hook_trust(&project_path) {
+        Ok(HookTrustStatus::Trusted(hooks)) => hooks.on_create,
+        Ok(HookTrustStatus::NeedsTrust { hooks, hooks_hash }) => {
+            if approve(&hooks)? {
+                repo_config::trust_repo(&project_path, &hooks_hash)?;
+                hooks.on_create
+            } else {
+                Vec::new()
+            }
+        }
+        Ok(HookTrustStatus::NoHooks) => Vec::new(),
+        Err(e) => {
+            tracing::warn!("Failed to check repo hooks: {}", e);
+            Vec::new()
+        }
+    };
+    if !repo_on_create.is_empty() {
+        on_create = repo_on_create;
+    }
+    if on_create.is_empty() {
+        return Ok(());
+    }
+
+    tracing::info!("Running on_create hooks for {}", instance.title);
+    let result = if instance.is_sandboxed() {
+        instance.ensure_container_running()?;
+        let container_name = instance
+            .sandbox_info
+            .as_ref()
+            .map(|s| s.container_name.clone())
+            .unwrap_or_default();
+        repo_config::execute_hooks_in_container(
+            &on_create,
+            &container_name,
+            &instance.container_workdir(),
+        )
+    } else {
+        repo_config::execute_hooks(&on_create, &project_path)
+    };
+    result.map_err(|e| anyhow::anyhow!("on_create hook failed: {}", e))
+}
+
+/// Start the tmux session (and container, if sandboxed) for the session
+/// matching `identifier` (ID, ID prefix, title or path). Returns the updated
+/// instance.
+pub fn start_session(profile: &str, identifier: &str) -> Result<Instance> {
+    let storage = Storage::new(profile)?;
+    let (mut instances, groups) = storage.load_with_groups()?;
+
+    let idx = super::resolve_session_index(identifier, &instances)?;
+    instances[idx].start_with_size(crate::terminal::get_size())?;
+
+    let group_tree = GroupTree::new_with_groups(&instances, &groups);
+    storage.save_with_groups(&instances, &group_tree)?;
+
+    Ok(instances[idx].clone())
+}
+
+/// Attach the current terminal to a running session, blocking until the user
+/// detaches. Fails if the session is not running.
+pub fn attach_session(profile: &str, identifier: &str) -> Result<()> {
+    let storage = Storage::new(profile)?;
+    let (mut instances, groups) = storage.load_with_groups()?;
+
+    let idx = super::resolve_session_index(identifier, &instances)?;
+    let tmux_session = instances[idx].tmux_session()?;
+
+    if !tmux_session.exists() {
+        bail!(
+            "Session is not running. Start it first with: agent-of-empires session start {}",
+            identifier
+        );
+    }
+
+    instances[idx].last_accessed_at = Some(chrono::Utc::now());
+    let group_tree = GroupTree::new_with_groups(&instances, &groups);
+    storage.save_with_groups(&instances, &group_tree)?;
+
+    tmux_session.attach()
+}
+
+/// Give the session matching `identifier` a new title, renaming its tmux
+/// session to match. Titles identify sessions on the command line, so one
+/// already used by another session in the profile is rejected.
+pub fn rename_session(profile: &str, identifier: &str, new_title: &str) -> Result<Instance> {
+    let new_title = new_title.trim();
+    if new_title.is_empty() {
+        bail!("Title cannot be empty");
+    }
+
+    let storage = Storage::new(profile)?;
+    let (mut instances, groups) = storage.load_with_groups()?;
+
+    let idx = super::resolve_session_index(identifier, &instances)?;
+    let id = instances[idx].id.clone();
+    if instances.iter().any(|i| i.id != id && i.title == new_title) {
+        bail!("A session titled '{}' already exists", new_title);
+    }
+
+    instances[idx].set_title(new_title)?;
+
+    let group_tree = GroupTree::new_with_groups(&instances, &groups);
+    storage.save_with_groups(&instances, &group_tree)?;
+
+    Ok(instances[idx].clone())
+}
+
+/// Which of a session's resources to remove along with it
+#[derive(Debug, Clone, Copy, Default)]
+pub struct CleanupOptions {
+    pub delete_worktree: bool,
+    pub delete_branch: bool,
+    pub delete_sandbox: bool,
+}
+
+/// Tear down everything a session owns: its aoe-managed worktree and branch
+/// and its container (each only if requested), then its tmux sessions.
+/// Every step is attempted even after an earlier one fails.
+/// The worktree and branch are kept while another of `instances` still uses
+/// them. Returns one message per resource that could not be removed.
+pub fn remove_session_resources(
+    instance: &Instance,
+    instances: &[Instance],
+    mut opts: CleanupOptions,
+) -> Vec<String> {
+    let mut errors = Vec::new();
+
+    if instance.worktree_shared_by(instances) > 0 {
+        opts.delete_worktree = false;
+        opts.delete_branch = false;
+    }
+
+    // Track branch info for potential deletion after worktree removal
+    let branch_to_delete = if opts.delete_branch {
+        instance
+            .worktree_info
+            .as_ref()
+            .filter(|wt| wt.managed_by_aoe)
+            .map(|wt| (wt.branch.clone(), PathBuf::from(&wt.main_repo_path)))
+    } else {
+        None
+    };
+
+    // Worktree cleanup must happen before branch deletion since the worktree
+    // is using the branch. One that is already gone, e.g. from an earlier
+    // attempt, needs no cleanup.
+    if opts.delete_worktree {
+        if let Some(wt_info) = &instance.worktree_info {
+            let worktree_path = PathBuf::from(&instance.project_path);
+            if wt_info.managed_by_aoe && worktree_path.exists() {
+                let main_repo = PathBuf::from(&wt_info.main_repo_path);
+                match GitWorktree::new(main_repo) {
+                    Ok(git_wt) => {
+                        if let Err(e) = git_wt.remove_worktree(&worktree_path) {
+                            errors.push(format!("Worktree: {}", e));
+                        }
+                    }
+                    Err(e) => {
+                        errors.push(format!("Worktree: failed to access git repository: {}", e))
+                    }
+                }
+            }
+        }
+    }
+
+    // Only delete the branch if worktree deletion succeeded (or wasn't requested)
+    if let Some((branch, main_repo)) = branch_to_delete {
+        let worktree_ok =
+            !opts.delete_worktree || !errors.iter().any(|e| e.starts_with("Worktree:"));
+        if worktree_ok {
+            if let Ok(git_wt) = GitWorktree::new(main_repo) {
+                if let Err(e) = git_wt.delete_branch(&branch) {
+                    errors.push(format!("Branch: {}", e));
+                }
+            }
+        }
+    }
+
+    if opts.delete_sandbox {
+        if let Some(sandbox) = &instance.sandbox_info {
+            if sandbox.enabled {
+                let container = DockerContainer::from_session_id(&instance.id);
+                if container.exists().unwrap_or(false) {
+                    if let Err(e) = container.remove(true) {
+                        errors.push(format!("Container: {}", e));
+                    }
+                }
+            }
+        }
+    }
+
+    // A session that is already gone needs no kill
+    if let Err(e) = instance.kill() {
+        errors.push(format!("Tmux: {}", e));
+    }
+    let _ = instance.kill_terminal();
+
+    errors
+}
+
+/// Remove the session matching `identifier` and the resources selected in
+/// `opts`, then drop it from storage. The session is kept if any resource
+/// could not be removed.
+pub fn delete_session(profile: &str, identifier: &str, opts: CleanupOptions) -> Result<()> {
+    let storage = Storage::new(profile)?;
+    let (mut instances, groups) = storage.load_with_groups()?;
+
+    let idx = super::resolve_session_index(identifier, &instances)?;
+    let errors = remove_session_resources(&instances[idx], &instances, opts);
+    if !errors.is_empty() {
+        bail!("Failed to remove session resources: {}", errors.join("; "));
+    }
+
+    instances.remove(idx);
+    let group_tree = GroupTree::new_with_groups(&instances, &groups);
+    storage.save_with_groups(&instances, &group_tree)?;
+
+    Ok(())
+}
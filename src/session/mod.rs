@@ -2,35 +2,51 @@
 
 pub mod builder;
 pub mod civilizations;
+pub mod claude;
 pub mod config;
 mod groups;
 mod instance;
+pub mod manager;
 pub mod profile_config;
+pub mod redact;
 pub mod repo_config;
+mod resolve;
 mod storage;
 
 pub use crate::sound::{SoundConfig, SoundConfigOverride};
 pub use config::{
-    get_claude_config_dir, get_update_settings, load_config, save_config, ClaudeConfig, Config,
-    DefaultTerminalMode, SandboxConfig, SessionConfig, ThemeConfig, TmuxMouseMode,
-    TmuxStatusBarMode, UpdatesConfig, WorktreeConfig,
+    expand_path, get_claude_config_dir, get_update_settings, is_offline, load_config, save_config,
+    AppConfig, AuthVolumeScope, ClaudeConfig, Config, DefaultTerminalMode, EventsConfig, LogLevel,
+    LoggingConfig, NewSessionOptions, PresetConfig, SandboxConfig, SessionConfig,
+    StatusPatternsConfig, ThemeConfig, TmuxMouseMode, TmuxStatusBarMode, UiConfig, UpdatesConfig,
+    WorktreeConfig, OFFLINE_ENV,
 };
 pub use groups::{flatten_tree, Group, GroupTree, Item};
 pub use instance::{
-    Instance, SandboxInfo, Status, TerminalInfo, WorktreeInfo, SUPPORTED_TOOLS,
+    detect_tool, parse_extra_volume, profile_auth_volumes, Instance, SandboxInfo, Status,
+    TerminalInfo, ToolNotFound, WorktreeInfo, STOPPED_ON_DETACH, SUPPORTED_TOOLS,
     YOLO_SUPPORTED_TOOLS,
 };
 pub use profile_config::{
     load_profile_config, merge_configs, resolve_config, save_profile_config,
-    validate_check_interval, validate_memory_limit, validate_path_exists, validate_volume_format,
-    ClaudeConfigOverride, HooksConfigOverride, ProfileConfig, SandboxConfigOverride,
-    SessionConfigOverride, ThemeConfigOverride, TmuxConfigOverride, UpdatesConfigOverride,
-    WorktreeConfigOverride,
+    validate_check_interval, validate_cpu_limit, validate_docker_retries, validate_log_max_files,
+    validate_memory_limit, validate_path_exists, validate_path_template,
+    validate_status_debounce_polls, validate_status_refresh_ms, validate_tick_ms,
+    validate_title_template, validate_volume_format, validate_webhook_url, ClaudeConfigOverride,
+    EventsConfigOverride, HooksConfigOverride, LoggingConfigOverride, ProfileConfig,
+    SandboxConfigOverride, SessionConfigOverride, ThemeConfigOverride, TmuxConfigOverride,
+    UiConfigOverride, UpdatesConfigOverride, WorktreeConfigOverride, MAX_TICK_MS,
+    MIN_STATUS_REFRESH_MS, MIN_TICK_MS,
 };
 pub use repo_config::{
     check_hook_trust, execute_hooks, execute_hooks_in_container, load_repo_config,
-    merge_repo_config, profile_to_repo_config, repo_config_to_profile, resolve_config_with_repo,
-    save_repo_config, trust_repo, ContextConfig, HookTrustStatus, HooksConfig, RepoConfig,
+    merge_repo_config, profile_to_repo_config, repo_config_path, repo_config_to_profile,
+    resolve_config_with_repo, save_repo_config, trust_repo, ContextConfig, HookTrustStatus,
+    HooksConfig, RepoConfig,
+};
+pub use resolve::{
+    glob_match, in_group, is_glob, resolve_session, resolve_session_index, short_id, truncate_id,
+    SHORT_ID_MIN_LEN,
 };
 pub use storage::Storage;
 
@@ -97,6 +113,39 @@ pub fn list_profiles() -> Result<Vec<String>> {
     Ok(profiles)
 }
 
+/// Session counts for a single profile, as shown by `aoe profile list`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProfileSummary {
+    pub name: String,
+    pub sessions: usize,
+    pub running: usize,
+}
+
+/// Summarize every profile by reading its sessions file. A session counts as
+/// running when its tmux session is alive; tmux is queried once for all profiles.
+pub fn summarize_profiles() -> Result<Vec<ProfileSummary>> {
+    crate::tmux::refresh_session_cache();
+
+    let mut summaries = Vec::new();
+    for name in list_profiles()? {
+        let instances = Storage::new(&name)?.load()?;
+        let running = instances
+            .iter()
+            .filter(|inst| {
+                inst.tmux_session()
+                    .map(|session| session.exists())
+                    .unwrap_or(false)
+            })
+            .count();
+        summaries.push(ProfileSummary {
+            name,
+            sessions: instances.len(),
+            running,
+        });
+    }
+    Ok(summaries)
+}
+
 pub fn create_profile(name: &str) -> Result<()> {
     if name.is_empty() {
         anyhow::bail!("Profile name cannot be empty");
@@ -127,12 +176,64 @@ pub fn delete_profile(name: &str) -> Result<()> {
     }
 
     fs::remove_dir_all(&profile_dir)?;
+
+    if let Some(mut config) = load_config()? {
+        if config.app_state.last_profile.as_deref() == Some(name) {
+            config.app_state.last_profile = None;
+            save_config(&config)?;
+        }
+    }
     Ok(())
 }
 
+/// Pick the profile to run with. `requested` comes from `--profile`, which clap
+/// already fills from `AGENT_OF_EMPIRES_PROFILE` when the flag is absent. Without
+/// either, and with `resume_last` (set when opening the TUI), the profile last
+/// switched to in the TUI is resumed if it still exists and has
+/// `ui.resume_last_profile` on. Otherwise the configured `default_profile`
+/// applies, then `DEFAULT_PROFILE`, so CLI subcommands don't depend on what the
+/// TUI showed last.
+pub fn resolve_profile(requested: Option<&str>, resume_last: bool) -> String {
+    if let Some(name) = requested.filter(|name| !name.is_empty()) {
+        return name.to_string();
+    }
+
+    match load_config() {
+        Ok(Some(config)) => {
+            if let Some(last) = config.app_state.last_profile.filter(|_| resume_last) {
+                if list_profiles().is_ok_and(|profiles| profiles.contains(&last))
+                    && resolve_config(&last).is_ok_and(|c| c.ui.resume_last_profile)
+                {
+                    return last;
+                }
+            }
+            if config.default_profile.is_empty() {
+                DEFAULT_PROFILE.to_string()
+            } else {
+                config.default_profile
+            }
+        }
+        Ok(None) => DEFAULT_PROFILE.to_string(),
+        Err(e) => {
+            tracing::warn!("Failed to load config, using default profile: {}", e);
+            DEFAULT_PROFILE.to_string()
+        }
+    }
+}
+
 pub fn set_default_profile(name: &str) -> Result<()> {
     let mut config = load_config()?.unwrap_or_default();
     config.default_profile = name.to_string();
+    // An explicit default wins over whatever was last switched to
+    config.app_state.last_profile = None;
+    save_config(&config)?;
+    Ok(())
+}
+
+/// Record the profile switched to in the TUI so the next launch resumes it
+pub fn remember_last_profile(name: &str) -> Result<()> {
+    let mut config = load_config()?.unwrap_or_default();
+    config.app_state.last_profile = Some(name.to_string());
     save_config(&config)?;
     Ok(())
 }
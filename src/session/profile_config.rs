@@ -8,7 +8,10 @@ use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use std::fs;
 
-use super::config::{Config, DefaultTerminalMode, TmuxMouseMode, TmuxStatusBarMode};
+use super::config::{
+    AuthVolumeScope, Config, DefaultTerminalMode, PresetConfig, StatusPatternsConfig,
+    TmuxMouseMode, TmuxStatusBarMode,
+};
 use super::get_profile_dir;
 
 /// Profile-specific settings. All fields are Option<T> - None means "inherit from global"
@@ -40,6 +43,62 @@ pub struct ProfileConfig {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub sound: Option<crate::sound::SoundConfigOverride>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ui: Option<UiConfigOverride>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub logging: Option<LoggingConfigOverride>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub events: Option<EventsConfigOverride>,
+
+    /// Replaces the global `status_patterns` tables as a whole
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_patterns: Option<HashMap<String, StatusPatternsConfig>>,
+
+    /// Replaces the global `presets` as a whole
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presets: Option<HashMap<String, PresetConfig>>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct EventsConfigOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub webhook: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub command: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct LoggingConfigOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub enabled: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub level: Option<super::config::LogLevel>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_files: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct UiConfigOverride {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub tick_ms: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_refresh_ms: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub status_debounce_polls: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remember_new_session: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resume_last_profile: Option<bool>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -80,6 +139,9 @@ pub struct WorktreeConfigOverride {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub bare_repo_path_template: Option<String>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub branch_separator: Option<String>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub auto_cleanup: Option<bool>,
 
@@ -101,7 +163,11 @@ pub struct SandboxConfigOverride {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_image: Option<String>,
 
-    #[serde(default, skip_serializing_if = "Option::is_none")]
+    #[serde(
+        default,
+        alias = "extra_mounts",
+        skip_serializing_if = "Option::is_none"
+    )]
     pub extra_volumes: Option<Vec<String>>,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -119,14 +185,47 @@ pub struct SandboxConfigOverride {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub memory_limit: Option<String>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_terminal_mode: Option<DefaultTerminalMode>,
 
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auth_volume_scope: Option<AuthVolumeScope>,
+
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub volume_ignores: Option<Vec<String>>,
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mount_ssh: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_only_rootfs: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hardened: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cap_drop: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cap_add: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub security_opt: Option<Vec<String>>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub docker_retries: Option<u64>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub auto_restart: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stop_on_detach: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_concurrent_containers: Option<u64>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -136,12 +235,30 @@ pub struct TmuxConfigOverride {
 
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub mouse: Option<TmuxMouseMode>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub history_limit: Option<u64>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct SessionConfigOverride {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub default_tool: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_group: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_path: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub create_dir: Option<bool>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_template: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title_words: Option<Vec<String>>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -171,7 +288,8 @@ pub fn load_profile_config(profile: &str) -> Result<ProfileConfig> {
 pub fn save_profile_config(profile: &str, config: &ProfileConfig) -> Result<()> {
     let path = get_profile_config_path(profile)?;
     let content = toml::to_string_pretty(config)?;
-    fs::write(&path, content)?;
+    fs::write(&path, &content)?;
+    super::config::remember_own_write(&path, content);
     Ok(())
 }
 
@@ -232,15 +350,48 @@ pub fn apply_sandbox_overrides(
     if let Some(ref memory_limit) = source.memory_limit {
         target.memory_limit = Some(memory_limit.clone());
     }
+    if let Some(ref network) = source.network {
+        target.network = Some(network.clone());
+    }
     if let Some(default_terminal_mode) = source.default_terminal_mode {
         target.default_terminal_mode = default_terminal_mode;
     }
+    if let Some(auth_volume_scope) = source.auth_volume_scope {
+        target.auth_volume_scope = auth_volume_scope;
+    }
     if let Some(ref volume_ignores) = source.volume_ignores {
         target.volume_ignores = volume_ignores.clone();
     }
     if let Some(mount_ssh) = source.mount_ssh {
         target.mount_ssh = mount_ssh;
     }
+    if let Some(read_only_rootfs) = source.read_only_rootfs {
+        target.read_only_rootfs = read_only_rootfs;
+    }
+    if let Some(hardened) = source.hardened {
+        target.hardened = hardened;
+    }
+    if let Some(ref cap_drop) = source.cap_drop {
+        target.cap_drop = cap_drop.clone();
+    }
+    if let Some(ref cap_add) = source.cap_add {
+        target.cap_add = cap_add.clone();
+    }
+    if let Some(ref security_opt) = source.security_opt {
+        target.security_opt = security_opt.clone();
+    }
+    if let Some(docker_retries) = source.docker_retries {
+        target.docker_retries = docker_retries;
+    }
+    if let Some(auto_restart) = source.auto_restart {
+        target.auto_restart = auto_restart;
+    }
+    if let Some(stop_on_detach) = source.stop_on_detach {
+        target.stop_on_detach = stop_on_detach;
+    }
+    if let Some(max_concurrent_containers) = source.max_concurrent_containers {
+        target.max_concurrent_containers = max_concurrent_containers;
+    }
 }
 
 /// Apply worktree config overrides to a target config.
@@ -257,6 +408,9 @@ pub fn apply_worktree_overrides(
     if let Some(ref bare_repo_path_template) = source.bare_repo_path_template {
         target.bare_repo_path_template = bare_repo_path_template.clone();
     }
+    if let Some(ref branch_separator) = source.branch_separator {
+        target.branch_separator = branch_separator.clone();
+    }
     if let Some(auto_cleanup) = source.auto_cleanup {
         target.auto_cleanup = auto_cleanup;
     }
@@ -289,6 +443,21 @@ pub fn apply_session_overrides(
     if source.default_tool.is_some() {
         target.default_tool = source.default_tool.clone();
     }
+    if source.default_group.is_some() {
+        target.default_group = source.default_group.clone();
+    }
+    if source.default_path.is_some() {
+        target.default_path = source.default_path.clone();
+    }
+    if let Some(create_dir) = source.create_dir {
+        target.create_dir = create_dir;
+    }
+    if source.title_template.is_some() {
+        target.title_template = source.title_template.clone();
+    }
+    if let Some(ref title_words) = source.title_words {
+        target.title_words = title_words.clone();
+    }
 }
 
 /// Apply tmux config overrides to a target config.
@@ -299,6 +468,54 @@ pub fn apply_tmux_overrides(target: &mut super::config::TmuxConfig, source: &Tmu
     if let Some(mouse) = source.mouse {
         target.mouse = mouse;
     }
+    if let Some(history_limit) = source.history_limit {
+        target.history_limit = history_limit;
+    }
+}
+
+pub fn apply_ui_overrides(target: &mut super::config::UiConfig, source: &UiConfigOverride) {
+    if let Some(tick_ms) = source.tick_ms {
+        target.tick_ms = tick_ms;
+    }
+    if let Some(status_refresh_ms) = source.status_refresh_ms {
+        target.status_refresh_ms = status_refresh_ms;
+    }
+    if let Some(status_debounce_polls) = source.status_debounce_polls {
+        target.status_debounce_polls = status_debounce_polls;
+    }
+    if let Some(remember_new_session) = source.remember_new_session {
+        target.remember_new_session = remember_new_session;
+    }
+    if let Some(resume_last_profile) = source.resume_last_profile {
+        target.resume_last_profile = resume_last_profile;
+    }
+}
+
+pub fn apply_events_overrides(
+    target: &mut super::config::EventsConfig,
+    source: &EventsConfigOverride,
+) {
+    if source.webhook.is_some() {
+        target.webhook = source.webhook.clone();
+    }
+    if source.command.is_some() {
+        target.command = source.command.clone();
+    }
+}
+
+pub fn apply_logging_overrides(
+    target: &mut super::config::LoggingConfig,
+    source: &LoggingConfigOverride,
+) {
+    if let Some(enabled) = source.enabled {
+        target.enabled = enabled;
+    }
+    if let Some(level) = source.level {
+        target.level = level;
+    }
+    if let Some(max_files) = source.max_files {
+        target.max_files = max_files;
+    }
 }
 
 /// Merge profile overrides into global config
@@ -346,6 +563,18 @@ pub fn merge_configs(mut global: Config, profile: &ProfileConfig) -> Config {
         apply_session_overrides(&mut global.session, session_override);
     }
 
+    if let Some(ref ui_override) = profile.ui {
+        apply_ui_overrides(&mut global.ui, ui_override);
+    }
+
+    if let Some(ref logging_override) = profile.logging {
+        apply_logging_overrides(&mut global.logging, logging_override);
+    }
+
+    if let Some(ref events_override) = profile.events {
+        apply_events_overrides(&mut global.events, events_override);
+    }
+
     if let Some(ref hooks_override) = profile.hooks {
         apply_hooks_overrides(&mut global.hooks, hooks_override);
     }
@@ -354,6 +583,14 @@ pub fn merge_configs(mut global: Config, profile: &ProfileConfig) -> Config {
         crate::sound::apply_sound_overrides(&mut global.sound, sound_override);
     }
 
+    if let Some(ref status_patterns) = profile.status_patterns {
+        global.status_patterns = status_patterns.clone();
+    }
+
+    if let Some(ref presets) = profile.presets {
+        global.presets = presets.clone();
+    }
+
     global
 }
 
@@ -412,6 +649,147 @@ pub fn validate_memory_limit(limit: &str) -> Result<(), String> {
     }
 }
 
+pub const MIN_TICK_MS: u64 = 10;
+pub const MAX_TICK_MS: u64 = 1000;
+pub const MIN_STATUS_REFRESH_MS: u64 = 100;
+
+/// Validate the TUI tick rate in milliseconds.
+pub fn validate_tick_ms(ms: u64) -> Result<(), String> {
+    if !(MIN_TICK_MS..=MAX_TICK_MS).contains(&ms) {
+        Err(format!(
+            "Tick rate must be between {} and {} ms",
+            MIN_TICK_MS, MAX_TICK_MS
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate the status refresh interval in milliseconds.
+pub fn validate_status_refresh_ms(ms: u64) -> Result<(), String> {
+    if ms < MIN_STATUS_REFRESH_MS {
+        Err(format!(
+            "Status refresh must be at least {} ms",
+            MIN_STATUS_REFRESH_MS
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate the number of polls a status change must persist for.
+pub fn validate_status_debounce_polls(polls: u64) -> Result<(), String> {
+    if !(1..=10).contains(&polls) {
+        Err("Status debounce must be between 1 and 10 polls".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate Docker CPU limit format (a positive number such as "2" or "1.5")
+pub fn validate_cpu_limit(limit: &str) -> Result<(), String> {
+    if limit.is_empty() {
+        return Ok(());
+    }
+
+    match limit.parse::<f64>() {
+        Ok(cpus) if cpus.is_finite() && cpus > 0.0 => Ok(()),
+        _ => Err("CPU limit must be a positive number, e.g. 2 or 1.5".to_string()),
+    }
+}
+
+/// Validate a worktree path template: every `{...}` must be a known placeholder,
+/// and the template must include `{branch}`, `{branch_slug}` or `{session-id}` so that
+/// different worktrees do not resolve to the same directory.
+pub fn validate_path_template(template: &str) -> Result<(), String> {
+    if template.trim().is_empty() {
+        return Err("Path template cannot be empty".to_string());
+    }
+
+    check_placeholders(
+        template,
+        &crate::git::template::PLACEHOLDERS,
+        "Path template",
+    )?;
+
+    if !["{branch}", "{branch_slug}", "{session-id}"]
+        .iter()
+        .any(|p| template.contains(p))
+    {
+        return Err(
+            "Path template must include {branch}, {branch_slug} or {session-id}".to_string(),
+        );
+    }
+
+    Ok(())
+}
+
+/// Validate a session title template. Empty is allowed and means random
+/// titles are used.
+pub fn validate_title_template(template: &str) -> Result<(), String> {
+    check_placeholders(
+        template,
+        &super::civilizations::TITLE_PLACEHOLDERS,
+        "Title template",
+    )
+}
+
+/// Check that every `{...}` in `template` is balanced and one of `known`
+fn check_placeholders(template: &str, known: &[&str], kind: &str) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(start) = rest.find(['{', '}']) {
+        if rest[start..].starts_with('}') {
+            return Err(format!("{} has an unmatched '}}'", kind));
+        }
+        let Some(len) = rest[start..].find('}') else {
+            return Err(format!("{} has an unclosed '{{'", kind));
+        };
+        let placeholder = &rest[start..=start + len];
+        if !known.contains(&placeholder) {
+            return Err(format!(
+                "Unknown placeholder {} (use {})",
+                placeholder,
+                known.join(", ")
+            ));
+        }
+        rest = &rest[start + len + 1..];
+    }
+    Ok(())
+}
+
+/// Validate the number of log files to keep.
+pub fn validate_log_max_files(count: u64) -> Result<(), String> {
+    if count == 0 {
+        Err("Log files kept must be at least 1".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+/// Validate an event webhook URL (empty means no webhook).
+pub fn validate_webhook_url(url: &str) -> Result<(), String> {
+    if url.is_empty() || url.starts_with("http://") || url.starts_with("https://") {
+        Ok(())
+    } else {
+        Err("Webhook URL must start with http:// or https://".to_string())
+    }
+}
+
+/// Upper bound for `sandbox.docker_retries`; backoff doubles per attempt
+pub const MAX_DOCKER_RETRIES: u64 = 5;
+
+/// Validate the number of Docker command retries.
+pub fn validate_docker_retries(count: u64) -> Result<(), String> {
+    if count > MAX_DOCKER_RETRIES {
+        Err(format!(
+            "Docker retries must be at most {}",
+            MAX_DOCKER_RETRIES
+        ))
+    } else {
+        Ok(())
+    }
+}
+
 /// Validate check interval is positive
 pub fn validate_check_interval(hours: u64) -> Result<(), String> {
     if hours == 0 {
@@ -551,6 +929,42 @@ mod tests {
         assert!(validate_memory_limit("512mb").is_err());
     }
 
+    #[test]
+    fn test_validate_cpu_limit() {
+        assert!(validate_cpu_limit("").is_ok());
+        assert!(validate_cpu_limit("2").is_ok());
+        assert!(validate_cpu_limit("1.5").is_ok());
+        assert!(validate_cpu_limit("0").is_err());
+        assert!(validate_cpu_limit("-1").is_err());
+        assert!(validate_cpu_limit("two").is_err());
+        assert!(validate_cpu_limit("NaN").is_err());
+    }
+
+    #[test]
+    fn test_validate_path_template() {
+        assert!(validate_path_template("../{repo-name}-worktrees/{branch}").is_ok());
+        assert!(validate_path_template("./{branch}").is_ok());
+        assert!(validate_path_template("/tmp/wt/{session-id}").is_ok());
+        assert!(validate_path_template("{repo_parent}/{repo}-worktrees/{branch_slug}").is_ok());
+
+        assert!(validate_path_template("").is_err());
+        assert!(validate_path_template("../worktrees").is_err());
+        assert!(validate_path_template("../{repo-name}").is_err());
+        assert!(validate_path_template("../{brnach}").is_err());
+        assert!(validate_path_template("../{branch").is_err());
+        assert!(validate_path_template("../branch}/{branch}").is_err());
+    }
+
+    #[test]
+    fn test_validate_title_template() {
+        assert!(validate_title_template("").is_ok());
+        assert!(validate_title_template("{branch} @ {dir}").is_ok());
+        assert!(validate_title_template("review").is_ok());
+
+        assert!(validate_title_template("{repo-name}").is_err());
+        assert!(validate_title_template("{branch").is_err());
+    }
+
     #[test]
     fn test_validate_check_interval() {
         assert!(validate_check_interval(1).is_ok());
@@ -584,6 +998,7 @@ mod tests {
             tmux: Some(TmuxConfigOverride {
                 status_bar: Some(TmuxStatusBarMode::Enabled),
                 mouse: None,
+                history_limit: None,
             }),
             ..Default::default()
         };
@@ -649,6 +1064,79 @@ mod tests {
         assert!(merged.sandbox.enabled_by_default);
     }
 
+    #[test]
+    fn test_merge_configs_sandbox_image_and_limits_override() {
+        let mut global = Config::default();
+        global.sandbox.default_image = "global-image:latest".to_string();
+        global.sandbox.cpu_limit = Some("8".to_string());
+        global.sandbox.memory_limit = Some("16g".to_string());
+        global.sandbox.environment = vec!["GH_TOKEN".to_string()];
+
+        let profile = ProfileConfig {
+            sandbox: Some(SandboxConfigOverride {
+                default_image: Some("ci-image:pinned".to_string()),
+                cpu_limit: Some("2".to_string()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let merged = merge_configs(global, &profile);
+        assert_eq!(merged.sandbox.default_image, "ci-image:pinned");
+        assert_eq!(merged.sandbox.cpu_limit.as_deref(), Some("2"));
+        assert_eq!(merged.sandbox.memory_limit.as_deref(), Some("16g"));
+        assert_eq!(merged.sandbox.environment, vec!["GH_TOKEN"]);
+    }
+
+    #[test]
+    fn test_merge_configs_status_patterns_override() {
+        let mut global = Config::default();
+        global.status_patterns.insert(
+            "claude".to_string(),
+            StatusPatternsConfig {
+                waiting: vec!["global".to_string()],
+                running: vec![],
+            },
+        );
+
+        let merged = merge_configs(global.clone(), &ProfileConfig::default());
+        assert_eq!(merged.status_patterns, global.status_patterns);
+
+        let codex = StatusPatternsConfig {
+            waiting: vec![],
+            running: vec!["^Compacting".to_string()],
+        };
+        let profile = ProfileConfig {
+            status_patterns: Some(HashMap::from([("codex".to_string(), codex.clone())])),
+            ..Default::default()
+        };
+
+        let merged = merge_configs(global, &profile);
+        assert_eq!(merged.status_patterns.len(), 1);
+        assert_eq!(merged.status_patterns["codex"], codex);
+    }
+
+    #[test]
+    fn test_merge_configs_presets_override() {
+        let mut global = Config::default();
+        global
+            .presets
+            .insert("fast".to_string(), PresetConfig::default());
+
+        let boxed = PresetConfig {
+            sandbox: Some(true),
+            ..Default::default()
+        };
+        let profile = ProfileConfig {
+            presets: Some(HashMap::from([("boxed".to_string(), boxed.clone())])),
+            ..Default::default()
+        };
+
+        let merged = merge_configs(global, &profile);
+        assert_eq!(merged.preset_names(), vec!["boxed"]);
+        assert_eq!(merged.presets["boxed"], boxed);
+    }
+
     #[test]
     fn test_volume_ignores_override_serialization() {
         let config = ProfileConfig {
@@ -675,6 +1163,7 @@ mod tests {
             tmux: Some(TmuxConfigOverride {
                 status_bar: Some(TmuxStatusBarMode::Enabled),
                 mouse: Some(TmuxMouseMode::Enabled),
+                history_limit: None,
             }),
             ..Default::default()
         };
@@ -0,0 +1,147 @@
+//! Masking of secret environment values in logs and error messages
+//!
+//! Sandbox sessions pass host environment variables into containers. Values
+//! whose keys look like credentials must never show up verbatim in tracing
+//! output or in errors shown to the user, which often echo the failing
+//! command line.
+
+/// Replacement text for a masked value
+pub const REDACTED: &str = "[REDACTED]";
+
+/// Values shorter than this are not masked; replacing every "1" or "on" in a
+/// message would destroy it without protecting anything.
+const MIN_SECRET_LEN: usize = 4;
+
+/// Whether an environment variable name looks like it holds a credential:
+/// `*_TOKEN`, `*_KEY`, `*_SECRET` or anything containing `PASSWORD`.
+pub fn is_secret_key(key: &str) -> bool {
+    let key = key.to_ascii_uppercase();
+    ["TOKEN", "KEY", "SECRET"]
+        .iter()
+        .any(|suffix| key == *suffix || key.ends_with(&format!("_{}", suffix)))
+        || key.contains("PASSWORD")
+}
+
+/// Masks the values of secret environment variables wherever they appear in
+/// a piece of text.
+#[derive(Debug, Default, Clone)]
+pub struct Redactor {
+    secrets: Vec<String>,
+}
+
+impl Redactor {
+    /// Collect the values of secret keys from `KEY`/`value` pairs
+    pub fn from_env<'a, I>(pairs: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, &'a str)>,
+    {
+        let secrets = pairs
+            .into_iter()
+            .filter(|(key, value)| is_secret_key(key) && value.len() >= MIN_SECRET_LEN)
+            .map(|(_, value)| value.to_string())
+            .collect();
+        Self::from_secrets(secrets)
+    }
+
+    fn from_secrets(mut secrets: Vec<String>) -> Self {
+        // Longest first, so a secret containing another (or an escaped form
+        // containing the raw one) is masked whole
+        secrets.sort_by(|a, b| b.len().cmp(&a.len()).then_with(|| a.cmp(b)));
+        secrets.dedup();
+        Self { secrets }
+    }
+
+    /// Also mask every known form of each secret as `encode` writes it, e.g.
+    /// escaped for a shell. Text that quotes a value no longer contains it
+    /// verbatim, so callers register each escaping the text went through;
+    /// chained calls cover values escaped several times over.
+    pub fn with_encoding(self, encode: impl Fn(&str) -> String) -> Self {
+        let encoded: Vec<String> = self.secrets.iter().map(|s| encode(s)).collect();
+        let mut secrets = self.secrets;
+        secrets.extend(encoded);
+        Self::from_secrets(secrets)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.secrets.is_empty()
+    }
+
+    /// `text` with every known secret value replaced by `REDACTED`
+    pub fn redact(&self, text: &str) -> String {
+        self.secrets.iter().fold(text.to_string(), |acc, secret| {
+            acc.replace(secret, REDACTED)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_secret_key() {
+        assert!(is_secret_key("GITHUB_TOKEN"));
+        assert!(is_secret_key("ANTHROPIC_API_KEY"));
+        assert!(is_secret_key("aws_secret"));
+        assert!(is_secret_key("DB_PASSWORD"));
+        assert!(is_secret_key("PASSWORD"));
+        assert!(is_secret_key("TOKEN"));
+
+        assert!(!is_secret_key("TERM"));
+        assert!(!is_secret_key("KEYBOARD_LAYOUT"));
+        assert!(!is_secret_key("HOME"));
+    }
+
+    #[test]
+    fn test_redact_masks_secret_values_only() {
+        let redactor = Redactor::from_env([
+            ("GITHUB_TOKEN", "ghp_abcdef123456"),
+            ("TERM", "xterm-256color"),
+        ]);
+        let text = "docker run -e GITHUB_TOKEN=ghp_abcdef123456 -e TERM=xterm-256color";
+        assert_eq!(
+            redactor.redact(text),
+            "docker run -e GITHUB_TOKEN=[REDACTED] -e TERM=xterm-256color"
+        );
+    }
+
+    #[test]
+    fn test_short_values_are_not_masked() {
+        let redactor = Redactor::from_env([("USE_KEY", "1")]);
+        assert!(redactor.is_empty());
+        assert_eq!(redactor.redact("exit code 1"), "exit code 1");
+    }
+
+    #[test]
+    fn test_secret_in_formatted_error_is_masked() {
+        let secret = "sk-live-0123456789";
+        let redactor = Redactor::from_env([("OPENAI_API_KEY", secret)]);
+        let err = anyhow::anyhow!("invalid argument \"OPENAI_API_KEY={}\" for \"-e\"", secret);
+        let shown = redactor.redact(&format!("{:#}", err));
+        assert!(!shown.contains(secret));
+        assert!(shown.contains(REDACTED));
+    }
+
+    #[test]
+    fn test_encoded_secret_values_are_masked() {
+        let secret = "pa$s\"w'd\\x";
+        let escape_double = |v: &str| {
+            v.replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('$', "\\$")
+        };
+        let escape_single = |v: &str| v.replace('\'', "'\\''");
+        let redactor = Redactor::from_env([("DB_PASSWORD", secret)])
+            .with_encoding(escape_double)
+            .with_encoding(escape_single);
+
+        let quoted = format!("-e DB_PASSWORD=\"{}\"", escape_double(secret));
+        let wrapped = format!("bash -c '{}'", escape_single(&quoted));
+        for text in [secret.to_string(), quoted, wrapped] {
+            let shown = redactor.redact(&text);
+            // Every form of the secret starts with "pa"
+            assert!(!shown.contains("pa"), "{}", shown);
+            assert!(shown.contains(REDACTED), "{}", shown);
+        }
+    }
+}
@@ -133,6 +133,11 @@ impl HooksConfig {
 /// Path to the repo config file relative to the project root.
 const REPO_CONFIG_PATH: &str = ".aoe/config.toml";
 
+/// Path of the repo config file for `project_path`
+pub fn repo_config_path(project_path: &Path) -> PathBuf {
+    project_path.join(REPO_CONFIG_PATH)
+}
+
 /// Load repo config from `<project_path>/.aoe/config.toml`.
 /// Returns `None` if the file doesn't exist.
 pub fn load_repo_config(project_path: &Path) -> Result<Option<RepoConfig>> {
@@ -654,6 +659,7 @@ pub const INIT_TEMPLATE: &str = r#"# Agent of Empires - Repository Configuration
 
 # [session]
 # default_tool = "claude"
+# default_group = "my-project"
 
 # [sandbox]
 # enabled_by_default = true
@@ -786,6 +792,7 @@ mod tests {
         let repo = RepoConfig {
             session: Some(SessionConfigOverride {
                 default_tool: Some("opencode".to_string()),
+                ..Default::default()
             }),
             ..Default::default()
         };
@@ -809,6 +816,35 @@ mod tests {
         assert_eq!(merged.sandbox.volume_ignores, vec!["node_modules"]);
     }
 
+    #[test]
+    fn test_merge_repo_config_session_defaults_keep_other_overrides() {
+        let config = Config::default();
+        let repo = RepoConfig {
+            session: Some(SessionConfigOverride {
+                default_tool: Some("opencode".to_string()),
+                default_group: Some("backend".to_string()),
+                ..Default::default()
+            }),
+            sandbox: Some(SandboxConfigOverride {
+                default_image: Some("example/repo-image:1".to_string()),
+                environment: Some(vec!["NODE_ENV".to_string()]),
+                ..Default::default()
+            }),
+            worktree: Some(WorktreeConfigOverride {
+                enabled: Some(true),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+
+        let merged = merge_repo_config(config, &repo);
+        assert_eq!(merged.session.default_tool, Some("opencode".to_string()));
+        assert_eq!(merged.session.default_group(), Some("backend"));
+        assert_eq!(merged.sandbox.default_image, "example/repo-image:1");
+        assert_eq!(merged.sandbox.environment, vec!["NODE_ENV"]);
+        assert!(merged.worktree.enabled);
+    }
+
     #[test]
     fn test_merge_repo_config_worktree() {
         let config = Config::default();
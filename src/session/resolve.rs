@@ -0,0 +1,229 @@
+//! Resolving session identifiers and patterns to sessions
+
+use anyhow::{bail, Result};
+
+use super::Instance;
+
+/// Resolve an identifier to a single session. Tries, in order: exact ID, ID prefix,
+/// exact title, and project path. The first tier with any match wins; if that tier
+/// matches more than one session the identifier is ambiguous and an error listing
+/// the candidates is returned.
+pub fn resolve_session<'a>(identifier: &str, instances: &'a [Instance]) -> Result<&'a Instance> {
+    // Exact ID match is always unambiguous
+    if let Some(inst) = instances.iter().find(|i| i.id == identifier) {
+        return Ok(inst);
+    }
+
+    type Matcher = fn(&Instance, &str) -> bool;
+    let tiers: [(&str, Matcher); 3] = [
+        ("ID prefix", |i, ident| i.id.starts_with(ident)),
+        ("title", |i, ident| i.title == ident),
+        ("path", |i, ident| i.project_path == ident),
+    ];
+
+    for (label, matches) in tiers {
+        let found: Vec<&Instance> = instances
+            .iter()
+            .filter(|i| matches(i, identifier))
+            .collect();
+        match found.as_slice() {
+            [] => continue,
+            [inst] => return Ok(inst),
+            _ => {
+                let candidates: Vec<String> = found
+                    .iter()
+                    .map(|i| {
+                        let id =
+                            short_id(&i.id, instances, SHORT_ID_MIN_LEN.max(identifier.len() + 1));
+                        format!("  {}  {} ({})", id, i.title, i.project_path)
+                    })
+                    .collect();
+                bail!(
+                    "Ambiguous {} '{}' matches {} sessions:\n{}\nUse one of the IDs above to disambiguate.",
+                    label,
+                    identifier,
+                    found.len(),
+                    candidates.join("\n")
+                );
+            }
+        }
+    }
+
+    bail!("Session not found: {}", identifier)
+}
+
+/// Like [`resolve_session`], but returns the index so callers can mutate the instance.
+pub fn resolve_session_index(identifier: &str, instances: &[Instance]) -> Result<usize> {
+    let id = &resolve_session(identifier, instances)?.id;
+    Ok(instances
+        .iter()
+        .position(|i| &i.id == id)
+        .expect("resolved session is in the slice"))
+}
+
+/// Whether an identifier should be treated as a glob pattern rather than a literal.
+pub fn is_glob(pattern: &str) -> bool {
+    pattern.contains(['*', '?'])
+}
+
+/// Match `text` against a shell-style glob where `*` matches any run of
+/// characters (including `/`) and `?` matches exactly one.
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+    let regex = regex::escape(pattern)
+        .replace(r"\*", ".*")
+        .replace(r"\?", ".");
+    regex::Regex::new(&format!("^{}$", regex))
+        .map(|re| re.is_match(text))
+        .unwrap_or(false)
+}
+
+/// Whether a session's group path is `group` itself or one of its subgroups.
+pub fn in_group(group_path: &str, group: &str) -> bool {
+    group_path == group || group_path.starts_with(&format!("{}/", group))
+}
+
+/// Shortest length at which session IDs are shown by commands that print them
+pub const SHORT_ID_MIN_LEN: usize = 8;
+
+/// The shortest prefix of `id`, at least `min_len` long, that no other
+/// session's ID starts with, so it resolves back to this session when passed
+/// as an ID prefix.
+pub fn short_id<'a>(id: &'a str, instances: &[Instance], min_len: usize) -> &'a str {
+    let mut len = min_len.min(id.len());
+    while len < id.len()
+        && instances
+            .iter()
+            .any(|i| i.id != id && i.id.starts_with(&id[..len]))
+    {
+        len += 1;
+    }
+    &id[..len]
+}
+
+pub fn truncate_id(id: &str, max_len: usize) -> &str {
+    if id.len() > max_len {
+        &id[..max_len]
+    } else {
+        id
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn instance_with_id(id: &str, title: &str, path: &str) -> Instance {
+        let mut inst = Instance::new(title, path);
+        inst.id = id.to_string();
+        inst
+    }
+
+    #[test]
+    fn test_short_id_extends_until_unambiguous() {
+        let instances = vec![
+            instance_with_id("aaaaaaaa1111", "one", "/tmp/one"),
+            instance_with_id("aaaaaaaa1122", "two", "/tmp/two"),
+            instance_with_id("bbbbbbbb0000", "three", "/tmp/three"),
+        ];
+        assert_eq!(short_id("aaaaaaaa1111", &instances, 8), "aaaaaaaa111");
+        assert_eq!(short_id("aaaaaaaa1122", &instances, 8), "aaaaaaaa112");
+        assert_eq!(short_id("bbbbbbbb0000", &instances, 8), "bbbbbbbb");
+        for inst in &instances {
+            let prefix = short_id(&inst.id, &instances, 8);
+            assert_eq!(resolve_session(prefix, &instances).unwrap().id, inst.id);
+        }
+    }
+
+    #[test]
+    fn test_short_id_falls_back_to_full_id() {
+        let instances = vec![
+            instance_with_id("abc", "one", "/tmp/one"),
+            instance_with_id("abcdef", "two", "/tmp/two"),
+        ];
+        assert_eq!(short_id("abc", &instances, 2), "abc");
+        assert_eq!(short_id("abcdef", &instances, 2), "abcd");
+    }
+
+    #[test]
+    fn test_resolve_session_exact_id_wins_over_prefix() {
+        let instances = vec![
+            instance_with_id("abc", "one", "/tmp/one"),
+            instance_with_id("abcdef", "two", "/tmp/two"),
+        ];
+        let inst = resolve_session("abc", &instances).unwrap();
+        assert_eq!(inst.title, "one");
+    }
+
+    #[test]
+    fn test_resolve_session_unique_prefix() {
+        let instances = vec![
+            instance_with_id("abc123", "one", "/tmp/one"),
+            instance_with_id("def456", "two", "/tmp/two"),
+        ];
+        let inst = resolve_session("de", &instances).unwrap();
+        assert_eq!(inst.title, "two");
+    }
+
+    #[test]
+    fn test_resolve_session_ambiguous_prefix() {
+        let instances = vec![
+            instance_with_id("abc123", "one", "/tmp/one"),
+            instance_with_id("abd456", "two", "/tmp/two"),
+        ];
+        let err = resolve_session("ab", &instances).unwrap_err().to_string();
+        assert!(err.contains("Ambiguous ID prefix"));
+        assert!(err.contains("abc123"));
+        assert!(err.contains("abd456"));
+    }
+
+    #[test]
+    fn test_resolve_session_duplicate_titles() {
+        let instances = vec![
+            instance_with_id("abc123", "api", "/tmp/one"),
+            instance_with_id("def456", "api", "/tmp/two"),
+        ];
+        let err = resolve_session("api", &instances).unwrap_err().to_string();
+        assert!(err.contains("Ambiguous title"));
+        assert!(err.contains("abc123"));
+        assert!(err.contains("def456"));
+    }
+
+    #[test]
+    fn test_resolve_session_not_found() {
+        let instances = vec![instance_with_id("abc123", "one", "/tmp/one")];
+        let err = resolve_session("zzz", &instances).unwrap_err().to_string();
+        assert!(err.contains("Session not found"));
+    }
+
+    #[test]
+    fn test_is_glob() {
+        assert!(is_glob("feat/*"));
+        assert!(is_glob("test-?"));
+        assert!(!is_glob("my-session"));
+    }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("feat/*", "feat/login"));
+        assert!(glob_match("feat/*", "feat/auth/oauth"));
+        assert!(!glob_match("feat/*", "fix/login"));
+        assert!(glob_match("api-?", "api-1"));
+        assert!(!glob_match("api-?", "api-10"));
+        assert!(glob_match("*", "anything"));
+    }
+
+    #[test]
+    fn test_glob_match_treats_regex_chars_literally() {
+        assert!(glob_match("v1.2*", "v1.2-hotfix"));
+        assert!(!glob_match("v1.2*", "v1x2-hotfix"));
+        assert!(glob_match("(wip)*", "(wip) refactor"));
+    }
+
+    #[test]
+    fn test_in_group() {
+        assert!(in_group("work", "work"));
+        assert!(in_group("work/api", "work"));
+        assert!(!in_group("workshop", "work"));
+        assert!(!in_group("", "work"));
+    }
+}
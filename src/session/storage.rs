@@ -43,7 +43,10 @@ impl Storage {
             return Ok(Vec::new());
         }
 
-        let instances: Vec<Instance> = serde_json::from_str(&content)?;
+        let mut instances: Vec<Instance> = serde_json::from_str(&content)?;
+        for instance in &mut instances {
+            instance.profile = self.profile.clone();
+        }
         Ok(instances)
     }
 
@@ -335,6 +338,20 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[serial]
+    fn test_storage_load_sets_instance_profile() -> Result<()> {
+        let temp = tempdir()?;
+        setup_test_home(temp.path());
+
+        let storage = Storage::new("work")?;
+        storage.save(&[Instance::new("test", "/tmp/test")])?;
+
+        let loaded = storage.load()?;
+        assert_eq!(loaded[0].profile, "work");
+        Ok(())
+    }
+
     #[test]
     #[serial]
     fn test_storage_groups_file_empty() -> Result<()> {
@@ -385,7 +385,7 @@ pub fn play_for_transition(old: Status, new: Status, config: &SoundConfig) {
         Status::Running => config.on_running.as_deref(),
         Status::Waiting => config.on_waiting.as_deref(),
         Status::Idle => config.on_idle.as_deref(),
-        Status::Error => config.on_error.as_deref(),
+        Status::Error | Status::Exited => config.on_error.as_deref(),
         Status::Deleting => return, // No sound for deletion
     };
 
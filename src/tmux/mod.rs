@@ -10,7 +10,8 @@ pub use session::Session;
 pub use status_bar::{get_session_info_for_current, get_status_for_current_session};
 pub use status_detection::{
     detect_claude_status, detect_codex_status, detect_gemini_status, detect_opencode_status,
-    detect_vibe_status,
+    detect_pane_status, detect_status_from_content, detect_vibe_status, PaneCapture,
+    StatusPatterns, STATUS_CAPTURE_LINES,
 };
 pub use terminal_session::{ContainerTerminalSession, TerminalSession};
 
@@ -19,6 +20,8 @@ use std::process::Command;
 use std::sync::RwLock;
 use std::time::{Duration, Instant};
 
+use crate::process::{run_with_timeout, PROBE_TIMEOUT};
+
 pub const SESSION_PREFIX: &str = "aoe_";
 pub const TERMINAL_PREFIX: &str = "aoe_term_";
 pub const CONTAINER_TERMINAL_PREFIX: &str = "aoe_cterm_";
@@ -30,46 +33,75 @@ static SESSION_CACHE: RwLock<SessionCache> = RwLock::new(SessionCache {
 
 struct SessionCache {
     data: Option<HashMap<String, i64>>,
+    /// When the `list-sessions` call behind `data` was started
     time: Option<Instant>,
 }
 
+/// How long a `list-sessions` snapshot answers existence checks
+const SESSION_CACHE_TTL: Duration = Duration::from_secs(2);
+
+/// Re-list tmux sessions into the shared cache. Creating, killing or renaming
+/// a session through aoe calls this, so the cache never lags aoe's own changes.
+///
+/// Activity is the window's: `session_activity` only moves on client input,
+/// while `window_activity` also moves when the pane prints output. aoe
+/// sessions have a single window.
 pub fn refresh_session_cache() {
-    let output = Command::new("tmux")
-        .args([
-            "list-sessions",
-            "-F",
-            "#{session_name}\t#{session_activity}",
-        ])
-        .output();
+    let started = Instant::now();
+    let output = run_with_timeout(
+        Command::new("tmux").args(["list-sessions", "-F", "#{session_name}\t#{window_activity}"]),
+        PROBE_TIMEOUT,
+    );
 
     let new_data = match output {
         Ok(out) if out.status.success() => {
-            let stdout = String::from_utf8_lossy(&out.stdout);
-            let mut map = HashMap::new();
-            for line in stdout.lines() {
-                if let Some((name, activity)) = line.split_once('\t') {
-                    let activity: i64 = activity.parse().unwrap_or(0);
-                    map.insert(name.to_string(), activity);
-                }
-            }
-            Some(map)
+            Some(parse_session_list(&String::from_utf8_lossy(&out.stdout)))
+        }
+        // With no server running there are simply no sessions
+        Ok(out) if is_no_server_error(&String::from_utf8_lossy(&out.stderr)) => {
+            Some(HashMap::new())
         }
         _ => None,
     };
 
-    if let Ok(mut cache) = SESSION_CACHE.write() {
-        cache.data = new_data;
-        cache.time = Some(Instant::now());
+    store_listing(&SESSION_CACHE, started, new_data);
+}
+
+/// Store a listing started at `started`, unless the cache already holds one
+/// started later. The poller and UI threads both refresh the cache, and a
+/// slow listing that began before a session was created must not hide it.
+fn store_listing(
+    cache: &RwLock<SessionCache>,
+    started: Instant,
+    data: Option<HashMap<String, i64>>,
+) {
+    if let Ok(mut cache) = cache.write() {
+        if cache.time.is_some_and(|time| time > started) {
+            return;
+        }
+        cache.data = data;
+        cache.time = Some(started);
     }
 }
 
+fn parse_session_list(stdout: &str) -> HashMap<String, i64> {
+    stdout
+        .lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(name, activity)| (name.to_string(), activity.parse().unwrap_or(0)))
+        .collect()
+}
+
+fn is_no_server_error(stderr: &str) -> bool {
+    stderr.contains("no server running") || stderr.contains("error connecting to")
+}
+
 pub fn session_exists_from_cache(name: &str) -> Option<bool> {
     let cache = SESSION_CACHE.read().ok()?;
 
-    // Cache valid for 2 seconds
     if cache
         .time
-        .map(|t| t.elapsed() > Duration::from_secs(2))
+        .map(|t| t.elapsed() > SESSION_CACHE_TTL)
         .unwrap_or(true)
     {
         return None;
@@ -78,11 +110,139 @@ pub fn session_exists_from_cache(name: &str) -> Option<bool> {
     cache.data.as_ref().map(|m| m.contains_key(name))
 }
 
+/// Whether a tmux session named `name` exists. Answers from the shared cache,
+/// refreshing it once when stale so that checking many sessions in one poll or
+/// render costs a single `list-sessions`. Falls back to `has-session` only if
+/// tmux could not be listed.
+pub(crate) fn session_exists(name: &str) -> bool {
+    if let Some(exists) = session_exists_from_cache(name) {
+        return exists;
+    }
+
+    refresh_session_cache();
+    if let Some(exists) = session_exists_from_cache(name) {
+        return exists;
+    }
+
+    run_with_timeout(
+        Command::new("tmux").args(["has-session", "-t", name]),
+        PROBE_TIMEOUT,
+    )
+    .map(|o| o.status.success())
+    .unwrap_or(false)
+}
+
+/// Rename the tmux session `name` to `new_name`. A session that doesn't
+/// exist is left alone.
+pub(crate) fn rename_session(name: &str, new_name: &str) -> anyhow::Result<()> {
+    if !session_exists(name) {
+        tracing::debug!("No tmux session {} to rename", name);
+        return Ok(());
+    }
+
+    let output = run_with_timeout(
+        Command::new("tmux").args(["rename-session", "-t", name, new_name]),
+        crate::process::COMMAND_TIMEOUT,
+    )?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        anyhow::bail!("Failed to rename tmux session: {}", stderr);
+    }
+
+    refresh_session_cache();
+
+    Ok(())
+}
+
+/// Last output or input time (unix seconds) tmux reported for a session's
+/// window, if the cache is fresh and the session exists
+pub fn session_activity_from_cache(name: &str) -> Option<i64> {
+    let cache = SESSION_CACHE.read().ok()?;
+
+    if cache
+        .time
+        .map(|t| t.elapsed() > SESSION_CACHE_TTL)
+        .unwrap_or(true)
+    {
+        return None;
+    }
+
+    cache.data.as_ref()?.get(name).copied()
+}
+
+/// Whether any tmux client is attached to the session `name`. Errors (e.g. the
+/// session is gone) count as no clients.
+pub(crate) fn session_has_clients(name: &str) -> bool {
+    run_with_timeout(
+        Command::new("tmux").args(["list-clients", "-t", name, "-F", "#{client_name}"]),
+        PROBE_TIMEOUT,
+    )
+    .is_ok_and(|out| out.status.success() && out.stdout.iter().any(|b| !b.is_ascii_whitespace()))
+}
+
+/// When the session was created, in unix seconds
+pub(crate) fn session_created(name: &str) -> Option<i64> {
+    let output = run_with_timeout(
+        Command::new("tmux").args(["display-message", "-p", "-t", name, "#{session_created}"]),
+        PROBE_TIMEOUT,
+    )
+    .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout).trim().parse().ok()
+}
+
+/// Put `text` in the tmux paste buffer. tmux 3.2 and newer also pass it on to
+/// the system clipboard when its `set-clipboard` option allows.
+pub fn set_buffer(text: &str) -> anyhow::Result<()> {
+    let output = run_with_timeout(
+        Command::new("tmux").args(["set-buffer", "-w", "--", text]),
+        PROBE_TIMEOUT,
+    )?;
+    if output.status.success() {
+        return Ok(());
+    }
+
+    // Older tmux has no -w flag
+    let output = run_with_timeout(
+        Command::new("tmux").args(["set-buffer", "--", text]),
+        PROBE_TIMEOUT,
+    )?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "tmux set-buffer failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+/// Names of every running tmux session aoe created (agent, terminal and
+/// container terminal sessions, across all profiles)
+pub fn list_aoe_sessions() -> Vec<String> {
+    let output = run_with_timeout(
+        Command::new("tmux").args(["list-sessions", "-F", "#{session_name}"]),
+        PROBE_TIMEOUT,
+    );
+
+    match output {
+        Ok(out) if out.status.success() => String::from_utf8_lossy(&out.stdout)
+            .lines()
+            .filter(|name| name.starts_with(SESSION_PREFIX))
+            .map(str::to_string)
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
 pub fn get_current_session_name() -> Option<String> {
-    let output = Command::new("tmux")
-        .args(["display-message", "-p", "#{session_name}"])
-        .output()
-        .ok()?;
+    let output = run_with_timeout(
+        Command::new("tmux").args(["display-message", "-p", "#{session_name}"]),
+        PROBE_TIMEOUT,
+    )
+    .ok()?;
 
     if output.status.success() {
         let name = String::from_utf8_lossy(&output.stdout).trim().to_string();
@@ -94,7 +254,7 @@ pub fn get_current_session_name() -> Option<String> {
 }
 
 pub fn is_tmux_available() -> bool {
-    Command::new("tmux").arg("-V").output().is_ok()
+    run_with_timeout(Command::new("tmux").arg("-V"), PROBE_TIMEOUT).is_ok()
 }
 
 pub fn is_claude_available() -> bool {
@@ -133,6 +293,19 @@ pub fn is_gemini_available() -> bool {
         .unwrap_or(false)
 }
 
+/// Whether a supported tool's binary can be launched from PATH, or None for a
+/// tool this doesn't know how to look up
+pub fn is_tool_available(tool: &str) -> Option<bool> {
+    match tool {
+        "claude" => Some(is_claude_available()),
+        "opencode" => Some(is_opencode_available()),
+        "vibe" => Some(is_vibe_available()),
+        "codex" => Some(is_codex_available()),
+        "gemini" => Some(is_gemini_available()),
+        _ => None,
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AvailableTools {
     pub claude: bool,
@@ -177,3 +350,59 @@ impl AvailableTools {
         tools
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_older_listing_does_not_replace_newer() {
+        let cache = RwLock::new(SessionCache {
+            data: None,
+            time: None,
+        });
+        let older = Instant::now();
+        let newer = older + Duration::from_millis(10);
+        let listing = |name: &str| Some(HashMap::from([(name.to_string(), 0)]));
+
+        store_listing(&cache, newer, listing("aoe_new_abcd1234"));
+        store_listing(&cache, older, listing("aoe_old_abcd1234"));
+        let stored = cache.read().unwrap();
+        assert!(stored
+            .data
+            .as_ref()
+            .unwrap()
+            .contains_key("aoe_new_abcd1234"));
+        assert_eq!(stored.time, Some(newer));
+        drop(stored);
+
+        let newest = newer + Duration::from_millis(10);
+        store_listing(&cache, newest, listing("aoe_newest_abcd1234"));
+        assert!(cache
+            .read()
+            .unwrap()
+            .data
+            .as_ref()
+            .unwrap()
+            .contains_key("aoe_newest_abcd1234"));
+    }
+
+    #[test]
+    fn test_parse_session_list() {
+        let sessions = parse_session_list("aoe_one_abcd1234\t1700000000\nother\tnot-a-number\n\n");
+        assert_eq!(sessions.len(), 2);
+        assert_eq!(sessions["aoe_one_abcd1234"], 1700000000);
+        assert_eq!(sessions["other"], 0);
+    }
+
+    #[test]
+    fn test_no_server_means_no_sessions() {
+        assert!(is_no_server_error(
+            "no server running on /tmp/tmux-1000/default\n"
+        ));
+        assert!(is_no_server_error(
+            "error connecting to /tmp/tmux-1000/default (No such file or directory)\n"
+        ));
+        assert!(!is_no_server_error("unknown option -- z\n"));
+    }
+}
@@ -3,10 +3,15 @@
 use anyhow::{bail, Result};
 use std::process::Command;
 
-use super::{refresh_session_cache, session_exists_from_cache, SESSION_PREFIX};
-use crate::cli::truncate_id;
+use super::status_detection::PaneCapture;
+use super::utils::create_with_history_limit;
+use super::{
+    refresh_session_cache, rename_session, session_activity_from_cache, session_exists,
+    session_has_clients, SESSION_PREFIX,
+};
 use crate::process;
-use crate::session::Status;
+use crate::process::{run_with_timeout, COMMAND_TIMEOUT, PROBE_TIMEOUT};
+use crate::session::{truncate_id, Status};
 
 pub struct Session {
     name: String,
@@ -19,25 +24,39 @@ impl Session {
         })
     }
 
+    /// Wrap an existing tmux session by its full name
+    pub fn from_name(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+        }
+    }
+
     pub fn generate_name(id: &str, title: &str) -> String {
         let safe_title = sanitize_session_name(title);
         format!("{}{}_{}", SESSION_PREFIX, safe_title, truncate_id(id, 8))
     }
 
     pub fn exists(&self) -> bool {
-        if let Some(exists) = session_exists_from_cache(&self.name) {
-            return exists;
-        }
+        session_exists(&self.name)
+    }
+
+    /// Last output time in unix seconds, from the session cache only
+    pub fn activity(&self) -> Option<i64> {
+        session_activity_from_cache(&self.name)
+    }
+
+    /// Whether a tmux client is currently viewing this session
+    pub fn has_clients(&self) -> bool {
+        session_has_clients(&self.name)
+    }
 
-        Command::new("tmux")
-            .args(["has-session", "-t", &self.name])
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
+    /// When the session (and so the agent in it) was started, in unix seconds
+    pub fn created(&self) -> Option<i64> {
+        super::session_created(&self.name)
     }
 
     pub fn create(&self, working_dir: &str, command: Option<&str>) -> Result<()> {
-        self.create_with_size(working_dir, command, None)
+        self.create_with_size(working_dir, command, None, None)
     }
 
     pub fn create_with_size(
@@ -45,13 +64,14 @@ impl Session {
         working_dir: &str,
         command: Option<&str>,
         size: Option<(u16, u16)>,
+        history_limit: Option<u64>,
     ) -> Result<()> {
         if self.exists() {
             return Ok(());
         }
 
         let args = build_create_args(&self.name, working_dir, command, size);
-        let output = Command::new("tmux").args(&args).output()?;
+        let output = create_with_history_limit(&self.name, &args, history_limit)?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -75,9 +95,10 @@ impl Session {
             process::kill_process_tree(pane_pid);
         }
 
-        let output = Command::new("tmux")
-            .args(["kill-session", "-t", &self.name])
-            .output()?;
+        let output = run_with_timeout(
+            Command::new("tmux").args(["kill-session", "-t", &self.name]),
+            COMMAND_TIMEOUT,
+        )?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -90,20 +111,7 @@ impl Session {
     }
 
     pub fn rename(&self, new_name: &str) -> Result<()> {
-        if !self.exists() {
-            return Ok(());
-        }
-
-        let output = Command::new("tmux")
-            .args(["rename-session", "-t", &self.name, new_name])
-            .output()?;
-
-        if !output.status.success() {
-            let stderr = String::from_utf8_lossy(&output.stderr);
-            bail!("Failed to rename tmux session: {}", stderr);
-        }
-
-        Ok(())
+        rename_session(&self.name, new_name)
     }
 
     pub fn attach(&self) -> Result<()> {
@@ -142,33 +150,50 @@ impl Session {
         Ok(())
     }
 
+    /// Capture plain pane text, used for status detection.
     pub fn capture_pane(&self, lines: usize) -> Result<String> {
-        self.capture_pane_with_size(lines, None, None)
+        self.capture(lines, false, false)
     }
 
+    /// Capture pane text including SGR escape sequences, for rendering in the preview.
+    /// With `join_wrapped`, lines tmux soft-wrapped at the pane width are joined back
+    /// together so the caller can re-wrap them at its own width.
     pub fn capture_pane_with_size(
         &self,
         lines: usize,
         _width: Option<u16>,
         _height: Option<u16>,
+        join_wrapped: bool,
     ) -> Result<String> {
+        self.capture(lines, true, join_wrapped)
+    }
+
+    fn capture(&self, lines: usize, escapes: bool, join_wrapped: bool) -> Result<String> {
         if !self.exists() {
             return Ok(String::new());
         }
 
-        let output = Command::new("tmux")
-            .args([
-                "capture-pane",
-                "-t",
-                &self.name,
-                "-p",
-                "-S",
-                &format!("-{}", lines),
-            ])
-            .output()?;
+        let start = format!("-{}", lines);
+        let mut args = vec!["capture-pane", "-t", &self.name, "-p", "-S", &start];
+        if escapes {
+            args.push("-e");
+        }
+        if join_wrapped {
+            args.push("-J");
+        }
+        let output = run_with_timeout(Command::new("tmux").args(&args), PROBE_TIMEOUT)?;
 
         if output.status.success() {
-            Ok(String::from_utf8_lossy(&output.stdout).to_string())
+            let text = String::from_utf8_lossy(&output.stdout);
+            if join_wrapped {
+                // -J preserves trailing spaces, which would wrap into blank rows
+                return Ok(text
+                    .lines()
+                    .map(str::trim_end)
+                    .collect::<Vec<_>>()
+                    .join("\n"));
+            }
+            Ok(text.to_string())
         } else {
             Ok(String::new())
         }
@@ -183,12 +208,26 @@ impl Session {
         process::get_foreground_pid(pane_pid).or(Some(pane_pid))
     }
 
-    pub fn detect_status(&self, tool: &str) -> Result<Status> {
-        let content = self.capture_pane(50)?;
-        let fg_pid = self.get_foreground_pid();
-        Ok(super::status_detection::detect_status_from_content(
-            &content, tool, fg_pid,
-        ))
+    /// Name of the process in the pane's foreground (e.g. `claude`, `zsh`)
+    pub fn foreground_command(&self) -> Option<String> {
+        process::get_process_name(self.get_foreground_pid()?)
+    }
+
+    /// Detect the status of `tool` in this pane, using the status patterns
+    /// configured for `profile`
+    pub fn detect_status(&self, tool: &str, profile: &str) -> Result<Status> {
+        let patterns = super::status_detection::configured_patterns(profile, tool);
+        super::status_detection::detect_pane_status(self, tool, patterns.as_deref())
+    }
+}
+
+impl PaneCapture for Session {
+    fn capture_pane(&self, lines: usize) -> Result<String> {
+        Session::capture_pane(self, lines)
+    }
+
+    fn foreground_pid(&self) -> Option<u32> {
+        self.get_foreground_pid()
     }
 }
 
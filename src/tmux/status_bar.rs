@@ -3,6 +3,8 @@
 use anyhow::Result;
 use std::process::Command;
 
+use crate::process::{run_with_timeout, COMMAND_TIMEOUT, PROBE_TIMEOUT};
+
 /// Information about a sandboxed session for status bar display.
 pub struct SandboxDisplay {
     pub container_name: String,
@@ -64,9 +66,10 @@ pub fn apply_status_bar(
 
 /// Set a tmux option for a specific session.
 fn set_session_option(session_name: &str, option: &str, value: &str) -> Result<()> {
-    let output = Command::new("tmux")
-        .args(["set-option", "-t", session_name, option, value])
-        .output()?;
+    let output = run_with_timeout(
+        Command::new("tmux").args(["set-option", "-t", session_name, option, value]),
+        COMMAND_TIMEOUT,
+    )?;
 
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
@@ -171,10 +174,11 @@ pub fn get_status_for_current_session() -> Option<String> {
 
 /// Get a tmux option value for a session.
 fn get_session_option(session_name: &str, option: &str) -> Option<String> {
-    let output = Command::new("tmux")
-        .args(["show-options", "-t", session_name, "-v", option])
-        .output()
-        .ok()?;
+    let output = run_with_timeout(
+        Command::new("tmux").args(["show-options", "-t", session_name, "-v", option]),
+        PROBE_TIMEOUT,
+    )
+    .ok()?;
 
     if output.status.success() {
         let value = String::from_utf8_lossy(&output.stdout).trim().to_string();
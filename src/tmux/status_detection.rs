@@ -1,11 +1,140 @@
 //! Status detection for agent sessions
 
-use crate::session::Status;
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Result};
+use regex::{Regex, RegexBuilder};
+
+use crate::session::{resolve_config, Config, Status, StatusPatternsConfig};
 
 use super::utils::strip_ansi;
 
 const SPINNER_CHARS: &[&str] = &["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
 
+/// Lines of scrollback captured for status detection
+pub const STATUS_CAPTURE_LINES: usize = 50;
+
+/// Where status detection reads pane content from. [`super::Session`] captures
+/// from a live tmux server; tests can supply canned buffers instead.
+pub trait PaneCapture {
+    fn capture_pane(&self, lines: usize) -> Result<String>;
+    fn foreground_pid(&self) -> Option<u32>;
+}
+
+/// Capture the recent pane content and map it to a status for `tool`, trying
+/// `patterns` before the built-in heuristics
+pub fn detect_pane_status(
+    pane: &impl PaneCapture,
+    tool: &str,
+    patterns: Option<&StatusPatterns>,
+) -> Result<Status> {
+    let content = pane.capture_pane(STATUS_CAPTURE_LINES)?;
+    Ok(patterns
+        .and_then(|p| p.classify(&content))
+        .unwrap_or_else(|| detect_status_from_content(&content, tool, pane.foreground_pid())))
+}
+
+/// Compiled `[status_patterns.<tool>]` regexes
+#[derive(Debug, Default)]
+pub struct StatusPatterns {
+    waiting: Vec<Regex>,
+    running: Vec<Regex>,
+}
+
+impl StatusPatterns {
+    pub fn compile(config: &StatusPatternsConfig) -> Result<Self> {
+        let compile_all = |patterns: &[String]| {
+            patterns
+                .iter()
+                .map(|p| {
+                    RegexBuilder::new(p)
+                        .multi_line(true)
+                        .build()
+                        .map_err(|e| anyhow!("Invalid status pattern '{}': {}", p, e))
+                })
+                .collect::<Result<Vec<_>>>()
+        };
+
+        Ok(Self {
+            waiting: compile_all(&config.waiting)?,
+            running: compile_all(&config.running)?,
+        })
+    }
+
+    /// Status named by the first matching pattern, running before waiting like
+    /// the built-ins, or `None` to fall back to the built-ins
+    pub fn classify(&self, content: &str) -> Option<Status> {
+        let recent: Vec<String> = content
+            .lines()
+            .filter(|l| !l.trim().is_empty())
+            .map(strip_ansi)
+            .collect();
+        let recent = recent[recent.len().saturating_sub(30)..].join("\n");
+
+        if self.running.iter().any(|re| re.is_match(&recent)) {
+            Some(Status::Running)
+        } else if self.waiting.iter().any(|re| re.is_match(&recent)) {
+            Some(Status::Waiting)
+        } else {
+            None
+        }
+    }
+}
+
+/// How long compiled patterns are reused before the config is re-read, so
+/// edits apply without restarting aoe
+const PATTERN_CACHE_TTL: Duration = Duration::from_secs(5);
+
+type PatternMap = HashMap<String, Arc<StatusPatterns>>;
+
+/// Compiled patterns per profile, with the time they were loaded
+type ProfilePatterns = HashMap<String, (Instant, Arc<PatternMap>)>;
+
+static PATTERN_CACHE: RwLock<Option<ProfilePatterns>> = RwLock::new(None);
+
+/// Patterns configured for `tool` in `profile`'s resolved config, if any.
+/// Tools with an invalid pattern are skipped with a warning and use the
+/// built-ins.
+pub fn configured_patterns(profile: &str, tool: &str) -> Option<Arc<StatusPatterns>> {
+    let cached = PATTERN_CACHE
+        .read()
+        .ok()
+        .and_then(|c| c.as_ref().and_then(|c| c.get(profile).cloned()))
+        .filter(|(t, _)| t.elapsed() < PATTERN_CACHE_TTL)
+        .map(|(_, m)| m);
+
+    let map = cached.unwrap_or_else(|| {
+        let map = Arc::new(load_configured_patterns(profile));
+        if let Ok(mut cache) = PATTERN_CACHE.write() {
+            cache
+                .get_or_insert_with(HashMap::new)
+                .insert(profile.to_string(), (Instant::now(), map.clone()));
+        }
+        map
+    });
+
+    map.get(tool).cloned()
+}
+
+fn load_configured_patterns(profile: &str) -> PatternMap {
+    let config = resolve_config(profile)
+        .or_else(|_| Config::load())
+        .unwrap_or_default();
+    config
+        .status_patterns
+        .iter()
+        .filter_map(|(tool, patterns)| match StatusPatterns::compile(patterns) {
+            Ok(compiled) => Some((tool.clone(), Arc::new(compiled))),
+            Err(e) => {
+                tracing::warn!("Ignoring status_patterns.{}: {}", tool, e);
+                None
+            }
+        })
+        .collect()
+}
+
 pub fn detect_status_from_content(content: &str, tool: &str, _fg_pid: Option<u32>) -> Status {
     let content_lower = content.to_lowercase();
 
@@ -737,4 +866,71 @@ mod tests {
         assert_eq!(detect_gemini_status("file saved"), Status::Idle);
         assert_eq!(detect_gemini_status("random output text"), Status::Idle);
     }
+
+    fn patterns(waiting: &[&str], running: &[&str]) -> StatusPatterns {
+        StatusPatterns::compile(&StatusPatternsConfig {
+            waiting: waiting.iter().map(|s| s.to_string()).collect(),
+            running: running.iter().map(|s| s.to_string()).collect(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_status_patterns_classify() {
+        let p = patterns(&[r"^\? Pick one"], &["Crunching"]);
+        assert_eq!(p.classify("output\nCrunching data"), Some(Status::Running));
+        assert_eq!(p.classify("output\n? Pick one\n"), Some(Status::Waiting));
+        // `^` anchors at line starts, not just the start of the buffer
+        assert_eq!(p.classify("text ? Pick one"), None);
+        assert_eq!(p.classify("plain output"), None);
+    }
+
+    #[test]
+    fn test_status_patterns_running_wins() {
+        let p = patterns(&["Continue"], &["Crunching"]);
+        assert_eq!(p.classify("Continue\nCrunching"), Some(Status::Running));
+    }
+
+    #[test]
+    fn test_status_patterns_ignore_old_scrollback_and_ansi() {
+        let p = patterns(&["^READY$"], &[]);
+        let mut content = String::from("READY\n");
+        content.push_str(&"line\n".repeat(30));
+        assert_eq!(p.classify(&content), None);
+        assert_eq!(p.classify("\x1b[32mREADY\x1b[0m"), Some(Status::Waiting));
+    }
+
+    #[test]
+    fn test_status_patterns_reject_invalid_regex() {
+        let err = StatusPatterns::compile(&StatusPatternsConfig {
+            waiting: vec!["(unclosed".to_string()],
+            running: Vec::new(),
+        })
+        .unwrap_err();
+        assert!(err.to_string().contains("(unclosed"));
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_configured_patterns_follow_profile() {
+        let home = tempfile::TempDir::new().unwrap();
+        std::env::set_var("HOME", home.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", home.path().join(".config"));
+
+        let patterns = StatusPatternsConfig {
+            waiting: vec![r"^Approve\?".to_string()],
+            running: Vec::new(),
+        };
+        let work = crate::session::ProfileConfig {
+            status_patterns: Some(HashMap::from([("codex".to_string(), patterns)])),
+            ..Default::default()
+        };
+        crate::session::save_profile_config("patterns-work", &work).unwrap();
+        crate::session::save_profile_config("patterns-home", &Default::default()).unwrap();
+
+        let work = configured_patterns("patterns-work", "codex").unwrap();
+        assert_eq!(work.classify("Approve?"), Some(Status::Waiting));
+        assert!(configured_patterns("patterns-home", "codex").is_none());
+    }
 }
@@ -3,12 +3,14 @@
 use anyhow::{bail, Result};
 use std::process::Command;
 
-use super::utils::sanitize_session_name;
+use super::utils::{create_with_history_limit, sanitize_session_name};
 use super::{
-    refresh_session_cache, session_exists_from_cache, CONTAINER_TERMINAL_PREFIX, TERMINAL_PREFIX,
+    refresh_session_cache, rename_session, session_activity_from_cache, session_exists,
+    session_has_clients, CONTAINER_TERMINAL_PREFIX, TERMINAL_PREFIX,
 };
-use crate::cli::truncate_id;
 use crate::process;
+use crate::process::{run_with_timeout, COMMAND_TIMEOUT, PROBE_TIMEOUT};
+use crate::session::truncate_id;
 
 pub struct TerminalSession {
     name: String,
@@ -26,20 +28,17 @@ impl TerminalSession {
         format!("{}{}_{}", TERMINAL_PREFIX, safe_title, truncate_id(id, 8))
     }
 
-    pub fn exists(&self) -> bool {
-        if let Some(exists) = session_exists_from_cache(&self.name) {
-            return exists;
-        }
+    /// Last output time in unix seconds, from the session cache only
+    pub fn activity(&self) -> Option<i64> {
+        session_activity_from_cache(&self.name)
+    }
 
-        Command::new("tmux")
-            .args(["has-session", "-t", &self.name])
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
+    pub fn exists(&self) -> bool {
+        session_exists(&self.name)
     }
 
     pub fn create(&self, working_dir: &str) -> Result<()> {
-        self.create_with_size(working_dir, None, None)
+        self.create_with_size(working_dir, None, None, None)
     }
 
     pub fn create_with_size(
@@ -47,13 +46,14 @@ impl TerminalSession {
         working_dir: &str,
         command: Option<&str>,
         size: Option<(u16, u16)>,
+        history_limit: Option<u64>,
     ) -> Result<()> {
         if self.exists() {
             return Ok(());
         }
 
         let args = build_terminal_create_args(&self.name, working_dir, command, size);
-        let output = Command::new("tmux").args(&args).output()?;
+        let output = create_with_history_limit(&self.name, &args, history_limit)?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -75,9 +75,10 @@ impl TerminalSession {
             process::kill_process_tree(pane_pid);
         }
 
-        let output = Command::new("tmux")
-            .args(["kill-session", "-t", &self.name])
-            .output()?;
+        let output = run_with_timeout(
+            Command::new("tmux").args(["kill-session", "-t", &self.name]),
+            COMMAND_TIMEOUT,
+        )?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -89,6 +90,10 @@ impl TerminalSession {
         Ok(())
     }
 
+    pub fn rename(&self, new_name: &str) -> Result<()> {
+        rename_session(&self.name, new_name)
+    }
+
     pub fn get_pane_pid(&self) -> Option<u32> {
         process::get_pane_pid(&self.name)
     }
@@ -130,16 +135,18 @@ impl TerminalSession {
             return Ok(String::new());
         }
 
-        let output = Command::new("tmux")
-            .args([
+        let output = run_with_timeout(
+            Command::new("tmux").args([
                 "capture-pane",
                 "-t",
                 &self.name,
                 "-p",
+                "-e",
                 "-S",
                 &format!("-{}", lines),
-            ])
-            .output()?;
+            ]),
+            PROBE_TIMEOUT,
+        )?;
 
         if output.status.success() {
             Ok(String::from_utf8_lossy(&output.stdout).to_string())
@@ -172,16 +179,18 @@ impl ContainerTerminalSession {
         )
     }
 
+    /// Last output time in unix seconds, from the session cache only
+    pub fn activity(&self) -> Option<i64> {
+        session_activity_from_cache(&self.name)
+    }
+
     pub fn exists(&self) -> bool {
-        if let Some(exists) = session_exists_from_cache(&self.name) {
-            return exists;
-        }
+        session_exists(&self.name)
+    }
 
-        Command::new("tmux")
-            .args(["has-session", "-t", &self.name])
-            .output()
-            .map(|o| o.status.success())
-            .unwrap_or(false)
+    /// Whether a tmux client is currently viewing this session
+    pub fn has_clients(&self) -> bool {
+        session_has_clients(&self.name)
     }
 
     pub fn create_with_size(
@@ -189,13 +198,14 @@ impl ContainerTerminalSession {
         working_dir: &str,
         command: Option<&str>,
         size: Option<(u16, u16)>,
+        history_limit: Option<u64>,
     ) -> Result<()> {
         if self.exists() {
             return Ok(());
         }
 
         let args = build_terminal_create_args(&self.name, working_dir, command, size);
-        let output = Command::new("tmux").args(&args).output()?;
+        let output = create_with_history_limit(&self.name, &args, history_limit)?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -217,9 +227,10 @@ impl ContainerTerminalSession {
             process::kill_process_tree(pane_pid);
         }
 
-        let output = Command::new("tmux")
-            .args(["kill-session", "-t", &self.name])
-            .output()?;
+        let output = run_with_timeout(
+            Command::new("tmux").args(["kill-session", "-t", &self.name]),
+            COMMAND_TIMEOUT,
+        )?;
 
         if !output.status.success() {
             let stderr = String::from_utf8_lossy(&output.stderr);
@@ -231,6 +242,10 @@ impl ContainerTerminalSession {
         Ok(())
     }
 
+    pub fn rename(&self, new_name: &str) -> Result<()> {
+        rename_session(&self.name, new_name)
+    }
+
     pub fn get_pane_pid(&self) -> Option<u32> {
         process::get_pane_pid(&self.name)
     }
@@ -272,16 +287,18 @@ impl ContainerTerminalSession {
             return Ok(String::new());
         }
 
-        let output = Command::new("tmux")
-            .args([
+        let output = run_with_timeout(
+            Command::new("tmux").args([
                 "capture-pane",
                 "-t",
                 &self.name,
                 "-p",
+                "-e",
                 "-S",
                 &format!("-{}", lines),
-            ])
-            .output()?;
+            ]),
+            PROBE_TIMEOUT,
+        )?;
 
         if output.status.success() {
             Ok(String::from_utf8_lossy(&output.stdout).to_string())
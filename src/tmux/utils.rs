@@ -1,5 +1,9 @@
 //! tmux utility functions
 
+use std::process::{Command, Output};
+
+use crate::process::{run_with_timeout, COMMAND_TIMEOUT, PROBE_TIMEOUT};
+
 pub fn strip_ansi(content: &str) -> String {
     let mut result = content.to_string();
 
@@ -23,6 +27,60 @@ pub fn strip_ansi(content: &str) -> String {
     result
 }
 
+/// Run a `new-session` argument list so the new session's first pane keeps
+/// `limit` lines of scrollback. tmux sizes a pane's history from the global
+/// option when the pane is created, so the global value is raised for
+/// `new-session` and put back by a separate tmux call that runs even when
+/// creating the session fails. The session option is also set so panes split
+/// later get the same limit.
+pub fn create_with_history_limit(
+    session_name: &str,
+    create_args: &[String],
+    limit: Option<u64>,
+) -> std::io::Result<Output> {
+    let Some(limit) = limit else {
+        return run_with_timeout(Command::new("tmux").args(create_args), COMMAND_TIMEOUT);
+    };
+    let limit = limit.to_string();
+
+    // start-server loads the user's config when no server is running yet
+    let saved = run_with_timeout(
+        Command::new("tmux").args(["start-server", ";", "show-options", "-gv", "history-limit"]),
+        PROBE_TIMEOUT,
+    )
+    .ok()
+    .filter(|o| o.status.success())
+    .map(|o| String::from_utf8_lossy(&o.stdout).trim().to_string())
+    .filter(|v| !v.is_empty());
+
+    let output = run_with_timeout(
+        Command::new("tmux")
+            .args(["set-option", "-g", "history-limit", &limit, ";"])
+            .args(create_args)
+            .args([
+                ";",
+                "set-option",
+                "-t",
+                session_name,
+                "history-limit",
+                &limit,
+            ]),
+        COMMAND_TIMEOUT,
+    );
+
+    if let Some(saved) = saved {
+        let restored = run_with_timeout(
+            Command::new("tmux").args(["set-option", "-g", "history-limit", &saved]),
+            PROBE_TIMEOUT,
+        );
+        if !restored.is_ok_and(|o| o.status.success()) {
+            tracing::warn!("Failed to restore tmux history-limit to {}", saved);
+        }
+    }
+
+    output
+}
+
 pub fn sanitize_session_name(name: &str) -> String {
     name.chars()
         .map(|c| {
@@ -40,6 +98,49 @@ pub fn sanitize_session_name(name: &str) -> String {
 mod tests {
     use super::*;
 
+    fn global_history_limit() -> String {
+        let output = Command::new("tmux")
+            .args(["show-options", "-gv", "history-limit"])
+            .output()
+            .unwrap();
+        String::from_utf8_lossy(&output.stdout).trim().to_string()
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_history_limit_restored_when_create_fails() {
+        if !crate::tmux::is_tmux_available() {
+            eprintln!("Skipping test: tmux not available");
+            return;
+        }
+        let name = "aoe_test_histlimit";
+        let _ = Command::new("tmux")
+            .args(["kill-session", "-t", name])
+            .output();
+        let create: Vec<String> = ["new-session", "-d", "-s", name]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+
+        let output = create_with_history_limit(name, &create, Some(54321)).unwrap();
+        assert!(output.status.success());
+        let before = global_history_limit();
+        assert_ne!(before, "54321");
+        let pane_limit = Command::new("tmux")
+            .args(["display-message", "-p", "-t", name, "#{history_limit}"])
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&pane_limit.stdout).trim(), "54321");
+
+        // A duplicate name makes new-session fail partway through the chain
+        let output = create_with_history_limit(name, &create, Some(54321)).unwrap();
+        let _ = Command::new("tmux")
+            .args(["kill-session", "-t", name])
+            .output();
+        assert!(!output.status.success());
+        assert_eq!(global_history_limit(), before);
+    }
+
     #[test]
     fn test_sanitize_session_name() {
         assert_eq!(sanitize_session_name("my-project"), "my-project");
@@ -6,9 +6,13 @@ use ratatui::prelude::*;
 use std::path::PathBuf;
 use std::time::Duration;
 
+use super::config_watcher::ConfigWatcher;
 use super::home::{HomeView, TerminalMode};
 use super::styles::Theme;
-use crate::session::{get_update_settings, load_config, save_config, Storage};
+use crate::session::{
+    get_update_settings, load_config, remember_last_profile, resolve_config, save_config, Storage,
+    ToolNotFound, UiConfig, MAX_TICK_MS, MIN_STATUS_REFRESH_MS, MIN_TICK_MS,
+};
 use crate::tmux::AvailableTools;
 use crate::update::{check_for_update, UpdateInfo};
 
@@ -30,7 +34,10 @@ where
     )?;
     std::io::Write::flush(terminal.backend_mut())?;
 
-    let result = f();
+    let result = {
+        let _suspended = super::SuspendGuard::new();
+        f()
+    };
 
     crossterm::terminal::enable_raw_mode()?;
     crossterm::execute!(
@@ -57,6 +64,31 @@ pub struct App {
     needs_redraw: bool,
     update_info: Option<UpdateInfo>,
     update_rx: Option<tokio::sync::oneshot::Receiver<anyhow::Result<UpdateInfo>>>,
+    /// How long the event loop waits for input before running periodic work
+    tick: Duration,
+    /// How often session statuses are re-polled
+    status_refresh: Duration,
+    /// Picks up config edits made outside aoe while the TUI is open
+    config_watcher: Option<ConfigWatcher>,
+    /// Dashboard mode: only navigation and quit are allowed
+    read_only: bool,
+}
+
+/// The creation dialog spinner advances at this rate regardless of `tick_ms`
+const SPINNER_TICK_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Read the `[ui]` loop intervals for a profile, falling back to defaults
+fn ui_intervals(profile: &str) -> (Duration, Duration) {
+    intervals_from(&resolve_config(profile).unwrap_or_default().ui)
+}
+
+/// Clamp hand-edited values into the ranges the settings TUI enforces, so a
+/// `tick_ms = 0` can't turn the event loop into a busy loop
+fn intervals_from(ui: &UiConfig) -> (Duration, Duration) {
+    (
+        Duration::from_millis(ui.tick_ms.clamp(MIN_TICK_MS, MAX_TICK_MS)),
+        Duration::from_millis(ui.status_refresh_ms.max(MIN_STATUS_REFRESH_MS)),
+    )
 }
 
 /// Check if the app version changed and return the previous version if changelog should be shown.
@@ -75,25 +107,33 @@ pub fn check_version_change() -> Result<Option<String>> {
 }
 
 impl App {
-    pub fn new(profile: &str, available_tools: AvailableTools) -> Result<Self> {
+    pub fn new(profile: &str, available_tools: AvailableTools, read_only: bool) -> Result<Self> {
         let storage = Storage::new(profile)?;
         let mut home = HomeView::new(storage, available_tools)?;
+        home.set_read_only(read_only);
         let theme = Theme::default();
+        let (tick, status_refresh) = ui_intervals(profile);
 
         // Check if we need to show welcome or changelog dialogs
         let mut config = load_config()?.unwrap_or_default();
         let current_version = env!("CARGO_PKG_VERSION").to_string();
 
+        // A read-only dashboard shows these but leaves them unseen, since it
+        // writes no config
         if !config.app_state.has_seen_welcome {
             home.show_welcome();
             config.app_state.has_seen_welcome = true;
             config.app_state.last_seen_version = Some(current_version);
-            save_config(&config)?;
+            if !read_only {
+                save_config(&config)?;
+            }
         } else if config.app_state.last_seen_version.as_deref() != Some(&current_version) {
             // Cache should already be refreshed by tui::run() before App::new
             home.show_changelog(config.app_state.last_seen_version.clone());
             config.app_state.last_seen_version = Some(current_version);
-            save_config(&config)?;
+            if !read_only {
+                save_config(&config)?;
+            }
         }
 
         Ok(Self {
@@ -103,6 +143,10 @@ impl App {
             needs_redraw: true,
             update_info: None,
             update_rx: None,
+            tick,
+            status_refresh,
+            config_watcher: ConfigWatcher::new(profile),
+            read_only,
         })
     }
 
@@ -130,7 +174,7 @@ impl App {
 
         let mut last_status_refresh = std::time::Instant::now();
         let mut last_disk_refresh = std::time::Instant::now();
-        const STATUS_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+        let mut last_spinner_tick = std::time::Instant::now();
         const DISK_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
 
         loop {
@@ -140,8 +184,13 @@ impl App {
                 self.needs_redraw = false;
             }
 
-            // Poll with short timeout for responsive input
-            if event::poll(Duration::from_millis(50))? {
+            // Wake often enough to keep the spinner smooth while loading
+            let timeout = if self.home.is_creation_pending() {
+                self.tick.min(SPINNER_TICK_INTERVAL)
+            } else {
+                self.tick
+            };
+            if event::poll(timeout)? {
                 match event::read()? {
                     Event::Key(key) => {
                         self.handle_key(key, terminal).await?;
@@ -162,6 +211,14 @@ impl App {
 
                         continue;
                     }
+                    Event::Resize(_, _) => {
+                        // Recapture panes at the new size rather than showing
+                        // output laid out for the old one
+                        self.home.invalidate_preview_caches();
+                        terminal.autoresize()?;
+                        terminal.draw(|f| self.render(f))?;
+                        continue;
+                    }
                     _ => {}
                 }
             }
@@ -175,7 +232,7 @@ impl App {
             let mut refresh_needed = false;
 
             // Request status refresh every interval (non-blocking)
-            if last_status_refresh.elapsed() >= STATUS_REFRESH_INTERVAL {
+            if last_status_refresh.elapsed() >= self.status_refresh {
                 self.home.request_status_refresh();
                 last_status_refresh = std::time::Instant::now();
             }
@@ -185,6 +242,11 @@ impl App {
                 refresh_needed = true;
             }
 
+            // Show container logs read in the background (non-blocking)
+            if self.home.apply_container_logs() {
+                refresh_needed = true;
+            }
+
             // Check for and apply deletion results (non-blocking)
             if self.home.apply_deletion_results() {
                 refresh_needed = true;
@@ -198,8 +260,22 @@ impl App {
             }
 
             // Tick the dialog spinner if loading
-            if self.home.is_creation_pending() {
+            if self.home.is_creation_pending()
+                && last_spinner_tick.elapsed() >= SPINNER_TICK_INTERVAL
+            {
                 self.home.tick_dialog();
+                last_spinner_tick = std::time::Instant::now();
+                refresh_needed = true;
+            }
+
+            // Apply config edits made in an editor while the TUI is open
+            if self
+                .config_watcher
+                .as_mut()
+                .is_some_and(|watcher| watcher.poll_changed())
+            {
+                self.home.refresh_from_config();
+                (self.tick, self.status_refresh) = ui_intervals(self.home.profile());
                 refresh_needed = true;
             }
 
@@ -277,11 +353,11 @@ impl App {
     ) -> Result<()> {
         // Global keybindings
         match (key.code, key.modifiers) {
-            (KeyCode::Char('c'), KeyModifiers::CONTROL) | (KeyCode::Char('q'), _) => {
-                if !self.home.has_dialog() {
-                    self.should_quit = true;
-                    return Ok(());
-                }
+            (KeyCode::Char('c'), KeyModifiers::CONTROL) | (KeyCode::Char('q'), _)
+                if !self.home.has_dialog() =>
+            {
+                self.should_quit = true;
+                return Ok(());
             }
             _ => {}
         }
@@ -297,9 +373,7 @@ impl App {
                     self.attach_terminal(&id, mode, terminal)?;
                 }
                 Action::SwitchProfile(profile) => {
-                    let storage = Storage::new(&profile)?;
-                    let tools = self.home.available_tools();
-                    self.home = HomeView::new(storage, tools)?;
+                    self.switch_profile(&profile)?;
                 }
                 Action::EditFile(path) => {
                     self.edit_file(&path, terminal)?;
@@ -310,6 +384,22 @@ impl App {
         Ok(())
     }
 
+    fn switch_profile(&mut self, profile: &str) -> Result<()> {
+        let storage = Storage::new(profile)?;
+        let tools = self.home.available_tools();
+        self.home = HomeView::new(storage, tools)?;
+        self.home.set_read_only(self.read_only);
+        (self.tick, self.status_refresh) = ui_intervals(profile);
+        self.config_watcher = ConfigWatcher::new(profile);
+        if self.read_only {
+            return Ok(());
+        }
+        if let Err(e) = remember_last_profile(profile) {
+            tracing::warn!("Failed to remember last profile: {}", e);
+        }
+        Ok(())
+    }
+
     async fn handle_mouse(
         &mut self,
         mouse: MouseEvent,
@@ -326,9 +416,7 @@ impl App {
                     self.attach_terminal(&id, mode, terminal)?;
                 }
                 Action::SwitchProfile(profile) => {
-                    let storage = Storage::new(&profile)?;
-                    let tools = self.home.available_tools();
-                    self.home = HomeView::new(storage, tools)?;
+                    self.switch_profile(&profile)?;
                 }
                 Action::EditFile(path) => {
                     self.edit_file(&path, terminal)?;
@@ -363,6 +451,9 @@ impl App {
             if let Err(e) = inst.start_with_size_opts(size, skip_on_launch) {
                 self.home
                     .set_instance_error(session_id, Some(e.to_string()));
+                if let Some(missing) = e.downcast_ref::<ToolNotFound>() {
+                    self.home.show_tool_not_found(&missing.tool);
+                }
                 return Ok(());
             }
             self.home.set_instance_error(session_id, None);
@@ -371,9 +462,13 @@ impl App {
         let attach_result = with_raw_mode_disabled(terminal, || tmux_session.attach())?;
 
         self.needs_redraw = true;
+        if let Err(e) = self.home.mark_accessed(session_id) {
+            tracing::warn!("Failed to record session access time: {}", e);
+        }
         crate::tmux::refresh_session_cache();
         self.home.reload()?;
         self.home.select_session_by_id(session_id);
+        self.home.stop_container_on_detach(session_id);
 
         if let Err(e) = attach_result {
             tracing::warn!("tmux attach returned error: {}", e);
@@ -401,12 +496,13 @@ impl App {
             TerminalMode::Container if instance.is_sandboxed() => {
                 let container_session = instance.container_terminal_tmux_session()?;
                 if !container_session.exists() {
-                    if let Err(e) = self
+                    // On failure the home view has already switched back to
+                    // the host terminal and shown the error
+                    if self
                         .home
                         .start_container_terminal_for_instance_with_size(session_id, size)
+                        .is_err()
                     {
-                        self.home
-                            .set_instance_error(session_id, Some(e.to_string()));
                         return Ok(());
                     }
                 }
@@ -434,6 +530,7 @@ impl App {
         crate::tmux::refresh_session_cache();
         self.home.reload()?;
         self.home.select_session_by_id(session_id);
+        self.home.stop_container_on_detach(session_id);
 
         if let Err(e) = attach_result {
             tracing::warn!("tmux terminal attach returned error: {}", e);
@@ -491,6 +588,12 @@ impl App {
             }
         }
 
+        // Pick up edits to a config file opened from the settings view
+        if let Some(ref mut settings) = self.home.settings_view {
+            settings.reload();
+            self.home.refresh_from_config();
+        }
+
         // Log any editor errors but don't fail
         if let Err(e) = status {
             tracing::warn!("Editor '{}' returned error: {}", editor, e);
@@ -603,4 +706,27 @@ mod tests {
         assert_eq!(info.as_ref().unwrap().latest_version, "0.5.0");
         assert!(rx_out.is_none());
     }
+
+    #[test]
+    fn test_intervals_clamp_out_of_range_values() {
+        let ui = UiConfig {
+            tick_ms: 0,
+            status_refresh_ms: 5,
+            ..UiConfig::default()
+        };
+        assert_eq!(
+            intervals_from(&ui),
+            (Duration::from_millis(10), Duration::from_millis(100))
+        );
+
+        let ui = UiConfig {
+            tick_ms: 60_000,
+            ..UiConfig::default()
+        };
+        assert_eq!(intervals_from(&ui).0, Duration::from_millis(1000));
+        assert_eq!(
+            intervals_from(&UiConfig::default()),
+            (Duration::from_millis(50), Duration::from_millis(500))
+        );
+    }
 }
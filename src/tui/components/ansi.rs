@@ -0,0 +1,247 @@
+//! Minimal ANSI SGR parser for rendering captured pane output
+//!
+//! Only colors and basic text attributes are honored. Cursor movement, OSC
+//! titles/hyperlinks and any other escape sequences are stripped, as are
+//! control characters, which would corrupt the ratatui buffer. Tabs are
+//! expanded to spaces. Callers convert pane bytes with `from_utf8_lossy`, so
+//! invalid UTF-8 arrives here as U+FFFD.
+
+use ratatui::prelude::*;
+use unicode_width::UnicodeWidthChar;
+
+const TAB_WIDTH: usize = 8;
+
+/// Parse text containing ANSI escape sequences into styled lines.
+///
+/// Styles carry across line breaks, matching how a terminal would render them.
+pub fn parse_lines(text: &str) -> Vec<Line<'static>> {
+    let mut style = Style::default();
+    text.lines()
+        .map(|line| parse_line(line, &mut style))
+        .collect()
+}
+
+fn parse_line(line: &str, style: &mut Style) -> Line<'static> {
+    let mut spans = Vec::new();
+    let mut buf = String::new();
+    let mut col = 0;
+    let mut chars = line.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c == '\t' {
+            let spaces = TAB_WIDTH - col % TAB_WIDTH;
+            buf.extend(std::iter::repeat(' ').take(spaces));
+            col += spaces;
+            continue;
+        }
+        if c != '\x1b' {
+            if !c.is_control() {
+                buf.push(c);
+                col += c.width().unwrap_or(0);
+            }
+            continue;
+        }
+
+        match chars.next() {
+            Some('[') => {
+                let mut params = String::new();
+                let mut final_byte = None;
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        final_byte = Some(c);
+                        break;
+                    }
+                    params.push(c);
+                }
+                if final_byte == Some('m') {
+                    if !buf.is_empty() {
+                        spans.push(Span::styled(std::mem::take(&mut buf), *style));
+                    }
+                    apply_sgr(&params, style);
+                }
+            }
+            Some(']' | 'P' | 'X' | '^' | '_') => {
+                // OSC, DCS, SOS, PM and APC strings: terminated by BEL or ST (ESC \)
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            Some(' '..='/') => {
+                // nF escapes such as a charset selection (ESC ( B): intermediate
+                // bytes, then one final byte
+                for c in chars.by_ref() {
+                    if !(' '..='/').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    if !buf.is_empty() {
+        spans.push(Span::styled(buf, *style));
+    }
+    Line::from(spans)
+}
+
+fn apply_sgr(params: &str, style: &mut Style) {
+    let codes: Vec<u16> = params
+        .split([';', ':'])
+        .map(|p| p.parse().unwrap_or(0))
+        .collect();
+
+    let mut i = 0;
+    while i < codes.len() {
+        match codes[i] {
+            0 => *style = Style::default(),
+            1 => *style = style.add_modifier(Modifier::BOLD),
+            2 => *style = style.add_modifier(Modifier::DIM),
+            3 => *style = style.add_modifier(Modifier::ITALIC),
+            4 => *style = style.add_modifier(Modifier::UNDERLINED),
+            7 => *style = style.add_modifier(Modifier::REVERSED),
+            9 => *style = style.add_modifier(Modifier::CROSSED_OUT),
+            22 => *style = style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => *style = style.remove_modifier(Modifier::ITALIC),
+            24 => *style = style.remove_modifier(Modifier::UNDERLINED),
+            27 => *style = style.remove_modifier(Modifier::REVERSED),
+            29 => *style = style.remove_modifier(Modifier::CROSSED_OUT),
+            n @ 30..=37 => style.fg = Some(Color::Indexed((n - 30) as u8)),
+            n @ 90..=97 => style.fg = Some(Color::Indexed((n - 90 + 8) as u8)),
+            n @ 40..=47 => style.bg = Some(Color::Indexed((n - 40) as u8)),
+            n @ 100..=107 => style.bg = Some(Color::Indexed((n - 100 + 8) as u8)),
+            39 => style.fg = None,
+            49 => style.bg = None,
+            n @ (38 | 48) => {
+                let (color, consumed) = extended_color(&codes[i + 1..]);
+                if let Some(color) = color {
+                    if n == 38 {
+                        style.fg = Some(color);
+                    } else {
+                        style.bg = Some(color);
+                    }
+                }
+                i += consumed;
+            }
+            _ => {}
+        }
+        i += 1;
+    }
+}
+
+/// Parse the arguments following a 38/48 code. Returns the color and the
+/// number of parameters consumed.
+fn extended_color(rest: &[u16]) -> (Option<Color>, usize) {
+    match rest {
+        [5, n, ..] => (Some(Color::Indexed(*n as u8)), 2),
+        [2, r, g, b, ..] => (Some(Color::Rgb(*r as u8, *g as u8, *b as u8)), 4),
+        [5, ..] => (None, rest.len()),
+        [2, ..] => (None, rest.len()),
+        _ => (None, 0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn spans(line: &Line) -> Vec<(String, Style)> {
+        line.spans
+            .iter()
+            .map(|s| (s.content.to_string(), s.style))
+            .collect()
+    }
+
+    #[test]
+    fn test_plain_text_unchanged() {
+        let lines = parse_lines("hello\nworld");
+        assert_eq!(lines.len(), 2);
+        assert_eq!(spans(&lines[0]), vec![("hello".into(), Style::default())]);
+    }
+
+    #[test]
+    fn test_basic_colors_and_bold() {
+        let lines = parse_lines("a\x1b[1;31mred\x1b[0mb");
+        assert_eq!(
+            spans(&lines[0]),
+            vec![
+                ("a".into(), Style::default()),
+                (
+                    "red".into(),
+                    Style::default()
+                        .fg(Color::Indexed(1))
+                        .add_modifier(Modifier::BOLD)
+                ),
+                ("b".into(), Style::default()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_extended_colors() {
+        let lines = parse_lines("\x1b[38;5;208mx\x1b[48;2;1;2;3my");
+        let s = spans(&lines[0]);
+        assert_eq!(s[0].1.fg, Some(Color::Indexed(208)));
+        assert_eq!(s[1].1.bg, Some(Color::Rgb(1, 2, 3)));
+        assert_eq!(s[1].1.fg, Some(Color::Indexed(208)));
+    }
+
+    #[test]
+    fn test_style_carries_across_lines() {
+        let lines = parse_lines("\x1b[32mgreen\nstill\x1b[39m plain");
+        assert_eq!(spans(&lines[1])[0].1.fg, Some(Color::Indexed(2)));
+        assert_eq!(spans(&lines[1])[1].1.fg, None);
+    }
+
+    #[test]
+    fn test_unsupported_sequences_stripped() {
+        let lines = parse_lines("\x1b[2K\x1b]0;title\x07a\x1b[1Ab\x1b]8;;http://x\x1b\\c");
+        let text: String = lines[0].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(text, "abc");
+    }
+
+    fn text(line: &Line) -> String {
+        line.spans.iter().map(|s| s.content.as_ref()).collect()
+    }
+
+    #[test]
+    fn test_charset_and_string_escapes_stripped() {
+        let lines = parse_lines("a\x1b(Bb\x1b[m\x1bPq#0\x1b\\c\x1b_apc\x07d");
+        assert_eq!(text(&lines[0]), "abcd");
+    }
+
+    #[test]
+    fn test_tabs_expand_to_tab_stops() {
+        let lines = parse_lines("a\tb\n\x1b[31mabcdefgh\x1b[0m\tc");
+        assert_eq!(text(&lines[0]), "a       b");
+        assert_eq!(text(&lines[1]), "abcdefgh        c");
+    }
+
+    #[test]
+    fn test_invalid_utf8_and_controls_render() {
+        use ratatui::buffer::Buffer;
+        use ratatui::widgets::{Paragraph, Wrap};
+
+        let bytes = b"ok \xff\xfe\xc3 bad\x00\x07\x08\x7f\r\x1b[31m\xe2\x82red\x1b\n\x9b\x1b[";
+        let lines = parse_lines(&String::from_utf8_lossy(bytes));
+        assert_eq!(
+            text(&lines[0]),
+            "ok \u{fffd}\u{fffd}\u{fffd} bad\u{fffd}red"
+        );
+
+        let area = Rect::new(0, 0, 20, 4);
+        let mut buf = Buffer::empty(area);
+        Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .render(area, &mut buf);
+        for cell in buf.content() {
+            assert!(!cell.symbol().chars().any(char::is_control));
+        }
+    }
+}
@@ -6,7 +6,7 @@ use ratatui::widgets::*;
 use crate::tui::styles::Theme;
 
 const DIALOG_WIDTH: u16 = 50;
-const DIALOG_HEIGHT: u16 = 30;
+const DIALOG_HEIGHT: u16 = 34;
 #[cfg(test)]
 const BORDER_HEIGHT: u16 = 2;
 #[cfg(test)]
@@ -34,6 +34,8 @@ fn shortcuts() -> Vec<(&'static str, Vec<(&'static str, &'static str)>)> {
                 ("n", "New session"),
                 ("d", "Delete session/group"),
                 ("r", "Rename session"),
+                ("T", "Change session tool"),
+                ("y", "Copy launch command"),
             ],
         ),
         (
@@ -43,6 +45,9 @@ fn shortcuts() -> Vec<(&'static str, Vec<(&'static str, &'static str)>)> {
                 ("c", "Toggle container/host (sandbox)"),
                 ("D", "Diff view (git changes)"),
                 ("H/L", "Resize list panel"),
+                ("w", "Toggle preview line wrap"),
+                ("</>", "Scroll preview left / right"),
+                ("o", "Toggle container logs (sandbox)"),
             ],
         ),
         (
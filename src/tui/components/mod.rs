@@ -1,5 +1,6 @@
 //! TUI components
 
+mod ansi;
 mod dir_picker;
 mod help;
 mod list_picker;
@@ -3,6 +3,7 @@
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
+use crate::docker::ContainerState;
 use crate::session::Instance;
 use crate::tui::styles::Theme;
 
@@ -91,10 +92,7 @@ impl Preview {
                 .alignment(Alignment::Center);
             frame.render_widget(hint, inner);
         } else {
-            let output_lines: Vec<Line> = cached_output
-                .lines()
-                .map(|line| Line::from(Span::raw(line)))
-                .collect();
+            let output_lines = super::ansi::parse_lines(cached_output);
 
             let line_count = output_lines.len();
             let visible_height = inner.height as usize;
@@ -113,19 +111,18 @@ impl Preview {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn render_with_cache(
         frame: &mut Frame,
         area: Rect,
         instance: &Instance,
+        container: Option<&ContainerState>,
         cached_output: &str,
+        wrap: bool,
+        hscroll: u16,
         theme: &Theme,
     ) {
-        // Adjust height based on whether worktree info is present
-        let info_height = if instance.worktree_info.is_some() {
-            10 // Expanded to show worktree details
-        } else {
-            6 // Standard height
-        };
+        let info_height = info_height(instance);
 
         let chunks = Layout::default()
             .direction(Direction::Vertical)
@@ -135,11 +132,81 @@ impl Preview {
             ])
             .split(area);
 
-        Self::render_info(frame, chunks[0], instance, theme);
-        Self::render_output_cached(frame, chunks[1], instance, cached_output, theme);
+        Self::render_info(frame, chunks[0], instance, container, theme);
+        Self::render_output_cached(
+            frame,
+            chunks[1],
+            instance,
+            cached_output,
+            wrap,
+            hscroll,
+            theme,
+        );
+    }
+
+    /// Render the session info with the container's logs in place of the pane.
+    /// `logs` is `None` when the container doesn't exist.
+    pub fn render_container_logs(
+        frame: &mut Frame,
+        area: Rect,
+        instance: &Instance,
+        container: Option<&ContainerState>,
+        logs: Option<&str>,
+        theme: &Theme,
+    ) {
+        let info_height = info_height(instance);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(info_height), Constraint::Min(1)])
+            .split(area);
+
+        Self::render_info(frame, chunks[0], instance, container, theme);
+
+        let block = Block::default()
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(theme.border))
+            .title(" Container Logs ")
+            .title_style(Style::default().fg(theme.dimmed));
+
+        let inner = block.inner(chunks[1]);
+        frame.render_widget(block, chunks[1]);
+
+        let logs = match logs {
+            Some(logs) if !logs.is_empty() => logs,
+            _ => {
+                let hint = if logs.is_some() {
+                    "No container logs"
+                } else {
+                    "Container is not running"
+                };
+                let hint = Paragraph::new(hint)
+                    .style(Style::default().fg(theme.dimmed))
+                    .alignment(Alignment::Center);
+                frame.render_widget(hint, inner);
+                return;
+            }
+        };
+
+        let output_lines = super::ansi::parse_lines(logs);
+        let line_count = output_lines.len();
+        let visible_height = inner.height as usize;
+        let scroll_offset = line_count.saturating_sub(visible_height) as u16;
+
+        let paragraph = Paragraph::new(output_lines)
+            .style(Style::default().fg(theme.text))
+            .scroll((scroll_offset, 0));
+        frame.render_widget(paragraph, inner);
     }
 
-    fn render_info(frame: &mut Frame, area: Rect, instance: &Instance, theme: &Theme) {
+    fn render_info(
+        frame: &mut Frame,
+        area: Rect,
+        instance: &Instance,
+        container: Option<&ContainerState>,
+        theme: &Theme,
+    ) {
+        let now = chrono::Utc::now();
         let mut info_lines = vec![
             Line::from(vec![
                 Span::styled("Title:   ", Style::default().fg(theme.dimmed)),
@@ -160,14 +227,7 @@ impl Preview {
                 Span::styled("Status:  ", Style::default().fg(theme.dimmed)),
                 Span::styled(
                     format!("{:?}", instance.status),
-                    Style::default().fg(match instance.status {
-                        crate::session::Status::Running => theme.running,
-                        crate::session::Status::Waiting => theme.waiting,
-                        crate::session::Status::Idle => theme.idle,
-                        crate::session::Status::Error => theme.error,
-                        crate::session::Status::Starting => theme.dimmed,
-                        crate::session::Status::Deleting => theme.waiting,
-                    }),
+                    Style::default().fg(theme.status_color(instance.status)),
                 ),
             ]),
             Line::from(vec![
@@ -181,8 +241,58 @@ impl Preview {
                     Style::default().fg(theme.group),
                 ),
             ]),
+            Line::from(vec![
+                Span::styled("Sandbox: ", Style::default().fg(theme.dimmed)),
+                match instance.sandbox_info.as_ref().filter(|s| s.enabled) {
+                    Some(sandbox) => Span::styled(
+                        format!("{} ({})", sandbox.container_name, sandbox.image),
+                        Style::default().fg(Color::Magenta),
+                    ),
+                    None => Span::styled("off", Style::default().fg(theme.text)),
+                },
+            ]),
+            Line::from(vec![
+                Span::styled("Age:     ", Style::default().fg(theme.dimmed)),
+                Span::styled(
+                    humanize_duration(now - instance.created_at),
+                    Style::default().fg(theme.text),
+                ),
+                Span::styled("  idle ", Style::default().fg(theme.dimmed)),
+                Span::styled(
+                    instance
+                        .last_accessed_at
+                        .map(|at| humanize_duration(now - at))
+                        .unwrap_or_else(|| "never attached".to_string()),
+                    Style::default().fg(theme.text),
+                ),
+            ]),
         ];
 
+        if let Some(sandbox) = instance.sandbox_info.as_ref().filter(|s| s.enabled) {
+            let uptime = match container {
+                Some(state) => state
+                    .uptime(now)
+                    .map(humanize_duration)
+                    .unwrap_or_else(|| state.status.clone()),
+                None => "no container".to_string(),
+            };
+            let mut spans = vec![
+                Span::styled("Uptime:  ", Style::default().fg(theme.dimmed)),
+                Span::styled(uptime, Style::default().fg(theme.text)),
+            ];
+            if let Some(created_at) = sandbox.created_at {
+                spans.push(Span::styled(
+                    "  container age ",
+                    Style::default().fg(theme.dimmed),
+                ));
+                spans.push(Span::styled(
+                    humanize_duration(now - created_at),
+                    Style::default().fg(theme.text),
+                ));
+            }
+            info_lines.push(Line::from(spans));
+        }
+
         // Add worktree information if present
         if let Some(wt_info) = &instance.worktree_info {
             info_lines.push(Line::from(""));
@@ -230,12 +340,15 @@ impl Preview {
         area: Rect,
         instance: &Instance,
         cached_output: &str,
+        wrap: bool,
+        hscroll: u16,
         theme: &Theme,
     ) {
+        let title = if wrap { " Output (wrap) " } else { " Output " };
         let block = Block::default()
             .borders(Borders::TOP)
             .border_style(Style::default().fg(theme.border))
-            .title(" Output ")
+            .title(title)
             .title_style(Style::default().fg(theme.dimmed));
 
         let inner = block.inner(area);
@@ -264,30 +377,65 @@ impl Preview {
                 .alignment(Alignment::Center);
             frame.render_widget(hint, inner);
         } else {
-            let output_lines: Vec<Line> = cached_output
-                .lines()
-                .map(|line| Line::from(Span::raw(line)))
-                .collect();
-
-            let line_count = output_lines.len();
-            let visible_height = inner.height as usize;
+            let output_lines = super::ansi::parse_lines(cached_output);
 
-            // Scroll to show the bottom of the content
-            let scroll_offset = if line_count > visible_height {
-                (line_count - visible_height) as u16
+            let paragraph = Paragraph::new(output_lines).style(Style::default().fg(theme.text));
+            let (paragraph, hscroll) = if wrap {
+                (paragraph.wrap(Wrap { trim: false }), 0)
             } else {
-                0
+                (paragraph, hscroll)
             };
 
-            let paragraph = Paragraph::new(output_lines)
-                .style(Style::default().fg(theme.text))
-                .scroll((scroll_offset, 0));
+            // Scroll to show the bottom of the content
+            let scroll_offset = bottom_scroll_offset(&paragraph, inner.width, inner.height);
+            let paragraph = paragraph.scroll((scroll_offset, hscroll));
 
             frame.render_widget(paragraph, inner);
         }
     }
 }
 
+/// Rows for the info section: expanded for worktree details, plus the
+/// container uptime line for sandboxed sessions
+fn info_height(instance: &Instance) -> u16 {
+    let base = if instance.worktree_info.is_some() {
+        12
+    } else {
+        8
+    };
+    if instance.sandbox_info.as_ref().is_some_and(|s| s.enabled) {
+        base + 1
+    } else {
+        base
+    }
+}
+
+/// Vertical scroll that brings the last row of `paragraph` to the bottom of a
+/// `width` x `height` area. Rows are counted with the paragraph's own
+/// wrapping, so word-wrapped lines that take extra rows are accounted for.
+fn bottom_scroll_offset(paragraph: &Paragraph, width: u16, height: u16) -> u16 {
+    let rows = paragraph.line_count(width);
+    rows.saturating_sub(height as usize)
+        .try_into()
+        .unwrap_or(u16::MAX)
+}
+
+/// Format a duration as its two most significant units, e.g. "45s", "12m", "3h 5m", "2d 4h".
+fn humanize_duration(duration: chrono::Duration) -> String {
+    let secs = duration.num_seconds().max(0);
+    let (days, hours, mins) = (secs / 86_400, (secs % 86_400) / 3_600, (secs % 3_600) / 60);
+
+    if days > 0 {
+        format!("{}d {}h", days, hours)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, mins)
+    } else if mins > 0 {
+        format!("{}m", mins)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
 fn shorten_path(path: &str) -> String {
     let path_buf = std::path::PathBuf::from(path);
 
@@ -317,6 +465,37 @@ fn shorten_path(path: &str) -> String {
 mod tests {
     use super::*;
 
+    fn wrapped(lines: Vec<Line<'static>>) -> Paragraph<'static> {
+        Paragraph::new(lines).wrap(Wrap { trim: false })
+    }
+
+    #[test]
+    fn test_bottom_scroll_offset() {
+        let lines = vec![Line::from(""), Line::from("abcd"), Line::from("abcdefghij")];
+        assert_eq!(bottom_scroll_offset(&wrapped(lines.clone()), 4, 2), 5 - 2);
+        assert_eq!(bottom_scroll_offset(&wrapped(lines.clone()), 4, 10), 0);
+        assert_eq!(bottom_scroll_offset(&Paragraph::new(lines), 4, 2), 1);
+    }
+
+    #[test]
+    fn test_bottom_scroll_offset_counts_word_wrapped_rows() {
+        // Wraps at word boundaries into "aa", "bbbb", "cc", "dddd": 4 rows,
+        // where splitting every 6 characters would give only 3
+        let lines = vec![Line::from("aa bbbb cc dddd")];
+        assert_eq!(bottom_scroll_offset(&wrapped(lines), 6, 1), 3);
+    }
+
+    #[test]
+    fn test_humanize_duration() {
+        use chrono::Duration;
+
+        assert_eq!(humanize_duration(Duration::seconds(45)), "45s");
+        assert_eq!(humanize_duration(Duration::seconds(12 * 60 + 30)), "12m");
+        assert_eq!(humanize_duration(Duration::minutes(185)), "3h 5m");
+        assert_eq!(humanize_duration(Duration::hours(52)), "2d 4h");
+        assert_eq!(humanize_duration(Duration::seconds(-5)), "0s");
+    }
+
     #[test]
     fn test_shorten_path_with_home() {
         if let Some(home) = dirs::home_dir() {
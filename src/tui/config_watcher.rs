@@ -0,0 +1,141 @@
+//! Watch the global and profile config files for edits made outside aoe
+//!
+//! Editors often save by writing a temp file and renaming it over the
+//! original, so the containing directories are watched rather than the files
+//! themselves. Events only trigger a content comparison; a file counts as
+//! changed when its contents differ from what was last seen and from what
+//! aoe itself last wrote there.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::mpsc;
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+
+use crate::session::config::{get_config_path, is_own_write};
+use crate::session::profile_config::get_profile_config_path;
+
+pub struct ConfigWatcher {
+    _watcher: RecommendedWatcher,
+    events: mpsc::Receiver<()>,
+    /// Last seen contents of each watched file (None if it did not exist)
+    seen: HashMap<PathBuf, Option<String>>,
+}
+
+impl ConfigWatcher {
+    /// Start watching the global config and `profile`'s config. Returns None
+    /// if the platform watcher cannot be set up; live reload is then skipped.
+    pub fn new(profile: &str) -> Option<Self> {
+        let paths = [
+            get_config_path().ok()?,
+            get_profile_config_path(profile).ok()?,
+        ];
+
+        let (tx, events) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |res: notify::Result<notify::Event>| {
+            if res.is_ok() {
+                let _ = tx.send(());
+            }
+        })
+        .map_err(|e| tracing::warn!("Config live reload unavailable: {}", e))
+        .ok()?;
+
+        for dir in paths.iter().filter_map(|p| p.parent()) {
+            if let Err(e) = watcher.watch(dir, RecursiveMode::NonRecursive) {
+                tracing::warn!("Failed to watch {}: {}", dir.display(), e);
+            }
+        }
+
+        let seen = paths
+            .into_iter()
+            .map(|path| {
+                let content = std::fs::read_to_string(&path).ok();
+                (path, content)
+            })
+            .collect();
+
+        Some(Self {
+            _watcher: watcher,
+            events,
+            seen,
+        })
+    }
+
+    /// Drain pending file events and report whether any watched config was
+    /// edited by something other than aoe since the last call.
+    pub fn poll_changed(&mut self) -> bool {
+        if self.events.try_iter().count() == 0 {
+            return false;
+        }
+
+        let mut changed = false;
+        for (path, seen) in self.seen.iter_mut() {
+            let content = std::fs::read_to_string(path).ok();
+            if content == *seen {
+                continue;
+            }
+            let own = content
+                .as_deref()
+                .is_some_and(|content| is_own_write(path, content));
+            if !own {
+                changed = true;
+            }
+            *seen = content;
+        }
+        changed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::session::{save_config, Config};
+    use serial_test::serial;
+    use std::time::{Duration, Instant};
+
+    fn setup_test_home(temp: &tempfile::TempDir) {
+        std::env::set_var("HOME", temp.path());
+        #[cfg(target_os = "linux")]
+        std::env::set_var("XDG_CONFIG_HOME", temp.path().join(".config"));
+    }
+
+    /// Poll until a change is reported or the timeout passes
+    fn wait_for_change(watcher: &mut ConfigWatcher) -> bool {
+        let deadline = Instant::now() + Duration::from_secs(2);
+        while Instant::now() < deadline {
+            if watcher.poll_changed() {
+                return true;
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        }
+        false
+    }
+
+    #[test]
+    #[serial]
+    fn test_external_edit_is_reported() {
+        let temp = tempfile::TempDir::new().unwrap();
+        setup_test_home(&temp);
+        save_config(&Config::default()).unwrap();
+        let mut watcher = ConfigWatcher::new("default").unwrap();
+
+        std::fs::write(get_config_path().unwrap(), "[tmux]\nhistory_limit = 5000\n").unwrap();
+        assert!(wait_for_change(&mut watcher));
+        // Already seen, so nothing new
+        assert!(!watcher.poll_changed());
+    }
+
+    #[test]
+    #[serial]
+    fn test_own_write_is_ignored() {
+        let temp = tempfile::TempDir::new().unwrap();
+        setup_test_home(&temp);
+        save_config(&Config::default()).unwrap();
+        let mut watcher = ConfigWatcher::new("default").unwrap();
+
+        let mut config = Config::default();
+        config.tmux.history_limit = 5000;
+        save_config(&config).unwrap();
+        assert!(!wait_for_change(&mut watcher));
+    }
+}
@@ -0,0 +1,82 @@
+//! Background container log fetching for the preview's log view
+//!
+//! `docker logs` can take a while on a busy daemon, so it runs on a helper
+//! thread instead of the render path.
+
+use std::sync::mpsc;
+use std::thread;
+
+use crate::docker::DockerContainer;
+
+struct LogsRequest {
+    session_id: String,
+    tail: usize,
+}
+
+#[derive(Debug)]
+pub struct LogsResult {
+    pub session_id: String,
+    /// `None` when the logs couldn't be read, e.g. the container is gone
+    pub logs: Option<String>,
+}
+
+/// Background thread that reads container logs without blocking the UI
+pub struct ContainerLogsPoller {
+    request_tx: mpsc::Sender<LogsRequest>,
+    result_rx: mpsc::Receiver<LogsResult>,
+    _handle: thread::JoinHandle<()>,
+}
+
+impl ContainerLogsPoller {
+    pub fn new() -> Self {
+        let (request_tx, request_rx) = mpsc::channel::<LogsRequest>();
+        let (result_tx, result_rx) = mpsc::channel::<LogsResult>();
+
+        let handle = thread::spawn(move || {
+            Self::polling_loop(request_rx, result_tx);
+        });
+
+        Self {
+            request_tx,
+            result_rx,
+            _handle: handle,
+        }
+    }
+
+    fn polling_loop(request_rx: mpsc::Receiver<LogsRequest>, result_tx: mpsc::Sender<LogsResult>) {
+        while let Ok(mut request) = request_rx.recv() {
+            // Only the newest request matters once a slow call let several queue up
+            while let Ok(newer) = request_rx.try_recv() {
+                request = newer;
+            }
+            let logs = DockerContainer::from_session_id(&request.session_id)
+                .logs(request.tail)
+                .ok();
+            let result = LogsResult {
+                session_id: request.session_id,
+                logs,
+            };
+            if result_tx.send(result).is_err() {
+                break;
+            }
+        }
+    }
+
+    /// Request the last `tail` log lines of a session's container (non-blocking)
+    pub fn request_logs(&self, session_id: &str, tail: usize) {
+        let _ = self.request_tx.send(LogsRequest {
+            session_id: session_id.to_string(),
+            tail,
+        });
+    }
+
+    pub fn try_recv_result(&self) -> Option<LogsResult> {
+        self.result_rx.try_recv().ok()
+    }
+}
+
+impl Default for ContainerLogsPoller {
+    fn default() -> Self {
+        Self::new()
+    }
+}
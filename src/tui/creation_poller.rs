@@ -6,13 +6,15 @@
 use std::sync::mpsc;
 use std::thread;
 
-use crate::session::builder::{self, CreatedWorktree, InstanceParams};
+use crate::session::builder::{self, CreatedWorktree};
 use crate::session::repo_config::{self, HookProgress, HooksConfig};
 use crate::session::Instance;
 use crate::tui::dialogs::NewSessionData;
 
 pub struct CreationRequest {
     pub data: NewSessionData,
+    /// Profile the session is created in
+    pub profile: String,
     /// Existing instances, used for generating unique titles
     pub existing_instances: Vec<Instance>,
     /// Trusted hooks to execute after instance creation (already approved by user).
@@ -28,6 +30,10 @@ pub enum CreationResult {
         created_worktree: Option<CreatedWorktreeInfo>,
         /// Whether on_launch hooks were already executed in the background
         on_launch_hooks_ran: bool,
+        /// Whether to attach (and so launch the tool) straight away
+        start: bool,
+        /// Host environment variables the sandbox config refers to that were not set
+        missing_env_vars: Vec<String>,
     },
     Error(String),
 }
@@ -96,36 +102,26 @@ impl CreationPoller {
             .map(|i| i.title.as_str())
             .collect();
 
-        let params = InstanceParams {
-            title: data.title,
-            path: data.path.clone(),
-            group: data.group,
-            tool: data.tool,
-            worktree_branch: data.worktree_branch,
-            create_new_branch: data.create_new_branch,
-            sandbox: data.sandbox,
-            sandbox_image: data.sandbox_image,
-            yolo_mode: data.yolo_mode,
-            extra_env_keys: data.extra_env_keys,
-            extra_env_values: data.extra_env_values,
-        };
-
-        let build_result = match builder::build_instance(params, &existing_titles) {
-            Ok(r) => r,
-            Err(e) => return CreationResult::Error(e.to_string()),
-        };
+        let sandbox = data.sandbox;
+        let start = data.start;
+        let build_result =
+            match builder::build_instance(&request.profile, data.into(), &existing_titles) {
+                Ok(r) => r,
+                Err(e) => return CreationResult::Error(e.to_string()),
+            };
 
         let mut instance = build_result.instance;
         let created_worktree = build_result.created_worktree;
 
         let has_on_create = hooks.as_ref().is_some_and(|h| !h.on_create.is_empty());
-        let has_on_launch = hooks.as_ref().is_some_and(|h| !h.on_launch.is_empty());
+        // Sessions created without starting run on_launch hooks on first start
+        let has_on_launch = start && hooks.as_ref().is_some_and(|h| !h.on_launch.is_empty());
         let mut container_started = false;
 
         // Execute on_create hooks after worktree setup, before starting
         if has_on_create {
             let hooks = hooks.as_ref().unwrap();
-            if data.sandbox {
+            if sandbox {
                 // Ensure the container is running so we can exec hooks inside it.
                 // Don't create the tmux session yet -- that happens at attach time
                 // where the terminal size is available.
@@ -160,7 +156,7 @@ impl CreationPoller {
         // This prevents blocking the UI thread when the session is first attached.
         if has_on_launch {
             let hooks = hooks.as_ref().unwrap();
-            if data.sandbox {
+            if sandbox {
                 if !container_started {
                     if let Err(e) = instance.ensure_container_running() {
                         tracing::warn!(
@@ -193,7 +189,7 @@ impl CreationPoller {
             }
         }
 
-        if data.sandbox && !container_started {
+        if sandbox && !container_started {
             // Only ensure the Docker container is running here if hooks didn't already
             // start it. Don't create the tmux session yet -- that happens at attach time
             // where the terminal size is available.
@@ -204,12 +200,15 @@ impl CreationPoller {
         }
 
         let created_worktree_info = created_worktree.as_ref().map(CreatedWorktreeInfo::from);
+        let missing_env_vars = instance.missing_env_vars();
 
         CreationResult::Success {
             session_id: instance.id.clone(),
             instance: Box::new(instance),
             created_worktree: created_worktree_info,
             on_launch_hooks_ran: has_on_launch,
+            start,
+            missing_env_vars,
         }
     }
 
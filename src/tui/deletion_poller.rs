@@ -1,16 +1,17 @@
 //! Background deletion handler for TUI responsiveness
 
-use std::path::PathBuf;
 use std::sync::mpsc;
 use std::thread;
 
-use crate::docker::DockerContainer;
-use crate::git::GitWorktree;
+use crate::session::manager::{self, CleanupOptions};
 use crate::session::Instance;
 
 pub struct DeletionRequest {
     pub session_id: String,
     pub instance: Instance,
+    /// Every session in the profile, so a worktree another one still uses
+    /// is left in place
+    pub instances: Vec<Instance>,
     pub delete_worktree: bool,
     pub delete_branch: bool,
     pub delete_sandbox: bool,
@@ -58,70 +59,15 @@ impl DeletionPoller {
     }
 
     fn perform_deletion(request: &DeletionRequest) -> DeletionResult {
-        let mut errors = Vec::new();
-
-        // Track branch info for potential deletion after worktree removal
-        let branch_to_delete = if request.delete_branch {
-            request
-                .instance
-                .worktree_info
-                .as_ref()
-                .filter(|wt| wt.managed_by_aoe)
-                .map(|wt| (wt.branch.clone(), PathBuf::from(&wt.main_repo_path)))
-        } else {
-            None
-        };
-
-        // Worktree cleanup (if user opted to delete it)
-        // Must happen before branch deletion since the worktree is using the branch
-        if request.delete_worktree {
-            if let Some(wt_info) = &request.instance.worktree_info {
-                if wt_info.managed_by_aoe {
-                    let worktree_path = PathBuf::from(&request.instance.project_path);
-                    let main_repo = PathBuf::from(&wt_info.main_repo_path);
-
-                    if let Ok(git_wt) = GitWorktree::new(main_repo) {
-                        if let Err(e) = git_wt.remove_worktree(&worktree_path) {
-                            errors.push(format!("Worktree: {}", e));
-                        }
-                    }
-                }
-            }
-        }
-
-        // Branch cleanup (if user opted to delete it and worktree was successfully removed)
-        if let Some((branch, main_repo)) = branch_to_delete {
-            // Only delete branch if worktree deletion succeeded (or wasn't requested)
-            let worktree_ok =
-                !request.delete_worktree || !errors.iter().any(|e| e.starts_with("Worktree:"));
-            if worktree_ok {
-                if let Ok(git_wt) = GitWorktree::new(main_repo) {
-                    if let Err(e) = git_wt.delete_branch(&branch) {
-                        errors.push(format!("Branch: {}", e));
-                    }
-                }
-            }
-        }
-
-        // Container cleanup (if user opted to delete it)
-        if request.delete_sandbox {
-            if let Some(sandbox) = &request.instance.sandbox_info {
-                if sandbox.enabled {
-                    let container = DockerContainer::from_session_id(&request.instance.id);
-                    if container.exists().unwrap_or(false) {
-                        if let Err(e) = container.remove(true) {
-                            errors.push(format!("Container: {}", e));
-                        }
-                    }
-                }
-            }
-        }
-
-        // Tmux kill - non-fatal if session already gone
-        let _ = request.instance.kill();
-
-        // Kill paired terminal session if it exists
-        let _ = request.instance.kill_terminal();
+        let errors = manager::remove_session_resources(
+            &request.instance,
+            &request.instances,
+            CleanupOptions {
+                delete_worktree: request.delete_worktree,
+                delete_branch: request.delete_branch,
+                delete_sandbox: request.delete_sandbox,
+            },
+        );
 
         DeletionResult {
             session_id: request.session_id.clone(),
@@ -164,6 +110,7 @@ mod tests {
         let request = DeletionRequest {
             session_id: instance.id.clone(),
             instance,
+            instances: Vec::new(),
             delete_worktree: false,
             delete_branch: false,
             delete_sandbox: false,
@@ -182,6 +129,7 @@ mod tests {
         let request = DeletionRequest {
             session_id: instance.id.clone(),
             instance,
+            instances: Vec::new(),
             delete_worktree: true,
             delete_branch: false,
             delete_sandbox: false,
@@ -202,6 +150,7 @@ mod tests {
         poller.request_deletion(DeletionRequest {
             session_id: session_id.clone(),
             instance,
+            instances: Vec::new(),
             delete_worktree: false,
             delete_branch: false,
             delete_sandbox: false,
@@ -236,6 +185,7 @@ mod tests {
         let request = DeletionRequest {
             session_id: custom_id.clone(),
             instance,
+            instances: Vec::new(),
             delete_worktree: false,
             delete_branch: false,
             delete_sandbox: false,
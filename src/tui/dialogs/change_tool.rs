@@ -0,0 +1,166 @@
+//! Change tool dialog for switching a session's agent
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::DialogResult;
+use crate::tui::styles::Theme;
+
+pub struct ChangeToolDialog {
+    current_tool: String,
+    tools: Vec<String>,
+    selected: usize,
+}
+
+impl ChangeToolDialog {
+    pub fn new(current_tool: &str, tools: Vec<String>) -> Self {
+        let selected = tools.iter().position(|t| t == current_tool).unwrap_or(0);
+        Self {
+            current_tool: current_tool.to_string(),
+            tools,
+            selected,
+        }
+    }
+
+    /// Submits the chosen tool, or cancels if it is unchanged
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<String> {
+        match key.code {
+            KeyCode::Esc => DialogResult::Cancel,
+            KeyCode::Enter => match self.tools.get(self.selected) {
+                Some(tool) if *tool != self.current_tool => DialogResult::Submit(tool.clone()),
+                _ => DialogResult::Cancel,
+            },
+            KeyCode::Up | KeyCode::Char('k') => {
+                self.selected = self.selected.saturating_sub(1);
+                DialogResult::Continue
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                if self.selected + 1 < self.tools.len() {
+                    self.selected += 1;
+                }
+                DialogResult::Continue
+            }
+            _ => DialogResult::Continue,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let height = self.tools.len().max(1) as u16 + 6;
+        let dialog_area = super::centered_rect(area, 40, height);
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .title(" Change Tool ")
+            .title_style(Style::default().fg(theme.title).bold());
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Min(1), Constraint::Length(1)])
+            .split(inner);
+
+        let lines: Vec<Line> = if self.tools.is_empty() {
+            vec![Line::from(Span::styled(
+                "No supported tools found in PATH",
+                Style::default().fg(theme.dimmed),
+            ))]
+        } else {
+            self.tools
+                .iter()
+                .enumerate()
+                .map(|(i, tool)| {
+                    let is_selected = i == self.selected;
+                    let marker = if is_selected { "> " } else { "  " };
+                    let style = if is_selected {
+                        Style::default().fg(theme.accent).bold()
+                    } else {
+                        Style::default().fg(theme.text)
+                    };
+                    let mut spans = vec![Span::styled(format!("{}{}", marker, tool), style)];
+                    if *tool == self.current_tool {
+                        spans.push(Span::styled(
+                            " (current)",
+                            Style::default().fg(theme.dimmed),
+                        ));
+                    }
+                    Line::from(spans)
+                })
+                .collect()
+        };
+        frame.render_widget(Paragraph::new(lines), chunks[0]);
+
+        let hint = Line::from(vec![
+            Span::styled("Enter", Style::default().fg(theme.hint)),
+            Span::raw(" select  "),
+            Span::styled("Esc", Style::default().fg(theme.hint)),
+            Span::raw(" cancel"),
+        ]);
+        frame.render_widget(Paragraph::new(hint), chunks[1]);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    fn tools() -> Vec<String> {
+        vec!["claude".to_string(), "opencode".to_string()]
+    }
+
+    #[test]
+    fn test_starts_on_current_tool() {
+        let dialog = ChangeToolDialog::new("opencode", tools());
+        assert_eq!(dialog.selected, 1);
+    }
+
+    #[test]
+    fn test_enter_on_current_tool_cancels() {
+        let mut dialog = ChangeToolDialog::new("claude", tools());
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Cancel
+        ));
+    }
+
+    #[test]
+    fn test_select_other_tool() {
+        let mut dialog = ChangeToolDialog::new("claude", tools());
+        dialog.handle_key(key(KeyCode::Down));
+        dialog.handle_key(key(KeyCode::Down));
+        match dialog.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(tool) => assert_eq!(tool, "opencode"),
+            _ => panic!("Expected Submit"),
+        }
+    }
+
+    #[test]
+    fn test_missing_current_tool_selects_first() {
+        let mut dialog = ChangeToolDialog::new("vibe", tools());
+        match dialog.handle_key(key(KeyCode::Enter)) {
+            DialogResult::Submit(tool) => assert_eq!(tool, "claude"),
+            _ => panic!("Expected Submit"),
+        }
+    }
+
+    #[test]
+    fn test_empty_tool_list_cancels() {
+        let mut dialog = ChangeToolDialog::new("vibe", Vec::new());
+        dialog.handle_key(key(KeyCode::Down));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Cancel
+        ));
+    }
+}
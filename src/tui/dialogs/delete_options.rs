@@ -20,6 +20,15 @@ pub struct DeleteOptions {
 pub struct DeleteDialogConfig {
     pub worktree_branch: Option<String>,
     pub has_sandbox: bool,
+    /// Number of other sessions using the same worktree. A shared worktree is
+    /// never offered for deletion since removing it would break those sessions.
+    pub worktree_shared_by: usize,
+}
+
+impl DeleteDialogConfig {
+    fn can_delete_worktree(&self) -> bool {
+        self.worktree_branch.is_some() && self.worktree_shared_by == 0
+    }
 }
 
 /// Focus states for navigation
@@ -47,7 +56,7 @@ impl UnifiedDeleteDialog {
 
         let mut focusable_elements = Vec::new();
 
-        if config.worktree_branch.is_some() {
+        if config.can_delete_worktree() {
             focusable_elements.push(FocusElement::WorktreeCheckbox);
             focusable_elements.push(FocusElement::BranchCheckbox);
         }
@@ -58,7 +67,7 @@ impl UnifiedDeleteDialog {
         focusable_elements.push(FocusElement::YesButton);
         focusable_elements.push(FocusElement::NoButton);
 
-        let initial_focus = if config.worktree_branch.is_some() {
+        let initial_focus = if config.can_delete_worktree() {
             FocusElement::WorktreeCheckbox
         } else if config.has_sandbox {
             FocusElement::SandboxCheckbox
@@ -67,8 +76,8 @@ impl UnifiedDeleteDialog {
         };
 
         let options = DeleteOptions {
-            delete_worktree: config.worktree_branch.is_some() && user_config.worktree.auto_cleanup,
-            delete_branch: config.worktree_branch.is_some()
+            delete_worktree: config.can_delete_worktree() && user_config.worktree.auto_cleanup,
+            delete_branch: config.can_delete_worktree()
                 && user_config.worktree.delete_branch_on_cleanup,
             delete_sandbox: config.has_sandbox && user_config.sandbox.auto_cleanup,
         };
@@ -185,9 +194,15 @@ impl UnifiedDeleteDialog {
 
     pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let has_worktree = self.config.worktree_branch.is_some();
+        let shared_worktree = has_worktree && !self.config.can_delete_worktree();
         let has_sandbox = self.config.has_sandbox;
-        // Count checkboxes: worktree + branch (if worktree exists) + sandbox
-        let checkbox_count = if has_worktree { 2 } else { 0 } + (has_sandbox as u16);
+        // Count rows: worktree + branch checkboxes (or one note if the worktree is shared) + sandbox
+        let worktree_rows = match (has_worktree, shared_worktree) {
+            (false, _) => 0,
+            (true, false) => 2,
+            (true, true) => 1,
+        };
+        let checkbox_count = worktree_rows + (has_sandbox as u16);
 
         let dialog_width = 55;
         // Add extra height for spacing: 1 after message, 1 before buttons, 1 before hints
@@ -244,7 +259,22 @@ impl UnifiedDeleteDialog {
         chunk_idx += 1; // skip spacer
 
         if checkbox_count > 0 {
-            if let Some(branch) = &self.config.worktree_branch {
+            if shared_worktree {
+                let note = format!(
+                    "Worktree kept: shared by {} other session{}",
+                    self.config.worktree_shared_by,
+                    if self.config.worktree_shared_by == 1 {
+                        ""
+                    } else {
+                        "s"
+                    }
+                );
+                frame.render_widget(
+                    Paragraph::new(note).style(Style::default().fg(theme.waiting)),
+                    chunks[chunk_idx],
+                );
+                chunk_idx += 1;
+            } else if let Some(branch) = &self.config.worktree_branch {
                 let focused = self.focus == FocusElement::WorktreeCheckbox;
                 self.render_checkbox(
                     frame,
@@ -292,7 +322,12 @@ impl UnifiedDeleteDialog {
         chunk_idx += 1;
         chunk_idx += 1; // skip spacer
 
-        self.render_hints(frame, chunks[chunk_idx], theme, checkbox_count > 0);
+        self.render_hints(
+            frame,
+            chunks[chunk_idx],
+            theme,
+            self.focusable_elements.len() > 2,
+        );
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -408,10 +443,35 @@ mod tests {
             DeleteDialogConfig {
                 worktree_branch: Some("feature-branch".to_string()),
                 has_sandbox: true,
+                worktree_shared_by: 0,
             },
         )
     }
 
+    #[test]
+    fn test_shared_worktree_is_never_deleted() {
+        let mut dialog = UnifiedDeleteDialog::new(
+            "Test Session".to_string(),
+            DeleteDialogConfig {
+                worktree_branch: Some("feature-branch".to_string()),
+                has_sandbox: false,
+                worktree_shared_by: 1,
+            },
+        );
+        assert_eq!(dialog.focus, FocusElement::NoButton);
+        assert!(!dialog
+            .focusable_elements
+            .contains(&FocusElement::WorktreeCheckbox));
+
+        match dialog.handle_key(key(KeyCode::Char('y'))) {
+            DialogResult::Submit(options) => {
+                assert!(!options.delete_worktree);
+                assert!(!options.delete_branch);
+            }
+            _ => panic!("Expected Submit"),
+        }
+    }
+
     #[test]
     fn test_default_options() {
         let options = DeleteOptions::default();
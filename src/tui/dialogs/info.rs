@@ -7,6 +7,8 @@ use ratatui::widgets::*;
 use super::DialogResult;
 use crate::tui::styles::Theme;
 
+const DIALOG_WIDTH: u16 = 50;
+
 pub struct InfoDialog {
     title: String,
     message: String,
@@ -27,8 +29,21 @@ impl InfoDialog {
         }
     }
 
+    /// Rows the message takes once wrapped, plus one spare since word
+    /// wrapping can break lines earlier than a character count suggests
+    fn message_height(&self) -> u16 {
+        let width = (DIALOG_WIDTH - 4) as usize;
+        let rows: usize = self
+            .message
+            .lines()
+            .map(|line| line.chars().count().div_ceil(width).max(1))
+            .sum();
+        (rows + 1).min(u16::MAX as usize) as u16
+    }
+
     pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
-        let dialog_area = super::centered_rect(area, 50, 9);
+        let height = (self.message_height() + 6).max(9);
+        let dialog_area = super::centered_rect(area, DIALOG_WIDTH, height);
 
         frame.render_widget(Clear, dialog_area);
 
@@ -102,4 +117,13 @@ mod tests {
         let result = dialog.handle_key(key(KeyCode::Char('x')));
         assert!(matches!(result, DialogResult::Continue));
     }
+
+    #[test]
+    fn test_long_messages_grow_the_dialog() {
+        let short = InfoDialog::new("Test", "Message");
+        assert_eq!(short.message_height(), 2);
+
+        let long = InfoDialog::new("Test", &format!("Copied:\n\n{}", "x".repeat(100)));
+        assert_eq!(long.message_height(), 6);
+    }
 }
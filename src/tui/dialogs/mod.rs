@@ -1,5 +1,6 @@
 //! TUI dialog components
 
+mod change_tool;
 mod changelog;
 mod confirm;
 mod delete_options;
@@ -8,8 +9,10 @@ mod hook_trust;
 mod info;
 mod new_session;
 mod rename;
+mod unsaved_changes;
 mod welcome;
 
+pub use change_tool::ChangeToolDialog;
 pub use changelog::ChangelogDialog;
 pub use confirm::ConfirmDialog;
 pub use delete_options::{DeleteDialogConfig, DeleteOptions, UnifiedDeleteDialog};
@@ -18,6 +21,7 @@ pub use hook_trust::{HookTrustAction, HookTrustDialog};
 pub use info::InfoDialog;
 pub use new_session::{NewSessionData, NewSessionDialog};
 pub use rename::{RenameData, RenameDialog};
+pub use unsaved_changes::{UnsavedChangesAction, UnsavedChangesDialog};
 pub use welcome::WelcomeDialog;
 
 pub enum DialogResult<T> {
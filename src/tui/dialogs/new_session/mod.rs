@@ -5,16 +5,23 @@ mod render;
 #[cfg(test)]
 mod tests;
 
+use std::collections::HashMap;
+use std::path::Path;
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
-use super::DialogResult;
+use super::{ConfirmDialog, DialogResult};
 use crate::docker;
-use crate::session::repo_config::HookProgress;
+use crate::session::builder::{check_project_dir, InstanceParams};
+use crate::session::repo_config::{load_repo_config, merge_repo_config, HookProgress};
 #[cfg(test)]
 use crate::session::Config;
-use crate::session::{civilizations, resolve_config};
+use crate::session::{
+    civilizations, expand_path, resolve_config, resolve_config_with_repo, NewSessionOptions,
+    PresetConfig, SessionConfig,
+};
 use crate::tmux::AvailableTools;
 use crate::tui::components::{DirPicker, DirPickerResult, ListPicker, ListPickerResult};
 
@@ -74,12 +81,32 @@ pub(super) const FIELD_HELP: &[FieldHelp] = &[
     },
 ];
 
+/// The form's focusable fields, in tab order. Which of them are present
+/// depends on the dialog state (see `NewSessionDialog::visible_fields`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum Field {
+    Title,
+    Path,
+    Group,
+    Tool,
+    Worktree,
+    NewBranch,
+    Sandbox,
+    SandboxImage,
+    YoloMode,
+    Env,
+    EnvValues,
+}
+
 #[derive(Clone)]
 pub struct NewSessionData {
     pub title: String,
     pub path: String,
     pub group: String,
     pub tool: String,
+    /// Command to launch instead of the tool's default (from a preset).
+    /// Empty uses the default.
+    pub command: String,
     pub worktree_branch: Option<String>,
     pub create_new_branch: bool,
     pub sandbox: bool,
@@ -90,6 +117,32 @@ pub struct NewSessionData {
     pub extra_env_keys: Vec<String>,
     /// Custom KEY=VALUE environment variables to inject into the container.
     pub extra_env_values: Vec<String>,
+    /// Launch the tool straight away. When false the session is set up
+    /// (worktree, container, on_create hooks) and the tool starts on first
+    /// attach.
+    pub start: bool,
+}
+
+impl From<NewSessionData> for InstanceParams {
+    fn from(data: NewSessionData) -> Self {
+        Self {
+            title: data.title,
+            path: data.path,
+            group: data.group,
+            tool: data.tool,
+            command: data.command,
+            worktree_branch: data.worktree_branch,
+            create_new_branch: data.create_new_branch,
+            sandbox: data.sandbox,
+            sandbox_image: data.sandbox_image,
+            yolo_mode: data.yolo_mode,
+            extra_env_keys: data.extra_env_keys,
+            extra_env_values: data.extra_env_values,
+            extra_volumes: Vec::new(),
+            network: None,
+            parent_session_id: None,
+        }
+    }
 }
 
 /// Spinner frames for loading animation
@@ -104,6 +157,8 @@ pub struct NewSessionDialog {
     pub(super) focused_field: usize,
     pub(super) available_tools: Vec<&'static str>,
     pub(super) existing_titles: Vec<String>,
+    /// `[session]` config used to name the session when Title is left empty
+    pub(super) session_config: SessionConfig,
     pub(super) worktree_branch: Input,
     pub(super) create_new_branch: bool,
     pub(super) sandbox_enabled: bool,
@@ -130,6 +185,14 @@ pub struct NewSessionDialog {
     pub(super) group_picker: ListPicker,
     pub(super) branch_picker: ListPicker,
     pub(super) dir_picker: DirPicker,
+    /// `[presets]` from the config, applied with Ctrl+T
+    pub(super) presets: HashMap<String, PresetConfig>,
+    pub(super) preset_picker: ListPicker,
+    /// Name of the last applied preset, shown in the dialog title
+    pub(super) preset: Option<String>,
+    /// The applied preset's command and the tool it is for. Used only while
+    /// that tool stays selected.
+    pub(super) preset_command: Option<(String, String)>,
     pub(super) error_message: Option<String>,
     pub(super) show_help: bool,
     /// Whether the dialog is in loading state (creating session in background)
@@ -138,6 +201,24 @@ pub struct NewSessionDialog {
     pub(super) spinner_frame: usize,
     /// Whether a Docker image pull will be needed (image not present locally)
     pub(super) needs_image_pull: bool,
+    /// Confirmation shown before creating a sandboxed session whose image is
+    /// not present locally, with whether the session should start
+    pub(super) pull_confirm: Option<(ConfirmDialog, bool)>,
+    /// Image the user has agreed to pull
+    pub(super) pull_confirmed_image: Option<String>,
+    /// Checks whether an image is present locally
+    pub(super) image_present: fn(&str) -> bool,
+    /// Offline mode: a missing image is an error rather than a pull
+    pub(super) offline: bool,
+    /// Running sandbox containers allowed before asking (0 = no limit)
+    pub(super) max_containers: u64,
+    /// Confirmation shown when another sandbox would exceed `max_containers`,
+    /// with whether the session should start
+    pub(super) container_limit_confirm: Option<(ConfirmDialog, bool)>,
+    /// Whether the user has agreed to go over the container limit
+    pub(super) container_limit_confirmed: bool,
+    /// Counts the running sandbox containers
+    pub(super) running_containers: fn() -> usize,
     /// Whether hooks are being executed during loading
     pub(super) has_hooks: bool,
     /// The currently running hook command
@@ -235,15 +316,22 @@ impl NewSessionDialog {
         existing_groups: Vec<String>,
         profile: &str,
     ) -> Self {
-        let current_dir = std::env::current_dir()
-            .map(|p| p.to_string_lossy().to_string())
-            .unwrap_or_default();
-
         let available_tools = tools.available_list();
         let docker_available = docker::is_docker_available();
 
-        // Load resolved config (global merged with profile overrides)
-        let config = resolve_config(profile).unwrap_or_default();
+        // Load resolved config (global merged with profile overrides, then
+        // the overrides of a repo config in the start directory)
+        let mut config = resolve_config(profile).unwrap_or_default();
+
+        let start_dir = config
+            .session
+            .default_path()
+            .or_else(|| std::env::current_dir().ok())
+            .map(|p| p.to_string_lossy().to_string())
+            .unwrap_or_default();
+        if let Ok(Some(repo)) = load_repo_config(Path::new(&start_dir)) {
+            config = merge_repo_config(config, &repo);
+        }
 
         // Determine default tool index based on config
         let tool_index = if let Some(ref default_tool) = config.session.default_tool {
@@ -272,23 +360,34 @@ impl NewSessionDialog {
             (Vec::new(), Vec::new())
         };
 
-        Self {
+        let mut dialog = Self {
             profile: profile.to_string(),
             title: Input::default(),
-            path: Input::new(current_dir),
-            group: Input::default(),
+            path: Input::new(start_dir),
+            group: Input::new(
+                config
+                    .session
+                    .default_group()
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
             tool_index,
             focused_field: 0,
             available_tools,
             existing_titles,
+            session_config: config.session.clone(),
             existing_groups,
             group_picker: ListPicker::new("Select Group"),
             branch_picker: ListPicker::new("Select Branch"),
             dir_picker: DirPicker::new(),
+            presets: config.presets.clone(),
+            preset_picker: ListPicker::new("Select Preset"),
+            preset: None,
+            preset_command: None,
             worktree_branch: Input::default(),
             create_new_branch: true,
             sandbox_enabled,
-            sandbox_image: Input::new(docker::effective_default_image()),
+            sandbox_image: Input::new(config.sandbox.default_image.clone()),
             docker_available,
             yolo_mode,
             extra_env_keys,
@@ -306,10 +405,24 @@ impl NewSessionDialog {
             loading: false,
             spinner_frame: 0,
             needs_image_pull: false,
+            pull_confirm: None,
+            pull_confirmed_image: None,
+            image_present: docker::image_exists_locally,
+            offline: crate::session::is_offline(),
+            max_containers: config.sandbox.max_concurrent_containers,
+            container_limit_confirm: None,
+            container_limit_confirmed: false,
+            running_containers: docker::count_running_sandbox_containers,
             has_hooks: false,
             current_hook: None,
             hook_output: Vec::new(),
+        };
+        if config.ui.remember_new_session {
+            if let Some(ref last) = config.app_state.last_new_session {
+                dialog.apply_last_options(last);
+            }
         }
+        dialog
     }
 
     /// Set whether hooks will be executed during session creation
@@ -367,15 +480,26 @@ impl NewSessionDialog {
             profile: "default".to_string(),
             title: Input::default(),
             path: Input::new(path),
-            group: Input::default(),
+            group: Input::new(
+                config
+                    .session
+                    .default_group()
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
             tool_index,
             focused_field: 0,
             available_tools: tools,
             existing_titles: Vec::new(),
+            session_config: config.session.clone(),
             existing_groups: Vec::new(),
             group_picker: ListPicker::new("Select Group"),
             branch_picker: ListPicker::new("Select Branch"),
             dir_picker: DirPicker::new(),
+            presets: config.presets.clone(),
+            preset_picker: ListPicker::new("Select Preset"),
+            preset: None,
+            preset_command: None,
             worktree_branch: Input::default(),
             create_new_branch: true,
             sandbox_enabled: false,
@@ -397,6 +521,14 @@ impl NewSessionDialog {
             loading: false,
             spinner_frame: 0,
             needs_image_pull: false,
+            pull_confirm: None,
+            pull_confirmed_image: None,
+            image_present: |_| true,
+            offline: false,
+            max_containers: config.sandbox.max_concurrent_containers,
+            container_limit_confirm: None,
+            container_limit_confirmed: false,
+            running_containers: || 0,
             has_hooks: false,
             current_hook: None,
             hook_output: Vec::new(),
@@ -414,10 +546,15 @@ impl NewSessionDialog {
             focused_field: 0,
             available_tools: tools,
             existing_titles: Vec::new(),
+            session_config: SessionConfig::default(),
             existing_groups: Vec::new(),
             group_picker: ListPicker::new("Select Group"),
             branch_picker: ListPicker::new("Select Branch"),
             dir_picker: DirPicker::new(),
+            presets: HashMap::new(),
+            preset_picker: ListPicker::new("Select Preset"),
+            preset: None,
+            preset_command: None,
             worktree_branch: Input::default(),
             create_new_branch: true,
             sandbox_enabled: false,
@@ -439,6 +576,14 @@ impl NewSessionDialog {
             loading: false,
             spinner_frame: 0,
             needs_image_pull: false,
+            pull_confirm: None,
+            pull_confirmed_image: None,
+            image_present: |_| true,
+            offline: false,
+            max_containers: 0,
+            container_limit_confirm: None,
+            container_limit_confirmed: false,
+            running_containers: || 0,
             has_hooks: false,
             current_hook: None,
             hook_output: Vec::new(),
@@ -449,7 +594,18 @@ impl NewSessionDialog {
         self.error_message = Some(error);
     }
 
+    pub fn error(&self) -> Option<&str> {
+        self.error_message.as_deref()
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<NewSessionData> {
+        let fields = self.visible_fields();
+        let result = self.handle_key_inner(key);
+        self.keep_focused_field(&fields);
+        result
+    }
+
+    fn handle_key_inner(&mut self, key: KeyEvent) -> DialogResult<NewSessionData> {
         // When loading, only allow Esc to cancel
         if self.loading {
             if matches!(key.code, KeyCode::Esc) {
@@ -466,6 +622,34 @@ impl NewSessionDialog {
             return DialogResult::Continue;
         }
 
+        if let Some((confirm, start)) = self.pull_confirm.as_mut() {
+            let start = *start;
+            match confirm.handle_key(key) {
+                DialogResult::Continue => return DialogResult::Continue,
+                DialogResult::Cancel => self.pull_confirm = None,
+                DialogResult::Submit(()) => {
+                    self.pull_confirm = None;
+                    self.pull_confirmed_image = Some(self.sandbox_image.value().trim().to_string());
+                    return self.submit(start);
+                }
+            }
+            return DialogResult::Continue;
+        }
+
+        if let Some((confirm, start)) = self.container_limit_confirm.as_mut() {
+            let start = *start;
+            match confirm.handle_key(key) {
+                DialogResult::Continue => return DialogResult::Continue,
+                DialogResult::Cancel => self.container_limit_confirm = None,
+                DialogResult::Submit(()) => {
+                    self.container_limit_confirm = None;
+                    self.container_limit_confirmed = true;
+                    return self.submit(start);
+                }
+            }
+            return DialogResult::Continue;
+        }
+
         if self.group_picker.is_active() {
             if let ListPickerResult::Selected(value) = self.group_picker.handle_key(key) {
                 self.group = Input::new(value);
@@ -480,66 +664,32 @@ impl NewSessionDialog {
             return DialogResult::Continue;
         }
 
+        if self.preset_picker.is_active() {
+            if let ListPickerResult::Selected(name) = self.preset_picker.handle_key(key) {
+                self.apply_preset(&name);
+            }
+            return DialogResult::Continue;
+        }
+
         if self.dir_picker.is_active() {
             match self.dir_picker.handle_key(key) {
                 DirPickerResult::Selected(path) => {
-                    self.path = Input::new(path);
+                    self.path = Input::new(path.clone());
+                    self.apply_repo_defaults(&path);
                 }
                 DirPickerResult::Cancelled | DirPickerResult::Continue => {}
             }
             return DialogResult::Continue;
         }
 
-        let has_tool_selection = self.available_tools.len() > 1;
-        let has_sandbox = self.docker_available;
-        let has_worktree = !self.worktree_branch.value().is_empty();
-        let sandbox_options_visible = has_sandbox && self.sandbox_enabled;
-        // Fields: title(0), path(1), group(2), [tool(3)], worktree(3/4), [new_branch(4/5)], [sandbox(5/6)], [image(6/7)], [yolo(7/8)], [env(8/9)]
-        let tool_field = if has_tool_selection { 3 } else { usize::MAX };
-        let worktree_field = if has_tool_selection { 4 } else { 3 };
-        let new_branch_field = if has_worktree {
-            worktree_field + 1
-        } else {
-            usize::MAX
-        };
-        let sandbox_field = if has_sandbox {
-            if has_worktree {
-                new_branch_field + 1
-            } else {
-                worktree_field + 1
-            }
-        } else {
-            usize::MAX
-        };
-        let sandbox_image_field = if sandbox_options_visible {
-            sandbox_field + 1
-        } else {
-            usize::MAX
-        };
-        let yolo_mode_field = if sandbox_options_visible {
-            sandbox_image_field + 1
-        } else {
-            usize::MAX
-        };
-        let env_field = if sandbox_options_visible {
-            yolo_mode_field + 1
-        } else {
-            usize::MAX
-        };
-        let env_values_field = if sandbox_options_visible {
-            env_field + 1
-        } else {
-            usize::MAX
-        };
-        let max_field = if sandbox_options_visible {
-            env_values_field + 1
-        } else if has_sandbox {
-            sandbox_field + 1
-        } else if has_worktree {
-            new_branch_field + 1
-        } else {
-            worktree_field + 1
-        };
+        let tool_field = self.field_index(Field::Tool);
+        let worktree_field = self.field_index(Field::Worktree);
+        let new_branch_field = self.field_index(Field::NewBranch);
+        let sandbox_field = self.field_index(Field::Sandbox);
+        let yolo_mode_field = self.field_index(Field::YoloMode);
+        let env_field = self.field_index(Field::Env);
+        let env_values_field = self.field_index(Field::EnvValues);
+        let max_field = self.visible_fields().len();
 
         // Handle env list editing mode
         if self.env_list_expanded && self.focused_field == env_field {
@@ -549,6 +699,24 @@ impl NewSessionDialog {
             return self.handle_env_values_list_key(key);
         }
 
+        // Ctrl+S creates the session without launching the tool
+        if key.code == KeyCode::Char('s') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            return self.submit(false);
+        }
+
+        // Ctrl+T picks a preset
+        if key.code == KeyCode::Char('t') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            if self.presets.is_empty() {
+                self.error_message =
+                    Some("No presets configured. Add [presets.<name>] to config.toml".to_string());
+            } else {
+                let mut names: Vec<String> = self.presets.keys().cloned().collect();
+                names.sort_unstable();
+                self.preset_picker.activate(names);
+            }
+            return DialogResult::Continue;
+        }
+
         // Ctrl+P opens a context-sensitive picker
         if key.code == KeyCode::Char('p') && key.modifiers.contains(KeyModifiers::CONTROL) {
             if self.focused_field == 1 {
@@ -590,43 +758,7 @@ impl NewSessionDialog {
                 self.env_values_selected_index = 0;
                 DialogResult::Continue
             }
-            KeyCode::Enter => {
-                self.error_message = None;
-                let title_value = self.title.value().trim();
-                let final_title = if title_value.is_empty() {
-                    let refs: Vec<&str> = self.existing_titles.iter().map(|s| s.as_str()).collect();
-                    civilizations::generate_random_title(&refs)
-                } else {
-                    title_value.to_string()
-                };
-                let worktree_value = self.worktree_branch.value().trim();
-                let worktree_branch = if worktree_value.is_empty() {
-                    None
-                } else {
-                    Some(worktree_value.to_string())
-                };
-                DialogResult::Submit(NewSessionData {
-                    title: final_title,
-                    path: self.path.value().trim().to_string(),
-                    group: self.group.value().trim().to_string(),
-                    tool: self.available_tools[self.tool_index].to_string(),
-                    worktree_branch,
-                    create_new_branch: self.create_new_branch,
-                    sandbox: self.sandbox_enabled,
-                    sandbox_image: self.sandbox_image.value().trim().to_string(),
-                    yolo_mode: self.sandbox_enabled && self.yolo_mode,
-                    extra_env_keys: if self.sandbox_enabled {
-                        self.extra_env_keys.clone()
-                    } else {
-                        Vec::new()
-                    },
-                    extra_env_values: if self.sandbox_enabled {
-                        self.extra_env_values.clone()
-                    } else {
-                        Vec::new()
-                    },
-                })
-            }
+            KeyCode::Enter => self.submit(true),
             KeyCode::Tab | KeyCode::Down => {
                 self.focused_field = (self.focused_field + 1) % max_field;
                 DialogResult::Continue
@@ -656,30 +788,7 @@ impl NewSessionDialog {
             KeyCode::Left | KeyCode::Right | KeyCode::Char(' ')
                 if self.focused_field == sandbox_field =>
             {
-                self.sandbox_enabled = !self.sandbox_enabled;
-                if self.sandbox_enabled {
-                    // Apply yolo_mode_default and reload env keys/values from config
-                    let config = resolve_config(&self.profile).unwrap_or_default();
-                    self.yolo_mode = config.sandbox.yolo_mode_default;
-                    self.extra_env_keys = config.sandbox.environment.clone();
-                    self.extra_env_values = config
-                        .sandbox
-                        .environment_values
-                        .iter()
-                        .map(|(k, v)| format!("{}={}", k, v))
-                        .collect();
-                } else {
-                    self.yolo_mode = false;
-                    self.extra_env_keys.clear();
-                    self.env_list_expanded = false;
-                    self.env_editing_input = None;
-                    self.extra_env_values.clear();
-                    self.env_values_list_expanded = false;
-                    self.env_values_editing_input = None;
-                    if self.focused_field > sandbox_field {
-                        self.focused_field = sandbox_field;
-                    }
-                }
+                self.set_sandbox_enabled(!self.sandbox_enabled);
                 DialogResult::Continue
             }
             KeyCode::Left | KeyCode::Right | KeyCode::Char(' ')
@@ -705,6 +814,258 @@ impl NewSessionDialog {
         }
     }
 
+    /// Fill the form from a preset. Fields the preset leaves unset keep their
+    /// current values, and everything stays editable afterwards.
+    pub(super) fn apply_preset(&mut self, name: &str) {
+        let Some(preset) = self.presets.get(name).cloned() else {
+            return;
+        };
+        self.error_message = None;
+
+        let tool = match preset.tool() {
+            Ok(tool) => tool,
+            Err(e) => {
+                self.error_message = Some(format!("Preset '{}': {}", name, e));
+                return;
+            }
+        };
+        if let Some(ref tool) = tool {
+            match self.available_tools.iter().position(|t| t == tool) {
+                Some(idx) => self.tool_index = idx,
+                None => {
+                    self.error_message = Some(format!(
+                        "Preset '{}' uses {}, which is not installed",
+                        name, tool
+                    ));
+                    return;
+                }
+            }
+        }
+        self.preset_command = preset
+            .command()
+            .zip(tool)
+            .map(|(cmd, tool)| (tool, cmd.to_string()));
+
+        if let Some(sandbox) = preset.sandbox {
+            if sandbox && !self.docker_available {
+                self.error_message = Some(format!(
+                    "Preset '{}' enables the sandbox, but Docker is not available",
+                    name
+                ));
+            } else {
+                self.sandbox_enabled = sandbox;
+            }
+        }
+        if let Some(image) = preset.sandbox_image {
+            self.sandbox_image = Input::new(image);
+        }
+        if let Some(yolo_mode) = preset.yolo_mode {
+            self.yolo_mode = yolo_mode;
+        }
+        for key in preset.environment {
+            if !self.extra_env_keys.contains(&key) {
+                self.extra_env_keys.push(key);
+            }
+        }
+        let mut values: Vec<_> = preset.environment_values.into_iter().collect();
+        values.sort_unstable();
+        for (key, value) in values {
+            let prefix = format!("{}=", key);
+            self.extra_env_values
+                .retain(|entry| !entry.starts_with(&prefix));
+            self.extra_env_values.push(format!("{}={}", key, value));
+        }
+
+        self.preset = Some(name.to_string());
+    }
+
+    fn set_sandbox_enabled(&mut self, enabled: bool) {
+        self.sandbox_enabled = enabled;
+        if enabled {
+            // Apply yolo_mode_default and reload env keys/values from config,
+            // including a repo config at the chosen path
+            let config = resolve_config_with_repo(&self.profile, Path::new(self.path.value()))
+                .or_else(|_| resolve_config(&self.profile))
+                .unwrap_or_default();
+            self.yolo_mode = config.sandbox.yolo_mode_default;
+            self.extra_env_keys = config.sandbox.environment.clone();
+            self.extra_env_values = config
+                .sandbox
+                .environment_values
+                .iter()
+                .map(|(k, v)| format!("{}={}", k, v))
+                .collect();
+        } else {
+            self.yolo_mode = false;
+            self.extra_env_keys.clear();
+            self.env_list_expanded = false;
+            self.env_editing_input = None;
+            self.extra_env_values.clear();
+            self.env_values_list_expanded = false;
+            self.env_values_editing_input = None;
+        }
+    }
+
+    /// Start from the options submitted last time (`ui.remember_new_session`).
+    /// A remembered tool that is no longer installed is ignored.
+    fn apply_last_options(&mut self, last: &NewSessionOptions) {
+        if let Some(index) = self.available_tools.iter().position(|&t| t == last.tool) {
+            self.tool_index = index;
+        }
+        self.group = Input::new(last.group.clone());
+        let sandbox = self.docker_available && last.sandbox;
+        if sandbox != self.sandbox_enabled {
+            self.set_sandbox_enabled(sandbox);
+        }
+        self.yolo_mode = self.sandbox_enabled && last.yolo_mode;
+        if !last.sandbox_image.is_empty() {
+            self.sandbox_image = Input::new(last.sandbox_image.clone());
+        }
+    }
+
+    /// Fill in the session defaults (tool, group, sandbox and image) that a
+    /// repo config at `path` sets
+    fn apply_repo_defaults(&mut self, path: &str) {
+        let repo = match load_repo_config(Path::new(path)) {
+            Ok(Some(repo)) => repo,
+            Ok(None) => return,
+            Err(e) => {
+                self.error_message = Some(format!("{:#}", e));
+                return;
+            }
+        };
+        if let Some(ref session) = repo.session {
+            if let Some(index) = session
+                .default_tool
+                .as_deref()
+                .and_then(|tool| self.available_tools.iter().position(|&t| t == tool))
+            {
+                self.tool_index = index;
+            }
+            if let Some(group) = session.default_group.as_deref().map(str::trim) {
+                if !group.is_empty() {
+                    self.group = Input::new(group.to_string());
+                }
+            }
+        }
+        if let Some(ref sandbox) = repo.sandbox {
+            if let Some(enabled) = sandbox.enabled_by_default {
+                let enabled = enabled && self.docker_available;
+                if enabled != self.sandbox_enabled {
+                    self.set_sandbox_enabled(enabled);
+                }
+            }
+            if let Some(ref image) = sandbox.default_image {
+                self.sandbox_image = Input::new(image.clone());
+            }
+        }
+    }
+
+    /// Validate the form and submit it. `start` is false when the session
+    /// should be set up without launching the tool.
+    fn submit(&mut self, start: bool) -> DialogResult<NewSessionData> {
+        self.error_message = None;
+        let path = match expand_path(self.path.value().trim()).and_then(|p| {
+            // A missing directory is created with the session, not here
+            check_project_dir(&p, self.session_config.create_dir)?;
+            Ok(p)
+        }) {
+            Ok(p) => p.to_string_lossy().to_string(),
+            Err(e) => {
+                self.error_message = Some(e.to_string());
+                return DialogResult::Continue;
+            }
+        };
+        if self.docker_available
+            && self.sandbox_enabled
+            && self.max_containers > 0
+            && !self.container_limit_confirmed
+        {
+            let running = (self.running_containers)();
+            if running as u64 >= self.max_containers {
+                let message = format!(
+                    "{} sandbox containers are already running (limit {}). Another may exhaust memory. Create it anyway?",
+                    running, self.max_containers
+                );
+                self.container_limit_confirm = Some((
+                    ConfirmDialog::new("Container Limit", &message, "container_limit"),
+                    start,
+                ));
+                return DialogResult::Continue;
+            }
+        }
+        if self.docker_available && self.sandbox_enabled {
+            let image = self.sandbox_image.value().trim();
+            if !image.is_empty()
+                && self.pull_confirmed_image.as_deref() != Some(image)
+                && !(self.image_present)(image)
+            {
+                if self.offline {
+                    self.error_message = Some(format!(
+                        "offline: image not present: {} (pull it while online)",
+                        image
+                    ));
+                    return DialogResult::Continue;
+                }
+                let message = format!(
+                    "{} is not present locally and will be pulled first (can be several GB). Continue?",
+                    image
+                );
+                self.pull_confirm = Some((
+                    ConfirmDialog::new("Pull Image", &message, "pull_image"),
+                    start,
+                ));
+                return DialogResult::Continue;
+            }
+        }
+        let worktree_value = self.worktree_branch.value().trim();
+        let worktree_branch = if worktree_value.is_empty() {
+            None
+        } else {
+            Some(worktree_value.to_string())
+        };
+        let title_value = self.title.value().trim();
+        let final_title = if title_value.is_empty() {
+            let refs: Vec<&str> = self.existing_titles.iter().map(|s| s.as_str()).collect();
+            civilizations::generate_title(
+                &self.session_config,
+                worktree_branch.as_deref(),
+                &path,
+                &refs,
+            )
+        } else {
+            title_value.to_string()
+        };
+        let tool = self.available_tools[self.tool_index].to_string();
+        let command = match &self.preset_command {
+            Some((preset_tool, cmd)) if *preset_tool == tool => cmd.clone(),
+            _ => String::new(),
+        };
+        DialogResult::Submit(NewSessionData {
+            title: final_title,
+            path,
+            group: self.group.value().trim().to_string(),
+            tool,
+            command,
+            worktree_branch,
+            create_new_branch: self.create_new_branch,
+            sandbox: self.sandbox_enabled,
+            sandbox_image: self.sandbox_image.value().trim().to_string(),
+            yolo_mode: self.sandbox_enabled && self.yolo_mode,
+            extra_env_keys: if self.sandbox_enabled {
+                self.extra_env_keys.clone()
+            } else {
+                Vec::new()
+            },
+            extra_env_values: if self.sandbox_enabled {
+                self.extra_env_values.clone()
+            } else {
+                Vec::new()
+            },
+            start,
+        })
+    }
+
     /// Handle key events when the env list is expanded
     fn handle_env_list_key(&mut self, key: KeyEvent) -> DialogResult<NewSessionData> {
         let validate =
@@ -734,37 +1095,62 @@ impl NewSessionDialog {
         )
     }
 
-    fn current_input_mut(&mut self) -> &mut Input {
-        let has_tool_selection = self.available_tools.len() > 1;
-        let has_worktree = !self.worktree_branch.value().is_empty();
-
-        let worktree_field = if has_tool_selection { 4 } else { 3 };
-        let new_branch_field = if has_worktree {
-            worktree_field + 1
-        } else {
-            usize::MAX
-        };
-        let sandbox_field = if self.docker_available {
-            if has_worktree {
-                new_branch_field + 1
-            } else {
-                worktree_field + 1
+    /// The fields currently shown, in tab order. `focused_field` indexes
+    /// into this list.
+    pub(super) fn visible_fields(&self) -> Vec<Field> {
+        let mut fields = vec![Field::Title, Field::Path, Field::Group];
+        if self.available_tools.len() > 1 {
+            fields.push(Field::Tool);
+        }
+        fields.push(Field::Worktree);
+        if !self.worktree_branch.value().is_empty() {
+            fields.push(Field::NewBranch);
+        }
+        if self.docker_available {
+            fields.push(Field::Sandbox);
+            if self.sandbox_enabled {
+                fields.extend([
+                    Field::SandboxImage,
+                    Field::YoloMode,
+                    Field::Env,
+                    Field::EnvValues,
+                ]);
             }
-        } else {
-            usize::MAX
-        };
-        let sandbox_image_field = if self.docker_available && self.sandbox_enabled {
-            sandbox_field + 1
-        } else {
-            usize::MAX
-        };
+        }
+        fields
+    }
+
+    /// Index of `field` in the current layout, or `usize::MAX` if hidden
+    pub(super) fn field_index(&self, field: Field) -> usize {
+        self.visible_fields()
+            .iter()
+            .position(|&f| f == field)
+            .unwrap_or(usize::MAX)
+    }
 
-        match self.focused_field {
-            0 => &mut self.title,
-            1 => &mut self.path,
-            2 => &mut self.group,
-            n if n == worktree_field => &mut self.worktree_branch,
-            n if n == sandbox_image_field => &mut self.sandbox_image,
+    /// Keep focus on the same logical field after the layout changed.
+    /// Fields appear and disappear as the form is edited (New Branch with
+    /// the worktree branch, the sandbox options with the sandbox checkbox),
+    /// which would otherwise shift focus onto a neighbouring field. If the
+    /// focused field was hidden, focus moves to the nearest field before it.
+    fn keep_focused_field(&mut self, previous: &[Field]) {
+        let fields = self.visible_fields();
+        if fields == previous || self.focused_field >= previous.len() {
+            return;
+        }
+        self.focused_field = previous[..=self.focused_field]
+            .iter()
+            .rev()
+            .find_map(|field| fields.iter().position(|f| f == field))
+            .unwrap_or(0);
+    }
+
+    fn current_input_mut(&mut self) -> &mut Input {
+        match self.visible_fields().get(self.focused_field) {
+            Some(Field::Path) => &mut self.path,
+            Some(Field::Group) => &mut self.group,
+            Some(Field::Worktree) => &mut self.worktree_branch,
+            Some(Field::SandboxImage) => &mut self.sandbox_image,
             _ => &mut self.title,
         }
     }
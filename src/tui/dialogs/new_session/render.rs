@@ -3,7 +3,7 @@
 use ratatui::prelude::*;
 use ratatui::widgets::*;
 
-use super::{NewSessionDialog, FIELD_HELP, HELP_DIALOG_WIDTH, SPINNER_FRAMES};
+use super::{Field, NewSessionDialog, FIELD_HELP, HELP_DIALOG_WIDTH, SPINNER_FRAMES};
 use crate::tui::components::render_text_field;
 use crate::tui::styles::Theme;
 
@@ -81,7 +81,10 @@ impl NewSessionDialog {
         let block = Block::default()
             .borders(Borders::ALL)
             .border_style(Style::default().fg(theme.accent))
-            .title(" New Session ")
+            .title(match &self.preset {
+                Some(name) => format!(" New Session (preset: {}) ", name),
+                None => " New Session ".to_string(),
+            })
             .title_style(Style::default().fg(theme.title).bold());
 
         let inner = block.inner(dialog_area);
@@ -129,8 +132,8 @@ impl NewSessionDialog {
         }
 
         // Tool (always shown, interactive or read-only)
-        let worktree_field = if has_tool_selection { 4 } else { 3 };
-        let is_tool_focused = self.focused_field == 3;
+        let worktree_field = self.field_index(Field::Worktree);
+        let is_tool_focused = self.focused_field == self.field_index(Field::Tool);
 
         if has_tool_selection {
             let label_style = if is_tool_focused {
@@ -186,9 +189,8 @@ impl NewSessionDialog {
         ci += 1;
 
         // New Branch checkbox (only when worktree is set)
-        let new_branch_field = worktree_field + 1;
         if has_worktree {
-            let is_nb_focused = self.focused_field == new_branch_field;
+            let is_nb_focused = self.focused_field == self.field_index(Field::NewBranch);
             let nb_label_style = if is_nb_focused {
                 Style::default().fg(theme.accent).underlined()
             } else {
@@ -224,12 +226,7 @@ impl NewSessionDialog {
 
         // Sandbox checkbox (only when Docker available)
         if has_sandbox {
-            let sandbox_field = if has_worktree {
-                new_branch_field + 1
-            } else {
-                worktree_field + 1
-            };
-            let is_sandbox_focused = self.focused_field == sandbox_field;
+            let is_sandbox_focused = self.focused_field == self.field_index(Field::Sandbox);
             let sandbox_label_style = if is_sandbox_focused {
                 Style::default().fg(theme.accent).underlined()
             } else {
@@ -261,7 +258,7 @@ impl NewSessionDialog {
 
             if sandbox_options_visible {
                 // Image field
-                let sandbox_image_field = sandbox_field + 1;
+                let sandbox_image_field = self.field_index(Field::SandboxImage);
                 render_text_field(
                     frame,
                     chunks[ci],
@@ -274,7 +271,7 @@ impl NewSessionDialog {
                 ci += 1;
 
                 // YOLO Mode checkbox
-                let yolo_mode_field = sandbox_image_field + 1;
+                let yolo_mode_field = self.field_index(Field::YoloMode);
                 let is_yolo_focused = self.focused_field == yolo_mode_field;
                 let yolo_label_style = if is_yolo_focused {
                     Style::default().fg(theme.accent).underlined()
@@ -306,12 +303,12 @@ impl NewSessionDialog {
                 ci += 1;
 
                 // Environment variables field
-                let env_field = yolo_mode_field + 1;
+                let env_field = self.field_index(Field::Env);
                 self.render_env_field(frame, chunks[ci], env_field, theme);
                 ci += 1;
 
                 // Environment values field (KEY=VALUE)
-                let env_values_field = env_field + 1;
+                let env_values_field = self.field_index(Field::EnvValues);
                 self.render_env_values_field(frame, chunks[ci], env_values_field, theme);
                 ci += 1;
             }
@@ -370,6 +367,18 @@ impl NewSessionDialog {
         if self.dir_picker.is_active() {
             self.dir_picker.render(frame, area, theme);
         }
+
+        if let Some((confirm, _)) = &self.pull_confirm {
+            confirm.render(frame, area, theme);
+        }
+
+        if let Some((confirm, _)) = &self.container_limit_confirm {
+            confirm.render(frame, area, theme);
+        }
+
+        if self.preset_picker.is_active() {
+            self.preset_picker.render(frame, area, theme);
+        }
     }
 
     fn render_env_field(&self, frame: &mut Frame, area: Rect, env_field: usize, theme: &Theme) {
@@ -602,7 +611,7 @@ impl NewSessionDialog {
         let show_sandbox_options_help = has_sandbox && self.sandbox_enabled;
 
         let dialog_width: u16 = HELP_DIALOG_WIDTH;
-        let base_height: u16 = 17;
+        let base_height: u16 = 20;
         let dialog_height: u16 = base_height
             + if has_tool_selection { 3 } else { 0 }
             + if has_sandbox { 3 } else { 0 }
@@ -654,6 +663,27 @@ impl NewSessionDialog {
             lines.push(Line::from(""));
         }
 
+        lines.push(Line::from(vec![
+            Span::styled("Enter", Style::default().fg(theme.hint)),
+            Span::styled(
+                " creates and starts the session, ",
+                Style::default().fg(theme.text),
+            ),
+            Span::styled("Ctrl+S", Style::default().fg(theme.hint)),
+            Span::styled(
+                " creates it without starting the tool",
+                Style::default().fg(theme.text),
+            ),
+        ]));
+        lines.push(Line::from(vec![
+            Span::styled("Ctrl+T", Style::default().fg(theme.hint)),
+            Span::styled(
+                " fills the form from a [presets.<name>] entry in config.toml",
+                Style::default().fg(theme.text),
+            ),
+        ]));
+        lines.push(Line::from(""));
+
         lines.push(Line::from(vec![
             Span::styled("Press ", Style::default().fg(theme.dimmed)),
             Span::styled("?", Style::default().fg(theme.hint)),
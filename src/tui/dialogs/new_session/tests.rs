@@ -1,5 +1,7 @@
 use super::*;
-use crate::session::{merge_configs, Config, ProfileConfig, SessionConfigOverride};
+use crate::session::{
+    merge_configs, Config, NewSessionOptions, PresetConfig, ProfileConfig, SessionConfigOverride,
+};
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 fn key(code: KeyCode) -> KeyEvent {
@@ -10,19 +12,33 @@ fn shift_key(code: KeyCode) -> KeyEvent {
     KeyEvent::new(code, KeyModifiers::SHIFT)
 }
 
+thread_local! {
+    // Each test runs on its own thread, so each gets its own directory
+    static TEST_DIR: tempfile::TempDir = tempfile::TempDir::new().unwrap();
+}
+
+/// An existing directory named "project", since submitting checks the path
+fn project_dir() -> String {
+    TEST_DIR.with(|temp| {
+        let dir = temp.path().join("project");
+        std::fs::create_dir_all(&dir).unwrap();
+        dir.to_string_lossy().to_string()
+    })
+}
+
 fn single_tool_dialog() -> NewSessionDialog {
-    NewSessionDialog::new_with_tools(vec!["claude"], "/tmp/project".to_string())
+    NewSessionDialog::new_with_tools(vec!["claude"], project_dir())
 }
 
 fn multi_tool_dialog() -> NewSessionDialog {
-    NewSessionDialog::new_with_tools(vec!["claude", "opencode"], "/tmp/project".to_string())
+    NewSessionDialog::new_with_tools(vec!["claude", "opencode"], project_dir())
 }
 
 #[test]
 fn test_initial_state() {
     let dialog = single_tool_dialog();
     assert_eq!(dialog.title.value(), "");
-    assert_eq!(dialog.path.value(), "/tmp/project");
+    assert_eq!(dialog.path.value(), project_dir());
     assert_eq!(dialog.group.value(), "");
     assert_eq!(dialog.focused_field, 0);
     assert_eq!(dialog.tool_index, 0);
@@ -48,7 +64,7 @@ fn test_enter_submits_with_auto_title() {
                 "Expected a civilization name, got: {}",
                 data.title
             );
-            assert_eq!(data.path, "/tmp/project");
+            assert_eq!(data.path, project_dir());
             assert_eq!(data.group, "");
             assert_eq!(data.tool, "claude");
         }
@@ -69,6 +85,200 @@ fn test_enter_preserves_custom_title() {
     }
 }
 
+#[test]
+fn test_enter_expands_tilde_in_path() {
+    let mut dialog = single_tool_dialog();
+    dialog.title = Input::new("Expanded".to_string());
+    dialog.path = Input::new("~".to_string());
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => {
+            let expected = dirs::home_dir().unwrap();
+            assert_eq!(data.path, expected.to_string_lossy());
+        }
+        _ => panic!("Expected Submit"),
+    }
+}
+
+#[test]
+fn test_enter_with_unset_path_variable_shows_error() {
+    let mut dialog = single_tool_dialog();
+    dialog.path = Input::new("$AOE_TEST_UNSET_DIALOG_VAR/project".to_string());
+    let result = dialog.handle_key(key(KeyCode::Enter));
+    assert!(matches!(result, DialogResult::Continue));
+    assert!(dialog
+        .error_message
+        .as_deref()
+        .is_some_and(|e| e.contains("AOE_TEST_UNSET_DIALOG_VAR")));
+}
+
+#[test]
+fn test_enter_with_missing_path_shows_error() {
+    let mut dialog = single_tool_dialog();
+    let missing = format!("{}/does-not-exist", project_dir());
+    dialog.path = Input::new(missing.clone());
+    assert!(matches!(
+        dialog.handle_key(key(KeyCode::Enter)),
+        DialogResult::Continue
+    ));
+    assert_eq!(
+        dialog.error_message.as_deref(),
+        Some(format!("Path does not exist: {}", missing).as_str())
+    );
+}
+
+#[test]
+fn test_enter_with_file_path_shows_error() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let file = temp.path().join("notes.txt");
+    std::fs::write(&file, "").unwrap();
+
+    let mut dialog = single_tool_dialog();
+    dialog.path = Input::new(file.to_string_lossy().to_string());
+    assert!(matches!(
+        dialog.handle_key(key(KeyCode::Enter)),
+        DialogResult::Continue
+    ));
+    assert!(dialog
+        .error_message
+        .as_deref()
+        .is_some_and(|e| e.starts_with("Path is not a directory")));
+}
+
+#[test]
+fn test_enter_accepts_missing_path_when_create_dir_is_set() {
+    let temp = tempfile::TempDir::new().unwrap();
+    let missing = temp.path().join("new/project");
+
+    let mut config = Config::default();
+    config.session.create_dir = true;
+    let mut dialog = NewSessionDialog::new_with_config(
+        vec!["claude"],
+        missing.to_string_lossy().to_string(),
+        config,
+    );
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => assert_eq!(data.path, missing.to_string_lossy()),
+        _ => panic!("Expected Submit"),
+    }
+    // Creating the directory is left to session creation
+    assert!(!missing.exists());
+}
+
+#[test]
+fn test_enter_submits_with_start() {
+    let mut dialog = single_tool_dialog();
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => assert!(data.start),
+        _ => panic!("Expected Submit"),
+    }
+}
+
+#[test]
+fn test_ctrl_s_submits_without_start() {
+    let mut dialog = single_tool_dialog();
+    dialog.title = Input::new("Later".to_string());
+    match dialog.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL)) {
+        DialogResult::Submit(data) => {
+            assert!(!data.start);
+            assert_eq!(data.title, "Later");
+            assert_eq!(data.path, project_dir());
+        }
+        _ => panic!("Expected Submit"),
+    }
+}
+
+fn preset_dialog() -> NewSessionDialog {
+    let mut config = Config::default();
+    config.presets.insert(
+        "fast".to_string(),
+        PresetConfig {
+            command: Some("opencode --model small".to_string()),
+            environment: vec!["GH_TOKEN".to_string()],
+            environment_values: [("LOG".to_string(), "debug".to_string())].into(),
+            yolo_mode: Some(true),
+            ..Default::default()
+        },
+    );
+    config.presets.insert(
+        "gem".to_string(),
+        PresetConfig {
+            tool: Some("gemini".to_string()),
+            ..Default::default()
+        },
+    );
+    NewSessionDialog::new_with_config(vec!["claude", "opencode"], project_dir(), config)
+}
+
+#[test]
+fn test_ctrl_t_opens_preset_picker() {
+    let mut dialog = preset_dialog();
+    dialog.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL));
+    assert!(dialog.preset_picker.is_active());
+    assert_eq!(dialog.preset_picker.filtered_items(), vec!["fast", "gem"]);
+
+    dialog.handle_key(key(KeyCode::Enter));
+    assert!(!dialog.preset_picker.is_active());
+    assert_eq!(dialog.preset.as_deref(), Some("fast"));
+}
+
+#[test]
+fn test_ctrl_t_without_presets_shows_error() {
+    let mut dialog = single_tool_dialog();
+    dialog.handle_key(KeyEvent::new(KeyCode::Char('t'), KeyModifiers::CONTROL));
+    assert!(!dialog.preset_picker.is_active());
+    assert!(dialog
+        .error_message
+        .as_deref()
+        .is_some_and(|e| e.contains("No presets")));
+}
+
+#[test]
+fn test_apply_preset_fills_form_and_command() {
+    let mut dialog = preset_dialog();
+    dialog.extra_env_values = vec!["LOG=info".to_string()];
+    dialog.apply_preset("fast");
+
+    assert_eq!(dialog.available_tools[dialog.tool_index], "opencode");
+    assert!(dialog.yolo_mode);
+    assert_eq!(dialog.extra_env_keys, vec!["GH_TOKEN"]);
+    assert_eq!(dialog.extra_env_values, vec!["LOG=debug"]);
+
+    dialog.title = Input::new("Fast".to_string());
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => {
+            assert_eq!(data.tool, "opencode");
+            assert_eq!(data.command, "opencode --model small");
+        }
+        _ => panic!("Expected Submit"),
+    }
+}
+
+#[test]
+fn test_changing_tool_after_preset_drops_its_command() {
+    let mut dialog = preset_dialog();
+    dialog.apply_preset("fast");
+    dialog.focused_field = 3;
+    dialog.handle_key(key(KeyCode::Left));
+    assert_eq!(dialog.available_tools[dialog.tool_index], "claude");
+
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => assert_eq!(data.command, ""),
+        _ => panic!("Expected Submit"),
+    }
+}
+
+#[test]
+fn test_apply_preset_with_missing_tool_shows_error() {
+    let mut dialog = preset_dialog();
+    dialog.apply_preset("gem");
+    assert_eq!(dialog.tool_index, 0);
+    assert_eq!(dialog.preset, None);
+    assert!(dialog
+        .error_message
+        .as_deref()
+        .is_some_and(|e| e.contains("gemini, which is not installed")));
+}
+
 #[test]
 fn test_tab_cycles_fields_single_tool() {
     let mut dialog = single_tool_dialog();
@@ -162,7 +372,7 @@ fn test_char_input_to_path() {
     dialog.focused_field = 1;
     dialog.handle_key(key(KeyCode::Char('/')));
     dialog.handle_key(key(KeyCode::Char('a')));
-    assert_eq!(dialog.path.value(), "/tmp/project/a");
+    assert_eq!(dialog.path.value(), format!("{}/a", project_dir()));
 }
 
 #[test]
@@ -388,6 +598,104 @@ fn test_tab_skips_sandbox_image_when_sandbox_disabled() {
     assert_eq!(dialog.focused_field, 0); // wrap to start
 }
 
+fn focused(dialog: &NewSessionDialog) -> Option<Field> {
+    dialog.visible_fields().get(dialog.focused_field).copied()
+}
+
+#[test]
+fn test_typing_worktree_branch_keeps_focus_coherent() {
+    let mut dialog = multi_tool_dialog();
+    dialog.docker_available = true;
+    dialog.focused_field = dialog.field_index(Field::Worktree);
+
+    // The first character adds the New Branch checkbox after the worktree field
+    for c in "feat".chars() {
+        dialog.handle_key(key(KeyCode::Char(c)));
+        assert_eq!(focused(&dialog), Some(Field::Worktree));
+    }
+    assert_eq!(dialog.worktree_branch.value(), "feat");
+
+    dialog.handle_key(key(KeyCode::Tab));
+    assert_eq!(focused(&dialog), Some(Field::NewBranch));
+    dialog.handle_key(key(KeyCode::Char(' ')));
+    assert!(!dialog.create_new_branch);
+    dialog.handle_key(key(KeyCode::Tab));
+    assert_eq!(focused(&dialog), Some(Field::Sandbox));
+
+    // Clearing the branch removes the checkbox again
+    dialog.handle_key(shift_key(KeyCode::BackTab));
+    dialog.handle_key(shift_key(KeyCode::BackTab));
+    assert_eq!(focused(&dialog), Some(Field::Worktree));
+    for _ in 0..4 {
+        dialog.handle_key(key(KeyCode::Backspace));
+        assert_eq!(focused(&dialog), Some(Field::Worktree));
+    }
+    assert_eq!(dialog.worktree_branch.value(), "");
+
+    dialog.handle_key(key(KeyCode::Tab));
+    assert_eq!(focused(&dialog), Some(Field::Sandbox));
+}
+
+#[test]
+fn test_focus_tracks_field_when_layout_changes() {
+    let mut dialog = multi_tool_dialog();
+    dialog.docker_available = true;
+    dialog.sandbox_enabled = true;
+    dialog.focused_field = dialog.field_index(Field::YoloMode);
+
+    // A branch set outside the worktree input (e.g. by the branch picker)
+    let previous = dialog.visible_fields();
+    dialog.worktree_branch = Input::new("feature".to_string());
+    dialog.keep_focused_field(&previous);
+    assert_eq!(focused(&dialog), Some(Field::YoloMode));
+
+    // The focused field disappears: focus falls back to the one before it
+    let previous = dialog.visible_fields();
+    dialog.sandbox_enabled = false;
+    dialog.keep_focused_field(&previous);
+    assert_eq!(focused(&dialog), Some(Field::Sandbox));
+}
+
+#[test]
+fn test_apply_last_options() {
+    let mut dialog = multi_tool_dialog();
+    dialog.docker_available = true;
+    dialog.apply_last_options(&NewSessionOptions {
+        tool: "opencode".to_string(),
+        group: "backend".to_string(),
+        sandbox: true,
+        sandbox_image: "custom/image:tag".to_string(),
+        yolo_mode: true,
+    });
+    assert_eq!(dialog.tool_index, 1);
+    assert_eq!(dialog.group.value(), "backend");
+    assert!(dialog.sandbox_enabled);
+    assert!(dialog.yolo_mode);
+    assert_eq!(dialog.sandbox_image.value(), "custom/image:tag");
+    // Title and path are never carried over
+    assert_eq!(dialog.title.value(), "");
+    assert_eq!(dialog.path.value(), project_dir());
+}
+
+#[test]
+fn test_apply_last_options_skips_unavailable_tool_and_sandbox() {
+    let mut dialog = multi_tool_dialog();
+    dialog.apply_last_options(&NewSessionOptions {
+        tool: "vibe".to_string(),
+        sandbox: true,
+        yolo_mode: true,
+        ..Default::default()
+    });
+    assert_eq!(dialog.tool_index, 0);
+    // Docker is not available in this dialog
+    assert!(!dialog.sandbox_enabled);
+    assert!(!dialog.yolo_mode);
+    assert_eq!(
+        dialog.sandbox_image.value(),
+        crate::docker::effective_default_image()
+    );
+}
+
 #[test]
 fn test_submit_with_custom_sandbox_image() {
     let mut dialog = multi_tool_dialog();
@@ -574,16 +882,14 @@ fn test_profile_override_sets_default_tool() {
     let profile_config = ProfileConfig {
         session: Some(SessionConfigOverride {
             default_tool: Some("opencode".to_string()),
+            ..Default::default()
         }),
         ..Default::default()
     };
 
     let resolved = merge_configs(global, &profile_config);
-    let dialog = NewSessionDialog::new_with_config(
-        vec!["claude", "opencode"],
-        "/tmp/project".to_string(),
-        resolved,
-    );
+    let dialog =
+        NewSessionDialog::new_with_config(vec!["claude", "opencode"], project_dir(), resolved);
 
     assert_eq!(
         dialog.tool_index, 1,
@@ -600,6 +906,7 @@ fn test_profile_override_beats_global_default_tool() {
     let profile_config = ProfileConfig {
         session: Some(SessionConfigOverride {
             default_tool: Some("opencode".to_string()),
+            ..Default::default()
         }),
         ..Default::default()
     };
@@ -611,11 +918,8 @@ fn test_profile_override_beats_global_default_tool() {
         "Profile override should take precedence over global default"
     );
 
-    let dialog = NewSessionDialog::new_with_config(
-        vec!["claude", "opencode"],
-        "/tmp/project".to_string(),
-        resolved,
-    );
+    let dialog =
+        NewSessionDialog::new_with_config(vec!["claude", "opencode"], project_dir(), resolved);
 
     assert_eq!(
         dialog.tool_index, 1,
@@ -623,3 +927,371 @@ fn test_profile_override_beats_global_default_tool() {
     );
     assert_eq!(dialog.available_tools[dialog.tool_index], "opencode");
 }
+
+#[test]
+fn test_default_group_prefills_group_field() {
+    let mut config = Config::default();
+    config.session.default_group = Some(" work/backend ".to_string());
+
+    let dialog = NewSessionDialog::new_with_config(vec!["claude"], project_dir(), config);
+    assert_eq!(dialog.group.value(), "work/backend");
+}
+
+#[test]
+fn test_empty_default_group_leaves_group_blank() {
+    let mut config = Config::default();
+    config.session.default_group = Some("   ".to_string());
+
+    let dialog = NewSessionDialog::new_with_config(vec!["claude"], project_dir(), config);
+    assert_eq!(dialog.group.value(), "");
+}
+
+#[test]
+fn test_title_template_used_when_title_empty() {
+    let mut config = Config::default();
+    config.session.title_template = Some("{branch} @ {dir}".to_string());
+
+    let mut dialog = NewSessionDialog::new_with_config(vec!["claude"], project_dir(), config);
+    dialog.worktree_branch = Input::new("feat/login".to_string());
+
+    match dialog.handle_key(key(KeyCode::Enter)) {
+        DialogResult::Submit(data) => assert_eq!(data.title, "feat/login @ project"),
+        _ => panic!("Expected Submit"),
+    }
+}
+
+fn missing_image_dialog() -> NewSessionDialog {
+    let mut dialog = single_tool_dialog();
+    dialog.docker_available = true;
+    dialog.sandbox_enabled = true;
+    dialog.sandbox_image = Input::new("aoe-test/missing-image:never".to_string());
+    dialog.image_present = |_| false;
+    dialog
+}
+
+#[test]
+fn test_submit_asks_before_pulling_missing_image() {
+    let mut dialog = missing_image_dialog();
+    assert!(matches!(
+        dialog.handle_key(key(KeyCode::Enter)),
+        DialogResult::Continue
+    ));
+    assert!(dialog.pull_confirm.is_some());
+
+    match dialog.handle_key(key(KeyCode::Char('y'))) {
+        DialogResult::Submit(data) => {
+            assert!(data.sandbox);
+            assert!(data.start);
+            assert_eq!(data.sandbox_image, "aoe-test/missing-image:never");
+        }
+        _ => panic!("Expected Submit after confirming the pull"),
+    }
+    assert!(dialog.pull_confirm.is_none());
+}
+
+#[test]
+fn test_offline_missing_image_errors_instead_of_pulling() {
+    let mut dialog = missing_image_dialog();
+    dialog.offline = true;
+    assert!(matches!(
+        dialog.handle_key(key(KeyCode::Enter)),
+        DialogResult::Continue
+    ));
+    assert!(dialog.pull_confirm.is_none());
+    assert!(dialog
+        .error()
+        .unwrap()
+        .starts_with("offline: image not present"));
+}
+
+#[test]
+fn test_declining_image_pull_returns_to_form() {
+    let mut dialog = missing_image_dialog();
+    dialog.handle_key(KeyEvent::new(KeyCode::Char('s'), KeyModifiers::CONTROL));
+    assert!(dialog.pull_confirm.is_some());
+
+    assert!(matches!(
+        dialog.handle_key(key(KeyCode::Char('n'))),
+        DialogResult::Continue
+    ));
+    assert!(dialog.pull_confirm.is_none());
+    assert!(!dialog.loading);
+
+    // Asks again on the next attempt
+    dialog.handle_key(key(KeyCode::Enter));
+    assert!(dialog.pull_confirm.is_some());
+}
+
+fn sandbox_dialog_with_running(max: u64, running: fn() -> usize) -> NewSessionDialog {
+    let mut dialog = single_tool_dialog();
+    dialog.docker_available = true;
+    dialog.sandbox_enabled = true;
+    dialog.max_containers = max;
+    dialog.running_containers = running;
+    dialog
+}
+
+#[test]
+fn test_submit_asks_when_container_limit_reached() {
+    let mut dialog = sandbox_dialog_with_running(2, || 2);
+    assert!(matches!(
+        dialog.handle_key(key(KeyCode::Enter)),
+        DialogResult::Continue
+    ));
+    let (confirm, _) = dialog.container_limit_confirm.as_ref().unwrap();
+    assert_eq!(confirm.action(), "container_limit");
+
+    match dialog.handle_key(key(KeyCode::Char('y'))) {
+        DialogResult::Submit(data) => assert!(data.sandbox),
+        _ => panic!("Expected Submit after confirming"),
+    }
+    assert!(dialog.container_limit_confirm.is_none());
+}
+
+#[test]
+fn test_declining_container_limit_returns_to_form() {
+    let mut dialog = sandbox_dialog_with_running(1, || 3);
+    dialog.handle_key(key(KeyCode::Enter));
+    assert!(matches!(
+        dialog.handle_key(key(KeyCode::Char('n'))),
+        DialogResult::Continue
+    ));
+    assert!(dialog.container_limit_confirm.is_none());
+
+    dialog.handle_key(key(KeyCode::Enter));
+    assert!(dialog.container_limit_confirm.is_some());
+}
+
+#[test]
+fn test_no_container_limit_prompt_below_limit_or_unlimited() {
+    let mut dialog = sandbox_dialog_with_running(3, || 2);
+    assert!(matches!(
+        dialog.handle_key(key(KeyCode::Enter)),
+        DialogResult::Submit(_)
+    ));
+
+    let mut dialog = sandbox_dialog_with_running(0, || 50);
+    assert!(matches!(
+        dialog.handle_key(key(KeyCode::Enter)),
+        DialogResult::Submit(_)
+    ));
+
+    let mut dialog = sandbox_dialog_with_running(1, || 5);
+    dialog.sandbox_enabled = false;
+    assert!(matches!(
+        dialog.handle_key(key(KeyCode::Enter)),
+        DialogResult::Submit(_)
+    ));
+}
+
+#[test]
+fn test_picking_a_repo_applies_its_session_defaults() {
+    let repo = std::path::Path::new(&project_dir()).join("repo-with-defaults");
+    std::fs::create_dir_all(repo.join(".aoe")).unwrap();
+    std::fs::write(
+        repo.join(".aoe/config.toml"),
+        "[session]\ndefault_tool = \"opencode\"\ndefault_group = \"backend\"\n\n[sandbox]\ndefault_image = \"example/repo-image:1\"\n",
+    )
+    .unwrap();
+
+    let mut dialog = multi_tool_dialog();
+    dialog.apply_repo_defaults(&repo.to_string_lossy());
+
+    assert_eq!(dialog.available_tools[dialog.tool_index], "opencode");
+    assert_eq!(dialog.group.value(), "backend");
+    assert_eq!(dialog.sandbox_image.value(), "example/repo-image:1");
+    assert!(dialog.error_message.is_none());
+}
+
+fn type_text(dialog: &mut NewSessionDialog, values_list: bool, text: &str) {
+    for c in text.chars() {
+        let k = key(KeyCode::Char(c));
+        if values_list {
+            dialog.handle_env_values_list_key(k);
+        } else {
+            dialog.handle_env_list_key(k);
+        }
+    }
+}
+
+fn env_keys_dialog(keys: &[&str]) -> NewSessionDialog {
+    let mut dialog = single_tool_dialog();
+    dialog.extra_env_keys = keys.iter().map(|k| k.to_string()).collect();
+    dialog.env_list_expanded = true;
+    dialog.env_selected_index = 0;
+    dialog
+}
+
+#[test]
+fn test_env_list_add_appends_and_selects() {
+    let mut dialog = env_keys_dialog(&["FOO"]);
+    dialog.handle_env_list_key(key(KeyCode::Char('a')));
+    assert!(dialog.env_adding_new);
+    type_text(&mut dialog, false, "BAR");
+    dialog.handle_env_list_key(key(KeyCode::Enter));
+
+    assert_eq!(dialog.extra_env_keys, vec!["FOO", "BAR"]);
+    assert_eq!(dialog.env_selected_index, 1);
+    assert!(dialog.env_editing_input.is_none());
+    assert!(!dialog.env_adding_new);
+}
+
+#[test]
+fn test_env_list_add_duplicate_key_rejected() {
+    let mut dialog = env_keys_dialog(&["FOO", "BAR"]);
+    dialog.handle_env_list_key(key(KeyCode::Char('a')));
+    type_text(&mut dialog, false, "FOO");
+    dialog.handle_env_list_key(key(KeyCode::Enter));
+
+    assert_eq!(dialog.extra_env_keys, vec!["FOO", "BAR"]);
+    assert_eq!(dialog.env_selected_index, 0);
+    assert!(dialog.env_editing_input.is_none());
+}
+
+#[test]
+fn test_env_list_add_empty_key_rejected() {
+    let mut dialog = env_keys_dialog(&["FOO"]);
+    dialog.handle_env_list_key(key(KeyCode::Char('a')));
+    type_text(&mut dialog, false, "   ");
+    dialog.handle_env_list_key(key(KeyCode::Enter));
+    assert_eq!(dialog.extra_env_keys, vec!["FOO"]);
+}
+
+#[test]
+fn test_env_list_esc_cancels_add_then_collapses() {
+    let mut dialog = env_keys_dialog(&["FOO"]);
+    dialog.handle_env_list_key(key(KeyCode::Char('a')));
+    type_text(&mut dialog, false, "BAR");
+    dialog.handle_env_list_key(key(KeyCode::Esc));
+
+    assert_eq!(dialog.extra_env_keys, vec!["FOO"]);
+    assert!(dialog.env_editing_input.is_none());
+    assert!(!dialog.env_adding_new);
+    assert!(dialog.env_list_expanded);
+
+    dialog.handle_env_list_key(key(KeyCode::Esc));
+    assert!(!dialog.env_list_expanded);
+}
+
+#[test]
+fn test_env_list_navigation_clamps_at_bounds() {
+    let mut dialog = env_keys_dialog(&["A", "B"]);
+    dialog.handle_env_list_key(key(KeyCode::Up));
+    assert_eq!(dialog.env_selected_index, 0);
+
+    dialog.handle_env_list_key(key(KeyCode::Char('j')));
+    assert_eq!(dialog.env_selected_index, 1);
+    dialog.handle_env_list_key(key(KeyCode::Down));
+    assert_eq!(dialog.env_selected_index, 1);
+
+    dialog.handle_env_list_key(key(KeyCode::Char('k')));
+    assert_eq!(dialog.env_selected_index, 0);
+}
+
+#[test]
+fn test_env_list_delete_middle_keeps_index() {
+    let mut dialog = env_keys_dialog(&["A", "B", "C"]);
+    dialog.env_selected_index = 1;
+    dialog.handle_env_list_key(key(KeyCode::Char('d')));
+
+    assert_eq!(dialog.extra_env_keys, vec!["A", "C"]);
+    assert_eq!(dialog.env_selected_index, 1);
+}
+
+#[test]
+fn test_env_list_delete_last_moves_selection_up() {
+    let mut dialog = env_keys_dialog(&["A", "B", "C"]);
+    dialog.env_selected_index = 2;
+    dialog.handle_env_list_key(key(KeyCode::Char('d')));
+
+    assert_eq!(dialog.extra_env_keys, vec!["A", "B"]);
+    assert_eq!(dialog.env_selected_index, 1);
+}
+
+#[test]
+fn test_env_list_delete_until_empty() {
+    let mut dialog = env_keys_dialog(&["A", "B"]);
+    dialog.handle_env_list_key(key(KeyCode::Char('d')));
+    assert_eq!(dialog.extra_env_keys, vec!["B"]);
+    assert_eq!(dialog.env_selected_index, 0);
+
+    dialog.handle_env_list_key(key(KeyCode::Char('d')));
+    assert!(dialog.extra_env_keys.is_empty());
+    assert_eq!(dialog.env_selected_index, 0);
+
+    // Deleting from an empty list is a no-op, as is editing
+    dialog.handle_env_list_key(key(KeyCode::Char('d')));
+    dialog.handle_env_list_key(key(KeyCode::Enter));
+    assert!(dialog.extra_env_keys.is_empty());
+    assert!(dialog.env_editing_input.is_none());
+}
+
+#[test]
+fn test_env_list_edit_replaces_in_place() {
+    let mut dialog = env_keys_dialog(&["A", "B", "C"]);
+    dialog.env_selected_index = 1;
+    dialog.handle_env_list_key(key(KeyCode::Enter));
+    assert_eq!(
+        dialog.env_editing_input.as_ref().map(|i| i.value()),
+        Some("B")
+    );
+    assert!(!dialog.env_adding_new);
+
+    dialog.handle_env_list_key(key(KeyCode::Backspace));
+    type_text(&mut dialog, false, "X");
+    dialog.handle_env_list_key(key(KeyCode::Enter));
+
+    assert_eq!(dialog.extra_env_keys, vec!["A", "X", "C"]);
+    assert_eq!(dialog.env_selected_index, 1);
+}
+
+#[test]
+fn test_env_list_edit_to_existing_key_rejected() {
+    let mut dialog = env_keys_dialog(&["A", "B"]);
+    dialog.env_selected_index = 1;
+    dialog.handle_env_list_key(key(KeyCode::Enter));
+    dialog.handle_env_list_key(key(KeyCode::Backspace));
+    type_text(&mut dialog, false, "A");
+    dialog.handle_env_list_key(key(KeyCode::Enter));
+
+    assert_eq!(dialog.extra_env_keys, vec!["A", "B"]);
+}
+
+#[test]
+fn test_env_values_list_requires_key_value() {
+    let mut dialog = single_tool_dialog();
+    dialog.extra_env_values = Vec::new();
+    dialog.env_values_list_expanded = true;
+
+    dialog.handle_env_values_list_key(key(KeyCode::Char('a')));
+    type_text(&mut dialog, true, "FOO");
+    dialog.handle_env_values_list_key(key(KeyCode::Enter));
+    assert!(dialog.extra_env_values.is_empty());
+
+    dialog.handle_env_values_list_key(key(KeyCode::Char('a')));
+    type_text(&mut dialog, true, "FOO=bar");
+    dialog.handle_env_values_list_key(key(KeyCode::Enter));
+    assert_eq!(dialog.extra_env_values, vec!["FOO=bar"]);
+    assert_eq!(dialog.env_values_selected_index, 0);
+}
+
+#[test]
+fn test_env_values_list_edit_keeps_key_value_form() {
+    let mut dialog = single_tool_dialog();
+    dialog.extra_env_values = vec!["FOO=bar".to_string()];
+    dialog.env_values_list_expanded = true;
+
+    // Editing into something without '=' leaves the entry unchanged
+    dialog.handle_env_values_list_key(key(KeyCode::Enter));
+    for _ in 0.."=bar".len() {
+        dialog.handle_env_values_list_key(key(KeyCode::Backspace));
+    }
+    dialog.handle_env_values_list_key(key(KeyCode::Enter));
+    assert_eq!(dialog.extra_env_values, vec!["FOO=bar"]);
+
+    // Duplicate values are allowed, unlike keys
+    dialog.handle_env_values_list_key(key(KeyCode::Char('a')));
+    type_text(&mut dialog, true, "FOO=bar");
+    dialog.handle_env_values_list_key(key(KeyCode::Enter));
+    assert_eq!(dialog.extra_env_values, vec!["FOO=bar", "FOO=bar"]);
+}
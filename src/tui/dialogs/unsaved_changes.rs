@@ -0,0 +1,159 @@
+//! Save/discard prompt shown when closing settings with unsaved edits
+
+use crossterm::event::{KeyCode, KeyEvent};
+use ratatui::prelude::*;
+use ratatui::widgets::*;
+
+use super::DialogResult;
+use crate::tui::styles::Theme;
+
+/// Result from the unsaved changes dialog. Cancel is `DialogResult::Cancel`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnsavedChangesAction {
+    Save,
+    Discard,
+}
+
+const BUTTONS: [&str; 3] = ["[Save]", "[Discard]", "[Cancel]"];
+
+pub struct UnsavedChangesDialog {
+    selected: usize, // index into BUTTONS
+}
+
+impl Default for UnsavedChangesDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl UnsavedChangesDialog {
+    pub fn new() -> Self {
+        Self { selected: 0 }
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> DialogResult<UnsavedChangesAction> {
+        match key.code {
+            KeyCode::Esc | KeyCode::Char('c') | KeyCode::Char('C') => DialogResult::Cancel,
+            KeyCode::Char('s') | KeyCode::Char('S') => {
+                DialogResult::Submit(UnsavedChangesAction::Save)
+            }
+            KeyCode::Char('d') | KeyCode::Char('D') => {
+                DialogResult::Submit(UnsavedChangesAction::Discard)
+            }
+            KeyCode::Enter => match self.selected {
+                0 => DialogResult::Submit(UnsavedChangesAction::Save),
+                1 => DialogResult::Submit(UnsavedChangesAction::Discard),
+                _ => DialogResult::Cancel,
+            },
+            KeyCode::Left | KeyCode::Char('h') | KeyCode::BackTab => {
+                self.selected = (self.selected + BUTTONS.len() - 1) % BUTTONS.len();
+                DialogResult::Continue
+            }
+            KeyCode::Right | KeyCode::Char('l') | KeyCode::Tab => {
+                self.selected = (self.selected + 1) % BUTTONS.len();
+                DialogResult::Continue
+            }
+            _ => DialogResult::Continue,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
+        let dialog_area = super::centered_rect(area, 50, 8);
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.error))
+            .title(" Unsaved Changes ")
+            .title_style(Style::default().fg(theme.error).bold());
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Min(1), Constraint::Length(2)])
+            .split(inner);
+
+        let message = Paragraph::new("You have unsaved changes. Save them before closing?")
+            .style(Style::default().fg(theme.text))
+            .wrap(Wrap { trim: true });
+        frame.render_widget(message, chunks[0]);
+
+        let mut spans = vec![Span::raw("  ")];
+        for (i, label) in BUTTONS.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw("  "));
+            }
+            let style = if i == self.selected {
+                Style::default().fg(theme.accent).bold()
+            } else {
+                Style::default().fg(theme.dimmed)
+            };
+            spans.push(Span::styled(*label, style));
+        }
+
+        frame.render_widget(
+            Paragraph::new(Line::from(spans)).alignment(Alignment::Center),
+            chunks[1],
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::KeyModifiers;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_enter_defaults_to_save() {
+        let mut dialog = UnsavedChangesDialog::new();
+        let result = dialog.handle_key(key(KeyCode::Enter));
+        assert!(matches!(
+            result,
+            DialogResult::Submit(UnsavedChangesAction::Save)
+        ));
+    }
+
+    #[test]
+    fn test_shortcuts() {
+        let mut dialog = UnsavedChangesDialog::new();
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('d'))),
+            DialogResult::Submit(UnsavedChangesAction::Discard)
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Char('s'))),
+            DialogResult::Submit(UnsavedChangesAction::Save)
+        ));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Esc)),
+            DialogResult::Cancel
+        ));
+    }
+
+    #[test]
+    fn test_navigation_wraps() {
+        let mut dialog = UnsavedChangesDialog::new();
+        dialog.handle_key(key(KeyCode::Right));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Submit(UnsavedChangesAction::Discard)
+        ));
+        dialog.handle_key(key(KeyCode::Right));
+        assert!(matches!(
+            dialog.handle_key(key(KeyCode::Enter)),
+            DialogResult::Cancel
+        ));
+        dialog.handle_key(key(KeyCode::Right));
+        assert_eq!(dialog.selected, 0);
+        dialog.handle_key(key(KeyCode::Left));
+        assert_eq!(dialog.selected, 2);
+    }
+}
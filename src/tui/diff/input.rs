@@ -4,6 +4,8 @@ use std::path::PathBuf;
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind};
 
+use tui_input::backend::crossterm::EventHandler;
+
 use super::DiffView;
 use crate::tui::dialogs::DialogResult;
 
@@ -50,6 +52,10 @@ impl DiffView {
             return self.handle_branch_select_key(key);
         }
 
+        if self.ref_input.is_some() || self.commit_input.is_some() {
+            return self.handle_text_input_key(key);
+        }
+
         // Normal diff view mode
         self.handle_normal_key(key)
     }
@@ -69,6 +75,16 @@ impl DiffView {
                 DiffAction::Continue
             }
 
+            // Hunk navigation (crosses into adjacent files at the ends)
+            (KeyCode::Char(']'), _) => {
+                self.next_hunk();
+                DiffAction::Continue
+            }
+            (KeyCode::Char('['), _) => {
+                self.prev_hunk();
+                DiffAction::Continue
+            }
+
             // Diff scrolling
             (KeyCode::PageUp, _) => {
                 self.page_up();
@@ -110,6 +126,21 @@ impl DiffView {
                 DiffAction::Continue
             }
 
+            // Stage / commit
+            (KeyCode::Char('s'), _) => {
+                self.stage_selected_file();
+                DiffAction::Continue
+            }
+            (KeyCode::Char('c'), _) => {
+                self.open_commit_input();
+                DiffAction::Continue
+            }
+
+            (KeyCode::Char('B'), _) => {
+                self.open_ref_input();
+                DiffAction::Continue
+            }
+
             // Refresh
             (KeyCode::Char('r'), _) => {
                 if let Err(e) = self.refresh_files() {
@@ -153,25 +184,46 @@ impl DiffView {
                     self.select_branch(branch);
                 }
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if state.selected > 0 {
-                    state.selected -= 1;
-                }
+            KeyCode::Up | KeyCode::Char('k') if state.selected > 0 => {
+                state.selected -= 1;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if state.selected < state.branches.len().saturating_sub(1) {
-                    state.selected += 1;
-                }
+            KeyCode::Down | KeyCode::Char('j')
+                if state.selected < state.branches.len().saturating_sub(1) =>
+            {
+                state.selected += 1;
             }
             _ => {}
         }
         DiffAction::Continue
     }
 
+    /// Keys for the base ref and commit message inputs
+    fn handle_text_input_key(&mut self, key: KeyEvent) -> DiffAction {
+        let committing = self.commit_input.is_some();
+        match key.code {
+            KeyCode::Esc => {
+                self.ref_input = None;
+                self.commit_input = None;
+            }
+            KeyCode::Enter if committing => self.submit_commit_input(),
+            KeyCode::Enter => self.submit_ref_input(),
+            _ => {
+                if let Some(input) = self.commit_input.as_mut().or(self.ref_input.as_mut()) {
+                    input.handle_event(&crossterm::event::Event::Key(key));
+                }
+            }
+        }
+        DiffAction::Continue
+    }
+
     /// Handle a mouse event
     pub fn handle_mouse(&mut self, mouse: MouseEvent) -> DiffAction {
-        // Don't handle mouse in help overlay or branch select dialog
-        if self.show_help || self.branch_select.is_some() {
+        // Don't handle mouse in help overlay or branch/ref dialogs
+        if self.show_help
+            || self.branch_select.is_some()
+            || self.ref_input.is_some()
+            || self.commit_input.is_some()
+        {
             return DiffAction::Continue;
         }
 
@@ -193,9 +245,12 @@ impl DiffView {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::git::diff::{DiffFile, DiffHunk, DiffLine, FileDiff, FileStatus};
     use crate::tui::dialogs::InfoDialog;
     use crossterm::event::KeyModifiers;
+    use similar::ChangeTag;
     use std::collections::HashMap;
+    use tui_input::Input;
 
     fn key(code: KeyCode) -> KeyEvent {
         KeyEvent::new(code, KeyModifiers::NONE)
@@ -212,6 +267,9 @@ mod tests {
             visible_lines: 20,
             total_lines: 0,
             branch_select: None,
+            ref_input: None,
+            commit_input: None,
+            base_changed: false,
             error_message: None,
             success_message: None,
             context_lines: 3,
@@ -232,6 +290,9 @@ mod tests {
             visible_lines: 20,
             total_lines: 0,
             branch_select: None,
+            ref_input: None,
+            commit_input: None,
+            base_changed: false,
             error_message: None,
             success_message: None,
             context_lines: 3,
@@ -282,4 +343,125 @@ mod tests {
         let action = view.handle_key(key(KeyCode::Char('q')));
         assert!(matches!(action, DiffAction::Close));
     }
+
+    fn file_with_hunks(name: &str, hunk_sizes: &[usize]) -> (DiffFile, FileDiff) {
+        let file = DiffFile {
+            path: PathBuf::from(name),
+            old_path: None,
+            status: FileStatus::Modified,
+            additions: 0,
+            deletions: 0,
+        };
+        let hunks = hunk_sizes
+            .iter()
+            .map(|&n| DiffHunk {
+                old_start: 1,
+                old_lines: n,
+                new_start: 1,
+                new_lines: n,
+                lines: (0..n)
+                    .map(|_| DiffLine {
+                        tag: ChangeTag::Equal,
+                        old_line_num: Some(1),
+                        new_line_num: Some(1),
+                        content: String::new(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        let diff = FileDiff {
+            file: file.clone(),
+            hunks,
+            is_binary: false,
+        };
+        (file, diff)
+    }
+
+    /// Two files: a.rs has hunks at rows 0, 12, 24 (30 rows); b.rs has one hunk.
+    fn make_diff_view_with_hunks() -> DiffView {
+        let mut view = make_diff_view_no_warning();
+        view.visible_lines = 10;
+        for (name, sizes) in [("a.rs", &[10, 10, 4][..]), ("b.rs", &[3][..])] {
+            let (file, diff) = file_with_hunks(name, sizes);
+            view.diff_cache.insert(file.path.clone(), diff);
+            view.files.push(file);
+        }
+        view
+    }
+
+    #[test]
+    fn test_next_hunk_moves_within_file_then_to_next_file() {
+        let mut view = make_diff_view_with_hunks();
+
+        view.handle_key(key(KeyCode::Char(']')));
+        assert_eq!((view.selected_file, view.scroll_offset), (0, 12));
+
+        // The last hunk (row 24) is past the max scroll of 20, so move on
+        view.handle_key(key(KeyCode::Char(']')));
+        assert_eq!((view.selected_file, view.scroll_offset), (1, 0));
+
+        // No more hunks or files: stay put
+        view.handle_key(key(KeyCode::Char(']')));
+        assert_eq!((view.selected_file, view.scroll_offset), (1, 0));
+    }
+
+    #[test]
+    fn test_prev_hunk_moves_to_last_hunk_of_previous_file() {
+        let mut view = make_diff_view_with_hunks();
+        view.selected_file = 1;
+
+        view.handle_key(key(KeyCode::Char('[')));
+        assert_eq!((view.selected_file, view.scroll_offset), (0, 20));
+
+        view.handle_key(key(KeyCode::Char('[')));
+        assert_eq!((view.selected_file, view.scroll_offset), (0, 12));
+
+        view.handle_key(key(KeyCode::Char('[')));
+        view.handle_key(key(KeyCode::Char('[')));
+        assert_eq!((view.selected_file, view.scroll_offset), (0, 0));
+    }
+
+    #[test]
+    fn test_ref_input_rejects_unknown_ref() {
+        let mut view = make_diff_view_no_warning();
+        view.handle_key(key(KeyCode::Char('B')));
+        assert_eq!(view.ref_input.as_ref().unwrap().value(), "main");
+
+        view.handle_key(key(KeyCode::Char('x')));
+        view.handle_key(key(KeyCode::Enter));
+        assert!(view.ref_input.is_none());
+        assert_eq!(view.base_branch, "main");
+        assert!(!view.base_changed);
+        assert!(view.error_message.unwrap().contains("mainx"));
+    }
+
+    #[test]
+    fn test_ref_input_esc_cancels() {
+        let mut view = make_diff_view_no_warning();
+        view.handle_key(key(KeyCode::Char('B')));
+        let action = view.handle_key(key(KeyCode::Esc));
+        assert!(matches!(action, DiffAction::Continue));
+        assert!(view.ref_input.is_none());
+    }
+
+    #[test]
+    fn test_commit_input_requires_message() {
+        let mut view = make_diff_view_no_warning();
+        view.handle_key(key(KeyCode::Char('c')));
+        assert!(view.commit_input.is_some());
+
+        // 'q' is typed into the message rather than closing the view
+        let action = view.handle_key(key(KeyCode::Char('q')));
+        assert!(matches!(action, DiffAction::Continue));
+        assert_eq!(view.commit_input.as_ref().unwrap().value(), "q");
+
+        // Whitespace-only messages are rejected
+        view.commit_input = Some(Input::new("  ".to_string()));
+        view.handle_key(key(KeyCode::Enter));
+        assert!(view.commit_input.is_none());
+        assert_eq!(
+            view.error_message.as_deref(),
+            Some("Commit message cannot be empty")
+        );
+    }
 }
@@ -6,9 +6,11 @@ mod render;
 use std::collections::HashMap;
 use std::path::PathBuf;
 
+use tui_input::Input;
+
 use crate::git::diff::{
-    check_merge_base_status, compute_changed_files, compute_file_diff, get_default_branch,
-    list_branches, DiffFile, FileDiff,
+    check_merge_base_status, commit_staged, compute_changed_files, compute_file_diff,
+    get_default_branch, list_branches, ref_exists, stage_file, DiffFile, FileDiff,
 };
 use crate::session::config::{load_config, save_config};
 use crate::session::Config;
@@ -52,6 +54,15 @@ pub struct DiffView {
     /// Branch selection dialog state
     pub(crate) branch_select: Option<BranchSelectState>,
 
+    /// Free-form base ref input (any branch, tag, or revision)
+    pub(crate) ref_input: Option<Input>,
+
+    /// Commit message input, shown while composing a commit
+    pub(crate) commit_input: Option<Input>,
+
+    /// Whether the base was changed while the view was open
+    pub(crate) base_changed: bool,
+
     /// Error message to display
     pub(crate) error_message: Option<String>,
 
@@ -72,15 +83,14 @@ pub struct DiffView {
 }
 
 impl DiffView {
-    /// Create a new diff view for a repository
-    pub fn new(repo_path: PathBuf) -> anyhow::Result<Self> {
+    /// Create a new diff view for a repository. `base` (typically the session's
+    /// last-used or creation base) takes precedence over the configured default.
+    pub fn new(repo_path: PathBuf, base: Option<String>) -> anyhow::Result<Self> {
         let config = Config::load().unwrap_or_default();
 
         // Determine base branch
-        let base_branch = config
-            .diff
-            .default_branch
-            .clone()
+        let base_branch = base
+            .or_else(|| config.diff.default_branch.clone())
             .or_else(|| get_default_branch(&repo_path).ok())
             .unwrap_or_else(|| "main".to_string());
 
@@ -99,6 +109,9 @@ impl DiffView {
             visible_lines: 20,
             total_lines: 0,
             branch_select: None,
+            ref_input: None,
+            commit_input: None,
+            base_changed: false,
             error_message: None,
             success_message: None,
             context_lines,
@@ -168,10 +181,75 @@ impl DiffView {
         }
     }
 
+    /// Open the free-form base ref input, prefilled with the current base
+    pub fn open_ref_input(&mut self) {
+        self.ref_input = Some(Input::new(self.base_branch.clone()));
+    }
+
+    /// Use the typed ref as the new base if it resolves to a commit
+    pub fn submit_ref_input(&mut self) {
+        let Some(input) = self.ref_input.take() else {
+            return;
+        };
+        let reference = input.value().trim();
+        if reference.is_empty() || reference == self.base_branch {
+            return;
+        }
+        if !ref_exists(&self.repo_path, reference) {
+            self.error_message = Some(format!("Unknown ref: {}", reference));
+            return;
+        }
+        self.select_branch(reference.to_string());
+    }
+
+    /// Stage the selected file with `git add`
+    pub fn stage_selected_file(&mut self) {
+        let Some(file) = self.selected_file() else {
+            return;
+        };
+        let path = file.path.clone();
+        match stage_file(&self.repo_path, &path) {
+            Ok(()) => {
+                self.error_message = None;
+                self.success_message = Some(format!("Staged {}", path.display()));
+            }
+            Err(e) => self.error_message = Some(format!("Failed to stage: {}", e)),
+        }
+    }
+
+    /// Open the commit message input
+    pub fn open_commit_input(&mut self) {
+        self.commit_input = Some(Input::default());
+    }
+
+    /// Commit staged changes with the typed message, then refresh the diff
+    pub fn submit_commit_input(&mut self) {
+        let Some(input) = self.commit_input.take() else {
+            return;
+        };
+        let message = input.value().trim();
+        if message.is_empty() {
+            self.error_message = Some("Commit message cannot be empty".to_string());
+            return;
+        }
+        match commit_staged(&self.repo_path, message) {
+            Ok(hash) => {
+                self.error_message = None;
+                self.success_message = Some(format!("Committed {}", hash));
+                if let Err(e) = self.refresh_files() {
+                    self.error_message = Some(format!("Failed to refresh: {}", e));
+                }
+            }
+            Err(e) => self.error_message = Some(format!("Failed to commit: {}", e)),
+        }
+    }
+
     /// Select a branch and refresh
     pub fn select_branch(&mut self, branch: String) {
+        self.base_changed |= branch != self.base_branch;
         self.base_branch = branch;
         self.branch_select = None;
+        self.error_message = None;
         self.warning_dialog = check_merge_base_status(&self.repo_path, &self.base_branch)
             .map(|msg| InfoDialog::new("Warning", &msg));
         if let Err(e) = self.refresh_files() {
@@ -195,6 +273,47 @@ impl DiffView {
         }
     }
 
+    /// Jump to the next hunk, continuing into the next file after the last one
+    pub fn next_hunk(&mut self) {
+        let visible = self.visible_lines;
+        let scroll = self.scroll_offset;
+        let Some(diff) = self.get_current_diff() else {
+            return;
+        };
+        let (offsets, total) = hunk_layout(diff);
+        let max_scroll = total.saturating_sub(visible);
+
+        match offsets.into_iter().find(|&o| o > scroll && o <= max_scroll) {
+            Some(offset) => self.scroll_offset = offset,
+            None => self.next_file(),
+        }
+    }
+
+    /// Jump to the previous hunk, continuing into the previous file before the first one
+    pub fn prev_hunk(&mut self) {
+        let scroll = self.scroll_offset;
+        let Some(diff) = self.get_current_diff() else {
+            return;
+        };
+        let (offsets, _) = hunk_layout(diff);
+
+        if let Some(offset) = offsets.into_iter().rev().find(|&o| o < scroll) {
+            self.scroll_offset = offset;
+            return;
+        }
+        if self.selected_file == 0 {
+            return;
+        }
+
+        self.prev_file();
+        let visible = self.visible_lines;
+        if let Some(diff) = self.get_current_diff() {
+            let (offsets, total) = hunk_layout(diff);
+            let max_scroll = total.saturating_sub(visible);
+            self.scroll_offset = offsets.last().copied().unwrap_or(0).min(max_scroll);
+        }
+    }
+
     /// Scroll diff content down
     pub fn scroll_down(&mut self, amount: u16) {
         let max_scroll = self.total_lines.saturating_sub(self.visible_lines);
@@ -245,3 +364,15 @@ impl DiffView {
         }
     }
 }
+
+/// Row offset of each hunk header in the rendered diff, plus the total row count.
+/// Mirrors the layout in `render_diff_content`: a header, the hunk's lines, then a blank row.
+fn hunk_layout(diff: &FileDiff) -> (Vec<u16>, u16) {
+    let mut offsets = Vec::with_capacity(diff.hunks.len());
+    let mut row = 0usize;
+    for hunk in &diff.hunks {
+        offsets.push(row as u16);
+        row += hunk.lines.len() + 2;
+    }
+    (offsets, row as u16)
+}
@@ -11,9 +11,11 @@ use ratatui::{
     Frame,
 };
 use similar::ChangeTag;
+use tui_input::Input;
 
 use super::DiffView;
 use crate::git::diff::FileStatus;
+use crate::tui::components::render_text_field;
 use crate::tui::styles::Theme;
 
 /// Truncate a string from the left, adding an ellipsis prefix if it doesn't fit.
@@ -55,6 +57,29 @@ impl DiffView {
         self.render_content(frame, layout[1], theme);
         self.render_footer(frame, layout[2], theme);
 
+        if let Some(input) = &self.ref_input {
+            Self::render_input_dialog(
+                frame,
+                area,
+                " Base Ref ",
+                "Ref:",
+                "branch, tag, or commit",
+                input,
+                theme,
+            );
+        }
+        if let Some(input) = &self.commit_input {
+            Self::render_input_dialog(
+                frame,
+                area,
+                " Commit Staged Changes ",
+                "Message:",
+                "commit message",
+                input,
+                theme,
+            );
+        }
+
         // Render help overlay if active
         if self.show_help {
             self.render_help(frame, area, theme);
@@ -335,6 +360,8 @@ impl DiffView {
             Line::from(vec![
                 Span::styled("j/k", Style::default().fg(theme.accent)),
                 Span::styled(": files  ", Style::default().fg(theme.dimmed)),
+                Span::styled("[/]", Style::default().fg(theme.accent)),
+                Span::styled(": hunks  ", Style::default().fg(theme.dimmed)),
                 Span::styled("h/l", Style::default().fg(theme.accent)),
                 Span::styled(": resize  ", Style::default().fg(theme.dimmed)),
                 Span::styled("scroll", Style::default().fg(theme.accent)),
@@ -343,6 +370,8 @@ impl DiffView {
                 Span::styled(": edit  ", Style::default().fg(theme.dimmed)),
                 Span::styled("b", Style::default().fg(theme.accent)),
                 Span::styled(": branch  ", Style::default().fg(theme.dimmed)),
+                Span::styled("s/c", Style::default().fg(theme.accent)),
+                Span::styled(": stage/commit  ", Style::default().fg(theme.dimmed)),
                 Span::styled("?", Style::default().fg(theme.accent)),
                 Span::styled(": help  ", Style::default().fg(theme.dimmed)),
                 Span::styled("q/Esc", Style::default().fg(theme.accent)),
@@ -429,9 +458,45 @@ impl DiffView {
         frame.render_widget(list, inner);
     }
 
+    fn render_input_dialog(
+        frame: &mut Frame,
+        area: Rect,
+        title: &str,
+        label: &str,
+        placeholder: &str,
+        input: &Input,
+        theme: &Theme,
+    ) {
+        let dialog_width = 60u16.min(area.width);
+        let dialog_height = 3u16;
+        let dialog_area = Rect {
+            x: area.x + (area.width.saturating_sub(dialog_width)) / 2,
+            y: area.y + (area.height.saturating_sub(dialog_height)) / 2,
+            width: dialog_width,
+            height: dialog_height,
+        };
+
+        frame.render_widget(Clear, dialog_area);
+
+        let block = Block::default()
+            .title(title)
+            .title_bottom(Line::styled(
+                " Enter: apply  Esc: cancel ",
+                Style::default().fg(theme.dimmed),
+            ))
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(theme.accent))
+            .style(Style::default().bg(theme.background));
+
+        let inner = block.inner(dialog_area);
+        frame.render_widget(block, dialog_area);
+
+        render_text_field(frame, inner, label, input, true, Some(placeholder), theme);
+    }
+
     fn render_help(&self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let dialog_width = 55u16;
-        let dialog_height = 19u16;
+        let dialog_height = 23u16;
 
         let x = area.x + (area.width.saturating_sub(dialog_width)) / 2;
         let y = area.y + (area.height.saturating_sub(dialog_height)) / 2;
@@ -464,6 +529,7 @@ impl DiffView {
                 "Navigation",
                 vec![
                     ("j/k, ↑/↓", "Navigate between files"),
+                    ("]/[", "Next / previous hunk"),
                     ("PgUp/Dn", "Page up / down in diff"),
                     ("Ctrl+u/d", "Half-page up / down"),
                     ("g/G", "Go to top / bottom of diff"),
@@ -475,6 +541,9 @@ impl DiffView {
                 vec![
                     ("e/Enter", "Edit file in external editor"),
                     ("b", "Select base branch"),
+                    ("B", "Type any base ref (tag, commit)"),
+                    ("s", "Stage selected file (git add)"),
+                    ("c", "Commit staged changes"),
                     ("r", "Refresh diff"),
                 ],
             ),
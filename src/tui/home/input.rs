@@ -5,40 +5,61 @@ use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
 use super::{HomeView, TerminalMode, ViewMode};
-use crate::session::{flatten_tree, list_profiles, repo_config, resolve_config, Item, Status};
+use crate::session::{
+    flatten_tree, list_profiles, repo_config, resolve_config, Item, Status, SUPPORTED_TOOLS,
+};
 use crate::tui::app::Action;
 use crate::tui::dialogs::{
-    ConfirmDialog, DeleteDialogConfig, DialogResult, GroupDeleteOptionsDialog, HookTrustAction,
-    InfoDialog, NewSessionData, NewSessionDialog, RenameDialog, UnifiedDeleteDialog,
+    ChangeToolDialog, ConfirmDialog, DeleteDialogConfig, DialogResult, GroupDeleteOptionsDialog,
+    HookTrustAction, InfoDialog, NewSessionData, NewSessionDialog, RenameDialog,
+    UnifiedDeleteDialog, UnsavedChangesAction, UnsavedChangesDialog,
 };
 use crate::tui::diff::{DiffAction, DiffView};
 use crate::tui::settings::{SettingsAction, SettingsView};
 
 impl HomeView {
+    /// Keys that create, change, or attach to sessions, or that save view
+    /// preferences, which the read-only dashboard ignores. The diff view and
+    /// settings are included since both can write (diff base, editor launches,
+    /// config files), as are preview wrap (`w`) and pane resizing (`H`/`L`).
+    /// Expanding and collapsing groups is refused in `toggle_group_collapsed`.
+    fn is_mutating_key(&self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('n' | 's' | 'D' | 'd' | 'T' | 'w' | 'H' | 'L') => true,
+            KeyCode::Char('r') => !key.modifiers.contains(KeyModifiers::SHIFT),
+            KeyCode::Enter => self.selected_session.is_some(),
+            _ => false,
+        }
+    }
+
     pub fn handle_key(&mut self, key: KeyEvent) -> Option<Action> {
-        // Handle unsaved changes confirmation for settings (shown over settings view)
-        if self.settings_close_confirm {
-            if let Some(dialog) = &mut self.confirm_dialog {
-                match dialog.handle_key(key) {
-                    DialogResult::Continue => return None,
-                    DialogResult::Cancel => {
-                        // User chose not to discard, go back to settings
-                        self.confirm_dialog = None;
-                        self.settings_close_confirm = false;
-                        return None;
-                    }
-                    DialogResult::Submit(_) => {
-                        // User chose to discard changes
-                        if let Some(ref mut settings) = self.settings_view {
-                            settings.force_close();
+        // Handle unsaved changes prompt for settings (shown over settings view)
+        if let Some(dialog) = &mut self.settings_close_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => {
+                    // Go back to editing
+                    self.settings_close_dialog = None;
+                }
+                DialogResult::Submit(UnsavedChangesAction::Save) => {
+                    self.settings_close_dialog = None;
+                    // Stay in settings if validation or saving failed
+                    if let Some(ref mut settings) = self.settings_view {
+                        if settings.save_and_report() {
+                            self.settings_view = None;
+                            self.refresh_from_config();
                         }
-                        self.settings_view = None;
-                        self.confirm_dialog = None;
-                        self.settings_close_confirm = false;
-                        return None;
                     }
                 }
+                DialogResult::Submit(UnsavedChangesAction::Discard) => {
+                    if let Some(ref mut settings) = self.settings_view {
+                        settings.force_close();
+                    }
+                    self.settings_view = None;
+                    self.settings_close_dialog = None;
+                }
             }
+            return None;
         }
 
         // Handle settings view (full-screen takeover)
@@ -52,15 +73,12 @@ impl HomeView {
                     return None;
                 }
                 SettingsAction::UnsavedChangesWarning => {
-                    // Show confirmation dialog
-                    self.confirm_dialog = Some(ConfirmDialog::new(
-                        "Unsaved Changes",
-                        "You have unsaved changes. Discard them?",
-                        "discard_settings",
-                    ));
-                    self.settings_close_confirm = true;
+                    self.settings_close_dialog = Some(UnsavedChangesDialog::new());
                     return None;
                 }
+                SettingsAction::EditConfig(path) => {
+                    return Some(Action::EditFile(path));
+                }
             }
         }
 
@@ -69,7 +87,11 @@ impl HomeView {
             match diff_view.handle_key(key) {
                 DiffAction::Continue => return None,
                 DiffAction::Close => {
-                    self.diff_view = None;
+                    if let Some(view) = self.diff_view.take() {
+                        if view.base_changed {
+                            self.save_diff_base(view.base_branch);
+                        }
+                    }
                     return None;
                 }
                 DiffAction::EditFile(path) => {
@@ -174,6 +196,7 @@ impl HomeView {
                     }
                 }
                 DialogResult::Submit(data) => {
+                    self.remember_new_session(&data);
                     // Check for hooks before creating the session
                     match repo_config::check_hook_trust(std::path::Path::new(&data.path)) {
                         Ok(repo_config::HookTrustStatus::NeedsTrust { hooks, hooks_hash }) => {
@@ -215,6 +238,8 @@ impl HomeView {
                         if let Err(e) = self.delete_selected_group() {
                             tracing::error!("Failed to delete group: {}", e);
                         }
+                    } else if action == "restart_session" {
+                        self.restart_selected();
                     }
                 }
             }
@@ -277,6 +302,26 @@ impl HomeView {
             return None;
         }
 
+        if let Some(dialog) = &mut self.change_tool_dialog {
+            match dialog.handle_key(key) {
+                DialogResult::Continue => {}
+                DialogResult::Cancel => {
+                    self.change_tool_dialog = None;
+                }
+                DialogResult::Submit(tool) => {
+                    self.change_tool_dialog = None;
+                    if let Err(e) = self.change_selected_tool(&tool) {
+                        tracing::error!("Failed to change session tool: {}", e);
+                        self.info_dialog = Some(InfoDialog::new(
+                            "Error",
+                            &format!("Failed to change session tool: {}", e),
+                        ));
+                    }
+                }
+            }
+            return None;
+        }
+
         // Search mode
         if self.search_active {
             match key.code {
@@ -297,6 +342,10 @@ impl HomeView {
             return None;
         }
 
+        if self.read_only && self.is_mutating_key(key) {
+            return None;
+        }
+
         // Normal mode keybindings
         match key.code {
             KeyCode::Char('q') => return Some(Action::Quit),
@@ -314,20 +363,18 @@ impl HomeView {
                     ViewMode::Terminal => ViewMode::Agent,
                 };
             }
-            KeyCode::Char('c') => {
-                // Toggle container/host terminal mode (only in Terminal view for sandboxed sessions)
-                if self.view_mode == ViewMode::Terminal {
-                    if let Some(id) = &self.selected_session {
-                        if let Some(inst) = self.instance_map.get(id) {
-                            if inst.is_sandboxed() {
-                                let id = id.clone();
-                                self.toggle_terminal_mode(&id);
-                            } else {
-                                self.info_dialog = Some(InfoDialog::new(
-                                    "Not Available",
-                                    "Only sandboxed sessions support container terminals. This session runs directly on the host.",
-                                ));
-                            }
+            // Toggle container/host terminal mode (only in Terminal view for sandboxed sessions)
+            KeyCode::Char('c') if self.view_mode == ViewMode::Terminal => {
+                if let Some(id) = &self.selected_session {
+                    if let Some(inst) = self.instance_map.get(id) {
+                        if inst.is_sandboxed() {
+                            let id = id.clone();
+                            self.toggle_terminal_mode(&id);
+                        } else {
+                            self.info_dialog = Some(InfoDialog::new(
+                                "Not Available",
+                                "Only sandboxed sessions support container terminals. This session runs directly on the host.",
+                            ));
                         }
                     }
                 }
@@ -387,7 +434,12 @@ impl HomeView {
                 };
 
                 let repo_path = std::path::PathBuf::from(&inst.project_path);
-                match DiffView::new(repo_path) {
+                let base = inst.diff_base.clone().or_else(|| {
+                    inst.worktree_info
+                        .as_ref()
+                        .and_then(|wt| wt.base_branch.clone())
+                });
+                match DiffView::new(repo_path, base) {
                     Ok(view) => self.diff_view = Some(view),
                     Err(e) => {
                         tracing::error!("Failed to open diff view: {}", e);
@@ -420,6 +472,7 @@ impl HomeView {
                                 .filter(|wt| wt.managed_by_aoe)
                                 .map(|wt| wt.branch.clone()),
                             has_sandbox: inst.sandbox_info.as_ref().is_some_and(|s| s.enabled),
+                            worktree_shared_by: inst.worktree_shared_by(&self.instances),
                         };
 
                         self.unified_delete_dialog =
@@ -476,6 +529,30 @@ impl HomeView {
                     }
                 }
             }
+            KeyCode::Char('y') => {
+                self.copy_launch_command();
+            }
+            KeyCode::Char('T') => {
+                if let Some(id) = &self.selected_session {
+                    if let Some(inst) = self.instance_map.get(id) {
+                        if inst.status == Status::Deleting {
+                            return None;
+                        }
+                        // Sandboxed sessions run the tool inside the container,
+                        // so host availability doesn't limit the choice
+                        let tools: Vec<String> = if inst.is_sandboxed() {
+                            SUPPORTED_TOOLS.iter().map(|t| t.to_string()).collect()
+                        } else {
+                            self.available_tools
+                                .available_list()
+                                .into_iter()
+                                .map(String::from)
+                                .collect()
+                        };
+                        self.change_tool_dialog = Some(ChangeToolDialog::new(&inst.tool, tools));
+                    }
+                }
+            }
             KeyCode::Up | KeyCode::Char('k') => {
                 self.move_cursor(-1);
             }
@@ -492,11 +569,9 @@ impl HomeView {
                 self.cursor = 0;
                 self.update_selected();
             }
-            KeyCode::End | KeyCode::Char('G') => {
-                if !self.flat_items.is_empty() {
-                    self.cursor = self.flat_items.len() - 1;
-                    self.update_selected();
-                }
+            KeyCode::End | KeyCode::Char('G') if !self.flat_items.is_empty() => {
+                self.cursor = self.flat_items.len() - 1;
+                self.update_selected();
             }
             KeyCode::Enter => {
                 if let Some(id) = &self.selected_session {
@@ -525,6 +600,18 @@ impl HomeView {
                     self.toggle_group_collapsed(&path);
                 }
             }
+            KeyCode::Char('w') => {
+                self.toggle_preview_wrap();
+            }
+            KeyCode::Char('o') if self.view_mode == ViewMode::Agent => {
+                self.toggle_preview_logs();
+            }
+            KeyCode::Char('<') => {
+                self.scroll_preview_left();
+            }
+            KeyCode::Char('>') => {
+                self.scroll_preview_right();
+            }
             KeyCode::Char('H') => {
                 self.shrink_list();
             }
@@ -604,6 +691,11 @@ impl HomeView {
     }
 
     fn toggle_group_collapsed(&mut self, path: &str) {
+        // Collapse state is saved with the sessions, which a read-only
+        // dashboard must not write
+        if self.read_only {
+            return;
+        }
         self.group_tree.toggle_collapsed(path);
         self.flat_items = flatten_tree(&self.group_tree, &self.instances);
         if let Err(e) = self
@@ -687,7 +779,11 @@ impl HomeView {
             match diff_view.handle_mouse(mouse) {
                 DiffAction::Continue => return None,
                 DiffAction::Close => {
-                    self.diff_view = None;
+                    if let Some(view) = self.diff_view.take() {
+                        if view.base_changed {
+                            self.save_diff_base(view.base_branch);
+                        }
+                    }
                     return None;
                 }
                 DiffAction::EditFile(path) => {
@@ -8,25 +8,30 @@ mod render;
 mod tests;
 
 use std::collections::{HashMap, HashSet};
-use std::time::Instant;
+use std::time::{Duration, Instant};
 
 use tui_input::Input;
 
+use crate::docker::{ContainerState, DockerContainer};
+use crate::events::{self, Event, EventKind};
 use crate::session::{
     config::{load_config, save_config},
-    flatten_tree, resolve_config, DefaultTerminalMode, Group, GroupTree, Instance, Item, Storage,
+    flatten_tree, resolve_config, DefaultTerminalMode, Group, GroupTree, Instance, Item,
+    NewSessionOptions, Status, Storage, STOPPED_ON_DETACH,
 };
 use crate::tmux::AvailableTools;
 
+use super::container_logs_poller::ContainerLogsPoller;
 use super::creation_poller::{CreationPoller, CreationRequest};
 use super::deletion_poller::DeletionPoller;
 use super::dialogs::{
-    ChangelogDialog, ConfirmDialog, GroupDeleteOptionsDialog, HookTrustDialog, InfoDialog,
-    NewSessionData, NewSessionDialog, RenameDialog, UnifiedDeleteDialog, WelcomeDialog,
+    ChangeToolDialog, ChangelogDialog, ConfirmDialog, GroupDeleteOptionsDialog, HookTrustDialog,
+    InfoDialog, NewSessionData, NewSessionDialog, RenameDialog, UnifiedDeleteDialog,
+    UnsavedChangesDialog, WelcomeDialog,
 };
 use super::diff::DiffView;
 use super::settings::SettingsView;
-use super::status_poller::StatusPoller;
+use super::status_poller::{StatusDebouncer, StatusPoller};
 
 /// View mode for the home screen
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -50,6 +55,19 @@ pub(super) struct PreviewCache {
     pub(super) content: String,
     pub(super) last_refresh: Instant,
     pub(super) dimensions: (u16, u16),
+    /// tmux `window_activity` (unix seconds) of the pane when `content` was
+    /// captured. Pane output advances it, not just client input.
+    pub(super) activity: Option<i64>,
+    /// Unix seconds at which `content` was captured
+    pub(super) captured_at: i64,
+}
+
+/// `docker inspect` state of the selected session's container, for the
+/// preview's uptime line
+pub(super) struct ContainerStateCache {
+    pub(super) session_id: String,
+    pub(super) state: Option<ContainerState>,
+    pub(super) last_refresh: Instant,
 }
 
 impl Default for PreviewCache {
@@ -59,10 +77,52 @@ impl Default for PreviewCache {
             content: String::new(),
             last_refresh: Instant::now(),
             dimensions: (0, 0),
+            activity: None,
+            captured_at: 0,
         }
     }
 }
 
+/// Longest a quiet pane's capture is reused, in case tmux misses some output
+const PREVIEW_MAX_AGE: Duration = Duration::from_secs(5);
+
+impl PreviewCache {
+    /// Whether `content` still matches a pane whose current tmux activity
+    /// time is `activity`, so the capture can be skipped. Activity has
+    /// one-second resolution, so output in the capture's second counts as new.
+    /// A capture older than `PREVIEW_MAX_AGE` is never reused.
+    pub(super) fn is_current(
+        &self,
+        id: &str,
+        dimensions: (u16, u16),
+        activity: Option<i64>,
+    ) -> bool {
+        self.session_id.as_deref() == Some(id)
+            && self.dimensions == dimensions
+            && self.last_refresh.elapsed() < PREVIEW_MAX_AGE
+            && matches!(
+                (self.activity, activity),
+                (Some(prev), Some(now)) if prev == now && now < self.captured_at
+            )
+    }
+
+    /// Record a fresh capture
+    pub(super) fn store(
+        &mut self,
+        id: &str,
+        dimensions: (u16, u16),
+        activity: Option<i64>,
+        content: String,
+    ) {
+        self.content = content;
+        self.session_id = Some(id.to_string());
+        self.dimensions = dimensions;
+        self.activity = activity;
+        self.captured_at = chrono::Utc::now().timestamp();
+        self.last_refresh = Instant::now();
+    }
+}
+
 pub(super) const INDENTS: [&str; 10] = [
     "",
     "  ",
@@ -80,15 +140,14 @@ pub(super) fn get_indent(depth: usize) -> &'static str {
     INDENTS.get(depth).copied().unwrap_or(INDENTS[9])
 }
 
-pub(super) const ICON_RUNNING: &str = "●";
-pub(super) const ICON_WAITING: &str = "◐";
-pub(super) const ICON_IDLE: &str = "○";
-pub(super) const ICON_ERROR: &str = "✕";
-pub(super) const ICON_STARTING: &str = "◌";
-pub(super) const ICON_DELETING: &str = "✗";
+pub(super) const ICON_RUNNING: &str = Status::Running.icon();
+pub(super) const ICON_IDLE: &str = Status::Idle.icon();
 pub(super) const ICON_COLLAPSED: &str = "▶";
 pub(super) const ICON_EXPANDED: &str = "▼";
 
+/// Columns moved per horizontal scroll step in the preview
+const PREVIEW_HSCROLL_STEP: u16 = 8;
+
 pub struct HomeView {
     pub(super) storage: Storage,
     pub(super) instances: Vec<Instance>,
@@ -110,6 +169,7 @@ pub struct HomeView {
     pub(super) unified_delete_dialog: Option<UnifiedDeleteDialog>,
     pub(super) group_delete_options_dialog: Option<GroupDeleteOptionsDialog>,
     pub(super) rename_dialog: Option<RenameDialog>,
+    pub(super) change_tool_dialog: Option<ChangeToolDialog>,
     pub(super) hook_trust_dialog: Option<HookTrustDialog>,
     /// Session data pending hook trust approval
     pub(super) pending_hook_trust_data: Option<NewSessionData>,
@@ -128,6 +188,11 @@ pub struct HomeView {
     // Performance: background status polling
     pub(super) status_poller: StatusPoller,
     pub(super) pending_status_refresh: bool,
+    pub(super) status_debouncer: StatusDebouncer,
+    /// Restart sandboxed sessions whose container exited (`sandbox.auto_restart`)
+    pub(super) auto_restart: bool,
+    /// Stop a sandboxed session's container once nothing views it (`sandbox.stop_on_detach`)
+    pub(super) stop_on_detach: bool,
 
     // Performance: background deletion
     pub(super) deletion_poller: DeletionPoller,
@@ -143,6 +208,12 @@ pub struct HomeView {
     pub(super) preview_cache: PreviewCache,
     pub(super) terminal_preview_cache: PreviewCache,
     pub(super) container_terminal_preview_cache: PreviewCache,
+    /// Container logs for the selected sandboxed session, shown instead of the pane
+    pub(super) container_logs_cache: PreviewCache,
+    /// Whether the container logs could be read for the cached session
+    pub(super) container_logs_available: bool,
+    pub(super) container_logs_poller: ContainerLogsPoller,
+    pub(super) container_state_cache: Option<ContainerStateCache>,
 
     // Terminal mode for sandboxed sessions (per-session, ephemeral)
     pub(super) terminal_modes: HashMap<String, TerminalMode>,
@@ -151,17 +222,28 @@ pub struct HomeView {
 
     // Sound config for state transition sounds
     pub(super) sound_config: crate::sound::SoundConfig,
+    // External hooks for session lifecycle events
+    pub(super) events_config: crate::session::EventsConfig,
+    /// Dashboard mode (`aoe --readonly`): keys that change or attach to
+    /// sessions are ignored
+    pub(super) read_only: bool,
 
     // Settings view
     pub(super) settings_view: Option<SettingsView>,
-    /// Flag to indicate we're confirming settings close (unsaved changes)
-    pub(super) settings_close_confirm: bool,
+    /// Save/discard prompt when closing settings with unsaved changes
+    pub(super) settings_close_dialog: Option<UnsavedChangesDialog>,
 
     // Diff view
     pub(super) diff_view: Option<DiffView>,
 
     // Resizable list column width (percentage-like units)
     pub(super) list_width: u16,
+
+    // Preview line handling: wrap long lines, or clip them with horizontal scroll
+    pub(super) preview_wrap: bool,
+    pub(super) preview_hscroll: u16,
+    /// Show container logs instead of the agent pane for sandboxed sessions
+    pub(super) preview_logs: bool,
 }
 
 impl HomeView {
@@ -192,6 +274,20 @@ impl HomeView {
             .as_ref()
             .map(|config| config.sound.clone())
             .unwrap_or_default();
+        let events_config = resolved
+            .as_ref()
+            .map(|config| config.events.clone())
+            .unwrap_or_default();
+        let auto_restart = resolved
+            .as_ref()
+            .is_ok_and(|config| config.sandbox.auto_restart);
+        let stop_on_detach = resolved
+            .as_ref()
+            .is_ok_and(|config| config.sandbox.stop_on_detach);
+        let status_debounce_polls = resolved
+            .as_ref()
+            .map(|config| config.ui.status_debounce_polls)
+            .unwrap_or_else(|_| crate::session::UiConfig::default().status_debounce_polls);
 
         let mut view = Self {
             storage,
@@ -210,6 +306,7 @@ impl HomeView {
             unified_delete_dialog: None,
             group_delete_options_dialog: None,
             rename_dialog: None,
+            change_tool_dialog: None,
             hook_trust_dialog: None,
             pending_hook_trust_data: None,
             welcome_dialog: None,
@@ -220,6 +317,9 @@ impl HomeView {
             filtered_items: None,
             available_tools,
             status_poller: StatusPoller::new(),
+            status_debouncer: StatusDebouncer::new(status_debounce_polls),
+            auto_restart,
+            stop_on_detach,
             pending_status_refresh: false,
             deletion_poller: DeletionPoller::new(),
             creation_poller: CreationPoller::new(),
@@ -228,17 +328,29 @@ impl HomeView {
             preview_cache: PreviewCache::default(),
             terminal_preview_cache: PreviewCache::default(),
             container_terminal_preview_cache: PreviewCache::default(),
+            container_logs_cache: PreviewCache::default(),
+            container_logs_available: false,
+            container_logs_poller: ContainerLogsPoller::new(),
+            container_state_cache: None,
             terminal_modes: HashMap::new(),
             default_terminal_mode,
             sound_config,
+            events_config,
+            read_only: false,
             settings_view: None,
-            settings_close_confirm: false,
+            settings_close_dialog: None,
             diff_view: None,
             list_width: load_config()
                 .ok()
                 .flatten()
                 .and_then(|c| c.app_state.home_list_width)
                 .unwrap_or(35),
+            preview_wrap: load_config()
+                .ok()
+                .flatten()
+                .is_some_and(|c| c.app_state.preview_wrap),
+            preview_hscroll: 0,
+            preview_logs: false,
         };
 
         view.update_selected();
@@ -276,12 +388,17 @@ impl HomeView {
         Ok(())
     }
 
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
     /// Request a status refresh in the background (non-blocking).
     /// Call `apply_status_updates` to check for and apply results.
     pub fn request_status_refresh(&mut self) {
         if !self.pending_status_refresh {
             let instances: Vec<Instance> = self.instances.clone();
-            self.status_poller.request_refresh(instances);
+            self.status_poller
+                .request_refresh(instances, self.auto_restart && !self.read_only);
             self.pending_status_refresh = true;
         }
     }
@@ -289,42 +406,101 @@ impl HomeView {
     /// Apply any pending status updates from the background poller.
     /// Returns true if updates were applied.
     pub fn apply_status_updates(&mut self) -> bool {
-        use crate::session::Status;
-
         if let Some(updates) = self.status_poller.try_recv_updates() {
+            let mut session_ids_changed = false;
             for update in updates {
+                let mut status = update.status;
                 if let Some(inst) = self.instances.iter_mut().find(|i| i.id == update.id) {
+                    if update.claude_session_id.is_some()
+                        && inst.claude_session_id != update.claude_session_id
+                    {
+                        inst.claude_session_id = update.claude_session_id.clone();
+                        session_ids_changed = true;
+                    }
                     if inst.status != Status::Deleting {
                         let old_status = inst.status;
-                        inst.status = update.status;
+                        status =
+                            self.status_debouncer
+                                .settle(&update.id, old_status, update.status);
+                        inst.status = status;
                         inst.last_error = update.last_error.clone();
-                        if old_status != update.status {
+                        if old_status != status {
                             crate::sound::play_for_transition(
                                 old_status,
-                                update.status,
+                                status,
                                 &self.sound_config,
                             );
+                            let profile = self.storage.profile();
+                            if events::is_start_transition(old_status, status) {
+                                events::emit(
+                                    &self.events_config,
+                                    Event::new(EventKind::Started, inst, profile),
+                                );
+                            }
+                            events::emit(
+                                &self.events_config,
+                                Event::status_changed(inst, profile, old_status, status),
+                            );
                         }
                     }
                 }
                 if let Some(inst) = self.instance_map.get_mut(&update.id) {
+                    if update.claude_session_id.is_some() {
+                        inst.claude_session_id = update.claude_session_id;
+                    }
                     if inst.status != Status::Deleting {
-                        inst.status = update.status;
+                        inst.status = status;
                         inst.last_error = update.last_error;
                     }
                 }
             }
+            // A read-only dashboard must not overwrite another instance's sessions.json
+            if session_ids_changed && !self.read_only {
+                if let Err(e) = self
+                    .storage
+                    .save_with_groups(&self.instances, &self.group_tree)
+                {
+                    tracing::error!("Failed to save Claude session ids: {}", e);
+                }
+            }
             self.pending_status_refresh = false;
             return true;
         }
         false
     }
 
-    pub fn apply_deletion_results(&mut self) -> bool {
-        use crate::session::Status;
+    /// Show container logs read in the background for the selected session.
+    /// Returns true when the shown logs changed.
+    pub fn apply_container_logs(&mut self) -> bool {
+        let mut changed = false;
+        while let Some(result) = self.container_logs_poller.try_recv_result() {
+            if !self.preview_logs
+                || self.container_logs_cache.session_id.as_ref() != Some(&result.session_id)
+            {
+                continue;
+            }
+            let available = result.logs.is_some();
+            let logs = result.logs.unwrap_or_default();
+            if available != self.container_logs_available
+                || logs != self.container_logs_cache.content
+            {
+                self.container_logs_available = available;
+                self.container_logs_cache.content = logs;
+                changed = true;
+            }
+        }
+        changed
+    }
 
+    pub fn apply_deletion_results(&mut self) -> bool {
         if let Some(result) = self.deletion_poller.try_recv_result() {
             if result.success {
+                if let Some(inst) = self.instances.iter().find(|i| i.id == result.session_id) {
+                    events::emit(
+                        &self.events_config,
+                        Event::new(EventKind::Deleted, inst, self.storage.profile()),
+                    );
+                }
                 self.instances.retain(|i| i.id != result.session_id);
                 self.instance_map.remove(&result.session_id);
                 self.group_tree = GroupTree::new_with_groups(&self.instances, &self.groups);
@@ -356,6 +532,9 @@ impl HomeView {
     }
 
     /// Request background session creation. Used for sandbox sessions to avoid blocking UI.
+    ///
+    /// The poller runs the cheap sandbox checks before any slow work, so a
+    /// stopped Docker daemon comes back as an inline dialog error right away.
     pub fn request_creation(
         &mut self,
         data: NewSessionData,
@@ -372,6 +551,7 @@ impl HomeView {
         self.creation_cancelled = false;
         let request = CreationRequest {
             data,
+            profile: self.storage.profile().to_string(),
             existing_instances: self.instances.clone(),
             hooks,
         };
@@ -388,6 +568,7 @@ impl HomeView {
 
     /// Apply any pending creation results from the background poller.
     /// Returns Some(session_id) if creation succeeded and we should attach.
+    /// Sessions created without starting are selected instead.
     pub fn apply_creation_results(&mut self) -> Option<String> {
         use super::creation_poller::CreationResult;
         use crate::session::builder::{self, CreatedWorktree};
@@ -418,9 +599,15 @@ impl HomeView {
                 session_id,
                 instance,
                 on_launch_hooks_ran,
+                start,
+                missing_env_vars,
                 ..
             } => {
                 let instance = *instance;
+                events::emit(
+                    &self.events_config,
+                    Event::new(EventKind::Created, &instance, self.storage.profile()),
+                );
                 self.instances.push(instance.clone());
                 self.group_tree = GroupTree::new_with_groups(&self.instances, &self.groups);
                 if !instance.group_path.is_empty() {
@@ -441,7 +628,23 @@ impl HomeView {
                 let _ = self.reload();
                 self.new_dialog = None;
 
-                Some(session_id)
+                if !missing_env_vars.is_empty() {
+                    // Shown when the user returns from the new session
+                    self.info_dialog = Some(InfoDialog::new(
+                        "Missing Environment Variables",
+                        &format!(
+                            "These host variables are not set and were not passed to the container: {}",
+                            missing_env_vars.join(", ")
+                        ),
+                    ));
+                }
+
+                if start {
+                    Some(session_id)
+                } else {
+                    self.select_session_by_id(&session_id);
+                    None
+                }
             }
             CreationResult::Error(error) => {
                 if let Some(dialog) = &mut self.new_dialog {
@@ -483,6 +686,7 @@ impl HomeView {
             || self.unified_delete_dialog.is_some()
             || self.group_delete_options_dialog.is_some()
             || self.rename_dialog.is_some()
+            || self.change_tool_dialog.is_some()
             || self.hook_trust_dialog.is_some()
             || self.welcome_dialog.is_some()
             || self.changelog_dialog.is_some()
@@ -508,6 +712,78 @@ impl HomeView {
         }
     }
 
+    /// Store the submitted new session options so the next dialog starts
+    /// from them (`ui.remember_new_session`)
+    pub(super) fn remember_new_session(&self, data: &NewSessionData) {
+        let enabled = resolve_config(self.storage.profile())
+            .is_ok_and(|config| config.ui.remember_new_session);
+        if !enabled {
+            return;
+        }
+        if let Ok(mut config) = load_config().map(|c| c.unwrap_or_default()) {
+            config.app_state.last_new_session = Some(NewSessionOptions {
+                tool: data.tool.clone(),
+                group: data.group.clone(),
+                sandbox: data.sandbox,
+                sandbox_image: data.sandbox_image.clone(),
+                yolo_mode: data.yolo_mode,
+            });
+            let _ = save_config(&config);
+        }
+    }
+
+    pub fn toggle_preview_wrap(&mut self) {
+        self.preview_wrap = !self.preview_wrap;
+        self.preview_hscroll = 0;
+        // Force a recapture, since wrapping changes how lines are captured
+        self.preview_cache = PreviewCache::default();
+        if let Ok(mut config) = load_config().map(|c| c.unwrap_or_default()) {
+            config.app_state.preview_wrap = self.preview_wrap;
+            let _ = save_config(&config);
+        }
+    }
+
+    /// Force every preview to recapture on the next render, e.g. after a resize.
+    pub fn invalidate_preview_caches(&mut self) {
+        for cache in [
+            &mut self.preview_cache,
+            &mut self.terminal_preview_cache,
+            &mut self.container_terminal_preview_cache,
+            &mut self.container_logs_cache,
+        ] {
+            cache.dimensions = (0, 0);
+        }
+    }
+
+    pub fn toggle_preview_logs(&mut self) {
+        if !self.preview_logs {
+            let sandboxed = self
+                .selected_session
+                .as_ref()
+                .and_then(|id| self.instance_map.get(id))
+                .is_some_and(|inst| inst.is_sandboxed());
+            if !sandboxed {
+                self.info_dialog = Some(InfoDialog::new(
+                    "Not Available",
+                    "Only sandboxed sessions have container logs. This session runs directly on the host.",
+                ));
+                return;
+            }
+        }
+        self.preview_logs = !self.preview_logs;
+        self.container_logs_cache = PreviewCache::default();
+    }
+
+    pub fn scroll_preview_left(&mut self) {
+        self.preview_hscroll = self.preview_hscroll.saturating_sub(PREVIEW_HSCROLL_STEP);
+    }
+
+    pub fn scroll_preview_right(&mut self) {
+        if !self.preview_wrap {
+            self.preview_hscroll = self.preview_hscroll.saturating_add(PREVIEW_HSCROLL_STEP);
+        }
+    }
+
     pub fn show_welcome(&mut self) {
         self.welcome_dialog = Some(WelcomeDialog::new());
     }
@@ -546,6 +822,117 @@ impl HomeView {
         }
     }
 
+    /// Explain that a session's tool isn't installed, listing the ones that are
+    pub fn show_tool_not_found(&mut self, tool: &str) {
+        let installed = self.available_tools.available_list();
+        let hint = if installed.is_empty() {
+            "No supported tools were found in PATH.".to_string()
+        } else {
+            format!("Installed tools: {}.", installed.join(", "))
+        };
+        self.info_dialog = Some(InfoDialog::new(
+            "Tool Not Found",
+            &format!(
+                "'{}' is not installed or not in PATH, so this session cannot start. {} Press T to switch the session to another tool.",
+                tool, hint
+            ),
+        ));
+    }
+
+    /// Copy the selected session's launch command (secrets redacted) to the
+    /// tmux paste buffer
+    pub fn copy_launch_command(&mut self) {
+        let Some(inst) = self
+            .selected_session
+            .as_ref()
+            .and_then(|id| self.instance_map.get(id))
+        else {
+            return;
+        };
+
+        let (cmd, redactor) = inst.build_launch_command();
+        let Some(cmd) = cmd.map(|cmd| redactor.redact(&cmd)) else {
+            self.info_dialog = Some(InfoDialog::new(
+                "No Launch Command",
+                "This session starts a plain shell rather than a command.",
+            ));
+            return;
+        };
+
+        self.info_dialog = Some(match crate::tmux::set_buffer(&cmd) {
+            Ok(()) => InfoDialog::new(
+                "Launch Command Copied",
+                &format!(
+                    "Copied to the tmux paste buffer (prefix + ] pastes it):\n\n{}",
+                    cmd
+                ),
+            ),
+            Err(e) => InfoDialog::new(
+                "Error",
+                &format!("Failed to copy the launch command: {}", e),
+            ),
+        });
+    }
+
+    /// With `sandbox.stop_on_detach`, stop the session's container after a
+    /// detach if no other client still views the agent or container terminal.
+    /// The stop runs in the background; attaching again restarts the container.
+    pub fn stop_container_on_detach(&mut self, session_id: &str) {
+        if !self.stop_on_detach {
+            return;
+        }
+        let Some(inst) = self.instance_map.get(session_id) else {
+            return;
+        };
+        if !inst.is_sandboxed() || inst.has_container_viewers() {
+            return;
+        }
+
+        let container = DockerContainer::from_session_id(&inst.id);
+        if !container.is_running().unwrap_or(false) {
+            return;
+        }
+        let title = inst.title.clone();
+        std::thread::spawn(move || {
+            if let Err(e) = container.stop() {
+                tracing::warn!("Failed to stop container for '{}' on detach: {}", title, e);
+            }
+        });
+        self.set_instance_error(session_id, Some(STOPPED_ON_DETACH.to_string()));
+    }
+
+    /// Record that the user just attached to a session, for idle-time display.
+    pub fn mark_accessed(&mut self, id: &str) -> anyhow::Result<()> {
+        let now = chrono::Utc::now();
+        if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
+            inst.last_accessed_at = Some(now);
+        }
+        if let Some(inst) = self.instance_map.get_mut(id) {
+            inst.last_accessed_at = Some(now);
+        }
+        self.storage
+            .save_with_groups(&self.instances, &self.group_tree)
+    }
+
+    /// Remember the diff base picked for the selected session
+    pub(super) fn save_diff_base(&mut self, base: String) {
+        let Some(id) = self.selected_session.clone() else {
+            return;
+        };
+        if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
+            inst.diff_base = Some(base.clone());
+        }
+        if let Some(inst) = self.instance_map.get_mut(&id) {
+            inst.diff_base = Some(base);
+        }
+        if let Err(e) = self
+            .storage
+            .save_with_groups(&self.instances, &self.group_tree)
+        {
+            tracing::warn!("Failed to save diff base: {}", e);
+        }
+    }
+
     pub fn start_terminal_for_instance_with_size(
         &mut self,
         id: &str,
@@ -584,6 +971,10 @@ impl HomeView {
 
     /// Refresh all config-dependent state from the current profile's config.
     /// Call this after settings are saved to pick up any changes.
+    pub fn profile(&self) -> &str {
+        self.storage.profile()
+    }
+
     pub fn refresh_from_config(&mut self) {
         if let Ok(config) = resolve_config(self.storage.profile()) {
             // Refresh default terminal mode for sandboxed sessions
@@ -594,6 +985,12 @@ impl HomeView {
 
             // Refresh sound config
             self.sound_config = config.sound.clone();
+            self.events_config = config.events.clone();
+
+            self.status_debouncer
+                .set_required_polls(config.ui.status_debounce_polls);
+            self.auto_restart = config.sandbox.auto_restart;
+            self.stop_on_detach = config.sandbox.stop_on_detach;
         }
     }
 
@@ -607,10 +1004,24 @@ impl HomeView {
         self.terminal_modes.insert(session_id.to_string(), new_mode);
     }
 
+    /// Start the container terminal, falling back to the host terminal (see
+    /// `fall_back_to_host_terminal`) if the container can't be started
     pub fn start_container_terminal_for_instance_with_size(
         &mut self,
         id: &str,
         size: Option<(u16, u16)>,
+    ) -> anyhow::Result<()> {
+        let result = self.start_container_terminal(id, size);
+        if let Err(e) = &result {
+            self.fall_back_to_host_terminal(id, e);
+        }
+        result
+    }
+
+    fn start_container_terminal(
+        &mut self,
+        id: &str,
+        size: Option<(u16, u16)>,
     ) -> anyhow::Result<()> {
         if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
             inst.start_container_terminal_with_size(size)?;
@@ -621,4 +1032,21 @@ impl HomeView {
         // Don't save terminal info for container terminals - it's ephemeral
         Ok(())
     }
+
+    /// Switch a session whose container terminal failed to start back to the
+    /// host terminal and say why, rather than leaving an empty container view
+    pub(super) fn fall_back_to_host_terminal(&mut self, id: &str, error: &anyhow::Error) {
+        tracing::warn!("Container terminal for {} failed to start: {:#}", id, error);
+        self.terminal_modes
+            .insert(id.to_string(), TerminalMode::Host);
+        self.container_terminal_preview_cache = PreviewCache::default();
+        self.set_instance_error(id, Some(format!("{:#}", error)));
+        self.info_dialog = Some(InfoDialog::new(
+            "Container Terminal Unavailable",
+            &format!(
+                "{:#}\n\nSwitched to the host terminal. Press c to try the container again.",
+                error
+            ),
+        ));
+    }
 }
@@ -1,44 +1,16 @@
-//! Session operations for HomeView (create, delete, rename)
+//! Session operations for HomeView (create, delete, rename, change tool)
 
-use crate::session::builder::{self, InstanceParams};
-use crate::session::{flatten_tree, list_profiles, GroupTree, Status, Storage};
+use crate::session::{flatten_tree, list_profiles, manager, GroupTree, Status, Storage};
 use crate::tui::deletion_poller::DeletionRequest;
-use crate::tui::dialogs::{DeleteOptions, GroupDeleteOptions, NewSessionData};
+use crate::tui::dialogs::{ConfirmDialog, DeleteOptions, GroupDeleteOptions, NewSessionData};
 
 use super::HomeView;
 
 impl HomeView {
     pub(super) fn create_session(&mut self, data: NewSessionData) -> anyhow::Result<String> {
-        let existing_titles: Vec<&str> = self.instances.iter().map(|i| i.title.as_str()).collect();
-
-        let params = InstanceParams {
-            title: data.title,
-            path: data.path,
-            group: data.group,
-            tool: data.tool,
-            worktree_branch: data.worktree_branch,
-            create_new_branch: data.create_new_branch,
-            sandbox: data.sandbox,
-            sandbox_image: data.sandbox_image,
-            yolo_mode: data.yolo_mode,
-            extra_env_keys: data.extra_env_keys,
-            extra_env_values: data.extra_env_values,
-        };
-
-        let build_result = builder::build_instance(params, &existing_titles)?;
-        let instance = build_result.instance;
-
-        let session_id = instance.id.clone();
-        self.instances.push(instance.clone());
-        self.group_tree = GroupTree::new_with_groups(&self.instances, &self.groups);
-        if !instance.group_path.is_empty() {
-            self.group_tree.create_group(&instance.group_path);
-        }
-        self.storage
-            .save_with_groups(&self.instances, &self.group_tree)?;
-
+        let instance = manager::create_session(self.storage.profile(), data.into())?;
         self.reload()?;
-        Ok(session_id)
+        Ok(instance.id)
     }
 
     pub(super) fn delete_selected(&mut self, options: &DeleteOptions) -> anyhow::Result<()> {
@@ -56,6 +28,7 @@ impl HomeView {
                 let request = DeletionRequest {
                     session_id: id.clone(),
                     instance: inst.clone(),
+                    instances: self.instances.clone(),
                     delete_worktree: options.delete_worktree,
                     delete_branch: options.delete_branch,
                     delete_sandbox: options.delete_sandbox,
@@ -99,34 +72,35 @@ impl HomeView {
                 .map(|i| i.id.clone())
                 .collect();
 
-            for session_id in sessions_to_delete {
-                // Clear group_path when marking for deletion so these instances
-                // won't cause the group to be recreated during tree rebuilds
-                if let Some(inst) = self.instance_map.get_mut(&session_id) {
+            // Clear group_path when marking for deletion so these instances
+            // won't cause the group to be recreated during tree rebuilds
+            for session_id in &sessions_to_delete {
+                if let Some(inst) = self.instance_map.get_mut(session_id) {
                     inst.status = Status::Deleting;
                     inst.group_path = String::new();
                 }
-                if let Some(inst) = self.instances.iter_mut().find(|i| i.id == session_id) {
+                if let Some(inst) = self.instances.iter_mut().find(|i| &i.id == session_id) {
                     inst.status = Status::Deleting;
                     inst.group_path = String::new();
                 }
+            }
 
+            for session_id in sessions_to_delete {
                 if let Some(inst) = self.instance_map.get(&session_id) {
-                    let delete_worktree = options.delete_worktrees
-                        && inst
-                            .worktree_info
-                            .as_ref()
-                            .is_some_and(|wt| wt.managed_by_aoe);
-                    let delete_branch = options.delete_branches
-                        && inst
-                            .worktree_info
-                            .as_ref()
-                            .is_some_and(|wt| wt.managed_by_aoe);
+                    // Everything in the group is now Deleting, so only sessions
+                    // outside it keep a shared worktree alive
+                    let owns_worktree = inst
+                        .worktree_info
+                        .as_ref()
+                        .is_some_and(|wt| wt.managed_by_aoe);
+                    let delete_worktree = options.delete_worktrees && owns_worktree;
+                    let delete_branch = options.delete_branches && owns_worktree;
                     let delete_sandbox = options.delete_containers
                         && inst.sandbox_info.as_ref().is_some_and(|s| s.enabled);
                     let request = DeletionRequest {
                         session_id: session_id.clone(),
                         instance: inst.clone(),
+                        instances: self.instances.clone(),
                         delete_worktree,
                         delete_branch,
                         delete_sandbox,
@@ -206,24 +180,9 @@ impl HomeView {
                         .ok_or_else(|| anyhow::anyhow!("Session not found"))?;
 
                     // Apply title and group changes to the instance
-                    instance.title = effective_title.clone();
+                    instance.set_title(&effective_title)?;
                     instance.group_path = effective_group.clone();
-
-                    // Handle tmux rename if title changed
-                    if let Some(orig_inst) = self.instance_map.get(&id) {
-                        if orig_inst.title != effective_title {
-                            let tmux_session = orig_inst.tmux_session()?;
-                            if tmux_session.exists() {
-                                let new_tmux_name =
-                                    crate::tmux::Session::generate_name(&id, &effective_title);
-                                if let Err(e) = tmux_session.rename(&new_tmux_name) {
-                                    tracing::warn!("Failed to rename tmux session: {}", e);
-                                } else {
-                                    crate::tmux::refresh_session_cache();
-                                }
-                            }
-                        }
-                    }
+                    instance.profile = target_profile.to_string();
 
                     // Remove from current profile
                     self.instances.retain(|i| i.id != id);
@@ -253,26 +212,10 @@ impl HomeView {
 
             // No profile change - update in place
             if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
-                inst.title = effective_title.clone();
+                inst.set_title(&effective_title)?;
                 inst.group_path = effective_group.clone();
             }
 
-            // Handle tmux rename if title changed
-            if let Some(inst) = self.instance_map.get(&id) {
-                if inst.title != effective_title {
-                    let tmux_session = inst.tmux_session()?;
-                    if tmux_session.exists() {
-                        let new_tmux_name =
-                            crate::tmux::Session::generate_name(&id, &effective_title);
-                        if let Err(e) = tmux_session.rename(&new_tmux_name) {
-                            tracing::warn!("Failed to rename tmux session: {}", e);
-                        } else {
-                            crate::tmux::refresh_session_cache();
-                        }
-                    }
-                }
-            }
-
             // Rebuild group tree and create group if needed
             self.group_tree = GroupTree::new_with_groups(&self.instances, &self.groups);
             if !effective_group.is_empty() {
@@ -285,4 +228,64 @@ impl HomeView {
         }
         Ok(())
     }
+
+    /// Switch the selected session to another tool. A running session keeps
+    /// the old agent until restarted, so offer to restart it.
+    pub(super) fn change_selected_tool(&mut self, tool: &str) -> anyhow::Result<()> {
+        let Some(id) = self.selected_session.clone() else {
+            return Ok(());
+        };
+
+        if let Some(inst) = self.instances.iter_mut().find(|i| i.id == id) {
+            inst.set_tool(tool);
+        }
+        self.storage
+            .save_with_groups(&self.instances, &self.group_tree)?;
+        self.reload()?;
+        // Any launch error belonged to the previous tool
+        self.set_instance_error(&id, None);
+
+        let running = self
+            .instance_map
+            .get(&id)
+            .and_then(|inst| inst.tmux_session().ok())
+            .is_some_and(|session| session.exists());
+        if running {
+            self.confirm_dialog = Some(ConfirmDialog::new(
+                "Restart Session",
+                &format!("Restart the session now to launch {}?", tool),
+                "restart_session",
+            ));
+        }
+        Ok(())
+    }
+
+    /// Restart the selected session's agent in place
+    pub(super) fn restart_selected(&mut self) {
+        let Some(id) = self.selected_session.clone() else {
+            return;
+        };
+        let Some(mut inst) = self.instance_map.get(&id).cloned() else {
+            return;
+        };
+
+        if let Err(e) = inst.restart_with_size(crate::terminal::get_size()) {
+            tracing::error!("Failed to restart session: {}", e);
+            inst.last_error.get_or_insert_with(|| e.to_string());
+        }
+        crate::tmux::refresh_session_cache();
+
+        // Restarting can recreate the container, so keep the whole instance
+        // (new container id included) rather than just its status
+        if let Some(target) = self.instances.iter_mut().find(|i| i.id == id) {
+            *target = inst.clone();
+        }
+        self.instance_map.insert(id, inst);
+        if let Err(e) = self
+            .storage
+            .save_with_groups(&self.instances, &self.group_tree)
+        {
+            tracing::warn!("Failed to save restarted session: {}", e);
+        }
+    }
 }
@@ -5,10 +5,11 @@ use ratatui::widgets::*;
 use std::time::Instant;
 
 use super::{
-    get_indent, HomeView, TerminalMode, ViewMode, ICON_COLLAPSED, ICON_DELETING, ICON_ERROR,
-    ICON_EXPANDED, ICON_IDLE, ICON_RUNNING, ICON_STARTING, ICON_WAITING,
+    get_indent, ContainerStateCache, HomeView, TerminalMode, ViewMode, ICON_COLLAPSED,
+    ICON_EXPANDED, ICON_IDLE, ICON_RUNNING,
 };
-use crate::session::{Item, Status};
+use crate::docker::{ContainerState, DockerContainer};
+use crate::session::Item;
 use crate::tui::components::{HelpOverlay, Preview};
 use crate::tui::styles::Theme;
 use crate::update::UpdateInfo;
@@ -25,10 +26,8 @@ impl HomeView {
         if let Some(ref mut settings) = self.settings_view {
             settings.render(frame, area, theme);
             // Render unsaved changes confirmation dialog over settings
-            if self.settings_close_confirm {
-                if let Some(dialog) = &self.confirm_dialog {
-                    dialog.render(frame, area, theme);
-                }
+            if let Some(dialog) = &self.settings_close_dialog {
+                dialog.render(frame, area, theme);
             }
             return;
         }
@@ -96,6 +95,10 @@ impl HomeView {
             dialog.render(frame, area, theme);
         }
 
+        if let Some(dialog) = &self.change_tool_dialog {
+            dialog.render(frame, area, theme);
+        }
+
         if let Some(dialog) = &self.hook_trust_dialog {
             dialog.render(frame, area, theme);
         }
@@ -227,24 +230,8 @@ impl HomeView {
                 if let Some(inst) = self.instance_map.get(id) {
                     match self.view_mode {
                         ViewMode::Agent => {
-                            let icon = match inst.status {
-                                Status::Running => ICON_RUNNING,
-                                Status::Waiting => ICON_WAITING,
-                                Status::Idle => ICON_IDLE,
-                                Status::Error => ICON_ERROR,
-                                Status::Starting => ICON_STARTING,
-                                Status::Deleting => ICON_DELETING,
-                            };
-                            let color = match inst.status {
-                                Status::Running => theme.running,
-                                Status::Waiting => theme.waiting,
-                                Status::Idle => theme.idle,
-                                Status::Error => theme.error,
-                                Status::Starting => theme.dimmed,
-                                Status::Deleting => theme.waiting,
-                            };
-                            let style = Style::default().fg(color);
-                            (icon, Cow::Borrowed(&inst.title), style)
+                            let style = Style::default().fg(theme.status_color(inst.status));
+                            (inst.status.icon(), Cow::Borrowed(&inst.title), style)
                         }
                         ViewMode::Terminal => {
                             // For sandboxed sessions, check the appropriate terminal based on mode
@@ -345,12 +332,16 @@ impl HomeView {
         if needs_refresh {
             if let Some(id) = &self.selected_session {
                 if let Some(inst) = self.instance_map.get(id) {
-                    self.preview_cache.content = inst
-                        .capture_output_with_size(height as usize, width, height)
+                    let activity = inst.tmux_session().ok().and_then(|s| s.activity());
+                    if self.preview_cache.is_current(id, (width, height), activity) {
+                        self.preview_cache.last_refresh = Instant::now();
+                        return;
+                    }
+                    let content = inst
+                        .capture_output_with_size(height as usize, width, height, self.preview_wrap)
                         .unwrap_or_default();
-                    self.preview_cache.session_id = Some(id.clone());
-                    self.preview_cache.dimensions = (width, height);
-                    self.preview_cache.last_refresh = Instant::now();
+                    self.preview_cache
+                        .store(id, (width, height), activity, content);
                 }
             }
         }
@@ -377,13 +368,20 @@ impl HomeView {
         if needs_refresh {
             if let Some(id) = &self.selected_session {
                 if let Some(inst) = self.instance_map.get(id) {
-                    self.terminal_preview_cache.content = inst
-                        .terminal_tmux_session()
+                    let session = inst.terminal_tmux_session();
+                    let activity = session.as_ref().ok().and_then(|s| s.activity());
+                    if self
+                        .terminal_preview_cache
+                        .is_current(id, (width, height), activity)
+                    {
+                        self.terminal_preview_cache.last_refresh = Instant::now();
+                        return;
+                    }
+                    let content = session
                         .and_then(|s| s.capture_pane(height as usize))
                         .unwrap_or_default();
-                    self.terminal_preview_cache.session_id = Some(id.clone());
-                    self.terminal_preview_cache.dimensions = (width, height);
-                    self.terminal_preview_cache.last_refresh = Instant::now();
+                    self.terminal_preview_cache
+                        .store(id, (width, height), activity, content);
                 }
             }
         }
@@ -410,18 +408,102 @@ impl HomeView {
         if needs_refresh {
             if let Some(id) = &self.selected_session {
                 if let Some(inst) = self.instance_map.get(id) {
-                    self.container_terminal_preview_cache.content = inst
-                        .container_terminal_tmux_session()
+                    let session = inst.container_terminal_tmux_session();
+                    let activity = session.as_ref().ok().and_then(|s| s.activity());
+                    if self.container_terminal_preview_cache.is_current(
+                        id,
+                        (width, height),
+                        activity,
+                    ) {
+                        self.container_terminal_preview_cache.last_refresh = Instant::now();
+                        return;
+                    }
+                    let content = session
                         .and_then(|s| s.capture_pane(height as usize))
                         .unwrap_or_default();
-                    self.container_terminal_preview_cache.session_id = Some(id.clone());
-                    self.container_terminal_preview_cache.dimensions = (width, height);
-                    self.container_terminal_preview_cache.last_refresh = Instant::now();
+                    self.container_terminal_preview_cache.store(
+                        id,
+                        (width, height),
+                        activity,
+                        content,
+                    );
                 }
             }
         }
     }
 
+    /// Ask for fresh container logs if needed (same cadence as the pane
+    /// preview). They are read in the background and shown by
+    /// `apply_container_logs`.
+    fn refresh_container_logs_cache_if_needed(&mut self, width: u16, height: u16) {
+        const PREVIEW_REFRESH_MS: u128 = 250;
+
+        let needs_refresh = match &self.selected_session {
+            Some(id) => {
+                self.container_logs_cache.session_id.as_ref() != Some(id)
+                    || self.container_logs_cache.dimensions != (width, height)
+                    || self.container_logs_cache.last_refresh.elapsed().as_millis()
+                        > PREVIEW_REFRESH_MS
+            }
+            None => false,
+        };
+
+        if needs_refresh {
+            if let Some(id) = &self.selected_session {
+                if self.instance_map.contains_key(id) {
+                    if self.container_logs_cache.session_id.as_ref() != Some(id) {
+                        // Don't show the previous session's logs while these load
+                        self.container_logs_available = true;
+                        self.container_logs_cache.content.clear();
+                    }
+                    self.container_logs_poller.request_logs(id, height as usize);
+                    self.container_logs_cache.session_id = Some(id.clone());
+                    self.container_logs_cache.dimensions = (width, height);
+                    self.container_logs_cache.last_refresh = Instant::now();
+                }
+            }
+        }
+    }
+
+    /// Refresh the selected sandboxed session's container state every few
+    /// seconds; uptime only needs minute precision
+    fn refresh_container_state_cache_if_needed(&mut self) {
+        const CONTAINER_STATE_REFRESH_MS: u128 = 2000;
+
+        let Some(id) = self
+            .selected_session
+            .as_ref()
+            .filter(|id| self.instance_map.get(*id).is_some_and(|i| i.is_sandboxed()))
+        else {
+            return;
+        };
+        let fresh = self.container_state_cache.as_ref().is_some_and(|cache| {
+            &cache.session_id == id
+                && cache.last_refresh.elapsed().as_millis() <= CONTAINER_STATE_REFRESH_MS
+        });
+        if fresh {
+            return;
+        }
+
+        let state = DockerContainer::from_session_id(id)
+            .inspect_state()
+            .ok()
+            .flatten();
+        self.container_state_cache = Some(ContainerStateCache {
+            session_id: id.clone(),
+            state,
+            last_refresh: Instant::now(),
+        });
+    }
+
+    /// Cached container state for `id`, if it is the one cached
+    fn cached_container_state(&self, id: &str) -> Option<&ContainerState> {
+        self.container_state_cache
+            .as_ref()
+            .filter(|cache| cache.session_id == id)
+            .and_then(|cache| cache.state.as_ref())
+    }
+
     fn render_preview(&mut self, frame: &mut Frame, area: Rect, theme: &Theme) {
         let title = match self.view_mode {
             ViewMode::Agent => " Preview ",
@@ -442,16 +524,47 @@ impl HomeView {
 
         match self.view_mode {
             ViewMode::Agent => {
-                // Refresh cache before borrowing from instance_map to avoid borrow conflicts
-                self.refresh_preview_cache_if_needed(inner.width, inner.height);
+                let show_logs = self.preview_logs
+                    && self
+                        .selected_session
+                        .as_ref()
+                        .and_then(|id| self.instance_map.get(id))
+                        .is_some_and(|inst| inst.is_sandboxed());
 
-                if let Some(id) = &self.selected_session {
+                // Refresh cache before borrowing from instance_map to avoid borrow conflicts
+                if show_logs {
+                    self.refresh_container_logs_cache_if_needed(inner.width, inner.height);
+                } else {
+                    self.refresh_preview_cache_if_needed(inner.width, inner.height);
+                }
+                self.refresh_container_state_cache_if_needed();
+
+                if show_logs {
+                    if let Some(inst) = self
+                        .selected_session
+                        .as_ref()
+                        .and_then(|id| self.instance_map.get(id))
+                    {
+                        Preview::render_container_logs(
+                            frame,
+                            inner,
+                            inst,
+                            self.cached_container_state(&inst.id),
+                            self.container_logs_available
+                                .then_some(self.container_logs_cache.content.as_str()),
+                            theme,
+                        );
+                    }
+                } else if let Some(id) = &self.selected_session {
                     if let Some(inst) = self.instance_map.get(id) {
                         Preview::render_with_cache(
                             frame,
                             inner,
                             inst,
+                            self.cached_container_state(id),
                             &self.preview_cache.content,
+                            self.preview_wrap,
+                            self.preview_hscroll,
                             theme,
                         );
                     }
@@ -543,12 +656,19 @@ impl HomeView {
         };
         let mode_style = Style::default().fg(mode_color).bold();
 
-        let mut spans = vec![
+        let mut spans = Vec::new();
+        if self.read_only {
+            spans.extend([
+                Span::styled(" READ ONLY ", Style::default().fg(theme.error).bold()),
+                Span::styled("│", sep_style),
+            ]);
+        }
+        spans.extend([
             Span::styled(format!(" {} ", mode_indicator), mode_style),
             Span::styled("│", sep_style),
             Span::styled(" j/k", key_style),
             Span::styled(" Nav ", desc_style),
-        ];
+        ]);
         if let Some(enter_action_text) = match self.flat_items.get(self.cursor) {
             Some(Item::Group {
                 collapsed: true, ..
@@ -556,7 +676,8 @@ impl HomeView {
             Some(Item::Group {
                 collapsed: false, ..
             }) => Some(" Collapse "),
-            Some(Item::Session { .. }) => Some(" Attach "),
+            Some(Item::Session { .. }) if !self.read_only => Some(" Attach "),
+            Some(Item::Session { .. }) => None,
             None => None,
         } {
             spans.extend([
@@ -586,13 +707,15 @@ impl HomeView {
             }
         }
 
-        spans.extend([
-            Span::styled("│", sep_style),
-            Span::styled(" n", key_style),
-            Span::styled(" New ", desc_style),
-        ]);
+        if !self.read_only {
+            spans.extend([
+                Span::styled("│", sep_style),
+                Span::styled(" n", key_style),
+                Span::styled(" New ", desc_style),
+            ]);
+        }
 
-        if !self.flat_items.is_empty() {
+        if !self.flat_items.is_empty() && !self.read_only {
             spans.extend([
                 Span::styled("│", sep_style),
                 Span::styled(" d", key_style),
@@ -604,9 +727,15 @@ impl HomeView {
             Span::styled("│", sep_style),
             Span::styled(" /", key_style),
             Span::styled(" Search ", desc_style),
-            Span::styled("│", sep_style),
-            Span::styled(" D", key_style),
-            Span::styled(" Diff ", desc_style),
+        ]);
+        if !self.read_only {
+            spans.extend([
+                Span::styled("│", sep_style),
+                Span::styled(" D", key_style),
+                Span::styled(" Diff ", desc_style),
+            ]);
+        }
+        spans.extend([
             Span::styled("│", sep_style),
             Span::styled(" ?", key_style),
             Span::styled(" Help ", desc_style),
@@ -5,11 +5,11 @@ use serial_test::serial;
 use tempfile::TempDir;
 use tui_input::Input;
 
-use super::{HomeView, ViewMode};
+use super::{HomeView, PreviewCache, ViewMode};
 use crate::session::{Instance, Item, Storage};
 use crate::tmux::AvailableTools;
 use crate::tui::app::Action;
-use crate::tui::dialogs::{InfoDialog, NewSessionDialog};
+use crate::tui::dialogs::{InfoDialog, NewSessionData, NewSessionDialog};
 
 fn key(code: KeyCode) -> KeyEvent {
     KeyEvent::new(code, KeyModifiers::NONE)
@@ -183,6 +183,55 @@ fn test_has_dialog_returns_true_for_new_dialog() {
     assert!(env.view.has_dialog());
 }
 
+#[test]
+#[serial]
+fn test_sandbox_preflight_failure_shows_inline_error() {
+    let mut env = create_test_env_empty();
+    env.view.new_dialog = Some(NewSessionDialog::new(
+        AvailableTools {
+            claude: true,
+            opencode: false,
+            vibe: false,
+            codex: false,
+            gemini: false,
+        },
+        Vec::new(),
+        Vec::new(),
+        "default",
+    ));
+    let data = NewSessionData {
+        title: "sandboxed".to_string(),
+        path: "/tmp".to_string(),
+        group: String::new(),
+        tool: "claude".to_string(),
+        command: String::new(),
+        worktree_branch: None,
+        create_new_branch: false,
+        sandbox: true,
+        sandbox_image: "  ".to_string(),
+        yolo_mode: false,
+        extra_env_keys: Vec::new(),
+        extra_env_values: Vec::new(),
+        start: true,
+    };
+
+    env.view.request_creation(data, None);
+    for _ in 0..100 {
+        if !env.view.is_creation_pending() {
+            break;
+        }
+        env.view.apply_creation_results();
+        std::thread::sleep(std::time::Duration::from_millis(20));
+    }
+
+    let dialog = env.view.new_dialog.as_ref().unwrap();
+    assert!(!dialog.is_loading());
+    assert!(dialog
+        .error()
+        .is_some_and(|e| e.contains("No sandbox image")));
+    assert!(!env.view.is_creation_pending());
+}
+
 #[test]
 #[serial]
 fn test_cursor_down_j() {
@@ -329,6 +378,76 @@ fn test_enter_on_session_returns_attach_action() {
     assert!(matches!(action, Some(Action::AttachSession(_))));
 }
 
+#[test]
+#[serial]
+fn test_read_only_ignores_mutating_keys() {
+    let mut env = create_test_env_with_sessions(3);
+    env.view.set_read_only(true);
+    env.view.update_selected();
+
+    assert!(env.view.handle_key(key(KeyCode::Enter)).is_none());
+    for c in ['n', 'd', 'r', 's', 'D'] {
+        env.view.handle_key(key(KeyCode::Char(c)));
+        assert!(!env.view.has_dialog(), "'{}' opened a dialog", c);
+    }
+    assert!(env.view.diff_view.is_none());
+}
+
+#[test]
+#[serial]
+fn test_read_only_keeps_view_preferences_and_groups() {
+    let mut env = create_test_env_with_groups();
+    env.view.set_read_only(true);
+    let group_idx = env
+        .view
+        .flat_items
+        .iter()
+        .position(|item| matches!(item, Item::Group { .. }))
+        .expect("test env has groups");
+    env.view.cursor = group_idx;
+    env.view.update_selected();
+    let (wrap, width) = (env.view.preview_wrap, env.view.list_width);
+
+    for code in [
+        KeyCode::Char('w'),
+        KeyCode::Char('H'),
+        KeyCode::Char('L'),
+        KeyCode::Enter,
+        KeyCode::Char('h'),
+    ] {
+        env.view.handle_key(key(code));
+    }
+
+    assert_eq!(env.view.preview_wrap, wrap);
+    assert_eq!(env.view.list_width, width);
+    assert!(matches!(
+        env.view.flat_items[group_idx],
+        Item::Group {
+            collapsed: false,
+            ..
+        }
+    ));
+}
+
+#[test]
+#[serial]
+fn test_read_only_allows_navigation_and_quit() {
+    let mut env = create_test_env_with_sessions(3);
+    env.view.set_read_only(true);
+
+    env.view.handle_key(key(KeyCode::Char('j')));
+    assert_eq!(env.view.cursor, 1);
+    env.view.handle_key(key(KeyCode::Char('t')));
+    assert_eq!(env.view.view_mode, ViewMode::Terminal);
+    env.view.handle_key(key(KeyCode::Char('/')));
+    assert!(env.view.search_active);
+    env.view.handle_key(key(KeyCode::Esc));
+    assert!(matches!(
+        env.view.handle_key(key(KeyCode::Char('q'))),
+        Some(Action::Quit)
+    ));
+}
+
 #[test]
 #[serial]
 fn test_slash_enters_search_mode() {
@@ -547,6 +666,31 @@ fn test_rename_dialog_not_opened_on_group() {
     assert!(env.view.rename_dialog.is_none());
 }
 
+#[test]
+#[serial]
+fn test_change_tool_persists_and_clears_command() {
+    let mut env = create_test_env_with_sessions(1);
+    env.view.available_tools.opencode = true;
+    let id = env.view.instances[0].id.clone();
+    env.view.instances[0].command = "claude --model opus".to_string();
+    env.view.update_selected();
+
+    env.view.handle_key(key(KeyCode::Char('T')));
+    assert!(env.view.change_tool_dialog.is_some());
+    env.view.handle_key(key(KeyCode::Down));
+    env.view.handle_key(key(KeyCode::Enter));
+    assert!(env.view.change_tool_dialog.is_none());
+
+    let inst = env.view.get_instance(&id).unwrap();
+    assert_eq!(inst.tool, "opencode");
+    assert!(inst.command.is_empty());
+    // Session isn't running, so there's nothing to restart
+    assert!(env.view.confirm_dialog.is_none());
+
+    let saved = env.view.storage.load().unwrap();
+    assert_eq!(saved[0].tool, "opencode");
+}
+
 #[test]
 #[serial]
 fn test_has_dialog_returns_true_for_rename_dialog() {
@@ -720,6 +864,51 @@ fn test_d_shows_info_dialog_in_terminal_view() {
     assert!(view.unified_delete_dialog.is_none());
 }
 
+#[test]
+#[serial]
+fn test_o_shows_info_dialog_for_unsandboxed_session() {
+    let mut env = create_test_env_with_sessions(1);
+
+    env.view.handle_key(key(KeyCode::Char('o')));
+    assert!(!env.view.preview_logs);
+    assert!(env.view.info_dialog.is_some());
+}
+
+#[test]
+#[serial]
+fn test_o_toggles_container_logs_for_sandboxed_session() {
+    let mut env = create_test_env_with_group_sessions();
+    let sandboxed_id = env
+        .view
+        .instances
+        .iter()
+        .find(|i| i.is_sandboxed())
+        .unwrap()
+        .id
+        .clone();
+    env.view.select_session_by_id(&sandboxed_id);
+
+    env.view.handle_key(key(KeyCode::Char('o')));
+    assert!(env.view.preview_logs);
+    assert!(env.view.info_dialog.is_none());
+
+    env.view.handle_key(key(KeyCode::Char('o')));
+    assert!(!env.view.preview_logs);
+}
+
+#[test]
+#[serial]
+fn test_invalidate_preview_caches_forces_recapture() {
+    let mut env = create_test_env_with_sessions(1);
+    env.view.preview_cache.dimensions = (80, 24);
+    env.view.terminal_preview_cache.dimensions = (80, 24);
+
+    env.view.invalidate_preview_caches();
+
+    assert_eq!(env.view.preview_cache.dimensions, (0, 0));
+    assert_eq!(env.view.terminal_preview_cache.dimensions, (0, 0));
+}
+
 #[test]
 #[serial]
 fn test_has_dialog_includes_info_dialog() {
@@ -755,6 +944,60 @@ fn test_s_opens_settings_view() {
     assert!(env.view.settings_view.is_some());
 }
 
+#[test]
+#[serial]
+fn test_settings_e_edits_global_config_file() {
+    let mut env = create_test_env_empty();
+    env.view.handle_key(key(KeyCode::Char('s')));
+    let action = env.view.handle_key(key(KeyCode::Char('e')));
+    assert_eq!(
+        action,
+        Some(Action::EditFile(
+            crate::session::config::get_config_path().unwrap()
+        ))
+    );
+}
+
+#[test]
+#[serial]
+fn test_settings_reload_picks_up_and_rejects_external_edits() {
+    use crate::session::config::get_config_path;
+    use crate::tui::settings::{FieldKey, FieldValue, SettingsView};
+
+    let _env = create_test_env_empty();
+    let path = get_config_path().unwrap();
+    let mut settings = SettingsView::new("test", None).unwrap();
+
+    std::fs::write(&path, "[session]\ndefault_group = \"edited\"\n").unwrap();
+    settings.reload();
+    assert!(settings.error_message.is_none());
+    let group = settings
+        .fields
+        .iter()
+        .find(|f| f.key == FieldKey::DefaultGroup)
+        .map(|f| f.value.clone());
+    assert_eq!(
+        group,
+        Some(FieldValue::OptionalText(Some("edited".to_string())))
+    );
+
+    std::fs::write(&path, "[session\n").unwrap();
+    settings.reload();
+    assert!(settings
+        .error_message
+        .as_deref()
+        .is_some_and(|e| e.starts_with("Config not reloaded")));
+    let group = settings
+        .fields
+        .iter()
+        .find(|f| f.key == FieldKey::DefaultGroup)
+        .map(|f| f.value.clone());
+    assert_eq!(
+        group,
+        Some(FieldValue::OptionalText(Some("edited".to_string())))
+    );
+}
+
 // Group deletion tests
 
 fn create_test_env_with_group_sessions() -> TestEnv {
@@ -785,6 +1028,8 @@ fn create_test_env_with_group_sessions() -> TestEnv {
         yolo_mode: None,
         extra_env_keys: None,
         extra_env_values: None,
+        extra_volumes: None,
+        network: None,
     });
     instances.push(inst3);
 
@@ -826,6 +1071,7 @@ fn test_group_has_managed_worktrees() {
         managed_by_aoe: true,
         created_at: Utc::now(),
         cleanup_on_delete: true,
+        base_branch: None,
     });
 
     let mut inst2 = Instance::new("other-session", "/tmp/other");
@@ -866,6 +1112,8 @@ fn test_group_has_containers() {
         yolo_mode: None,
         extra_env_keys: None,
         extra_env_values: None,
+        extra_volumes: None,
+        network: None,
     });
 
     let mut inst2 = Instance::new("other-session", "/tmp/other");
@@ -990,6 +1238,7 @@ fn test_delete_group_with_sessions_respects_worktree_option() {
         managed_by_aoe: true,
         created_at: Utc::now(),
         cleanup_on_delete: true,
+        base_branch: None,
     });
 
     storage.save(&[inst1]).unwrap();
@@ -1043,6 +1292,8 @@ fn test_delete_group_with_sessions_respects_container_option() {
         yolo_mode: None,
         extra_env_keys: None,
         extra_env_values: None,
+        extra_volumes: None,
+        network: None,
     });
 
     storage.save(&[inst1]).unwrap();
@@ -1301,3 +1552,185 @@ fn test_uppercase_l_grows_list() {
     env.view.handle_key(key(KeyCode::Char('L')));
     assert_eq!(env.view.list_width, 40);
 }
+
+#[test]
+fn test_preview_cache_skips_capture_for_quiet_pane() {
+    let mut cache = PreviewCache::default();
+    assert!(!cache.is_current("abc", (80, 24), Some(100)));
+
+    cache.store("abc", (80, 24), Some(100), "output".to_string());
+    cache.captured_at = 105;
+    assert!(cache.is_current("abc", (80, 24), Some(100)));
+
+    // New output, a different session, or a resize all need a fresh capture
+    assert!(!cache.is_current("abc", (80, 24), Some(106)));
+    assert!(!cache.is_current("def", (80, 24), Some(100)));
+    assert!(!cache.is_current("abc", (100, 24), Some(100)));
+    // Without activity info the capture always runs
+    assert!(!cache.is_current("abc", (80, 24), None));
+}
+
+#[test]
+fn test_preview_cache_expires_quiet_capture() {
+    let mut cache = PreviewCache::default();
+    cache.store("abc", (80, 24), Some(100), "output".to_string());
+    cache.captured_at = 105;
+    cache.last_refresh = cache
+        .last_refresh
+        .checked_sub(std::time::Duration::from_secs(10))
+        .unwrap();
+    assert!(!cache.is_current("abc", (80, 24), Some(100)));
+}
+
+#[test]
+fn test_preview_cache_recaptures_output_in_capture_second() {
+    let mut cache = PreviewCache::default();
+    cache.store("abc", (80, 24), Some(105), "output".to_string());
+    cache.captured_at = 105;
+    assert!(!cache.is_current("abc", (80, 24), Some(105)));
+}
+
+/// Open settings and change the first Session field (default tool)
+fn open_settings_with_edit(env: &mut TestEnv) {
+    env.view.handle_key(key(KeyCode::Char('s')));
+    env.view.handle_key(key(KeyCode::Right));
+    env.view.handle_key(key(KeyCode::Enter));
+    assert!(env
+        .view
+        .settings_view
+        .as_ref()
+        .unwrap()
+        .has_unsaved_changes());
+}
+
+#[test]
+#[serial]
+fn test_closing_dirty_settings_prompts_and_cancel_keeps_editing() {
+    let mut env = create_test_env_empty();
+    open_settings_with_edit(&mut env);
+
+    env.view.handle_key(key(KeyCode::Esc));
+    assert!(env.view.settings_close_dialog.is_some());
+
+    env.view.handle_key(key(KeyCode::Esc));
+    assert!(env.view.settings_close_dialog.is_none());
+    assert!(env.view.settings_view.is_some());
+}
+
+#[test]
+#[serial]
+fn test_discarding_dirty_settings_closes_without_saving() {
+    let mut env = create_test_env_empty();
+    open_settings_with_edit(&mut env);
+
+    env.view.handle_key(key(KeyCode::Esc));
+    env.view.handle_key(key(KeyCode::Char('d')));
+    assert!(env.view.settings_view.is_none());
+    assert!(env.view.settings_close_dialog.is_none());
+
+    let config = crate::session::Config::load().unwrap();
+    assert_eq!(config.session.default_tool, None);
+}
+
+#[test]
+#[serial]
+fn test_saving_from_close_prompt_persists_and_closes() {
+    let mut env = create_test_env_empty();
+    open_settings_with_edit(&mut env);
+
+    env.view.handle_key(key(KeyCode::Esc));
+    env.view.handle_key(key(KeyCode::Char('s')));
+    assert!(env.view.settings_view.is_none());
+
+    let config = crate::session::Config::load().unwrap();
+    assert_eq!(config.session.default_tool.as_deref(), Some("claude"));
+}
+
+#[test]
+#[serial]
+fn test_reverting_an_edit_leaves_settings_clean() {
+    let mut env = create_test_env_empty();
+    open_settings_with_edit(&mut env);
+
+    // Cycle the select back round to where it started
+    for _ in 0..crate::session::SUPPORTED_TOOLS.len() {
+        env.view.handle_key(key(KeyCode::Enter));
+    }
+    assert!(!env
+        .view
+        .settings_view
+        .as_ref()
+        .unwrap()
+        .has_unsaved_changes());
+
+    env.view.handle_key(key(KeyCode::Esc));
+    assert!(env.view.settings_close_dialog.is_none());
+    assert!(env.view.settings_view.is_none());
+}
+
+fn remembered_data() -> NewSessionData {
+    NewSessionData {
+        title: "api".to_string(),
+        path: "/tmp/api".to_string(),
+        group: "backend".to_string(),
+        tool: "claude".to_string(),
+        command: String::new(),
+        worktree_branch: Some("feature".to_string()),
+        create_new_branch: true,
+        sandbox: false,
+        sandbox_image: "custom/image:tag".to_string(),
+        yolo_mode: false,
+        extra_env_keys: Vec::new(),
+        extra_env_values: Vec::new(),
+        start: true,
+    }
+}
+
+#[test]
+#[serial]
+fn test_remember_new_session_stores_options_when_enabled() {
+    let env = create_test_env_empty();
+    let mut config = crate::session::Config::default();
+    config.ui.remember_new_session = true;
+    crate::session::save_config(&config).unwrap();
+
+    env.view.remember_new_session(&remembered_data());
+
+    let config = crate::session::Config::load().unwrap();
+    let last = config.app_state.last_new_session.unwrap();
+    assert_eq!(last.tool, "claude");
+    assert_eq!(last.group, "backend");
+    assert_eq!(last.sandbox_image, "custom/image:tag");
+}
+
+#[test]
+#[serial]
+fn test_remember_new_session_is_off_by_default() {
+    let env = create_test_env_empty();
+    env.view.remember_new_session(&remembered_data());
+
+    let config = crate::session::Config::load().unwrap();
+    assert!(config.app_state.last_new_session.is_none());
+}
+
+#[test]
+#[serial]
+fn test_container_terminal_failure_falls_back_to_host() {
+    use super::TerminalMode;
+
+    let mut env = create_test_env_with_sessions(1);
+    let id = env.view.instances[0].id.clone();
+    env.view
+        .terminal_modes
+        .insert(id.clone(), TerminalMode::Container);
+
+    env.view
+        .fall_back_to_host_terminal(&id, &anyhow::anyhow!("image not found"));
+
+    assert_eq!(env.view.get_terminal_mode(&id), TerminalMode::Host);
+    assert!(env.view.info_dialog.is_some());
+    assert_eq!(
+        env.view.get_instance(&id).unwrap().last_error.as_deref(),
+        Some("image not found")
+    );
+}
@@ -2,6 +2,8 @@
 
 mod app;
 mod components;
+mod config_watcher;
+mod container_logs_poller;
 mod creation_poller;
 mod deletion_poller;
 pub mod dialogs;
@@ -9,23 +11,25 @@ pub mod diff;
 mod home;
 pub mod settings;
 mod status_poller;
-mod styles;
+pub mod styles;
 
 pub use app::*;
 
 use anyhow::Result;
 use crossterm::{
+    cursor,
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
 use ratatui::prelude::*;
 use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::session::get_update_settings;
 use crate::update::check_for_update;
 
-pub async fn run(profile: &str) -> Result<()> {
+pub async fn run(profile: &str, read_only: bool) -> Result<()> {
     // Check for tmux
     if !crate::tmux::is_tmux_available() {
         eprintln!("Error: tmux not found in PATH");
@@ -65,25 +69,106 @@ pub async fn run(profile: &str) -> Result<()> {
         }
     }
 
-    // Setup terminal
+    // Setup terminal. The guard restores it on every exit path, including
+    // errors and unwinding; the signal handler covers being killed.
     enable_raw_mode()?;
+    let guard = TerminalGuard;
+    install_panic_hook();
+    #[cfg(unix)]
+    spawn_signal_handler()?;
     let mut stdout = io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
     // Create app and run
-    let mut app = App::new(profile, available_tools)?;
+    let mut app = App::new(profile, available_tools, read_only)?;
     let result = app.run(&mut terminal).await;
 
-    // Restore terminal
-    disable_raw_mode()?;
-    execute!(
-        terminal.backend_mut(),
+    drop(guard);
+    result
+}
+
+/// Restores the terminal when dropped.
+struct TerminalGuard;
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+/// Leave raw mode, the alternate screen and mouse capture, and show the cursor.
+/// Errors are ignored since this runs on the way out; calling it twice is harmless.
+fn restore_terminal() {
+    let _ = disable_raw_mode();
+    let _ = execute!(
+        io::stdout(),
         LeaveAlternateScreen,
-        DisableMouseCapture
-    )?;
-    terminal.show_cursor()?;
+        DisableMouseCapture,
+        cursor::Show
+    );
+}
 
-    result
+/// Restore the terminal before the panic message is printed, so it lands on
+/// the normal screen instead of being wiped with the alternate one. Chains to
+/// the previous hook to keep the usual message and backtrace.
+fn install_panic_hook() {
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore_terminal();
+        default_hook(info);
+    }));
+}
+
+/// Set while the TUI has handed the terminal to another program, such as a
+/// tmux attach or an editor
+static SUSPENDED: AtomicBool = AtomicBool::new(false);
+
+/// Marks the TUI as suspended until dropped
+struct SuspendGuard;
+
+impl SuspendGuard {
+    fn new() -> Self {
+        SUSPENDED.store(true, Ordering::SeqCst);
+        Self
+    }
+}
+
+impl Drop for SuspendGuard {
+    fn drop(&mut self) {
+        SUSPENDED.store(false, Ordering::SeqCst);
+    }
+}
+
+/// Restore the terminal and exit on SIGINT/SIGTERM instead of dying with the
+/// shell stuck in raw mode. Ctrl-C arrives as a key event while in raw mode,
+/// so SIGINT here only comes from `kill -INT` and friends.
+///
+/// While suspended, Ctrl-C is meant for the program in the foreground, which
+/// gets its own SIGINT, so aoe ignores it. A SIGTERM still exits, but leaves
+/// the terminal alone since aoe is not drawing on it.
+#[cfg(unix)]
+fn spawn_signal_handler() -> Result<()> {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut sigint = signal(SignalKind::interrupt())?;
+    let mut sigterm = signal(SignalKind::terminate())?;
+    tokio::spawn(async move {
+        let code = loop {
+            tokio::select! {
+                _ = sigint.recv() => {
+                    if !SUSPENDED.load(Ordering::SeqCst) {
+                        break 130;
+                    }
+                }
+                _ = sigterm.recv() => break 143,
+            }
+        };
+        if !SUSPENDED.load(Ordering::SeqCst) {
+            restore_terminal();
+        }
+        std::process::exit(code);
+    });
+    Ok(())
 }
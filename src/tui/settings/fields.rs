@@ -3,8 +3,10 @@
 use std::collections::HashMap;
 
 use crate::session::{
-    validate_check_interval, Config, DefaultTerminalMode, ProfileConfig, TmuxMouseMode,
-    TmuxStatusBarMode,
+    merge_configs, validate_check_interval, validate_docker_retries, validate_log_max_files,
+    validate_status_debounce_polls, validate_status_refresh_ms, validate_tick_ms, AuthVolumeScope,
+    Config, DefaultTerminalMode, LogLevel, PresetConfig, ProfileConfig, StatusPatternsConfig,
+    TmuxMouseMode, TmuxStatusBarMode,
 };
 use crate::sound::{validate_sound_exists, SoundMode};
 
@@ -13,24 +15,32 @@ use super::SettingsScope;
 /// Categories of settings
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SettingsCategory {
+    App,
     Updates,
     Worktree,
     Sandbox,
     Tmux,
+    Ui,
+    Logging,
     Session,
     Sound,
+    Events,
     Hooks,
 }
 
 impl SettingsCategory {
     pub fn label(&self) -> &'static str {
         match self {
+            Self::App => "App",
             Self::Updates => "Updates",
             Self::Worktree => "Worktree",
             Self::Sandbox => "Sandbox",
             Self::Tmux => "Tmux",
+            Self::Ui => "UI",
+            Self::Logging => "Logging",
             Self::Session => "Session",
             Self::Sound => "Sound",
+            Self::Events => "Events",
             Self::Hooks => "Hooks",
         }
     }
@@ -39,6 +49,8 @@ impl SettingsCategory {
 /// Type-safe field identifiers (prevents typos in string matching)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FieldKey {
+    // App
+    Offline,
     // Updates
     CheckEnabled,
     CheckIntervalHours,
@@ -46,6 +58,7 @@ pub enum FieldKey {
     // Worktree
     PathTemplate,
     BareRepoPathTemplate,
+    BranchSeparator,
     WorktreeAutoCleanup,
     DeleteBranchOnCleanup,
     // Sandbox
@@ -57,15 +70,44 @@ pub enum FieldKey {
     SandboxAutoCleanup,
     CpuLimit,
     MemoryLimit,
+    Network,
     DefaultTerminalMode,
+    AuthVolumeScope,
     ExtraVolumes,
     VolumeIgnores,
     MountSsh,
+    ReadOnlyRootfs,
+    Hardened,
+    CapDrop,
+    CapAdd,
+    SecurityOpt,
+    DockerRetries,
+    AutoRestart,
+    StopOnDetach,
+    MaxConcurrentContainers,
     // Tmux
     StatusBar,
     Mouse,
+    HistoryLimit,
+    // UI
+    TickMs,
+    StatusRefreshMs,
+    StatusDebouncePolls,
+    RememberNewSession,
+    ResumeLastProfile,
+    // Logging
+    LoggingEnabled,
+    LogLevel,
+    LogMaxFiles,
     // Session
     DefaultTool,
+    DefaultGroup,
+    DefaultPath,
+    CreateDir,
+    TitleTemplate,
+    TitleWords,
+    StatusPatterns,
+    Presets,
     // Sound
     SoundEnabled,
     SoundMode,
@@ -74,11 +116,38 @@ pub enum FieldKey {
     SoundOnWaiting,
     SoundOnIdle,
     SoundOnError,
+    // Events
+    EventWebhook,
+    EventCommand,
     // Hooks
     HookOnCreate,
     HookOnLaunch,
 }
 
+/// The config layer a field's value comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FieldSource {
+    /// Built-in default, not changed by any config file
+    Default,
+    /// Set in the global config
+    Global,
+    /// Overridden by the profile config
+    Profile,
+    /// Overridden by the repo config
+    Repo,
+}
+
+impl FieldSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            Self::Default => "default",
+            Self::Global => "global config",
+            Self::Profile => "profile override",
+            Self::Repo => "repo override",
+        }
+    }
+}
+
 /// Resolve a field value from global config and optional profile override.
 /// Returns (value, has_override).
 fn resolve_value<T: Clone>(scope: SettingsScope, global: T, profile: Option<T>) -> (T, bool) {
@@ -131,7 +200,7 @@ fn set_or_clear_override<T, S, F>(
 }
 
 /// Value types for settings fields
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum FieldValue {
     Bool(bool),
     Text(String),
@@ -163,10 +232,66 @@ impl SettingField {
                 validate_check_interval(*n)?;
                 Ok(())
             }
+            (FieldKey::TickMs, FieldValue::Number(n)) => {
+                validate_tick_ms(*n)?;
+                Ok(())
+            }
+            (FieldKey::StatusRefreshMs, FieldValue::Number(n)) => {
+                validate_status_refresh_ms(*n)?;
+                Ok(())
+            }
+            (FieldKey::StatusDebouncePolls, FieldValue::Number(n)) => {
+                validate_status_debounce_polls(*n)?;
+                Ok(())
+            }
+            (FieldKey::LogMaxFiles, FieldValue::Number(n)) => {
+                validate_log_max_files(*n)?;
+                Ok(())
+            }
+            (FieldKey::DockerRetries, FieldValue::Number(n)) => {
+                validate_docker_retries(*n)?;
+                Ok(())
+            }
             (FieldKey::MemoryLimit, FieldValue::OptionalText(Some(v))) => {
                 crate::session::validate_memory_limit(v)?;
                 Ok(())
             }
+            (FieldKey::CpuLimit, FieldValue::OptionalText(Some(v))) => {
+                crate::session::validate_cpu_limit(v)?;
+                Ok(())
+            }
+            (FieldKey::PathTemplate | FieldKey::BareRepoPathTemplate, FieldValue::Text(v)) => {
+                crate::session::validate_path_template(v)?;
+                Ok(())
+            }
+            (FieldKey::BranchSeparator, FieldValue::Text(v)) => {
+                crate::git::template::validate_branch_separator(v)?;
+                Ok(())
+            }
+            (FieldKey::TitleTemplate, FieldValue::OptionalText(Some(v))) => {
+                crate::session::validate_title_template(v)?;
+                Ok(())
+            }
+            (FieldKey::EventWebhook, FieldValue::OptionalText(Some(v))) => {
+                crate::session::validate_webhook_url(v)?;
+                Ok(())
+            }
+            (FieldKey::StatusPatterns, FieldValue::List(v)) => {
+                for entry in v {
+                    parse_status_pattern_entry(entry)?;
+                }
+                for patterns in parse_status_patterns_list(v).values() {
+                    crate::tmux::StatusPatterns::compile(patterns).map_err(|e| e.to_string())?;
+                }
+                Ok(())
+            }
+            (FieldKey::Presets, FieldValue::List(v)) => {
+                for entry in v {
+                    let (name, preset) = parse_preset_entry(entry)?;
+                    preset.tool().map_err(|e| format!("Preset '{name}': {e}"))?;
+                }
+                Ok(())
+            }
             // Sound field validation - check if sound file exists
             (
                 FieldKey::SoundOnStart
@@ -196,17 +321,113 @@ pub fn build_fields_for_category(
     global: &Config,
     profile: &ProfileConfig,
 ) -> Vec<SettingField> {
+    if scope == SettingsScope::Repo {
+        // A repo config has no sections for these: UI and logging are
+        // personal, and events can run commands (see `build_events_fields`)
+        if matches!(
+            category,
+            SettingsCategory::Ui | SettingsCategory::Logging | SettingsCategory::Events
+        ) {
+            return Vec::new();
+        }
+        return build_fields_for_category(category, SettingsScope::Profile, global, profile);
+    }
+
     match category {
+        SettingsCategory::App => build_app_fields(scope, global),
         SettingsCategory::Updates => build_updates_fields(scope, global, profile),
         SettingsCategory::Worktree => build_worktree_fields(scope, global, profile),
         SettingsCategory::Sandbox => build_sandbox_fields(scope, global, profile),
         SettingsCategory::Tmux => build_tmux_fields(scope, global, profile),
+        SettingsCategory::Ui => build_ui_fields(scope, global, profile),
+        SettingsCategory::Logging => build_logging_fields(scope, global, profile),
         SettingsCategory::Session => build_session_fields(scope, global, profile),
         SettingsCategory::Sound => build_sound_fields(scope, global, profile),
+        SettingsCategory::Events => build_events_fields(scope, global, profile),
         SettingsCategory::Hooks => build_hooks_fields(scope, global, profile),
     }
 }
 
+/// Work out which layer each field of a category takes its value from, in
+/// the order `build_fields_for_category` returns them for `scope`. `repo`
+/// holds the repo overrides and is only consulted in repo scope.
+pub fn resolve_field_sources(
+    category: SettingsCategory,
+    scope: SettingsScope,
+    global: &Config,
+    profile: &ProfileConfig,
+    repo: &ProfileConfig,
+) -> Vec<FieldSource> {
+    let defaults = build_fields_for_category(
+        category,
+        SettingsScope::Global,
+        &Config::default(),
+        &ProfileConfig::default(),
+    );
+    let inherited = |field: &SettingField| {
+        if defaults
+            .iter()
+            .any(|d| d.key == field.key && d.value == field.value)
+        {
+            FieldSource::Default
+        } else {
+            FieldSource::Global
+        }
+    };
+    let from_profile = |field: &SettingField| {
+        if field.has_override {
+            FieldSource::Profile
+        } else {
+            inherited(field)
+        }
+    };
+    let profile_fields =
+        build_fields_for_category(category, SettingsScope::Profile, global, profile);
+
+    match scope {
+        SettingsScope::Global => {
+            build_fields_for_category(category, SettingsScope::Global, global, profile)
+                .iter()
+                .map(inherited)
+                .collect()
+        }
+        SettingsScope::Profile => profile_fields.iter().map(from_profile).collect(),
+        SettingsScope::Repo => {
+            let base = merge_configs(global.clone(), profile);
+            build_fields_for_category(category, SettingsScope::Repo, &base, repo)
+                .iter()
+                .map(|field| {
+                    if field.has_override {
+                        FieldSource::Repo
+                    } else {
+                        profile_fields
+                            .iter()
+                            .find(|p| p.key == field.key)
+                            .map_or(FieldSource::Default, from_profile)
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+/// Offline mode describes the machine rather than a profile or repo, so only
+/// the global config sets it.
+fn build_app_fields(scope: SettingsScope, global: &Config) -> Vec<SettingField> {
+    if scope != SettingsScope::Global {
+        return Vec::new();
+    }
+
+    vec![SettingField {
+        key: FieldKey::Offline,
+        label: "Offline Mode",
+        description: "Skip update checks and never pull sandbox images (AGENT_OF_EMPIRES_OFFLINE also sets this)",
+        value: FieldValue::Bool(global.app.offline),
+        category: SettingsCategory::App,
+        has_override: false,
+    }]
+}
+
 fn build_updates_fields(
     scope: SettingsScope,
     global: &Config,
@@ -275,6 +496,11 @@ fn build_worktree_fields(
         global.worktree.bare_repo_path_template.clone(),
         wt.and_then(|w| w.bare_repo_path_template.clone()),
     );
+    let (branch_separator, o_sep) = resolve_value(
+        scope,
+        global.worktree.branch_separator.clone(),
+        wt.and_then(|w| w.branch_separator.clone()),
+    );
     let (auto_cleanup, o3) = resolve_value(
         scope,
         global.worktree.auto_cleanup,
@@ -303,6 +529,14 @@ fn build_worktree_fields(
             category: SettingsCategory::Worktree,
             has_override: o2,
         },
+        SettingField {
+            key: FieldKey::BranchSeparator,
+            label: "Branch Separator",
+            description: "Replaces '/' in branch names for worktree directories",
+            value: FieldValue::Text(branch_separator),
+            category: SettingsCategory::Worktree,
+            has_override: o_sep,
+        },
         SettingField {
             key: FieldKey::WorktreeAutoCleanup,
             label: "Auto Cleanup",
@@ -379,11 +613,22 @@ fn build_sandbox_fields(
         sb.and_then(|s| s.memory_limit.clone()),
         sb.map(|s| s.memory_limit.is_some()).unwrap_or(false),
     );
+    let (network, o_net) = resolve_optional(
+        scope,
+        global.sandbox.network.clone(),
+        sb.and_then(|s| s.network.clone()),
+        sb.map(|s| s.network.is_some()).unwrap_or(false),
+    );
     let (default_terminal_mode, o6) = resolve_value(
         scope,
         global.sandbox.default_terminal_mode,
         sb.and_then(|s| s.default_terminal_mode),
     );
+    let (auth_volume_scope, o_avs) = resolve_value(
+        scope,
+        global.sandbox.auth_volume_scope,
+        sb.and_then(|s| s.auth_volume_scope),
+    );
     let (extra_volumes, o_ev) = resolve_value(
         scope,
         global.sandbox.extra_volumes.clone(),
@@ -399,11 +644,57 @@ fn build_sandbox_fields(
         global.sandbox.mount_ssh,
         sb.and_then(|s| s.mount_ssh),
     );
+    let (read_only_rootfs, o_ro) = resolve_value(
+        scope,
+        global.sandbox.read_only_rootfs,
+        sb.and_then(|s| s.read_only_rootfs),
+    );
+    let (hardened, o_hard) =
+        resolve_value(scope, global.sandbox.hardened, sb.and_then(|s| s.hardened));
+    let (cap_drop, o_cd) = resolve_value(
+        scope,
+        global.sandbox.cap_drop.clone(),
+        sb.and_then(|s| s.cap_drop.clone()),
+    );
+    let (cap_add, o_ca) = resolve_value(
+        scope,
+        global.sandbox.cap_add.clone(),
+        sb.and_then(|s| s.cap_add.clone()),
+    );
+    let (security_opt, o_so) = resolve_value(
+        scope,
+        global.sandbox.security_opt.clone(),
+        sb.and_then(|s| s.security_opt.clone()),
+    );
+    let (docker_retries, o_dr) = resolve_value(
+        scope,
+        global.sandbox.docker_retries,
+        sb.and_then(|s| s.docker_retries),
+    );
+    let (auto_restart, o_ar) = resolve_value(
+        scope,
+        global.sandbox.auto_restart,
+        sb.and_then(|s| s.auto_restart),
+    );
+    let (stop_on_detach, o_sod) = resolve_value(
+        scope,
+        global.sandbox.stop_on_detach,
+        sb.and_then(|s| s.stop_on_detach),
+    );
+    let (max_concurrent_containers, o_mcc) = resolve_value(
+        scope,
+        global.sandbox.max_concurrent_containers,
+        sb.and_then(|s| s.max_concurrent_containers),
+    );
 
     let terminal_mode_selected = match default_terminal_mode {
         DefaultTerminalMode::Host => 0,
         DefaultTerminalMode::Container => 1,
     };
+    let auth_volume_scope_selected = match auth_volume_scope {
+        AuthVolumeScope::Global => 0,
+        AuthVolumeScope::Profile => 1,
+    };
 
     vec![
         SettingField {
@@ -470,6 +761,14 @@ fn build_sandbox_fields(
             category: SettingsCategory::Sandbox,
             has_override: o_mem,
         },
+        SettingField {
+            key: FieldKey::Network,
+            label: "Network",
+            description: "Docker network for containers (\"host\", \"none\", or a named network)",
+            value: FieldValue::OptionalText(network),
+            category: SettingsCategory::Sandbox,
+            has_override: o_net,
+        },
         SettingField {
             key: FieldKey::DefaultTerminalMode,
             label: "Default Terminal Mode",
@@ -481,6 +780,17 @@ fn build_sandbox_fields(
             category: SettingsCategory::Sandbox,
             has_override: o6,
         },
+        SettingField {
+            key: FieldKey::AuthVolumeScope,
+            label: "Auth Volume Scope",
+            description: "Share agent logins across all profiles, or keep them per profile",
+            value: FieldValue::Select {
+                selected: auth_volume_scope_selected,
+                options: vec!["Global".into(), "Profile".into()],
+            },
+            category: SettingsCategory::Sandbox,
+            has_override: o_avs,
+        },
         SettingField {
             key: FieldKey::ExtraVolumes,
             label: "Extra Volumes",
@@ -505,6 +815,78 @@ fn build_sandbox_fields(
             category: SettingsCategory::Sandbox,
             has_override: o8,
         },
+        SettingField {
+            key: FieldKey::ReadOnlyRootfs,
+            label: "Read-only Root FS",
+            description: "Mount the container root filesystem read-only (some tools may break)",
+            value: FieldValue::Bool(read_only_rootfs),
+            category: SettingsCategory::Sandbox,
+            has_override: o_ro,
+        },
+        SettingField {
+            key: FieldKey::Hardened,
+            label: "Hardened",
+            description: "Drop all capabilities except a minimal set and set no-new-privileges",
+            value: FieldValue::Bool(hardened),
+            category: SettingsCategory::Sandbox,
+            has_override: o_hard,
+        },
+        SettingField {
+            key: FieldKey::CapDrop,
+            label: "Drop Capabilities",
+            description: "Linux capabilities to drop (e.g. ALL, NET_RAW)",
+            value: FieldValue::List(cap_drop),
+            category: SettingsCategory::Sandbox,
+            has_override: o_cd,
+        },
+        SettingField {
+            key: FieldKey::CapAdd,
+            label: "Add Capabilities",
+            description: "Linux capabilities to add back (e.g. CHOWN)",
+            value: FieldValue::List(cap_add),
+            category: SettingsCategory::Sandbox,
+            has_override: o_ca,
+        },
+        SettingField {
+            key: FieldKey::SecurityOpt,
+            label: "Security Options",
+            description: "Docker --security-opt values (e.g. seccomp=/path/profile.json)",
+            value: FieldValue::List(security_opt),
+            category: SettingsCategory::Sandbox,
+            has_override: o_so,
+        },
+        SettingField {
+            key: FieldKey::DockerRetries,
+            label: "Docker Retries",
+            description: "Extra attempts for Docker commands that fail transiently",
+            value: FieldValue::Number(docker_retries),
+            category: SettingsCategory::Sandbox,
+            has_override: o_dr,
+        },
+        SettingField {
+            key: FieldKey::AutoRestart,
+            label: "Auto Restart",
+            description: "Restart sessions whose container exited unexpectedly (e.g. OOM)",
+            value: FieldValue::Bool(auto_restart),
+            category: SettingsCategory::Sandbox,
+            has_override: o_ar,
+        },
+        SettingField {
+            key: FieldKey::StopOnDetach,
+            label: "Stop on Detach",
+            description: "Stop a session's container when its last view is detached",
+            value: FieldValue::Bool(stop_on_detach),
+            category: SettingsCategory::Sandbox,
+            has_override: o_sod,
+        },
+        SettingField {
+            key: FieldKey::MaxConcurrentContainers,
+            label: "Max Running Containers",
+            description: "Ask before creating a sandbox beyond this many running (0 = no limit)",
+            value: FieldValue::Number(max_concurrent_containers),
+            category: SettingsCategory::Sandbox,
+            has_override: o_mcc,
+        },
     ]
 }
 
@@ -524,6 +906,12 @@ fn build_tmux_fields(
     let (mouse, mouse_override) =
         resolve_value(scope, global.tmux.mouse, tmux.and_then(|t| t.mouse));
 
+    let (history_limit, history_limit_override) = resolve_value(
+        scope,
+        global.tmux.history_limit,
+        tmux.and_then(|t| t.history_limit),
+    );
+
     let status_bar_selected = match status_bar {
         TmuxStatusBarMode::Auto => 0,
         TmuxStatusBarMode::Enabled => 1,
@@ -559,6 +947,137 @@ fn build_tmux_fields(
             category: SettingsCategory::Tmux,
             has_override: mouse_override,
         },
+        SettingField {
+            key: FieldKey::HistoryLimit,
+            label: "History Limit",
+            description: "Scrollback lines for new sessions (0 keeps your tmux setting)",
+            value: FieldValue::Number(history_limit),
+            category: SettingsCategory::Tmux,
+            has_override: history_limit_override,
+        },
+    ]
+}
+
+fn build_ui_fields(
+    scope: SettingsScope,
+    global: &Config,
+    profile: &ProfileConfig,
+) -> Vec<SettingField> {
+    let ui = profile.ui.as_ref();
+
+    let (tick_ms, o1) = resolve_value(scope, global.ui.tick_ms, ui.and_then(|u| u.tick_ms));
+    let (status_refresh_ms, o2) = resolve_value(
+        scope,
+        global.ui.status_refresh_ms,
+        ui.and_then(|u| u.status_refresh_ms),
+    );
+    let (status_debounce_polls, o3) = resolve_value(
+        scope,
+        global.ui.status_debounce_polls,
+        ui.and_then(|u| u.status_debounce_polls),
+    );
+    let (remember_new_session, o4) = resolve_value(
+        scope,
+        global.ui.remember_new_session,
+        ui.and_then(|u| u.remember_new_session),
+    );
+    let (resume_last_profile, o5) = resolve_value(
+        scope,
+        global.ui.resume_last_profile,
+        ui.and_then(|u| u.resume_last_profile),
+    );
+
+    vec![
+        SettingField {
+            key: FieldKey::TickMs,
+            label: "Tick Rate (ms)",
+            description: "How often the TUI polls for input and redraws (10-1000)",
+            value: FieldValue::Number(tick_ms),
+            category: SettingsCategory::Ui,
+            has_override: o1,
+        },
+        SettingField {
+            key: FieldKey::StatusRefreshMs,
+            label: "Status Refresh (ms)",
+            description: "How often session statuses are re-checked (minimum 100)",
+            value: FieldValue::Number(status_refresh_ms),
+            category: SettingsCategory::Ui,
+            has_override: o2,
+        },
+        SettingField {
+            key: FieldKey::StatusDebouncePolls,
+            label: "Status Debounce (polls)",
+            description: "Polls a new status must persist before it is shown (1-10, 1 = off)",
+            value: FieldValue::Number(status_debounce_polls),
+            category: SettingsCategory::Ui,
+            has_override: o3,
+        },
+        SettingField {
+            key: FieldKey::RememberNewSession,
+            label: "Remember New Session Options",
+            description: "Pre-fill new sessions with the tool, group and sandbox used last time",
+            value: FieldValue::Bool(remember_new_session),
+            category: SettingsCategory::Ui,
+            has_override: o4,
+        },
+        SettingField {
+            key: FieldKey::ResumeLastProfile,
+            label: "Resume Last Profile",
+            description: "Open the TUI in the profile last switched to instead of the default",
+            value: FieldValue::Bool(resume_last_profile),
+            category: SettingsCategory::Ui,
+            has_override: o5,
+        },
+    ]
+}
+
+fn build_logging_fields(
+    scope: SettingsScope,
+    global: &Config,
+    profile: &ProfileConfig,
+) -> Vec<SettingField> {
+    let logging = profile.logging.as_ref();
+
+    let (enabled, o1) = resolve_value(
+        scope,
+        global.logging.enabled,
+        logging.and_then(|l| l.enabled),
+    );
+    let (level, o2) = resolve_value(scope, global.logging.level, logging.and_then(|l| l.level));
+    let (max_files, o3) = resolve_value(
+        scope,
+        global.logging.max_files,
+        logging.and_then(|l| l.max_files),
+    );
+
+    vec![
+        SettingField {
+            key: FieldKey::LoggingEnabled,
+            label: "Log to File",
+            description: "Write logs to logs/aoe.log in the app directory (applies on restart)",
+            value: FieldValue::Bool(enabled),
+            category: SettingsCategory::Logging,
+            has_override: o1,
+        },
+        SettingField {
+            key: FieldKey::LogLevel,
+            label: "Log Level",
+            description: "Most verbose level written to the log file",
+            value: FieldValue::Select {
+                selected: LogLevel::ALL.iter().position(|l| *l == level).unwrap_or(0),
+                options: LogLevel::ALL.iter().map(|l| l.as_str().into()).collect(),
+            },
+            category: SettingsCategory::Logging,
+            has_override: o2,
+        },
+        SettingField {
+            key: FieldKey::LogMaxFiles,
+            label: "Log Files Kept",
+            description: "Daily log files to keep before the oldest is deleted",
+            value: FieldValue::Number(max_files),
+            category: SettingsCategory::Logging,
+            has_override: o3,
+        },
     ]
 }
 
@@ -586,24 +1105,125 @@ fn build_session_fields(
         _ => 0, // Auto (use first available)
     };
 
-    vec![SettingField {
-        key: FieldKey::DefaultTool,
-        label: "Default Tool",
-        description: "Default coding tool for new sessions",
-        value: FieldValue::Select {
-            selected,
-            options: vec![
-                "Auto (first available)".into(),
-                "claude".into(),
-                "opencode".into(),
-                "vibe".into(),
-                "codex".into(),
-                "gemini".into(),
-            ],
+    let (default_group, o_group) = resolve_optional(
+        scope,
+        global.session.default_group.clone(),
+        session.and_then(|s| s.default_group.clone()),
+        session.map(|s| s.default_group.is_some()).unwrap_or(false),
+    );
+
+    let (default_path, o_path) = resolve_optional(
+        scope,
+        global.session.default_path.clone(),
+        session.and_then(|s| s.default_path.clone()),
+        session.map(|s| s.default_path.is_some()).unwrap_or(false),
+    );
+
+    let (create_dir, o_create) = resolve_value(
+        scope,
+        global.session.create_dir,
+        session.and_then(|s| s.create_dir),
+    );
+
+    let (title_template, o_title) = resolve_optional(
+        scope,
+        global.session.title_template.clone(),
+        session.and_then(|s| s.title_template.clone()),
+        session.map(|s| s.title_template.is_some()).unwrap_or(false),
+    );
+
+    let (title_words, o_words) = resolve_value(
+        scope,
+        global.session.title_words.clone(),
+        session.and_then(|s| s.title_words.clone()),
+    );
+
+    let (status_patterns, o_patterns) = resolve_value(
+        scope,
+        global.status_patterns.clone(),
+        profile.status_patterns.clone(),
+    );
+
+    let (presets, o_presets) =
+        resolve_value(scope, global.presets.clone(), profile.presets.clone());
+
+    vec![
+        SettingField {
+            key: FieldKey::DefaultTool,
+            label: "Default Tool",
+            description: "Default coding tool for new sessions",
+            value: FieldValue::Select {
+                selected,
+                options: vec![
+                    "Auto (first available)".into(),
+                    "claude".into(),
+                    "opencode".into(),
+                    "vibe".into(),
+                    "codex".into(),
+                    "gemini".into(),
+                ],
+            },
+            category: SettingsCategory::Session,
+            has_override,
         },
-        category: SettingsCategory::Session,
-        has_override,
-    }]
+        SettingField {
+            key: FieldKey::DefaultGroup,
+            label: "Default Group",
+            description: "Group for new sessions when none is given (empty = ungrouped)",
+            value: FieldValue::OptionalText(default_group),
+            category: SettingsCategory::Session,
+            has_override: o_group,
+        },
+        SettingField {
+            key: FieldKey::DefaultPath,
+            label: "Default Path",
+            description: "Directory new sessions start in, e.g. \"~/code\" (empty = current dir)",
+            value: FieldValue::OptionalText(default_path),
+            category: SettingsCategory::Session,
+            has_override: o_path,
+        },
+        SettingField {
+            key: FieldKey::CreateDir,
+            label: "Create Missing Dir",
+            description: "Create a new session's project directory if it does not exist",
+            value: FieldValue::Bool(create_dir),
+            category: SettingsCategory::Session,
+            has_override: o_create,
+        },
+        SettingField {
+            key: FieldKey::TitleTemplate,
+            label: "Title Template",
+            description: "Title for untitled sessions, e.g. \"{branch} @ {dir}\" (empty = random)",
+            value: FieldValue::OptionalText(title_template),
+            category: SettingsCategory::Session,
+            has_override: o_title,
+        },
+        SettingField {
+            key: FieldKey::TitleWords,
+            label: "Title Words",
+            description: "Names for random session titles (empty = civilizations)",
+            value: FieldValue::List(title_words),
+            category: SettingsCategory::Session,
+            has_override: o_words,
+        },
+        SettingField {
+            key: FieldKey::StatusPatterns,
+            label: "Status Patterns",
+            description:
+                "TOOL.waiting=REGEX or TOOL.running=REGEX, checked before built-in detection",
+            value: FieldValue::List(status_patterns_list(&status_patterns)),
+            category: SettingsCategory::Session,
+            has_override: o_patterns,
+        },
+        SettingField {
+            key: FieldKey::Presets,
+            label: "Presets",
+            description: "New session presets, e.g. fast = { tool = \"claude\", sandbox = true }",
+            value: FieldValue::List(presets_list(&presets)),
+            category: SettingsCategory::Session,
+            has_override: o_presets,
+        },
+    ]
 }
 
 fn build_sound_fields(
@@ -720,6 +1340,50 @@ fn build_sound_fields(
     ]
 }
 
+/// Events can run commands and send session data off-machine, so a cloned
+/// repo's `.aoe/config.toml` can't set them.
+fn build_events_fields(
+    scope: SettingsScope,
+    global: &Config,
+    profile: &ProfileConfig,
+) -> Vec<SettingField> {
+    let events = profile.events.as_ref();
+
+    let (webhook, o1) = resolve_optional(
+        scope,
+        global.events.webhook.clone(),
+        events.and_then(|e| e.webhook.clone()),
+        events.is_some_and(|e| e.webhook.is_some()),
+    );
+    let (command, o2) = resolve_optional(
+        scope,
+        global.events.command.clone(),
+        events.and_then(|e| e.command.clone()),
+        events.is_some_and(|e| e.command.is_some()),
+    );
+
+    vec![
+        SettingField {
+            key: FieldKey::EventWebhook,
+            label: "Webhook URL",
+            description:
+                "POST a JSON payload here on session created/started/status_changed/deleted",
+            value: FieldValue::OptionalText(webhook),
+            category: SettingsCategory::Events,
+            has_override: o1,
+        },
+        SettingField {
+            key: FieldKey::EventCommand,
+            label: "Command",
+            description:
+                "Shell command run on each session event, with details in AOE_EVENT* env vars",
+            value: FieldValue::OptionalText(command),
+            category: SettingsCategory::Events,
+            has_override: o2,
+        },
+    ]
+}
+
 fn build_hooks_fields(
     scope: SettingsScope,
     global: &Config,
@@ -776,6 +1440,8 @@ pub fn apply_field_to_config(
 
 fn apply_field_to_global(field: &SettingField, config: &mut Config) {
     match (&field.key, &field.value) {
+        // App
+        (FieldKey::Offline, FieldValue::Bool(v)) => config.app.offline = *v,
         // Updates
         (FieldKey::CheckEnabled, FieldValue::Bool(v)) => config.updates.check_enabled = *v,
         (FieldKey::CheckIntervalHours, FieldValue::Number(v)) => {
@@ -787,6 +1453,9 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
         (FieldKey::BareRepoPathTemplate, FieldValue::Text(v)) => {
             config.worktree.bare_repo_path_template = v.clone()
         }
+        (FieldKey::BranchSeparator, FieldValue::Text(v)) => {
+            config.worktree.branch_separator = v.clone()
+        }
         (FieldKey::WorktreeAutoCleanup, FieldValue::Bool(v)) => config.worktree.auto_cleanup = *v,
         (FieldKey::DeleteBranchOnCleanup, FieldValue::Bool(v)) => {
             config.worktree.delete_branch_on_cleanup = *v
@@ -804,6 +1473,17 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
         (FieldKey::ExtraVolumes, FieldValue::List(v)) => config.sandbox.extra_volumes = v.clone(),
         (FieldKey::VolumeIgnores, FieldValue::List(v)) => config.sandbox.volume_ignores = v.clone(),
         (FieldKey::MountSsh, FieldValue::Bool(v)) => config.sandbox.mount_ssh = *v,
+        (FieldKey::ReadOnlyRootfs, FieldValue::Bool(v)) => config.sandbox.read_only_rootfs = *v,
+        (FieldKey::Hardened, FieldValue::Bool(v)) => config.sandbox.hardened = *v,
+        (FieldKey::CapDrop, FieldValue::List(v)) => config.sandbox.cap_drop = v.clone(),
+        (FieldKey::CapAdd, FieldValue::List(v)) => config.sandbox.cap_add = v.clone(),
+        (FieldKey::SecurityOpt, FieldValue::List(v)) => config.sandbox.security_opt = v.clone(),
+        (FieldKey::DockerRetries, FieldValue::Number(v)) => config.sandbox.docker_retries = *v,
+        (FieldKey::AutoRestart, FieldValue::Bool(v)) => config.sandbox.auto_restart = *v,
+        (FieldKey::StopOnDetach, FieldValue::Bool(v)) => config.sandbox.stop_on_detach = *v,
+        (FieldKey::MaxConcurrentContainers, FieldValue::Number(v)) => {
+            config.sandbox.max_concurrent_containers = *v
+        }
         (FieldKey::SandboxAutoCleanup, FieldValue::Bool(v)) => config.sandbox.auto_cleanup = *v,
         (FieldKey::CpuLimit, FieldValue::OptionalText(v)) => {
             config.sandbox.cpu_limit = v.clone();
@@ -811,12 +1491,21 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
         (FieldKey::MemoryLimit, FieldValue::OptionalText(v)) => {
             config.sandbox.memory_limit = v.clone();
         }
+        (FieldKey::Network, FieldValue::OptionalText(v)) => {
+            config.sandbox.network = v.clone();
+        }
         (FieldKey::DefaultTerminalMode, FieldValue::Select { selected, .. }) => {
             config.sandbox.default_terminal_mode = match selected {
                 0 => DefaultTerminalMode::Host,
                 _ => DefaultTerminalMode::Container,
             };
         }
+        (FieldKey::AuthVolumeScope, FieldValue::Select { selected, .. }) => {
+            config.sandbox.auth_volume_scope = match selected {
+                0 => AuthVolumeScope::Global,
+                _ => AuthVolumeScope::Profile,
+            };
+        }
         // Tmux
         (FieldKey::StatusBar, FieldValue::Select { selected, .. }) => {
             config.tmux.status_bar = match selected {
@@ -832,6 +1521,21 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
                 _ => TmuxMouseMode::Disabled,
             };
         }
+        (FieldKey::HistoryLimit, FieldValue::Number(v)) => config.tmux.history_limit = *v,
+        // UI
+        (FieldKey::TickMs, FieldValue::Number(v)) => config.ui.tick_ms = *v,
+        (FieldKey::StatusRefreshMs, FieldValue::Number(v)) => config.ui.status_refresh_ms = *v,
+        (FieldKey::StatusDebouncePolls, FieldValue::Number(v)) => {
+            config.ui.status_debounce_polls = *v
+        }
+        (FieldKey::RememberNewSession, FieldValue::Bool(v)) => config.ui.remember_new_session = *v,
+        (FieldKey::ResumeLastProfile, FieldValue::Bool(v)) => config.ui.resume_last_profile = *v,
+        // Logging
+        (FieldKey::LoggingEnabled, FieldValue::Bool(v)) => config.logging.enabled = *v,
+        (FieldKey::LogLevel, FieldValue::Select { selected, .. }) => {
+            config.logging.level = LogLevel::ALL.get(*selected).copied().unwrap_or_default();
+        }
+        (FieldKey::LogMaxFiles, FieldValue::Number(v)) => config.logging.max_files = *v,
         // Session
         (FieldKey::DefaultTool, FieldValue::Select { selected, .. }) => {
             config.session.default_tool = match selected {
@@ -843,6 +1547,21 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
                 _ => None, // Auto
             };
         }
+        (FieldKey::DefaultGroup, FieldValue::OptionalText(v)) => {
+            config.session.default_group = v.clone();
+        }
+        (FieldKey::DefaultPath, FieldValue::OptionalText(v)) => {
+            config.session.default_path = v.clone();
+        }
+        (FieldKey::CreateDir, FieldValue::Bool(v)) => config.session.create_dir = *v,
+        (FieldKey::TitleTemplate, FieldValue::OptionalText(v)) => {
+            config.session.title_template = v.clone();
+        }
+        (FieldKey::TitleWords, FieldValue::List(v)) => config.session.title_words = v.clone(),
+        (FieldKey::StatusPatterns, FieldValue::List(v)) => {
+            config.status_patterns = parse_status_patterns_list(v);
+        }
+        (FieldKey::Presets, FieldValue::List(v)) => config.presets = parse_presets_list(v),
         // Sound
         (FieldKey::SoundEnabled, FieldValue::Bool(v)) => config.sound.enabled = *v,
         (FieldKey::SoundMode, FieldValue::Select { selected, .. }) => {
@@ -866,6 +1585,9 @@ fn apply_field_to_global(field: &SettingField, config: &mut Config) {
         (FieldKey::SoundOnError, FieldValue::OptionalText(v)) => {
             config.sound.on_error = v.clone();
         }
+        // Events
+        (FieldKey::EventWebhook, FieldValue::OptionalText(v)) => config.events.webhook = v.clone(),
+        (FieldKey::EventCommand, FieldValue::OptionalText(v)) => config.events.command = v.clone(),
         // Hooks
         (FieldKey::HookOnCreate, FieldValue::List(v)) => config.hooks.on_create = v.clone(),
         (FieldKey::HookOnLaunch, FieldValue::List(v)) => config.hooks.on_launch = v.clone(),
@@ -919,6 +1641,14 @@ fn apply_field_to_profile(field: &SettingField, global: &Config, config: &mut Pr
                 |s, val| s.bare_repo_path_template = val,
             );
         }
+        (FieldKey::BranchSeparator, FieldValue::Text(v)) => {
+            set_or_clear_override(
+                v.clone(),
+                &global.worktree.branch_separator,
+                &mut config.worktree,
+                |s, val| s.branch_separator = val,
+            );
+        }
         (FieldKey::WorktreeAutoCleanup, FieldValue::Bool(v)) => {
             set_or_clear_override(
                 *v,
@@ -1001,6 +1731,78 @@ fn apply_field_to_profile(field: &SettingField, global: &Config, config: &mut Pr
                 |s, val| s.mount_ssh = val,
             );
         }
+        (FieldKey::ReadOnlyRootfs, FieldValue::Bool(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.sandbox.read_only_rootfs,
+                &mut config.sandbox,
+                |s, val| s.read_only_rootfs = val,
+            );
+        }
+        (FieldKey::Hardened, FieldValue::Bool(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.sandbox.hardened,
+                &mut config.sandbox,
+                |s, val| s.hardened = val,
+            );
+        }
+        (FieldKey::CapDrop, FieldValue::List(v)) => {
+            set_or_clear_override(
+                v.clone(),
+                &global.sandbox.cap_drop,
+                &mut config.sandbox,
+                |s, val| s.cap_drop = val,
+            );
+        }
+        (FieldKey::CapAdd, FieldValue::List(v)) => {
+            set_or_clear_override(
+                v.clone(),
+                &global.sandbox.cap_add,
+                &mut config.sandbox,
+                |s, val| s.cap_add = val,
+            );
+        }
+        (FieldKey::SecurityOpt, FieldValue::List(v)) => {
+            set_or_clear_override(
+                v.clone(),
+                &global.sandbox.security_opt,
+                &mut config.sandbox,
+                |s, val| s.security_opt = val,
+            );
+        }
+        (FieldKey::DockerRetries, FieldValue::Number(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.sandbox.docker_retries,
+                &mut config.sandbox,
+                |s, val| s.docker_retries = val,
+            );
+        }
+        (FieldKey::AutoRestart, FieldValue::Bool(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.sandbox.auto_restart,
+                &mut config.sandbox,
+                |s, val| s.auto_restart = val,
+            );
+        }
+        (FieldKey::StopOnDetach, FieldValue::Bool(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.sandbox.stop_on_detach,
+                &mut config.sandbox,
+                |s, val| s.stop_on_detach = val,
+            );
+        }
+        (FieldKey::MaxConcurrentContainers, FieldValue::Number(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.sandbox.max_concurrent_containers,
+                &mut config.sandbox,
+                |s, val| s.max_concurrent_containers = val,
+            );
+        }
         (FieldKey::SandboxAutoCleanup, FieldValue::Bool(v)) => {
             set_or_clear_override(
                 *v,
@@ -1035,6 +1837,19 @@ fn apply_field_to_profile(field: &SettingField, global: &Config, config: &mut Pr
                 s.memory_limit = v.clone();
             }
         }
+        (FieldKey::Network, FieldValue::OptionalText(v)) => {
+            if *v == global.sandbox.network {
+                if let Some(ref mut s) = config.sandbox {
+                    s.network = None;
+                }
+            } else {
+                use crate::session::SandboxConfigOverride;
+                let s = config
+                    .sandbox
+                    .get_or_insert_with(SandboxConfigOverride::default);
+                s.network = v.clone();
+            }
+        }
         (FieldKey::DefaultTerminalMode, FieldValue::Select { selected, .. }) => {
             let mode = match selected {
                 0 => DefaultTerminalMode::Host,
@@ -1047,6 +1862,18 @@ fn apply_field_to_profile(field: &SettingField, global: &Config, config: &mut Pr
                 |s, val| s.default_terminal_mode = val,
             );
         }
+        (FieldKey::AuthVolumeScope, FieldValue::Select { selected, .. }) => {
+            let scope = match selected {
+                0 => AuthVolumeScope::Global,
+                _ => AuthVolumeScope::Profile,
+            };
+            set_or_clear_override(
+                scope,
+                &global.sandbox.auth_volume_scope,
+                &mut config.sandbox,
+                |s, val| s.auth_volume_scope = val,
+            );
+        }
         // Tmux
         (FieldKey::StatusBar, FieldValue::Select { selected, .. }) => {
             let mode = match selected {
@@ -1068,6 +1895,78 @@ fn apply_field_to_profile(field: &SettingField, global: &Config, config: &mut Pr
                 s.mouse = val
             });
         }
+        (FieldKey::HistoryLimit, FieldValue::Number(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.tmux.history_limit,
+                &mut config.tmux,
+                |s, val| s.history_limit = val,
+            );
+        }
+        // UI
+        (FieldKey::TickMs, FieldValue::Number(v)) => {
+            set_or_clear_override(*v, &global.ui.tick_ms, &mut config.ui, |s, val| {
+                s.tick_ms = val
+            });
+        }
+        (FieldKey::StatusRefreshMs, FieldValue::Number(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.ui.status_refresh_ms,
+                &mut config.ui,
+                |s, val| s.status_refresh_ms = val,
+            );
+        }
+        (FieldKey::StatusDebouncePolls, FieldValue::Number(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.ui.status_debounce_polls,
+                &mut config.ui,
+                |s, val| s.status_debounce_polls = val,
+            );
+        }
+        (FieldKey::RememberNewSession, FieldValue::Bool(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.ui.remember_new_session,
+                &mut config.ui,
+                |s, val| s.remember_new_session = val,
+            );
+        }
+        (FieldKey::ResumeLastProfile, FieldValue::Bool(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.ui.resume_last_profile,
+                &mut config.ui,
+                |s, val| s.resume_last_profile = val,
+            );
+        }
+        // Logging
+        (FieldKey::LoggingEnabled, FieldValue::Bool(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.logging.enabled,
+                &mut config.logging,
+                |s, val| s.enabled = val,
+            );
+        }
+        (FieldKey::LogLevel, FieldValue::Select { selected, .. }) => {
+            let level = LogLevel::ALL.get(*selected).copied().unwrap_or_default();
+            set_or_clear_override(
+                level,
+                &global.logging.level,
+                &mut config.logging,
+                |s, val| s.level = val,
+            );
+        }
+        (FieldKey::LogMaxFiles, FieldValue::Number(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.logging.max_files,
+                &mut config.logging,
+                |s, val| s.max_files = val,
+            );
+        }
         // Session
         (FieldKey::DefaultTool, FieldValue::Select { selected, .. }) => {
             let tool = match selected {
@@ -1091,6 +1990,69 @@ fn apply_field_to_profile(field: &SettingField, global: &Config, config: &mut Pr
                 session.default_tool = tool;
             }
         }
+        (FieldKey::DefaultGroup, FieldValue::OptionalText(v)) => {
+            if *v == global.session.default_group {
+                if let Some(ref mut s) = config.session {
+                    s.default_group = None;
+                }
+            } else {
+                use crate::session::SessionConfigOverride;
+                let s = config
+                    .session
+                    .get_or_insert_with(SessionConfigOverride::default);
+                s.default_group = v.clone();
+            }
+        }
+        (FieldKey::DefaultPath, FieldValue::OptionalText(v)) => {
+            if *v == global.session.default_path {
+                if let Some(ref mut s) = config.session {
+                    s.default_path = None;
+                }
+            } else {
+                use crate::session::SessionConfigOverride;
+                let s = config
+                    .session
+                    .get_or_insert_with(SessionConfigOverride::default);
+                s.default_path = v.clone();
+            }
+        }
+        (FieldKey::CreateDir, FieldValue::Bool(v)) => {
+            set_or_clear_override(
+                *v,
+                &global.session.create_dir,
+                &mut config.session,
+                |s, val| s.create_dir = val,
+            );
+        }
+        (FieldKey::TitleTemplate, FieldValue::OptionalText(v)) => {
+            if *v == global.session.title_template {
+                if let Some(ref mut s) = config.session {
+                    s.title_template = None;
+                }
+            } else {
+                use crate::session::SessionConfigOverride;
+                let s = config
+                    .session
+                    .get_or_insert_with(SessionConfigOverride::default);
+                s.title_template = v.clone();
+            }
+        }
+        (FieldKey::TitleWords, FieldValue::List(v)) => {
+            set_or_clear_override(
+                v.clone(),
+                &global.session.title_words,
+                &mut config.session,
+                |s, val| s.title_words = val,
+            );
+        }
+        (FieldKey::StatusPatterns, FieldValue::List(v)) => {
+            let patterns = parse_status_patterns_list(v);
+            config.status_patterns = (patterns != global.status_patterns).then_some(patterns);
+        }
+        (FieldKey::Presets, FieldValue::List(v)) => {
+            let presets = parse_presets_list(v);
+            config.presets = (presets != global.presets).then_some(presets);
+        }
         // Sound
         (FieldKey::SoundEnabled, FieldValue::Bool(v)) => {
             set_or_clear_override(*v, &global.sound.enabled, &mut config.sound, |s, val| {
@@ -1166,6 +2128,23 @@ fn apply_field_to_profile(field: &SettingField, global: &Config, config: &mut Pr
                 s.on_error = v.clone();
             }
         }
+        // Events
+        (FieldKey::EventWebhook, FieldValue::OptionalText(v)) => {
+            set_or_clear_override(
+                v.clone(),
+                &global.events.webhook,
+                &mut config.events,
+                |s, val| s.webhook = val.flatten(),
+            );
+        }
+        (FieldKey::EventCommand, FieldValue::OptionalText(v)) => {
+            set_or_clear_override(
+                v.clone(),
+                &global.events.command,
+                &mut config.events,
+                |s, val| s.command = val.flatten(),
+            );
+        }
         // Hooks
         (FieldKey::HookOnCreate, FieldValue::List(v)) => {
             set_or_clear_override(
@@ -1204,10 +2183,334 @@ fn parse_env_values_list(entries: &[String]) -> HashMap<String, String> {
         .collect()
 }
 
+/// Split a `TOOL.waiting=REGEX` or `TOOL.running=REGEX` status pattern entry
+/// into its tool, kind and pattern.
+fn parse_status_pattern_entry(entry: &str) -> Result<(&str, &str, &str), String> {
+    let invalid = || format!("'{entry}' is not TOOL.waiting=REGEX or TOOL.running=REGEX");
+    let (key, pattern) = entry.split_once('=').ok_or_else(invalid)?;
+    match key.trim().split_once('.') {
+        Some((tool, kind @ ("waiting" | "running"))) if !tool.is_empty() => {
+            Ok((tool, kind, pattern))
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// One `TOOL.KIND=REGEX` entry per pattern, grouped by tool
+fn status_patterns_list(patterns: &HashMap<String, StatusPatternsConfig>) -> Vec<String> {
+    let mut tools: Vec<_> = patterns.iter().collect();
+    tools.sort_by_key(|(tool, _)| tool.as_str());
+    tools
+        .into_iter()
+        .flat_map(|(tool, p)| {
+            let waiting = p.waiting.iter().map(move |r| format!("{tool}.waiting={r}"));
+            let running = p.running.iter().map(move |r| format!("{tool}.running={r}"));
+            waiting.chain(running)
+        })
+        .collect()
+}
+
+fn parse_status_patterns_list(entries: &[String]) -> HashMap<String, StatusPatternsConfig> {
+    let mut patterns: HashMap<String, StatusPatternsConfig> = HashMap::new();
+    for entry in entries {
+        match parse_status_pattern_entry(entry) {
+            Ok((tool, kind, pattern)) => {
+                let tool = patterns.entry(tool.to_string()).or_default();
+                let list = if kind == "waiting" {
+                    &mut tool.waiting
+                } else {
+                    &mut tool.running
+                };
+                list.push(pattern.to_string());
+            }
+            Err(e) => tracing::warn!("Ignoring status pattern: {}", e),
+        }
+    }
+    patterns
+}
+
+/// Parse a `NAME = { ... }` preset entry, the TOML inline table form of a
+/// `[presets.NAME]` table.
+fn parse_preset_entry(entry: &str) -> Result<(String, PresetConfig), String> {
+    let presets: HashMap<String, PresetConfig> =
+        toml::from_str(entry).map_err(|e| format!("Invalid preset '{entry}': {}", e.message()))?;
+    let mut presets = presets.into_iter();
+    match (presets.next(), presets.next()) {
+        (Some(preset), None) => Ok(preset),
+        _ => Err(format!(
+            "'{entry}' must define exactly one NAME = {{ ... }} preset"
+        )),
+    }
+}
+
+/// One `NAME = { ... }` entry per preset, sorted by name
+fn presets_list(presets: &HashMap<String, PresetConfig>) -> Vec<String> {
+    let mut names: Vec<_> = presets.keys().collect();
+    names.sort();
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let key = if !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+            {
+                name.clone()
+            } else {
+                toml::Value::String(name.clone()).to_string()
+            };
+            let table = toml::Value::try_from(&presets[name]).ok()?;
+            Some(format!("{key} = {table}"))
+        })
+        .collect()
+}
+
+fn parse_presets_list(entries: &[String]) -> HashMap<String, PresetConfig> {
+    entries
+        .iter()
+        .filter_map(|entry| match parse_preset_entry(entry) {
+            Ok(preset) => Some(preset),
+            Err(e) => {
+                tracing::warn!("Ignoring preset: {}", e);
+                None
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::session::{Config, ProfileConfig};
+    use crate::session::{Config, ProfileConfig, SandboxConfigOverride};
+
+    fn source_of(
+        scope: SettingsScope,
+        global: &Config,
+        profile: &ProfileConfig,
+        repo: &ProfileConfig,
+        key: FieldKey,
+    ) -> FieldSource {
+        let base = merge_configs(global.clone(), profile);
+        let (fields_global, fields_profile) = match scope {
+            SettingsScope::Repo => (&base, repo),
+            _ => (global, profile),
+        };
+        let index = build_fields_for_category(
+            SettingsCategory::Sandbox,
+            scope,
+            fields_global,
+            fields_profile,
+        )
+        .iter()
+        .position(|f| f.key == key)
+        .unwrap();
+        resolve_field_sources(SettingsCategory::Sandbox, scope, global, profile, repo)[index]
+    }
+
+    #[test]
+    fn test_field_sources_follow_config_layers() {
+        let mut global = Config::default();
+        let mut profile = ProfileConfig::default();
+        let mut repo = ProfileConfig::default();
+        let key = FieldKey::DefaultImage;
+
+        for scope in [
+            SettingsScope::Global,
+            SettingsScope::Profile,
+            SettingsScope::Repo,
+        ] {
+            assert_eq!(
+                source_of(scope, &global, &profile, &repo, key),
+                FieldSource::Default
+            );
+        }
+
+        global.sandbox.default_image = "global-image:latest".to_string();
+        for scope in [
+            SettingsScope::Global,
+            SettingsScope::Profile,
+            SettingsScope::Repo,
+        ] {
+            assert_eq!(
+                source_of(scope, &global, &profile, &repo, key),
+                FieldSource::Global
+            );
+        }
+
+        profile.sandbox = Some(SandboxConfigOverride {
+            default_image: Some("profile-image:latest".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            source_of(SettingsScope::Global, &global, &profile, &repo, key),
+            FieldSource::Global
+        );
+        assert_eq!(
+            source_of(SettingsScope::Profile, &global, &profile, &repo, key),
+            FieldSource::Profile
+        );
+        assert_eq!(
+            source_of(SettingsScope::Repo, &global, &profile, &repo, key),
+            FieldSource::Profile
+        );
+
+        repo.sandbox = Some(SandboxConfigOverride {
+            default_image: Some("repo-image:latest".to_string()),
+            ..Default::default()
+        });
+        assert_eq!(
+            source_of(SettingsScope::Repo, &global, &profile, &repo, key),
+            FieldSource::Repo
+        );
+    }
+
+    #[test]
+    fn test_status_patterns_field_round_trips_profile_override() {
+        let mut global = Config::default();
+        global.status_patterns.insert(
+            "claude".to_string(),
+            StatusPatternsConfig {
+                waiting: vec!["^Allow\\?".to_string()],
+                running: Vec::new(),
+            },
+        );
+        let mut profile = ProfileConfig::default();
+        let field = |global: &Config, profile: &ProfileConfig| {
+            build_fields_for_category(
+                SettingsCategory::Session,
+                SettingsScope::Profile,
+                global,
+                profile,
+            )
+            .into_iter()
+            .find(|f| f.key == FieldKey::StatusPatterns)
+            .unwrap()
+        };
+
+        let mut f = field(&global, &profile);
+        assert_eq!(
+            f.value,
+            FieldValue::List(vec!["claude.waiting=^Allow\\?".into()])
+        );
+        assert!(!f.has_override);
+
+        f.value = FieldValue::List(vec![
+            "codex.running=^Compacting".into(),
+            "codex.waiting=a=b".into(),
+        ]);
+        assert!(f.validate().is_ok());
+        apply_field_to_config(&f, SettingsScope::Profile, &mut global, &mut profile);
+        let codex = &profile.status_patterns.as_ref().unwrap()["codex"];
+        assert_eq!(codex.waiting, vec!["a=b"]);
+        assert_eq!(codex.running, vec!["^Compacting"]);
+        assert!(field(&global, &profile).has_override);
+
+        f.value = field(&global, &ProfileConfig::default()).value;
+        apply_field_to_config(&f, SettingsScope::Profile, &mut global, &mut profile);
+        assert!(profile.status_patterns.is_none());
+    }
+
+    #[test]
+    fn test_status_patterns_field_rejects_bad_entries() {
+        let field = |entry: &str| SettingField {
+            key: FieldKey::StatusPatterns,
+            label: "",
+            description: "",
+            value: FieldValue::List(vec![entry.to_string()]),
+            category: SettingsCategory::Session,
+            has_override: false,
+        };
+        assert!(field("codex.waiting=^> ").validate().is_ok());
+        assert!(field("codex=^> ").validate().is_err());
+        assert!(field("codex.idle=^> ").validate().is_err());
+        assert!(field(".waiting=^> ").validate().is_err());
+        assert!(field("codex.waiting=(unclosed").validate().is_err());
+    }
+
+    #[test]
+    fn test_presets_field_round_trips_profile_override() {
+        let mut global = Config::default();
+        global.presets.insert(
+            "fast".to_string(),
+            PresetConfig {
+                command: Some("claude --model haiku".to_string()),
+                ..Default::default()
+            },
+        );
+        let mut profile = ProfileConfig::default();
+        let field = |global: &Config, profile: &ProfileConfig| {
+            build_fields_for_category(
+                SettingsCategory::Session,
+                SettingsScope::Profile,
+                global,
+                profile,
+            )
+            .into_iter()
+            .find(|f| f.key == FieldKey::Presets)
+            .unwrap()
+        };
+
+        let mut f = field(&global, &profile);
+        assert_eq!(
+            f.value,
+            FieldValue::List(vec![r#"fast = { command = "claude --model haiku" }"#.into()])
+        );
+        assert!(!f.has_override);
+
+        f.value = FieldValue::List(vec![
+            r#""my box" = { sandbox = true, environment = ["GH_TOKEN"] }"#.into(),
+        ]);
+        assert!(f.validate().is_ok());
+        apply_field_to_config(&f, SettingsScope::Profile, &mut global, &mut profile);
+        let presets = profile.presets.as_ref().unwrap();
+        assert_eq!(presets["my box"].sandbox, Some(true));
+        assert_eq!(presets["my box"].environment, vec!["GH_TOKEN"]);
+        let f = field(&global, &profile);
+        assert!(f.has_override);
+        assert_eq!(
+            f.value,
+            FieldValue::List(vec![
+                r#""my box" = { environment = ["GH_TOKEN"], sandbox = true }"#.into()
+            ])
+        );
+    }
+
+    #[test]
+    fn test_presets_field_rejects_bad_entries() {
+        let field = |entry: &str| SettingField {
+            key: FieldKey::Presets,
+            label: "",
+            description: "",
+            value: FieldValue::List(vec![entry.to_string()]),
+            category: SettingsCategory::Session,
+            has_override: false,
+        };
+        assert!(field(r#"fast = { tool = "codex" }"#).validate().is_ok());
+        assert!(field(r#"fast = { tool = "emacs" }"#).validate().is_err());
+        assert!(field(r#"fast = { command = "vim" }"#).validate().is_err());
+        assert!(field("fast").validate().is_err());
+        assert!(field("a = {}\nb = {}").validate().is_err());
+    }
+
+    #[test]
+    fn test_repo_scope_lists_only_sections_repo_config_has() {
+        let global = Config::default();
+        let repo = ProfileConfig::default();
+        let keys = |category| {
+            build_fields_for_category(category, SettingsScope::Repo, &global, &repo)
+                .iter()
+                .map(|f| f.key)
+                .collect::<Vec<_>>()
+        };
+
+        assert!(keys(SettingsCategory::Ui).is_empty());
+        assert!(keys(SettingsCategory::Logging).is_empty());
+        assert!(keys(SettingsCategory::Events).is_empty());
+        assert!(keys(SettingsCategory::Session).contains(&FieldKey::DefaultGroup));
+        assert!(keys(SettingsCategory::Sandbox).contains(&FieldKey::Environment));
+        assert!(keys(SettingsCategory::Worktree).contains(&FieldKey::PathTemplate));
+        assert!(keys(SettingsCategory::Tmux).contains(&FieldKey::HistoryLimit));
+    }
 
     #[test]
     fn test_profile_field_has_no_override_after_global_change() {
@@ -1294,6 +2597,65 @@ mod tests {
         );
     }
 
+    fn field(key: FieldKey, value: FieldValue) -> SettingField {
+        SettingField {
+            key,
+            label: "",
+            description: "",
+            value,
+            category: SettingsCategory::Sandbox,
+            has_override: false,
+        }
+    }
+
+    #[test]
+    fn test_validate_rejects_bad_limits_and_templates() {
+        let text = |v: &str| FieldValue::OptionalText(Some(v.to_string()));
+        assert!(field(FieldKey::CpuLimit, text("1.5")).validate().is_ok());
+        assert!(field(FieldKey::CpuLimit, text("lots")).validate().is_err());
+        assert!(field(FieldKey::MemoryLimit, text("2g")).validate().is_ok());
+        assert!(field(FieldKey::MemoryLimit, text("2 gigs"))
+            .validate()
+            .is_err());
+
+        let template = |v: &str| FieldValue::Text(v.to_string());
+        assert!(field(FieldKey::PathTemplate, template("../wt/{branch}"))
+            .validate()
+            .is_ok());
+        assert!(field(FieldKey::PathTemplate, template("../wt/{name}"))
+            .validate()
+            .is_err());
+        assert!(field(FieldKey::BareRepoPathTemplate, template("./wt"))
+            .validate()
+            .is_err());
+    }
+
+    #[test]
+    fn test_default_config_fields_are_valid() {
+        let global = Config::default();
+        let profile = ProfileConfig::default();
+        for category in [
+            SettingsCategory::Updates,
+            SettingsCategory::Worktree,
+            SettingsCategory::Sandbox,
+            SettingsCategory::Tmux,
+            SettingsCategory::Ui,
+            SettingsCategory::Session,
+            SettingsCategory::Events,
+            SettingsCategory::Hooks,
+        ] {
+            for field in
+                build_fields_for_category(category, SettingsScope::Global, &global, &profile)
+            {
+                assert!(
+                    field.validate().is_ok(),
+                    "Default value of {:?} fails validation",
+                    field.key
+                );
+            }
+        }
+    }
+
     #[test]
     fn test_default_tool_options_include_all_supported_tools() {
         use crate::session::SUPPORTED_TOOLS;
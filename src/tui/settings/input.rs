@@ -1,10 +1,14 @@
 //! Input handling for the settings view
 
+use std::path::PathBuf;
+
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 use tui_input::backend::crossterm::EventHandler;
 use tui_input::Input;
 
-use super::{FieldKey, FieldValue, ListEditState, SettingsFocus, SettingsScope, SettingsView};
+use super::{
+    ConfigSnapshot, FieldKey, FieldValue, ListEditState, SettingsFocus, SettingsScope, SettingsView,
+};
 
 /// Result of handling a key event in the settings view
 pub enum SettingsAction {
@@ -14,6 +18,8 @@ pub enum SettingsAction {
     Close,
     /// Close was cancelled due to unsaved changes
     UnsavedChangesWarning,
+    /// Open the config file for the current scope in an external editor
+    EditConfig(PathBuf),
 }
 
 impl SettingsView {
@@ -35,9 +41,7 @@ impl SettingsView {
         match (key.code, key.modifiers) {
             // Save
             (KeyCode::Char('s'), KeyModifiers::CONTROL) => {
-                if let Err(e) = self.save() {
-                    self.error_message = Some(format!("Failed to save: {}", e));
-                }
+                self.save_and_report();
                 SettingsAction::Continue
             }
 
@@ -182,6 +186,22 @@ impl SettingsView {
                 SettingsAction::Continue
             }
 
+            // Edit the current scope's config file directly
+            (KeyCode::Char('e'), _) => {
+                if self.has_changes {
+                    self.error_message =
+                        Some("Save or discard changes before editing the file".to_string());
+                    return SettingsAction::Continue;
+                }
+                match self.scope_config_path() {
+                    Ok(path) => SettingsAction::EditConfig(path),
+                    Err(e) => {
+                        self.error_message = Some(format!("Cannot edit config: {}", e));
+                        SettingsAction::Continue
+                    }
+                }
+            }
+
             // Reset field to default (clear profile/repo override)
             (KeyCode::Char('r'), _) => {
                 if (self.scope == SettingsScope::Profile || self.scope == SettingsScope::Repo)
@@ -273,10 +293,8 @@ impl SettingsView {
             KeyCode::Esc => {
                 self.list_edit_state = None;
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if state.selected_index > 0 {
-                    state.selected_index -= 1;
-                }
+            KeyCode::Up | KeyCode::Char('k') if state.selected_index > 0 => {
+                state.selected_index -= 1;
             }
             KeyCode::Down | KeyCode::Char('j') => {
                 if let FieldValue::List(items) = &self.fields[self.selected_field].value {
@@ -385,6 +403,8 @@ impl SettingsView {
         };
 
         match key {
+            // App (global only, nothing to clear)
+            FieldKey::Offline => {}
             // Updates
             FieldKey::CheckEnabled => {
                 if let Some(ref mut u) = config.updates {
@@ -412,6 +432,11 @@ impl SettingsView {
                     w.bare_repo_path_template = None;
                 }
             }
+            FieldKey::BranchSeparator => {
+                if let Some(ref mut w) = config.worktree {
+                    w.branch_separator = None;
+                }
+            }
             FieldKey::WorktreeAutoCleanup => {
                 if let Some(ref mut w) = config.worktree {
                     w.auto_cleanup = None;
@@ -454,12 +479,86 @@ impl SettingsView {
                     t.mouse = None;
                 }
             }
+            FieldKey::HistoryLimit => {
+                if let Some(ref mut t) = config.tmux {
+                    t.history_limit = None;
+                }
+            }
+            // UI
+            FieldKey::TickMs => {
+                if let Some(ref mut u) = config.ui {
+                    u.tick_ms = None;
+                }
+            }
+            FieldKey::StatusRefreshMs => {
+                if let Some(ref mut u) = config.ui {
+                    u.status_refresh_ms = None;
+                }
+            }
+            FieldKey::StatusDebouncePolls => {
+                if let Some(ref mut u) = config.ui {
+                    u.status_debounce_polls = None;
+                }
+            }
+            FieldKey::RememberNewSession => {
+                if let Some(ref mut u) = config.ui {
+                    u.remember_new_session = None;
+                }
+            }
+            FieldKey::ResumeLastProfile => {
+                if let Some(ref mut u) = config.ui {
+                    u.resume_last_profile = None;
+                }
+            }
+            // Logging
+            FieldKey::LoggingEnabled => {
+                if let Some(ref mut l) = config.logging {
+                    l.enabled = None;
+                }
+            }
+            FieldKey::LogLevel => {
+                if let Some(ref mut l) = config.logging {
+                    l.level = None;
+                }
+            }
+            FieldKey::LogMaxFiles => {
+                if let Some(ref mut l) = config.logging {
+                    l.max_files = None;
+                }
+            }
             // Session
             FieldKey::DefaultTool => {
                 if let Some(ref mut s) = config.session {
                     s.default_tool = None;
                 }
             }
+            FieldKey::DefaultGroup => {
+                if let Some(ref mut s) = config.session {
+                    s.default_group = None;
+                }
+            }
+            FieldKey::DefaultPath => {
+                if let Some(ref mut s) = config.session {
+                    s.default_path = None;
+                }
+            }
+            FieldKey::CreateDir => {
+                if let Some(ref mut s) = config.session {
+                    s.create_dir = None;
+                }
+            }
+            FieldKey::TitleTemplate => {
+                if let Some(ref mut s) = config.session {
+                    s.title_template = None;
+                }
+            }
+            FieldKey::TitleWords => {
+                if let Some(ref mut s) = config.session {
+                    s.title_words = None;
+                }
+            }
+            FieldKey::StatusPatterns => config.status_patterns = None,
+            FieldKey::Presets => config.presets = None,
             FieldKey::SandboxEnabledByDefault => {
                 if let Some(ref mut s) = config.sandbox {
                     s.enabled_by_default = None;
@@ -475,6 +574,11 @@ impl SettingsView {
                     s.default_terminal_mode = None;
                 }
             }
+            FieldKey::AuthVolumeScope => {
+                if let Some(ref mut s) = config.sandbox {
+                    s.auth_volume_scope = None;
+                }
+            }
             FieldKey::ExtraVolumes => {
                 if let Some(ref mut s) = config.sandbox {
                     s.extra_volumes = None;
@@ -490,6 +594,51 @@ impl SettingsView {
                     s.mount_ssh = None;
                 }
             }
+            FieldKey::ReadOnlyRootfs => {
+                if let Some(ref mut s) = config.sandbox {
+                    s.read_only_rootfs = None;
+                }
+            }
+            FieldKey::Hardened => {
+                if let Some(ref mut s) = config.sandbox {
+                    s.hardened = None;
+                }
+            }
+            FieldKey::CapDrop => {
+                if let Some(ref mut s) = config.sandbox {
+                    s.cap_drop = None;
+                }
+            }
+            FieldKey::CapAdd => {
+                if let Some(ref mut s) = config.sandbox {
+                    s.cap_add = None;
+                }
+            }
+            FieldKey::SecurityOpt => {
+                if let Some(ref mut s) = config.sandbox {
+                    s.security_opt = None;
+                }
+            }
+            FieldKey::DockerRetries => {
+                if let Some(ref mut s) = config.sandbox {
+                    s.docker_retries = None;
+                }
+            }
+            FieldKey::AutoRestart => {
+                if let Some(ref mut s) = config.sandbox {
+                    s.auto_restart = None;
+                }
+            }
+            FieldKey::StopOnDetach => {
+                if let Some(ref mut s) = config.sandbox {
+                    s.stop_on_detach = None;
+                }
+            }
+            FieldKey::MaxConcurrentContainers => {
+                if let Some(ref mut s) = config.sandbox {
+                    s.max_concurrent_containers = None;
+                }
+            }
             FieldKey::CpuLimit => {
                 if let Some(ref mut s) = config.sandbox {
                     s.cpu_limit = None;
@@ -500,6 +649,11 @@ impl SettingsView {
                     s.memory_limit = None;
                 }
             }
+            FieldKey::Network => {
+                if let Some(ref mut s) = config.sandbox {
+                    s.network = None;
+                }
+            }
             // Sound
             FieldKey::SoundEnabled => {
                 if let Some(ref mut s) = config.sound {
@@ -536,6 +690,17 @@ impl SettingsView {
                     s.on_error = None;
                 }
             }
+            // Events
+            FieldKey::EventWebhook => {
+                if let Some(ref mut e) = config.events {
+                    e.webhook = None;
+                }
+            }
+            FieldKey::EventCommand => {
+                if let Some(ref mut e) = config.events {
+                    e.command = None;
+                }
+            }
             // Hooks
             FieldKey::HookOnCreate => {
                 if let Some(ref mut h) = config.hooks {
@@ -556,7 +721,7 @@ impl SettingsView {
             ));
         }
 
-        self.has_changes = true;
+        self.refresh_dirty();
     }
 
     /// Force close without saving
@@ -580,6 +745,11 @@ impl SettingsView {
             .as_ref()
             .map(crate::session::repo_config_to_profile)
             .unwrap_or_default();
+        self.saved = ConfigSnapshot::capture(
+            &self.global_config,
+            &self.profile_config,
+            self.repo_config.as_ref(),
+        );
         self.has_changes = false;
         self.rebuild_fields();
         Ok(())
@@ -4,17 +4,40 @@ mod fields;
 mod input;
 mod render;
 
+use std::path::{Path, PathBuf};
+
 use tui_input::Input;
 
+use crate::session::config::get_config_path;
+use crate::session::profile_config::get_profile_config_path;
 use crate::session::{
-    load_profile_config, load_repo_config, merge_configs, profile_to_repo_config,
+    load_profile_config, load_repo_config, merge_configs, profile_to_repo_config, repo_config_path,
     repo_config_to_profile, save_config, save_profile_config, save_repo_config, Config,
     ProfileConfig, RepoConfig,
 };
 
-pub use fields::{FieldKey, FieldValue, SettingField, SettingsCategory};
+pub use fields::{FieldKey, FieldSource, FieldValue, SettingField, SettingsCategory};
 pub use input::SettingsAction;
 
+/// Serialized configs, compared to tell whether edits are unsaved. Editing a
+/// value and then changing it back leaves the view clean.
+#[derive(Debug, Default, PartialEq)]
+struct ConfigSnapshot {
+    global: Option<toml::Value>,
+    profile: Option<toml::Value>,
+    repo: Option<toml::Value>,
+}
+
+impl ConfigSnapshot {
+    fn capture(global: &Config, profile: &ProfileConfig, repo: Option<&RepoConfig>) -> Self {
+        Self {
+            global: toml::Value::try_from(global).ok(),
+            profile: toml::Value::try_from(profile).ok(),
+            repo: repo.and_then(|r| toml::Value::try_from(r).ok()),
+        }
+    }
+}
+
 /// Which scope of settings is being edited
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum SettingsScope {
@@ -93,9 +116,12 @@ pub struct SettingsView {
     /// Last known viewport height for the fields panel (set during render)
     pub(super) fields_viewport_height: u16,
 
-    /// Whether there are unsaved changes
+    /// Whether any config differs from `saved`
     pub(super) has_changes: bool,
 
+    /// The configs as last loaded or saved
+    saved: ConfigSnapshot,
+
     /// Error message to display
     pub(super) error_message: Option<String>,
 
@@ -124,10 +150,16 @@ impl SettingsView {
             SettingsCategory::Sandbox,
             SettingsCategory::Worktree,
             SettingsCategory::Updates,
+            SettingsCategory::App,
             SettingsCategory::Tmux,
+            SettingsCategory::Ui,
+            SettingsCategory::Logging,
             SettingsCategory::Sound,
+            SettingsCategory::Events,
         ];
 
+        let saved = ConfigSnapshot::capture(&global_config, &profile_config, repo_config.as_ref());
+
         let mut view = Self {
             profile: profile.to_string(),
             project_path,
@@ -147,6 +179,7 @@ impl SettingsView {
             fields_scroll_offset: 0,
             fields_viewport_height: 0,
             has_changes: false,
+            saved,
             error_message: None,
             success_message: None,
         };
@@ -158,7 +191,18 @@ impl SettingsView {
     /// Rebuild the fields list based on current category and scope
     pub(super) fn rebuild_fields(&mut self) {
         let category = self.categories[self.selected_category];
-        let (scope_for_fields, global_ref, profile_ref) = match self.scope {
+        let (scope_for_fields, global_ref, profile_ref) = self.field_sources();
+        self.fields =
+            fields::build_fields_for_category(category, scope_for_fields, global_ref, profile_ref);
+        if self.selected_field >= self.fields.len() {
+            self.selected_field = 0;
+        }
+        self.fields_scroll_offset = 0;
+    }
+
+    /// The scope and configs that fields are built from for the current scope
+    fn field_sources(&self) -> (SettingsScope, &Config, &ProfileConfig) {
+        match self.scope {
             SettingsScope::Global => (
                 SettingsScope::Global,
                 &self.global_config,
@@ -170,17 +214,93 @@ impl SettingsView {
                 &self.profile_config,
             ),
             SettingsScope::Repo => (
-                SettingsScope::Profile,
+                SettingsScope::Repo,
                 &self.resolved_base,
                 &self.repo_as_profile,
             ),
-        };
-        self.fields =
-            fields::build_fields_for_category(category, scope_for_fields, global_ref, profile_ref);
-        if self.selected_field >= self.fields.len() {
-            self.selected_field = 0;
         }
-        self.fields_scroll_offset = 0;
+    }
+
+    /// The config layer each field of the current category takes its value from
+    pub(super) fn current_field_sources(&self) -> Vec<FieldSource> {
+        fields::resolve_field_sources(
+            self.categories[self.selected_category],
+            self.scope,
+            &self.global_config,
+            &self.profile_config,
+            &self.repo_as_profile,
+        )
+    }
+
+    /// Path of the config file that the current scope edits
+    pub(super) fn scope_config_path(&self) -> anyhow::Result<PathBuf> {
+        match self.scope {
+            SettingsScope::Global => get_config_path(),
+            SettingsScope::Profile => get_profile_config_path(&self.profile),
+            SettingsScope::Repo => {
+                let project = self
+                    .project_path
+                    .as_deref()
+                    .ok_or_else(|| anyhow::anyhow!("No project selected"))?;
+                let path = repo_config_path(Path::new(project));
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                Ok(path)
+            }
+        }
+    }
+
+    /// Reload every config from disk after it was edited outside the view,
+    /// keeping the current scope and position. If a file no longer parses,
+    /// the previous values stay loaded and the error is shown instead.
+    pub fn reload(&mut self) {
+        let reloaded = self
+            .project_path
+            .as_deref()
+            .map_or(Ok(None), |p| load_repo_config(Path::new(p)))
+            .and_then(|_| Self::new(&self.profile, self.project_path.clone()));
+
+        match reloaded {
+            Ok(mut view) => {
+                view.scope = self.scope;
+                view.focus = self.focus;
+                view.selected_category = self.selected_category;
+                view.selected_field = self.selected_field;
+                view.rebuild_fields();
+                match view.first_invalid_field() {
+                    Some((_, _, error)) => view.error_message = Some(error),
+                    None => view.success_message = Some("Config reloaded".to_string()),
+                }
+                *self = view;
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Config not reloaded: {:#}", e));
+            }
+        }
+    }
+
+    /// Find the first invalid field across every category, as
+    /// (category index, field index, error)
+    fn first_invalid_field(&self) -> Option<(usize, usize, String)> {
+        let (scope, global, profile) = self.field_sources();
+        self.categories
+            .iter()
+            .enumerate()
+            .find_map(|(category_index, &category)| {
+                fields::build_fields_for_category(category, scope, global, profile)
+                    .iter()
+                    .enumerate()
+                    .find_map(|(field_index, field)| {
+                        field.validate().err().map(|e| {
+                            (
+                                category_index,
+                                field_index,
+                                format!("{} > {}: {}", category.label(), field.label, e),
+                            )
+                        })
+                    })
+            })
     }
 
     /// Ensure the selected field is visible within the given viewport height.
@@ -240,41 +360,66 @@ impl SettingsView {
                 self.repo_config = Some(profile_to_repo_config(&self.repo_as_profile));
             }
         }
-        self.has_changes = true;
+        self.refresh_dirty();
+    }
+
+    fn current_snapshot(&self) -> ConfigSnapshot {
+        ConfigSnapshot::capture(
+            &self.global_config,
+            &self.profile_config,
+            self.repo_config.as_ref(),
+        )
+    }
+
+    /// Recompute `has_changes` by comparing every config with its saved state
+    pub(super) fn refresh_dirty(&mut self) {
+        self.has_changes = self.current_snapshot() != self.saved;
     }
 
     /// Save the current configuration
     pub fn save(&mut self) -> anyhow::Result<()> {
-        // Validate all fields before saving
-        for field in &self.fields {
-            if let Err(e) = field.validate() {
-                self.error_message = Some(e);
-                return Ok(());
+        // Validate every category before saving, and jump to the first bad field
+        if let Some((category_index, field_index, error)) = self.first_invalid_field() {
+            if category_index != self.selected_category {
+                self.selected_category = category_index;
+                self.rebuild_fields();
             }
+            self.selected_field = field_index;
+            self.error_message = Some(error);
+            return Ok(());
         }
 
-        match self.scope {
-            SettingsScope::Global => {
-                save_config(&self.global_config)?;
-            }
-            SettingsScope::Profile => {
-                save_profile_config(&self.profile, &self.profile_config)?;
-            }
-            SettingsScope::Repo => {
-                if let (Some(ref project_path), Some(ref repo_config)) =
-                    (&self.project_path, &self.repo_config)
-                {
-                    save_repo_config(std::path::Path::new(project_path), repo_config)?;
-                }
+        // Save every scope that was edited, not only the one on screen
+        let current = self.current_snapshot();
+        if current.global != self.saved.global {
+            save_config(&self.global_config)?;
+        }
+        if current.profile != self.saved.profile {
+            save_profile_config(&self.profile, &self.profile_config)?;
+        }
+        if current.repo != self.saved.repo {
+            if let (Some(ref project_path), Some(ref repo_config)) =
+                (&self.project_path, &self.repo_config)
+            {
+                save_repo_config(std::path::Path::new(project_path), repo_config)?;
             }
         }
 
+        self.saved = current;
         self.has_changes = false;
         self.success_message = Some("Settings saved".to_string());
         self.error_message = None;
         Ok(())
     }
 
+    /// Save, showing any failure inline. Returns true once nothing is left unsaved.
+    pub fn save_and_report(&mut self) -> bool {
+        if let Err(e) = self.save() {
+            self.error_message = Some(format!("Failed to save: {}", e));
+        }
+        !self.has_changes
+    }
+
     /// Check if there are unsaved changes
     pub fn has_unsaved_changes(&self) -> bool {
         self.has_changes
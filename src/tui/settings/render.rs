@@ -12,7 +12,9 @@ use ratatui::{
 };
 use tui_input::Input;
 
-use super::{FieldValue, SettingsCategory, SettingsFocus, SettingsScope, SettingsView};
+use super::{
+    FieldSource, FieldValue, SettingsCategory, SettingsFocus, SettingsScope, SettingsView,
+};
 use crate::tui::styles::Theme;
 
 /// Detect if we're running over SSH
@@ -170,10 +172,10 @@ impl SettingsView {
         frame.render_widget(block, area);
 
         if self.fields.is_empty() {
-            let msg = if self.scope == SettingsScope::Repo {
-                "No repo-level settings for this category"
-            } else {
-                "No settings in this category"
+            let msg = match self.scope {
+                SettingsScope::Repo => "No repo-level settings for this category",
+                SettingsScope::Profile => "No profile-level settings for this category",
+                SettingsScope::Global => "No settings in this category",
             };
             let msg = Paragraph::new(msg).style(Style::default().fg(theme.dimmed));
             frame.render_widget(msg, inner);
@@ -230,6 +232,8 @@ impl SettingsView {
 
         let scroll_offset = self.fields_scroll_offset;
 
+        let sources = self.current_field_sources();
+
         // Render fields with scroll offset applied
         let mut y_pos = 0u16; // absolute position in content space
         for (i, field) in self.fields.iter().enumerate() {
@@ -257,7 +261,8 @@ impl SettingsView {
                 height: field_h.min(fields_viewport_height.saturating_sub(visible_y)),
             };
 
-            self.render_field(frame, field_area, field, i, is_selected, theme);
+            let source = sources.get(i).copied().unwrap_or(FieldSource::Default);
+            self.render_field(frame, field_area, field, source, i, is_selected, theme);
             y_pos += field_h + 1; // +1 for spacing
         }
 
@@ -308,23 +313,23 @@ impl SettingsView {
 
     pub(super) fn field_height(&self, field: &super::SettingField, index: usize) -> u16 {
         match &field.value {
-            FieldValue::List(items) => {
-                if self.list_edit_state.is_some() && index == self.selected_field {
-                    // label + description + header + items + add prompt
-                    1 + 1 + 1 + items.len() as u16 + 1
-                } else {
-                    1 + 1 + 1 // Label + description + summary
-                }
+            FieldValue::List(items)
+                if self.list_edit_state.is_some() && index == self.selected_field =>
+            {
+                // label + description + header + items + add prompt
+                1 + 1 + 1 + items.len() as u16 + 1
             }
-            _ => 1 + 1 + 1, // Label + description + value
+            _ => 1 + 1 + 1, // Label + description + value/summary
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn render_field(
         &self,
         frame: &mut Frame,
         area: Rect,
         field: &super::SettingField,
+        source: FieldSource,
         index: usize,
         is_selected: bool,
         theme: &Theme,
@@ -337,16 +342,16 @@ impl SettingsView {
             Style::default().fg(theme.text)
         };
 
-        // Show override indicator for profile scope
-        let override_indicator = if field.has_override && self.scope == SettingsScope::Profile {
-            Span::styled(" (override)", Style::default().fg(theme.accent))
+        // Show which layer the value comes from, highlighting overrides that
+        // `r` can reset in the current scope
+        let source_style = if field.has_override && self.scope != SettingsScope::Global {
+            Style::default().fg(theme.accent)
         } else {
-            Span::raw("")
+            Style::default().fg(theme.dimmed)
         };
-
         let label = Line::from(vec![
             Span::styled(field.label, label_style),
-            override_indicator,
+            Span::styled(format!(" ({})", source.label()), source_style),
         ]);
 
         frame.render_widget(Paragraph::new(label), area);
@@ -702,8 +707,10 @@ impl SettingsView {
             "Enter: confirm | Esc: cancel"
         } else if self.list_edit_state.is_some() {
             "a: add | d: delete | Enter: edit | Esc: close list"
+        } else if self.scope == SettingsScope::Global {
+            "Tab: switch scope | Arrow keys: navigate | Enter: edit | Space: toggle | e: edit file | Ctrl+s: save | Esc: close"
         } else {
-            "Tab: switch scope | Arrow keys: navigate | Enter: edit | Space: toggle | Ctrl+s: save | Esc: close"
+            "Tab: switch scope | Arrow keys: navigate | Enter: edit | Space: toggle | r: reset to inherited | e: edit file | Ctrl+s: save | Esc: close"
         };
 
         let help = Paragraph::new(help_text)
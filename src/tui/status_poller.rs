@@ -2,11 +2,30 @@
 //!
 //! This module provides non-blocking status updates for sessions by running
 //! tmux subprocess calls in a background thread.
+//!
+//! Status detection needs a pane capture, which is the expensive part of a
+//! poll. tmux already reports each window's last output time in the
+//! `list-sessions` call made once per cycle, so a session whose pane has
+//! not produced output since its last capture keeps its previous status
+//! without being captured again.
+//!
+//! Sandboxed sessions whose container exited can be restarted automatically;
+//! the exit reason itself comes from `Instance::update_status`.
 
+use std::collections::HashMap;
 use std::sync::mpsc;
 use std::thread;
+use std::time::{Duration, Instant};
 
-use crate::session::{Instance, Status};
+use crate::docker::DockerContainer;
+use crate::session::{Instance, Status, STOPPED_ON_DETACH};
+
+/// One round of status polling
+struct StatusRequest {
+    instances: Vec<Instance>,
+    /// Restart sessions whose container exited (`sandbox.auto_restart`)
+    auto_restart: bool,
+}
 
 /// Result of a status check for a single session
 #[derive(Debug)]
@@ -14,18 +33,179 @@ pub struct StatusUpdate {
     pub id: String,
     pub status: Status,
     pub last_error: Option<String>,
+    /// Claude Code conversation currently used by the session, if known
+    pub claude_session_id: Option<String>,
+}
+
+/// What a session looked like the last time its pane was captured
+#[derive(Debug, Clone, Copy)]
+struct Snapshot {
+    /// tmux `window_activity` (unix seconds) when the capture happened
+    activity: i64,
+    /// Unix seconds at which the capture started
+    captured_at: i64,
+    status: Status,
+}
+
+/// Whether the previous capture still describes a session whose current
+/// tmux activity time is `activity`. Activity has one-second resolution, so
+/// output in the same second as the capture forces another capture.
+fn can_reuse(snapshot: &Snapshot, activity: i64) -> bool {
+    snapshot.activity == activity
+        && activity < snapshot.captured_at
+        && !matches!(snapshot.status, Status::Starting | Status::Error)
+}
+
+/// Holds back status changes until they have been seen in enough consecutive
+/// polls, so a pane caught mid-redraw doesn't flip the shown status (and play
+/// its sound) for a single poll. Changes into `Error` are never held back.
+#[derive(Debug)]
+pub struct StatusDebouncer {
+    required_polls: u64,
+    /// Status each session is moving towards, and how many polls in a row saw it
+    pending: HashMap<String, (Status, u64)>,
+}
+
+impl StatusDebouncer {
+    pub fn new(required_polls: u64) -> Self {
+        Self {
+            required_polls,
+            pending: HashMap::new(),
+        }
+    }
+
+    pub fn set_required_polls(&mut self, required_polls: u64) {
+        self.required_polls = required_polls;
+    }
+
+    /// Status to show for session `id`, given the status currently shown and
+    /// the one just polled
+    pub fn settle(&mut self, id: &str, shown: Status, polled: Status) -> Status {
+        if polled == shown || polled == Status::Error || self.required_polls <= 1 {
+            self.pending.remove(id);
+            return polled;
+        }
+
+        let seen = match self.pending.get(id) {
+            Some(&(status, count)) if status == polled => count + 1,
+            _ => 1,
+        };
+        if seen >= self.required_polls {
+            self.pending.remove(id);
+            polled
+        } else {
+            self.pending.insert(id.to_string(), (polled, seen));
+            shown
+        }
+    }
+}
+
+/// How long a container inspection answers for an errored session
+const HEALTH_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Minimum time between automatic restarts of one session, so an agent that
+/// dies on startup isn't restarted in a tight loop
+const MIN_RESTART_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Container health of errored sandboxed sessions, for automatic restarts
+#[derive(Default)]
+struct ContainerHealth {
+    /// When each session's container was last inspected, and whether it had exited
+    checked: HashMap<String, (Instant, bool)>,
+    /// When each session was last restarted automatically
+    restarted: HashMap<String, Instant>,
+}
+
+impl ContainerHealth {
+    fn forget_missing(&mut self, instances: &[Instance]) {
+        let known = |id: &String| instances.iter().any(|inst| &inst.id == id);
+        self.checked.retain(|id, _| known(id));
+        self.restarted.retain(|id, _| known(id));
+    }
+
+    /// Whether the session's container ran and stopped, inspecting it at
+    /// most once per `HEALTH_CHECK_INTERVAL`
+    fn container_exited(&mut self, session_id: &str) -> bool {
+        if let Some(&(checked_at, exited)) = self.checked.get(session_id) {
+            if checked_at.elapsed() < HEALTH_CHECK_INTERVAL {
+                return exited;
+            }
+        }
+
+        let exited = DockerContainer::from_session_id(session_id)
+            .inspect_state()
+            .ok()
+            .flatten()
+            .is_some_and(|state| state.has_exited());
+        self.checked
+            .insert(session_id.to_string(), (Instant::now(), exited));
+        exited
+    }
+
+    fn may_restart(&self, session_id: &str) -> bool {
+        self.restarted
+            .get(session_id)
+            .map_or(true, |at| at.elapsed() >= MIN_RESTART_INTERVAL)
+    }
+
+    /// Restart an errored sandboxed session if its container exited and it
+    /// wasn't restarted too recently. Containers stopped on detach stay
+    /// stopped until the session is attached again.
+    fn check(&mut self, inst: &mut Instance, auto_restart: bool) {
+        if inst.status != Status::Error
+            || !inst.is_sandboxed()
+            || inst.last_error.as_deref() == Some(STOPPED_ON_DETACH)
+        {
+            self.checked.remove(&inst.id);
+            return;
+        }
+        if !auto_restart || !self.may_restart(&inst.id) || !self.container_exited(&inst.id) {
+            return;
+        }
+        self.restarted.insert(inst.id.clone(), Instant::now());
+        self.checked.remove(&inst.id);
+
+        let reason = inst
+            .last_error
+            .take()
+            .unwrap_or_else(|| "container exited".to_string());
+        tracing::warn!("Restarting session '{}' after {}", inst.title, reason);
+        if let Err(e) = inst.restart_with_size(crate::terminal::get_size()) {
+            inst.last_error = Some(format!("{}; restart failed: {}", reason, e));
+        }
+    }
+}
+
+/// Current Claude conversation for a session running Claude on the host.
+/// Sandboxed sessions keep their conversations inside the container.
+/// `claimed` maps conversation ids to the sessions already using them, so a
+/// session doesn't pick up a conversation of another one in the same
+/// directory.
+fn claude_session_id_for(
+    inst: &Instance,
+    projects_dir: &std::path::Path,
+    claimed: &HashMap<String, String>,
+) -> Option<String> {
+    if inst.tool != "claude" || inst.is_sandboxed() {
+        return None;
+    }
+    let started = inst.tmux_session().ok()?.created()?;
+    let since = std::time::UNIX_EPOCH + Duration::from_secs(started.max(0) as u64);
+    crate::session::claude::latest_session_id(projects_dir, &inst.project_path, since, |id| {
+        claimed.get(id).is_some_and(|owner| owner != &inst.id)
+    })
 }
 
 /// Background thread that polls session status without blocking the UI
 pub struct StatusPoller {
-    request_tx: mpsc::Sender<Vec<Instance>>,
+    request_tx: mpsc::Sender<StatusRequest>,
     result_rx: mpsc::Receiver<Vec<StatusUpdate>>,
     _handle: thread::JoinHandle<()>,
 }
 
 impl StatusPoller {
     pub fn new() -> Self {
-        let (request_tx, request_rx) = mpsc::channel::<Vec<Instance>>();
+        let (request_tx, request_rx) = mpsc::channel::<StatusRequest>();
         let (result_tx, result_rx) = mpsc::channel::<Vec<StatusUpdate>>();
 
         let handle = thread::spawn(move || {
@@ -40,21 +220,71 @@ impl StatusPoller {
     }
 
     fn polling_loop(
-        request_rx: mpsc::Receiver<Vec<Instance>>,
+        request_rx: mpsc::Receiver<StatusRequest>,
         result_tx: mpsc::Sender<Vec<StatusUpdate>>,
     ) {
-        while let Ok(instances) = request_rx.recv() {
+        let mut snapshots: HashMap<String, Snapshot> = HashMap::new();
+        let mut health = ContainerHealth::default();
+
+        while let Ok(StatusRequest {
+            instances,
+            auto_restart,
+        }) = request_rx.recv()
+        {
             crate::tmux::refresh_session_cache();
+            let claude_projects = crate::session::claude::projects_dir();
+            // Forget sessions that were deleted since the last cycle
+            snapshots.retain(|id, _| instances.iter().any(|inst| &inst.id == id));
+            health.forget_missing(&instances);
+            let claimed: HashMap<String, String> = instances
+                .iter()
+                .filter_map(|inst| Some((inst.claude_session_id.clone()?, inst.id.clone())))
+                .collect();
 
             let updates: Vec<StatusUpdate> = instances
                 .into_iter()
                 .map(|mut inst| {
-                    inst.update_status();
+                    let activity = inst.tmux_session().ok().and_then(|s| s.activity());
+                    let reusable = match (activity, snapshots.get(&inst.id)) {
+                        (Some(activity), Some(snapshot)) => can_reuse(snapshot, activity),
+                        _ => false,
+                    };
+
+                    if reusable {
+                        inst.status = snapshots[&inst.id].status;
+                    } else {
+                        let captured_at = chrono::Utc::now().timestamp();
+                        inst.update_status();
+                        health.check(&mut inst, auto_restart);
+                        // A new conversation produces output, so checking on
+                        // capture is enough to notice it
+                        if let Some(dir) = claude_projects.as_deref() {
+                            if let Some(id) = claude_session_id_for(&inst, dir, &claimed) {
+                                inst.claude_session_id = Some(id);
+                            }
+                        }
+                        match activity {
+                            Some(activity) => {
+                                snapshots.insert(
+                                    inst.id.clone(),
+                                    Snapshot {
+                                        activity,
+                                        captured_at,
+                                        status: inst.status,
+                                    },
+                                );
+                            }
+                            None => {
+                                snapshots.remove(&inst.id);
+                            }
+                        }
+                    }
 
                     StatusUpdate {
                         id: inst.id,
                         status: inst.status,
                         last_error: inst.last_error,
+                        claude_session_id: inst.claude_session_id,
                     }
                 })
                 .collect();
@@ -67,8 +297,12 @@ impl StatusPoller {
     }
 
     /// Request a status refresh for all given instances (non-blocking).
-    pub fn request_refresh(&self, instances: Vec<Instance>) {
-        let _ = self.request_tx.send(instances);
+    /// With `auto_restart`, sessions whose container exited are restarted.
+    pub fn request_refresh(&self, instances: Vec<Instance>, auto_restart: bool) {
+        let _ = self.request_tx.send(StatusRequest {
+            instances,
+            auto_restart,
+        });
     }
 
     /// Try to receive status updates without blocking.
@@ -83,3 +317,213 @@ impl Default for StatusPoller {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(activity: i64, captured_at: i64, status: Status) -> Snapshot {
+        Snapshot {
+            activity,
+            captured_at,
+            status,
+        }
+    }
+
+    #[test]
+    fn test_reuses_status_when_pane_is_quiet() {
+        assert!(can_reuse(&snapshot(100, 105, Status::Idle), 100));
+        assert!(can_reuse(&snapshot(100, 105, Status::Waiting), 100));
+    }
+
+    #[test]
+    fn test_recaptures_after_new_output() {
+        assert!(!can_reuse(&snapshot(100, 105, Status::Idle), 106));
+    }
+
+    fn kill_session(name: &str) {
+        let _ = std::process::Command::new("tmux")
+            .args(["kill-session", "-t", name])
+            .output();
+    }
+
+    /// Kills the named tmux session when dropped, so a failing test
+    /// doesn't leave it behind for the next run
+    struct KillOnDrop(String);
+
+    impl Drop for KillOnDrop {
+        fn drop(&mut self) {
+            kill_session(&self.0);
+        }
+    }
+
+    /// The session window's activity time, straight from tmux rather than
+    /// the shared session cache, which other tests refresh concurrently
+    fn window_activity(name: &str) -> i64 {
+        let output = std::process::Command::new("tmux")
+            .args(["display-message", "-p", "-t", name, "#{window_activity}"])
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "session {} should exist", name);
+        String::from_utf8_lossy(&output.stdout)
+            .trim()
+            .parse()
+            .unwrap()
+    }
+
+    #[test]
+    #[serial_test::serial]
+    fn test_output_without_input_forces_recapture() {
+        if !crate::tmux::is_tmux_available() {
+            eprintln!("Skipping test: tmux not available");
+            return;
+        }
+        // Session ids are cut to 8 characters, so the pid goes first (in hex)
+        let id = format!("{:x}pollout", std::process::id());
+        let name = crate::tmux::Session::generate_name(&id, "poller-output");
+        // Clear out a leftover from an interrupted run that had the same pid
+        kill_session(&name);
+        crate::tmux::refresh_session_cache();
+        crate::tmux::Session::from_name(&name)
+            .create("/tmp", Some("sh -c 'while :; do echo tick; sleep 1; done'"))
+            .unwrap();
+        let guard = KillOnDrop(name.clone());
+
+        let before = window_activity(&name);
+        // Nobody types into the pane, it only prints
+        std::thread::sleep(Duration::from_millis(2500));
+        let after = window_activity(&name);
+        drop(guard);
+
+        assert!(after > before, "output should advance the activity time");
+        let captured = snapshot(before, before + 1, Status::Running);
+        assert!(!can_reuse(&captured, after));
+    }
+
+    #[test]
+    fn test_recaptures_when_output_shares_capture_second() {
+        assert!(!can_reuse(&snapshot(105, 105, Status::Running), 105));
+    }
+
+    #[test]
+    fn test_recaptures_transient_statuses() {
+        assert!(!can_reuse(&snapshot(100, 105, Status::Starting), 100));
+        assert!(!can_reuse(&snapshot(100, 105, Status::Error), 100));
+    }
+
+    /// Feed `polled` statuses through a debouncer, returning what is shown after each
+    fn shown_after(required: u64, start: Status, polled: &[Status]) -> Vec<Status> {
+        let mut debouncer = StatusDebouncer::new(required);
+        let mut shown = start;
+        polled
+            .iter()
+            .map(|&status| {
+                shown = debouncer.settle("s", shown, status);
+                shown
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_debouncer_suppresses_flapping() {
+        use Status::{Running, Waiting};
+        let shown = shown_after(2, Running, &[Waiting, Running, Waiting, Running, Running]);
+        assert_eq!(shown, vec![Running; 5]);
+    }
+
+    #[test]
+    fn test_debouncer_commits_persistent_change() {
+        use Status::{Idle, Running, Waiting};
+        let shown = shown_after(2, Running, &[Waiting, Waiting, Waiting]);
+        assert_eq!(shown, vec![Running, Waiting, Waiting]);
+
+        let shown = shown_after(3, Idle, &[Running, Running, Running]);
+        assert_eq!(shown, vec![Idle, Idle, Running]);
+    }
+
+    #[test]
+    fn test_debouncer_restarts_count_when_target_changes() {
+        use Status::{Idle, Running, Waiting};
+        let shown = shown_after(2, Idle, &[Running, Waiting, Waiting]);
+        assert_eq!(shown, vec![Idle, Idle, Waiting]);
+    }
+
+    #[test]
+    fn test_debouncer_shows_errors_immediately() {
+        use Status::{Error, Running};
+        assert_eq!(shown_after(3, Running, &[Error]), vec![Error]);
+    }
+
+    #[test]
+    fn test_debouncer_disabled_with_one_poll() {
+        use Status::{Running, Waiting};
+        let shown = shown_after(1, Running, &[Waiting, Running]);
+        assert_eq!(shown, vec![Waiting, Running]);
+    }
+
+    #[test]
+    fn test_debouncer_tracks_sessions_separately() {
+        let mut debouncer = StatusDebouncer::new(2);
+        assert_eq!(
+            debouncer.settle("a", Status::Running, Status::Waiting),
+            Status::Running
+        );
+        assert_eq!(
+            debouncer.settle("b", Status::Running, Status::Waiting),
+            Status::Running
+        );
+        assert_eq!(
+            debouncer.settle("a", Status::Running, Status::Waiting),
+            Status::Waiting
+        );
+    }
+
+    #[test]
+    fn test_restart_is_rate_limited() {
+        let mut health = ContainerHealth::default();
+        assert!(health.may_restart("s"));
+
+        health.restarted.insert("s".to_string(), Instant::now());
+        assert!(!health.may_restart("s"));
+        assert!(health.may_restart("other"));
+
+        let long_ago = Instant::now() - MIN_RESTART_INTERVAL;
+        health.restarted.insert("s".to_string(), long_ago);
+        assert!(health.may_restart("s"));
+    }
+
+    #[test]
+    fn test_health_check_skips_healthy_and_host_sessions() {
+        let mut health = ContainerHealth::default();
+        let mut inst = Instance::new("host", "/tmp");
+        inst.status = Status::Error;
+        health.check(&mut inst, true);
+        assert!(inst.last_error.is_none());
+        assert!(health.checked.is_empty());
+        assert!(health.restarted.is_empty());
+    }
+
+    #[test]
+    fn test_health_check_leaves_containers_stopped_on_detach() {
+        let mut health = ContainerHealth::default();
+        let mut inst = Instance::new("sandboxed", "/tmp");
+        inst.sandbox_info = Some(crate::session::SandboxInfo {
+            enabled: true,
+            container_id: None,
+            image: "ubuntu:latest".to_string(),
+            container_name: "aoe-sandbox-test".to_string(),
+            created_at: None,
+            yolo_mode: None,
+            extra_env_keys: None,
+            extra_env_values: None,
+            extra_volumes: None,
+            network: None,
+        });
+        inst.status = Status::Error;
+        inst.last_error = Some(STOPPED_ON_DETACH.to_string());
+        health.check(&mut inst, true);
+        assert_eq!(inst.last_error.as_deref(), Some(STOPPED_ON_DETACH));
+        assert!(health.checked.is_empty());
+        assert!(health.restarted.is_empty());
+    }
+}
@@ -2,6 +2,8 @@
 
 use ratatui::style::Color;
 
+use crate::session::Status;
+
 #[derive(Debug, Clone)]
 pub struct Theme {
     // Background and borders
@@ -62,3 +64,18 @@ impl Theme {
         }
     }
 }
+
+impl Theme {
+    /// Color used for a session's status icon, shared by the TUI and CLI output.
+    pub fn status_color(&self, status: Status) -> Color {
+        match status {
+            Status::Running => self.running,
+            Status::Waiting => self.waiting,
+            Status::Idle => self.idle,
+            Status::Error => self.error,
+            Status::Starting => self.dimmed,
+            Status::Deleting => self.waiting,
+            Status::Exited => self.error,
+        }
+    }
+}
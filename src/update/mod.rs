@@ -6,7 +6,7 @@ use std::fs;
 use std::path::PathBuf;
 use tracing::warn;
 
-use crate::session::{get_app_dir, get_update_settings};
+use crate::session::{get_app_dir, get_update_settings, is_offline};
 
 const GITHUB_API_LATEST: &str =
     "https://api.github.com/repos/njbrake/agent-of-empires/releases/latest";
@@ -61,6 +61,9 @@ fn save_cache(cache: &UpdateCache) -> Result<()> {
 }
 
 pub async fn check_for_update(current_version: &str, force: bool) -> Result<UpdateInfo> {
+    if is_offline() {
+        anyhow::bail!("Offline mode is on, not checking for updates");
+    }
     let settings = get_update_settings();
 
     if !force {
@@ -209,7 +212,7 @@ fn is_newer_version(latest: &str, current: &str) -> bool {
 
 pub async fn print_update_notice() {
     let settings = get_update_settings();
-    if !settings.check_enabled || !settings.notify_in_cli {
+    if !settings.check_enabled || !settings.notify_in_cli || is_offline() {
         return;
     }
 
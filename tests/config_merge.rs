@@ -1,8 +1,9 @@
 //! Integration tests for the config merge pipeline: global + profile overrides with real TOML files.
 
 use agent_of_empires::session::{
-    load_profile_config, merge_configs, save_config, save_profile_config, Config, ProfileConfig,
-    SandboxConfigOverride, ThemeConfigOverride, UpdatesConfigOverride, WorktreeConfigOverride,
+    load_profile_config, merge_configs, resolve_config, save_config, save_profile_config, Config,
+    ProfileConfig, SandboxConfigOverride, ThemeConfigOverride, UpdatesConfigOverride,
+    WorktreeConfigOverride,
 };
 use anyhow::Result;
 use serial_test::serial;
@@ -174,3 +175,39 @@ fn test_empty_profile_config_returns_global() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+#[serial]
+fn test_profiles_resolve_independent_sandbox_defaults() -> Result<()> {
+    let _temp = setup_temp_home();
+
+    let mut global = Config::default();
+    global.sandbox.default_image = "dev-image:latest".to_string();
+    global.sandbox.memory_limit = Some("16g".to_string());
+    save_config(&global)?;
+
+    let ci = ProfileConfig {
+        sandbox: Some(SandboxConfigOverride {
+            default_image: Some("ci-image:1.2.3".to_string()),
+            cpu_limit: Some("2".to_string()),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    save_profile_config("ci", &ci)?;
+
+    let ci_config = resolve_config("ci")?;
+    assert_eq!(ci_config.sandbox.default_image, "ci-image:1.2.3");
+    assert_eq!(ci_config.sandbox.cpu_limit.as_deref(), Some("2"));
+    assert_eq!(
+        ci_config.sandbox.memory_limit.as_deref(),
+        Some("16g"),
+        "memory_limit should inherit from global"
+    );
+
+    let dev_config = resolve_config("dev")?;
+    assert_eq!(dev_config.sandbox.default_image, "dev-image:latest");
+    assert!(dev_config.sandbox.cpu_limit.is_none());
+
+    Ok(())
+}
@@ -4,7 +4,7 @@
 //! that uses them, specifically the auto_cleanup settings for worktrees and
 //! sandbox containers.
 
-use agent_of_empires::session::{save_config, Config, SandboxConfig, WorktreeConfig};
+use agent_of_empires::session::{save_config, Config, SandboxConfig, UiConfig, WorktreeConfig};
 use agent_of_empires::tui::dialogs::{DeleteDialogConfig, UnifiedDeleteDialog};
 use serial_test::serial;
 
@@ -28,6 +28,7 @@ fn test_delete_dialog_respects_worktree_auto_cleanup_true() {
         DeleteDialogConfig {
             worktree_branch: Some("main".to_string()),
             has_sandbox: false,
+            worktree_shared_by: 0,
         },
     );
     assert!(
@@ -50,6 +51,7 @@ fn test_delete_dialog_respects_worktree_auto_cleanup_false() {
         DeleteDialogConfig {
             worktree_branch: Some("main".to_string()),
             has_sandbox: false,
+            worktree_shared_by: 0,
         },
     );
     assert!(
@@ -72,6 +74,7 @@ fn test_delete_dialog_respects_sandbox_auto_cleanup_true() {
         DeleteDialogConfig {
             worktree_branch: None,
             has_sandbox: true,
+            worktree_shared_by: 0,
         },
     );
     assert!(
@@ -94,6 +97,7 @@ fn test_delete_dialog_respects_sandbox_auto_cleanup_false() {
         DeleteDialogConfig {
             worktree_branch: None,
             has_sandbox: true,
+            worktree_shared_by: 0,
         },
     );
     assert!(
@@ -141,6 +145,7 @@ fn test_all_worktree_config_fields_accessible() {
     let config = WorktreeConfig::default();
     let _ = config.enabled;
     let _ = config.path_template.as_str();
+    let _ = config.branch_separator.as_str();
     let _ = config.auto_cleanup;
     let _ = config.show_branch_in_tui;
 }
@@ -155,4 +160,88 @@ fn test_all_sandbox_config_fields_accessible() {
     let _ = config.auto_cleanup;
     let _ = &config.cpu_limit;
     let _ = &config.memory_limit;
+    let _ = &config.network;
+    let _ = config.read_only_rootfs;
+    let _ = config.hardened;
+    let _ = config.auth_volume_scope;
+    let _ = &config.cap_drop;
+    let _ = &config.cap_add;
+    let _ = &config.security_opt;
+    let _ = config.docker_retries;
+    let _ = config.auto_restart;
+    let _ = config.stop_on_detach;
+    let _ = config.max_concurrent_containers;
+}
+
+#[test]
+fn test_all_session_config_fields_accessible() {
+    let config = agent_of_empires::session::config::SessionConfig::default();
+    let _ = config.default_tool;
+    let _ = config.default_group;
+    let _ = config.default_path;
+    let _ = config.create_dir;
+    let _ = config.title_template;
+    let _ = config.title_words;
+}
+
+#[test]
+fn test_all_logging_config_fields_accessible() {
+    let config = agent_of_empires::session::LoggingConfig::default();
+    let _ = config.enabled;
+    let _ = config.level;
+    let _ = config.max_files;
+}
+
+#[test]
+fn test_all_app_config_fields_accessible() {
+    let config = agent_of_empires::session::AppConfig::default();
+    let _ = config.offline;
+}
+
+#[test]
+fn test_all_events_config_fields_accessible() {
+    let config = agent_of_empires::session::EventsConfig::default();
+    let _ = config.webhook;
+    let _ = config.command;
+}
+
+#[test]
+fn test_all_ui_config_fields_accessible() {
+    let config = UiConfig::default();
+    let _ = config.tick_ms;
+    let _ = config.status_refresh_ms;
+    let _ = config.status_debounce_polls;
+    let _ = config.remember_new_session;
+    let _ = config.resume_last_profile;
+}
+
+#[test]
+fn test_all_tmux_config_fields_accessible() {
+    let config = agent_of_empires::session::config::TmuxConfig::default();
+    let _ = config.status_bar;
+    let _ = config.mouse;
+    let _ = config.history_limit;
+}
+
+#[test]
+fn test_status_patterns_config_fields_accessible() {
+    let config = Config::default();
+    let _ = &config.status_patterns;
+    let patterns = agent_of_empires::session::StatusPatternsConfig::default();
+    let _ = &patterns.waiting;
+    let _ = &patterns.running;
+}
+
+#[test]
+fn test_all_preset_config_fields_accessible() {
+    let config = Config::default();
+    let _ = &config.presets;
+    let preset = agent_of_empires::session::PresetConfig::default();
+    let _ = &preset.tool;
+    let _ = &preset.command;
+    let _ = preset.sandbox;
+    let _ = &preset.sandbox_image;
+    let _ = preset.yolo_mode;
+    let _ = &preset.environment;
+    let _ = &preset.environment_values;
 }
@@ -1,9 +1,12 @@
 //! Integration tests for profile management: create, delete, list, default, and isolation.
 
+use agent_of_empires::cli::Cli;
 use agent_of_empires::session::{
-    create_profile, delete_profile, list_profiles, set_default_profile, Config, Instance, Storage,
+    create_profile, delete_profile, list_profiles, remember_last_profile, resolve_profile,
+    save_config, set_default_profile, summarize_profiles, Config, Instance, Storage,
 };
 use anyhow::Result;
+use clap::Parser;
 use serial_test::serial;
 
 fn setup_temp_home() -> tempfile::TempDir {
@@ -111,6 +114,29 @@ fn test_profile_session_isolation() -> Result<()> {
     Ok(())
 }
 
+#[test]
+#[serial]
+fn test_summarize_profiles_counts_sessions() -> Result<()> {
+    let _temp = setup_temp_home();
+
+    create_profile("alpha")?;
+    create_profile("beta")?;
+    Storage::new("alpha")?.save(&[
+        Instance::new("One", "/path/one"),
+        Instance::new("Two", "/path/two"),
+    ])?;
+
+    let summaries = summarize_profiles()?;
+    let alpha = summaries.iter().find(|s| s.name == "alpha").unwrap();
+    let beta = summaries.iter().find(|s| s.name == "beta").unwrap();
+
+    assert_eq!(alpha.sessions, 2);
+    assert_eq!(alpha.running, 0);
+    assert_eq!(beta.sessions, 0);
+
+    Ok(())
+}
+
 #[test]
 #[serial]
 fn test_profile_config_isolation() -> Result<()> {
@@ -149,3 +175,159 @@ fn test_profile_config_isolation() -> Result<()> {
 
     Ok(())
 }
+
+fn profile_from_args(args: &[&str]) -> String {
+    let cli = Cli::try_parse_from(args).unwrap();
+    resolve_profile(cli.profile.as_deref(), cli.command.is_none())
+}
+
+#[test]
+#[serial]
+fn test_profile_falls_back_to_builtin_default() -> Result<()> {
+    let _temp = setup_temp_home();
+    std::env::remove_var("AGENT_OF_EMPIRES_PROFILE");
+
+    assert_eq!(profile_from_args(&["aoe"]), "default");
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_profile_uses_configured_default() -> Result<()> {
+    let _temp = setup_temp_home();
+    std::env::remove_var("AGENT_OF_EMPIRES_PROFILE");
+
+    set_default_profile("work")?;
+    assert_eq!(profile_from_args(&["aoe"]), "work");
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_profile_env_overrides_configured_default() -> Result<()> {
+    let _temp = setup_temp_home();
+    set_default_profile("work")?;
+
+    std::env::set_var("AGENT_OF_EMPIRES_PROFILE", "from-env");
+    let profile = profile_from_args(&["aoe"]);
+    std::env::remove_var("AGENT_OF_EMPIRES_PROFILE");
+
+    assert_eq!(profile, "from-env");
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_profile_flag_overrides_env() -> Result<()> {
+    let _temp = setup_temp_home();
+    set_default_profile("work")?;
+
+    std::env::set_var("AGENT_OF_EMPIRES_PROFILE", "from-env");
+    let profile = profile_from_args(&["aoe", "--profile", "from-flag"]);
+    std::env::remove_var("AGENT_OF_EMPIRES_PROFILE");
+
+    assert_eq!(profile, "from-flag");
+    Ok(())
+}
+
+fn enable_resume_last_profile() -> Result<()> {
+    let mut config = Config::load()?;
+    config.ui.resume_last_profile = true;
+    save_config(&config)
+}
+
+#[test]
+#[serial]
+fn test_profile_resumes_last_switched_profile() -> Result<()> {
+    let _temp = setup_temp_home();
+    std::env::remove_var("AGENT_OF_EMPIRES_PROFILE");
+    set_default_profile("work")?;
+    create_profile("client")?;
+    enable_resume_last_profile()?;
+
+    remember_last_profile("client")?;
+    assert_eq!(profile_from_args(&["aoe"]), "client");
+    // Explicit requests still win
+    assert_eq!(profile_from_args(&["aoe", "-p", "other"]), "other");
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_subcommands_ignore_last_switched_profile() -> Result<()> {
+    let _temp = setup_temp_home();
+    std::env::remove_var("AGENT_OF_EMPIRES_PROFILE");
+    set_default_profile("work")?;
+    create_profile("client")?;
+    enable_resume_last_profile()?;
+
+    remember_last_profile("client")?;
+    assert_eq!(profile_from_args(&["aoe", "list"]), "work");
+    assert_eq!(profile_from_args(&["aoe"]), "client");
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_last_switched_profile_not_resumed_by_default() -> Result<()> {
+    let _temp = setup_temp_home();
+    std::env::remove_var("AGENT_OF_EMPIRES_PROFILE");
+    set_default_profile("work")?;
+    create_profile("client")?;
+
+    remember_last_profile("client")?;
+    assert_eq!(profile_from_args(&["aoe"]), "work");
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_profile_override_enables_resume() -> Result<()> {
+    use agent_of_empires::session::{save_profile_config, ProfileConfig, UiConfigOverride};
+
+    let _temp = setup_temp_home();
+    std::env::remove_var("AGENT_OF_EMPIRES_PROFILE");
+    set_default_profile("work")?;
+    create_profile("client")?;
+    let client_config = ProfileConfig {
+        ui: Some(UiConfigOverride {
+            resume_last_profile: Some(true),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    save_profile_config("client", &client_config)?;
+
+    remember_last_profile("client")?;
+    assert_eq!(profile_from_args(&["aoe"]), "client");
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_set_default_profile_forgets_last_profile() -> Result<()> {
+    let _temp = setup_temp_home();
+    std::env::remove_var("AGENT_OF_EMPIRES_PROFILE");
+    create_profile("client")?;
+    remember_last_profile("client")?;
+
+    set_default_profile("work")?;
+    assert_eq!(profile_from_args(&["aoe"]), "work");
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_deleted_last_profile_is_not_resumed() -> Result<()> {
+    let _temp = setup_temp_home();
+    std::env::remove_var("AGENT_OF_EMPIRES_PROFILE");
+    create_profile("client")?;
+    enable_resume_last_profile()?;
+    remember_last_profile("client")?;
+
+    delete_profile("client")?;
+    assert_eq!(profile_from_args(&["aoe"]), "default");
+    let config = Config::load()?;
+    assert_eq!(config.app_state.last_profile, None);
+    Ok(())
+}
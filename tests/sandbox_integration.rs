@@ -23,6 +23,8 @@ fn test_sandbox_info_serialization() {
         yolo_mode: None,
         extra_env_keys: Some(vec!["MY_VAR".to_string()]),
         extra_env_values: None,
+        extra_volumes: None,
+        network: None,
     };
 
     let json = serde_json::to_string(&sandbox_info).unwrap();
@@ -52,6 +54,8 @@ fn test_instance_is_sandboxed() {
         yolo_mode: None,
         extra_env_keys: None,
         extra_env_values: None,
+        extra_volumes: None,
+        network: None,
     });
     assert!(inst.is_sandboxed());
 
@@ -64,6 +68,8 @@ fn test_instance_is_sandboxed() {
         yolo_mode: None,
         extra_env_keys: None,
         extra_env_values: None,
+        extra_volumes: None,
+        network: None,
     });
     assert!(!inst.is_sandboxed());
 }
@@ -85,6 +91,8 @@ fn test_sandbox_info_persists_across_save_load() {
         yolo_mode: Some(true),
         extra_env_keys: Some(vec!["API_KEY".to_string(), "SECRET".to_string()]),
         extra_env_values: None,
+        extra_volumes: None,
+        network: None,
     });
 
     storage.save(&[inst.clone()]).unwrap();
@@ -142,6 +150,11 @@ fn test_container_lifecycle() {
         environment: vec![],
         cpu_limit: None,
         memory_limit: None,
+        network: None,
+        read_only_rootfs: false,
+        cap_drop: vec![],
+        cap_add: vec![],
+        security_opt: vec![],
     };
 
     let container_id = container.create(&config).unwrap();
@@ -183,6 +196,11 @@ fn test_container_force_remove() {
         environment: vec![],
         cpu_limit: None,
         memory_limit: None,
+        network: None,
+        read_only_rootfs: false,
+        cap_drop: vec![],
+        cap_add: vec![],
+        security_opt: vec![],
     };
 
     container.create(&config).unwrap();
@@ -192,3 +210,51 @@ fn test_container_force_remove() {
     container.remove(true).unwrap();
     assert!(!container.exists().unwrap());
 }
+
+#[test]
+#[ignore = "requires Docker daemon"]
+fn test_recreates_externally_removed_container() {
+    if !docker_available() {
+        eprintln!("Skipping: Docker not available");
+        return;
+    }
+
+    let project = tempfile::TempDir::new().unwrap();
+    let mut inst = Instance::new("stale", project.path().to_str().unwrap());
+    inst.sandbox_info = Some(SandboxInfo {
+        enabled: true,
+        // Remembered from a previous run; the container is long gone
+        container_id: Some("0123456789ab".to_string()),
+        image: "alpine:latest".to_string(),
+        container_name: DockerContainer::generate_name(&inst.id),
+        created_at: None,
+        yolo_mode: None,
+        extra_env_keys: None,
+        extra_env_values: None,
+        extra_volumes: None,
+        network: None,
+    });
+    let container_id = |inst: &Instance| inst.sandbox_info.as_ref().unwrap().container_id.clone();
+
+    inst.ensure_container_running().unwrap();
+    let first = container_id(&inst).unwrap();
+    assert_ne!(first, "0123456789ab");
+
+    // Remove the container behind aoe's back
+    let container = DockerContainer::from_session_id(&inst.id);
+    container.remove(true).unwrap();
+    assert!(!container.exists().unwrap());
+
+    inst.ensure_container_running().unwrap();
+    let second = container_id(&inst).unwrap();
+    assert_ne!(first, second);
+    assert!(container.is_running().unwrap());
+
+    // Stopped out of band: restarted in place, id unchanged
+    container.stop().unwrap();
+    inst.ensure_container_running().unwrap();
+    assert_eq!(container_id(&inst).unwrap(), second);
+    assert!(container.is_running().unwrap());
+
+    container.remove(true).unwrap();
+}
@@ -0,0 +1,284 @@
+//! Integration tests for the library-level session API.
+
+use agent_of_empires::session::builder::InstanceParams;
+use agent_of_empires::session::{manager, save_config, Config, Storage};
+use agent_of_empires::tmux::{refresh_session_cache, TerminalSession};
+use anyhow::Result;
+use serial_test::serial;
+use std::time::{Duration, Instant};
+
+fn setup_temp_home() -> tempfile::TempDir {
+    let temp = tempfile::TempDir::new().unwrap();
+    std::env::set_var("HOME", temp.path());
+    #[cfg(target_os = "linux")]
+    std::env::set_var("XDG_CONFIG_HOME", temp.path().join(".config"));
+    temp
+}
+
+fn params(title: &str, path: &std::path::Path, group: &str) -> InstanceParams {
+    InstanceParams {
+        title: title.to_string(),
+        path: path.to_string_lossy().to_string(),
+        group: group.to_string(),
+        tool: "claude".to_string(),
+        command: String::new(),
+        worktree_branch: None,
+        create_new_branch: false,
+        sandbox: false,
+        sandbox_image: String::new(),
+        yolo_mode: false,
+        extra_env_keys: Vec::new(),
+        extra_env_values: Vec::new(),
+        extra_volumes: Vec::new(),
+        network: None,
+        parent_session_id: None,
+    }
+}
+
+#[test]
+#[serial]
+fn test_create_session_persists_with_group() -> Result<()> {
+    let temp = setup_temp_home();
+    let project = temp.path().join("project");
+    std::fs::create_dir(&project)?;
+
+    let instance = manager::create_session("default", params("api", &project, "work/backend"))?;
+    assert_eq!(instance.title, "api");
+    assert_eq!(instance.group_path, "work/backend");
+
+    let (loaded, groups) = Storage::new("default")?.load_with_groups()?;
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].id, instance.id);
+    assert!(groups.iter().any(|g| g.path == "work/backend"));
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_create_session_rejects_missing_path() -> Result<()> {
+    let temp = setup_temp_home();
+    let missing = temp.path().join("missing");
+
+    let err = manager::create_session("default", params("api", &missing, "")).unwrap_err();
+    assert!(err.to_string().starts_with("Path does not exist"));
+    assert!(Storage::new("default")?.load()?.is_empty());
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_create_session_creates_missing_path_when_configured() -> Result<()> {
+    let temp = setup_temp_home();
+    let mut config = Config::default();
+    config.session.create_dir = true;
+    save_config(&config)?;
+    let missing = temp.path().join("new/project");
+
+    manager::create_session("default", params("api", &missing, ""))?;
+    assert!(missing.is_dir());
+    assert_eq!(Storage::new("default")?.load()?.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_create_session_keeps_existing_sessions() -> Result<()> {
+    let temp = setup_temp_home();
+
+    let first = manager::create_session("default", params("one", temp.path(), ""))?;
+    let second = manager::create_session("default", params("two", temp.path(), ""))?;
+
+    let (loaded, _) = Storage::new("default")?.load_with_groups()?;
+    let ids: Vec<&str> = loaded.iter().map(|i| i.id.as_str()).collect();
+    assert_eq!(ids, vec![first.id.as_str(), second.id.as_str()]);
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_unknown_session_is_an_error() {
+    let _temp = setup_temp_home();
+
+    let err = manager::start_session("default", "missing").unwrap_err();
+    assert!(err.to_string().contains("Session not found"));
+    let err = manager::attach_session("default", "missing").unwrap_err();
+    assert!(err.to_string().contains("Session not found"));
+}
+
+#[test]
+#[serial]
+fn test_rename_session_persists_new_title() -> Result<()> {
+    let temp = setup_temp_home();
+    let created = manager::create_session("default", params("api", temp.path(), ""))?;
+
+    let renamed = manager::rename_session("default", "api", "  backend  ")?;
+    assert_eq!(renamed.id, created.id);
+    assert_eq!(renamed.title, "backend");
+
+    let loaded = Storage::new("default")?.load()?;
+    assert_eq!(loaded[0].title, "backend");
+    assert_eq!(renamed.title_lower, "backend");
+
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_rename_session_rejects_taken_or_empty_title() -> Result<()> {
+    let temp = setup_temp_home();
+    manager::create_session("default", params("api", temp.path(), ""))?;
+    manager::create_session("default", params("web", temp.path(), ""))?;
+
+    let err = manager::rename_session("default", "api", "web").unwrap_err();
+    assert!(err.to_string().contains("already exists"));
+    let err = manager::rename_session("default", "api", "   ").unwrap_err();
+    assert!(err.to_string().contains("cannot be empty"));
+
+    // Keeping the same title is not a collision
+    manager::rename_session("default", "api", "api")?;
+
+    let titles: Vec<String> = Storage::new("default")?
+        .load()?
+        .into_iter()
+        .map(|i| i.title)
+        .collect();
+    assert_eq!(titles, vec!["api", "web"]);
+
+    Ok(())
+}
+
+fn write_repo_hooks(project: &std::path::Path, on_create: &str) -> Result<()> {
+    std::fs::create_dir_all(project.join(".aoe"))?;
+    std::fs::write(
+        project.join(".aoe/config.toml"),
+        format!("[hooks]\non_create = [\"{}\"]\n", on_create),
+    )?;
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_create_session_runs_profile_on_create_hooks() -> Result<()> {
+    let temp = setup_temp_home();
+    let project = temp.path().join("project");
+    std::fs::create_dir(&project)?;
+
+    let mut config = Config::default();
+    config.hooks.on_create = vec!["touch profile-hook-ran".to_string()];
+    save_config(&config)?;
+
+    manager::create_session("default", params("api", &project, ""))?;
+    assert!(project.join("profile-hook-ran").exists());
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_create_session_runs_repo_hooks_only_when_trusted() -> Result<()> {
+    let temp = setup_temp_home();
+    let project = temp.path().join("project");
+    std::fs::create_dir(&project)?;
+    write_repo_hooks(&project, "touch repo-hook-ran")?;
+
+    manager::create_session("default", params("skipped", &project, ""))?;
+    assert!(!project.join("repo-hook-ran").exists());
+
+    let mut asked = false;
+    manager::create_session_with_hook_approval("default", params("run", &project, ""), |h| {
+        asked = true;
+        assert_eq!(h.on_create, vec!["touch repo-hook-ran"]);
+        Ok(true)
+    })?;
+    assert!(asked);
+    assert!(project.join("repo-hook-ran").exists());
+
+    // Approved hooks are remembered as trusted
+    std::fs::remove_file(project.join("repo-hook-ran"))?;
+    manager::create_session("default", params("trusted", &project, ""))?;
+    assert!(project.join("repo-hook-ran").exists());
+    Ok(())
+}
+
+#[test]
+#[serial]
+fn test_create_session_failed_hook_saves_nothing() -> Result<()> {
+    let temp = setup_temp_home();
+    let project = temp.path().join("project");
+    std::fs::create_dir(&project)?;
+    write_repo_hooks(&project, "exit 3")?;
+
+    let err =
+        manager::create_session_with_hook_approval("default", params("api", &project, ""), |_| {
+            Ok(true)
+        })
+        .unwrap_err();
+    assert!(err.to_string().contains("on_create hook failed"), "{}", err);
+    assert!(Storage::new("default")?.load()?.is_empty());
+    Ok(())
+}
+
+fn tmux_available() -> bool {
+    std::process::Command::new("tmux")
+        .arg("-V")
+        .output()
+        .map(|o| o.status.success())
+        .unwrap_or(false)
+}
+
+/// Poll tmux directly until session `name` exists (or is gone, when `present`
+/// is false), giving up after a few seconds. Returns whether it got there.
+fn wait_for_session(name: &str, present: bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    loop {
+        let exists = std::process::Command::new("tmux")
+            .args(["has-session", "-t", &format!("={}", name)])
+            .output()
+            .map(|o| o.status.success())
+            .unwrap_or(false);
+        if exists == present {
+            return true;
+        }
+        if Instant::now() >= deadline {
+            return false;
+        }
+        std::thread::sleep(Duration::from_millis(50));
+    }
+}
+
+#[test]
+#[serial]
+fn test_rename_session_renames_paired_terminal() -> Result<()> {
+    if !tmux_available() {
+        eprintln!("Skipping test: tmux not available");
+        return Ok(());
+    }
+    let temp = setup_temp_home();
+    let created = manager::create_session("default", params("rename-src", temp.path(), ""))?;
+
+    let mut inst = created.clone();
+    inst.start_terminal_with_size(None)?;
+    let old_terminal = inst.terminal_tmux_session()?;
+    let old_name = TerminalSession::generate_name(&created.id, &created.title);
+    assert!(
+        wait_for_session(&old_name, true),
+        "terminal {} should start",
+        old_name
+    );
+
+    let renamed = manager::rename_session("default", &created.id, "rename-dst")?;
+    let new_terminal = renamed.terminal_tmux_session()?;
+    let new_name = TerminalSession::generate_name(&renamed.id, &renamed.title);
+    let moved = wait_for_session(&new_name, true);
+    let old_left = !wait_for_session(&old_name, false);
+    refresh_session_cache();
+    let _ = new_terminal.kill();
+    let _ = old_terminal.kill();
+
+    assert!(moved, "terminal should resolve under the new title");
+    assert!(!old_left, "old terminal name should be gone");
+    Ok(())
+}
@@ -13,9 +13,16 @@
 //! 2. Verify the new captures look correct
 //! 3. Update detection logic if needed
 //! 4. Re-run tests
+//!
+//! The `pane_capture` tests drive the same per-tool dispatch the live
+//! `Session` uses, through a fake pane, so no tmux server is needed.
 
-use agent_of_empires::session::Status;
-use agent_of_empires::tmux::{detect_claude_status, detect_opencode_status};
+use agent_of_empires::session::{Status, StatusPatternsConfig};
+use agent_of_empires::tmux::{
+    detect_claude_status, detect_opencode_status, detect_pane_status, PaneCapture, StatusPatterns,
+    STATUS_CAPTURE_LINES,
+};
+use std::cell::Cell;
 use std::fs;
 use std::path::PathBuf;
 
@@ -175,3 +182,190 @@ mod opencode {
         );
     }
 }
+
+/// Pane that returns a canned buffer instead of asking tmux
+struct FakePane {
+    content: Option<String>,
+    requested_lines: Cell<Option<usize>>,
+}
+
+impl FakePane {
+    fn new(content: &str) -> Self {
+        Self {
+            content: Some(content.to_string()),
+            requested_lines: Cell::new(None),
+        }
+    }
+
+    fn failing() -> Self {
+        Self {
+            content: None,
+            requested_lines: Cell::new(None),
+        }
+    }
+}
+
+impl PaneCapture for FakePane {
+    fn capture_pane(&self, lines: usize) -> anyhow::Result<String> {
+        self.requested_lines.set(Some(lines));
+        self.content
+            .clone()
+            .ok_or_else(|| anyhow::anyhow!("capture-pane failed"))
+    }
+
+    fn foreground_pid(&self) -> Option<u32> {
+        None
+    }
+}
+
+mod pane_capture {
+    use super::*;
+
+    fn status_for(tool: &str, content: &str) -> Status {
+        detect_pane_status(&FakePane::new(content), tool, None).unwrap()
+    }
+
+    fn fixture_statuses(tool_dir: &str, state: &str, tool: &str, expected: Status) {
+        test_all_fixtures_in_dir(tool_dir, state, expected, identity, |content| {
+            status_for(tool, content)
+        });
+    }
+
+    #[test]
+    fn test_claude_fixtures_through_pane() {
+        fixture_statuses("claude_code", "running", "claude", Status::Running);
+        fixture_statuses("claude_code", "waiting_question", "claude", Status::Waiting);
+        fixture_statuses(
+            "claude_code",
+            "waiting_permission",
+            "claude",
+            Status::Waiting,
+        );
+        fixture_statuses("claude_code", "idle", "claude", Status::Idle);
+    }
+
+    #[test]
+    fn test_opencode_fixtures_through_pane() {
+        fixture_statuses("opencode", "running", "opencode", Status::Running);
+        fixture_statuses(
+            "opencode",
+            "waiting_permission",
+            "opencode",
+            Status::Waiting,
+        );
+        fixture_statuses("opencode", "idle", "opencode", Status::Idle);
+    }
+
+    #[test]
+    fn test_codex_buffers() {
+        assert_eq!(
+            status_for("codex", "• Working (12s • Esc to interrupt)\n"),
+            Status::Running
+        );
+        assert_eq!(
+            status_for("codex", "Run command? git push\n  Approve (y/n)\n"),
+            Status::Waiting
+        );
+        assert_eq!(
+            status_for("codex", "Updated 3 files\n\ncodex>\n"),
+            Status::Waiting
+        );
+        assert_eq!(status_for("codex", "Updated 3 files\n"), Status::Idle);
+    }
+
+    #[test]
+    fn test_gemini_buffers() {
+        assert_eq!(
+            status_for("gemini", "⠙ Reading files (esc to cancel, 3s)\n"),
+            Status::Running
+        );
+        assert_eq!(
+            status_for(
+                "gemini",
+                "Allow execution of 'npm test'?\n● Yes, allow once\n"
+            ),
+            Status::Waiting
+        );
+        assert_eq!(status_for("gemini", "Done.\n\n> \n"), Status::Waiting);
+        assert_eq!(status_for("gemini", "Done.\n"), Status::Idle);
+    }
+
+    #[test]
+    fn test_vibe_buffers() {
+        assert_eq!(status_for("vibe", "⠹ Thinking\n"), Status::Running);
+        assert_eq!(
+            status_for(
+                "vibe",
+                "⚠ bash command\n↑↓ navigate  Enter select  ESC reject\n"
+            ),
+            Status::Waiting
+        );
+        assert_eq!(status_for("vibe", "All set.\n"), Status::Idle);
+    }
+
+    #[test]
+    fn test_same_buffer_differs_by_tool() {
+        // Claude treats a bare "> " prompt as waiting; Vibe has no such prompt
+        let content = "Finished the refactor\n> \n";
+        assert_eq!(status_for("claude", content), Status::Waiting);
+        assert_eq!(status_for("vibe", content), Status::Idle);
+    }
+
+    #[test]
+    fn test_unknown_tool_uses_claude_rules() {
+        assert_eq!(
+            status_for("some-new-agent", "✻ Pondering… (esc to interrupt)\n"),
+            Status::Running
+        );
+    }
+
+    #[test]
+    fn test_captures_status_window() {
+        let pane = FakePane::new("");
+        detect_pane_status(&pane, "claude", None).unwrap();
+        assert_eq!(pane.requested_lines.get(), Some(STATUS_CAPTURE_LINES));
+    }
+
+    #[test]
+    fn test_capture_error_propagates() {
+        assert!(detect_pane_status(&FakePane::failing(), "claude", None).is_err());
+    }
+
+    fn custom(waiting: &[&str], running: &[&str]) -> StatusPatterns {
+        StatusPatterns::compile(&StatusPatternsConfig {
+            waiting: waiting.iter().map(|s| s.to_string()).collect(),
+            running: running.iter().map(|s| s.to_string()).collect(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_custom_pattern_classifies_new_prompt() {
+        // A prompt the built-in codex rules don't know about
+        let content = "Applied patch to src/main.rs\n  Accept edits? ‹a› accept ‹r› reject\n";
+        assert_eq!(status_for("codex", content), Status::Idle);
+
+        let patterns = custom(&[r"Accept edits\?"], &[]);
+        let status = detect_pane_status(&FakePane::new(content), "codex", Some(&patterns));
+        assert_eq!(status.unwrap(), Status::Waiting);
+    }
+
+    #[test]
+    fn test_custom_pattern_overrides_builtin() {
+        // The built-in rules treat a bare prompt as waiting
+        let content = "Compacting conversation\n> \n";
+        assert_eq!(status_for("claude", content), Status::Waiting);
+
+        let patterns = custom(&[], &["^Compacting conversation"]);
+        let status = detect_pane_status(&FakePane::new(content), "claude", Some(&patterns));
+        assert_eq!(status.unwrap(), Status::Running);
+    }
+
+    #[test]
+    fn test_unmatched_custom_patterns_fall_back_to_builtins() {
+        let patterns = custom(&["never matches"], &["nor this"]);
+        let content = "✻ Pondering… (esc to interrupt)\n";
+        let status = detect_pane_status(&FakePane::new(content), "claude", Some(&patterns));
+        assert_eq!(status.unwrap(), Status::Running);
+    }
+}
@@ -3,8 +3,11 @@
 
 use agent_of_empires::git::error::GitError;
 use agent_of_empires::git::GitWorktree;
+use agent_of_empires::session::builder::InstanceParams;
+use agent_of_empires::session::manager::{self, CleanupOptions};
 use agent_of_empires::session::{Instance, Storage, WorktreeInfo};
 use chrono::Utc;
+use serial_test::serial;
 use tempfile::TempDir;
 
 fn setup_test_environment() -> (TempDir, git2::Repository, TempDir) {
@@ -49,6 +52,7 @@ fn test_add_session_with_worktree_flag() {
         managed_by_aoe: true,
         created_at: Utc::now(),
         cleanup_on_delete: true,
+        base_branch: None,
     });
 
     assert!(wt_path.exists());
@@ -71,6 +75,7 @@ fn test_session_has_worktree_info_after_creation() {
         managed_by_aoe: true,
         created_at: now,
         cleanup_on_delete: true,
+        base_branch: None,
     });
 
     let info = instance.worktree_info.as_ref().unwrap();
@@ -85,6 +90,7 @@ fn test_session_has_worktree_info_after_creation() {
 }
 
 #[test]
+#[serial]
 fn test_worktree_info_persists_across_save_load() {
     let temp_home = TempDir::new().unwrap();
     std::env::set_var("HOME", temp_home.path());
@@ -98,6 +104,7 @@ fn test_worktree_info_persists_across_save_load() {
         managed_by_aoe: true,
         created_at: Utc::now(),
         cleanup_on_delete: false,
+        base_branch: None,
     });
 
     storage.save(&[instance.clone()]).unwrap();
@@ -166,6 +173,7 @@ fn test_worktree_cleanup_on_session_removal() {
         managed_by_aoe: true,
         created_at: Utc::now(),
         cleanup_on_delete: true,
+        base_branch: None,
     });
 
     git_wt.remove_worktree(&wt_path).unwrap();
@@ -193,6 +201,7 @@ fn test_worktree_preserved_when_keep_flag_used() {
         managed_by_aoe: true,
         created_at: Utc::now(),
         cleanup_on_delete: false,
+        base_branch: None,
     });
 
     assert!(wt_path.exists());
@@ -262,3 +271,155 @@ fn test_create_new_branch_with_b_flag() {
         .is_ok();
     assert!(branch_exists_after);
 }
+
+/// A temp HOME holding a git repo at `<home>/repo`, so the default worktree
+/// template (`../{repo-name}-worktrees/{branch}`) also lands inside the temp dir
+fn setup_home_with_repo() -> (TempDir, std::path::PathBuf) {
+    let home = TempDir::new().unwrap();
+    std::env::set_var("HOME", home.path());
+    #[cfg(target_os = "linux")]
+    std::env::set_var("XDG_CONFIG_HOME", home.path().join(".config"));
+
+    let repo_path = home.path().join("repo");
+    let repo = git2::Repository::init(&repo_path).unwrap();
+    let sig = git2::Signature::now("Test", "test@example.com").unwrap();
+    let tree_id = repo.index().unwrap().write_tree().unwrap();
+    let tree = repo.find_tree(tree_id).unwrap();
+    repo.commit(Some("HEAD"), &sig, &sig, "Initial", &tree, &[])
+        .unwrap();
+
+    (home, repo_path)
+}
+
+fn worktree_params(repo_path: &std::path::Path, branch: &str) -> InstanceParams {
+    InstanceParams {
+        title: "feature session".to_string(),
+        path: repo_path.to_string_lossy().to_string(),
+        group: String::new(),
+        tool: "claude".to_string(),
+        command: String::new(),
+        worktree_branch: Some(branch.to_string()),
+        create_new_branch: true,
+        sandbox: false,
+        sandbox_image: String::new(),
+        yolo_mode: false,
+        extra_env_keys: Vec::new(),
+        extra_env_values: Vec::new(),
+        extra_volumes: Vec::new(),
+        network: None,
+        parent_session_id: None,
+    }
+}
+
+#[test]
+#[serial]
+fn test_create_session_with_new_branch_worktree() {
+    let (_home, repo_path) = setup_home_with_repo();
+
+    let instance =
+        manager::create_session("default", worktree_params(&repo_path, "e2e-feature")).unwrap();
+
+    let wt_path = std::path::PathBuf::from(&instance.project_path);
+    assert!(wt_path.exists());
+    assert_ne!(wt_path, repo_path);
+
+    let info = instance.worktree_info.as_ref().unwrap();
+    assert!(info.managed_by_aoe);
+    assert_eq!(info.branch, "e2e-feature");
+    assert_eq!(
+        std::path::PathBuf::from(&info.main_repo_path)
+            .canonicalize()
+            .unwrap(),
+        repo_path.canonicalize().unwrap()
+    );
+
+    let repo = git2::Repository::open(&repo_path).unwrap();
+    assert!(repo
+        .find_branch("e2e-feature", git2::BranchType::Local)
+        .is_ok());
+
+    let loaded = Storage::new("default").unwrap().load().unwrap();
+    assert_eq!(loaded.len(), 1);
+    assert_eq!(loaded[0].id, instance.id);
+    assert_eq!(loaded[0].project_path, instance.project_path);
+    assert!(loaded[0].worktree_info.as_ref().unwrap().managed_by_aoe);
+}
+
+#[test]
+#[serial]
+fn test_delete_session_removes_worktree_and_branch() {
+    let (_home, repo_path) = setup_home_with_repo();
+
+    let instance =
+        manager::create_session("default", worktree_params(&repo_path, "e2e-cleanup")).unwrap();
+    let wt_path = std::path::PathBuf::from(&instance.project_path);
+    assert!(wt_path.exists());
+
+    manager::delete_session(
+        "default",
+        &instance.id,
+        CleanupOptions {
+            delete_worktree: true,
+            delete_branch: true,
+            delete_sandbox: false,
+        },
+    )
+    .unwrap();
+
+    assert!(!wt_path.exists());
+    let repo = git2::Repository::open(&repo_path).unwrap();
+    assert!(repo
+        .find_branch("e2e-cleanup", git2::BranchType::Local)
+        .is_err());
+    assert!(Storage::new("default").unwrap().load().unwrap().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_delete_session_can_keep_worktree() {
+    let (_home, repo_path) = setup_home_with_repo();
+
+    let instance =
+        manager::create_session("default", worktree_params(&repo_path, "e2e-keep")).unwrap();
+    let wt_path = std::path::PathBuf::from(&instance.project_path);
+
+    manager::delete_session("default", &instance.id, CleanupOptions::default()).unwrap();
+
+    assert!(wt_path.exists());
+    assert!(Storage::new("default").unwrap().load().unwrap().is_empty());
+}
+
+#[test]
+#[serial]
+fn test_delete_session_keeps_worktree_shared_by_another_session() {
+    let (_home, repo_path) = setup_home_with_repo();
+
+    let instance =
+        manager::create_session("default", worktree_params(&repo_path, "e2e-shared")).unwrap();
+    let wt_path = std::path::PathBuf::from(&instance.project_path);
+
+    let storage = Storage::new("default").unwrap();
+    let mut instances = storage.load().unwrap();
+    let mut other = Instance::new("second session", &instance.project_path);
+    other.worktree_info = instance.worktree_info.clone();
+    instances.push(other);
+    storage.save(&instances).unwrap();
+
+    manager::delete_session(
+        "default",
+        &instance.id,
+        CleanupOptions {
+            delete_worktree: true,
+            delete_branch: true,
+            delete_sandbox: false,
+        },
+    )
+    .unwrap();
+
+    assert!(wt_path.exists());
+    let repo = git2::Repository::open(&repo_path).unwrap();
+    assert!(repo
+        .find_branch("e2e-shared", git2::BranchType::Local)
+        .is_ok());
+    assert_eq!(storage.load().unwrap().len(), 1);
+}